@@ -2,6 +2,8 @@
 -- Aggressive cleanup for TTS-oriented plain text conversion.
 
 local skipping_toc = false
+-- Set by the reader from `image_alt_text` in config.toml.
+local include_alt_text = os.getenv("EBUP_IMAGE_ALT_TEXT") == "include"
 
 local function trim(s)
   s = s:gsub("^%s+", "")
@@ -129,11 +131,25 @@ function Table(_)
   return {}
 end
 
-function Figure(_)
+-- Images are either dropped or kept as an "[Image: alt]" marker. Inline
+-- filters run before block filters, so a figure's images are already markers.
+function Figure(f)
+  if include_alt_text then
+    local s = trim(pandoc.utils.stringify(f.content))
+    if s ~= "" then
+      return pandoc.Para({ pandoc.Str(s) })
+    end
+  end
   return {}
 end
 
-function Image(_)
+function Image(img)
+  if include_alt_text then
+    local alt = trim(pandoc.utils.stringify(img)):gsub("%s+", " ")
+    if alt ~= "" then
+      return pandoc.Str("[Image: " .. alt .. "]")
+    end
+  end
   return {}
 end
 
@@ -158,8 +174,45 @@ function Link(l)
   return l.content
 end
 
+local function is_verse_container(d)
+  for _, class in ipairs(d.classes) do
+    local c = class:lower()
+    if c:match("^poe[mt]") or c:match("^verse") or c:match("^stanza") then
+      return true
+    end
+  end
+  return false
+end
+
+-- Verse containers (`<div class="poem">` with a paragraph per line) become
+-- line blocks, so each line comes out on its own line and the reader can keep
+-- the poem's shape instead of spreading it over one paragraph per line.
+local function verse_lines(blocks)
+  local out = {}
+  local lines = {}
+  local function flush()
+    if #lines > 0 then
+      table.insert(out, pandoc.LineBlock(lines))
+      lines = {}
+    end
+  end
+  for _, block in ipairs(blocks) do
+    if block.t == "Para" or block.t == "Plain" then
+      table.insert(lines, block.content)
+    else
+      flush()
+      table.insert(out, block)
+    end
+  end
+  flush()
+  return out
+end
+
 -- Flatten containers.
 function Div(d)
+  if is_verse_container(d) then
+    return verse_lines(d.content)
+  end
   return d.content
 end
 
@@ -1,9 +1,11 @@
 use crate::cache::Bookmark;
 use crate::calibre::{CalibreBook, CalibreColumn};
 use crate::config::AppConfig;
-use crate::config::{FontFamily, FontWeight};
-use crate::epub_loader::LoadedBook;
-use crate::normalizer::PageNormalization;
+use crate::config::{
+    FontFamily, FontWeight, HighlightStyle, LogLevel, ReadingMode, TextDirection, ThemeMode,
+};
+use crate::epub_loader::{BookDiagnostics, LoadedBook};
+use crate::normalizer::{PageNormalization, TextNormalizer};
 use iced::keyboard::{Key, Modifiers};
 use iced::widget::scrollable::RelativeOffset;
 use std::path::PathBuf;
@@ -17,8 +19,20 @@ pub enum Message {
     CloseReadingSession,
     FontSizeChanged(u32),
     ToggleTheme,
+    ThemeChanged(ThemeMode),
+    CustomThemeBackgroundChanged(Component, f32),
+    CustomThemeForegroundChanged(Component, f32),
     ToggleSettings,
     ToggleStats,
+    ToggleContents,
+    ToggleSentenceList,
+    ToggleNotes,
+    /// Jump to the page holding the TOC entry at this index.
+    OpenTocEntry(usize),
+    /// Jump past front matter to the first real chapter in the TOC.
+    SkipToFirstChapter,
+    PageInputChanged(String),
+    GoToPage(usize),
     ToggleSearch,
     SearchQueryChanged(String),
     SearchSubmit,
@@ -26,6 +40,8 @@ pub enum Message {
     SearchPrev,
     ToggleRecentBooks,
     OpenRecentBook(PathBuf),
+    /// Switch to another book in this session, saving the open book's position first.
+    OpenBook(PathBuf),
     DeleteRecentBook(PathBuf),
     ToggleCalibreBrowser,
     PrimeCalibreLoad,
@@ -57,13 +73,20 @@ pub enum Message {
         error: String,
     },
     ToggleTextOnly,
+    /// Start or stop automatic page turns (`auto_page_seconds`).
+    ToggleAutoPage,
     FontFamilyChanged(FontFamily),
+    /// A family loaded from the fonts directory was picked.
+    CustomFontChanged(String),
     FontWeightChanged(FontWeight),
+    TextDirectionChanged(TextDirection),
+    ReadingModeChanged(ReadingMode),
     LineSpacingChanged(f32),
     MarginHorizontalChanged(u16),
     MarginVerticalChanged(u16),
     WordSpacingChanged(u32),
     LetterSpacingChanged(u32),
+    ParagraphSpacingChanged(f32),
     LinesPerPageChanged(u32),
     ToggleTtsControls,
     JumpToCurrentAudio,
@@ -71,24 +94,67 @@ pub enum Message {
     RepeatCurrentSentence,
     SafeQuit,
     PauseAfterSentenceChanged(f32),
+    PauseAfterParagraphChanged(f32),
     DayHighlightChanged(Component, f32),
     NightHighlightChanged(Component, f32),
+    DaySearchHighlightChanged(Component, f32),
+    NightSearchHighlightChanged(Component, f32),
+    HighlightStyleChanged(HighlightStyle),
     BeginNumericSettingEdit(NumericSetting),
     NumericSettingInputChanged(String),
     CommitNumericSettingInput,
     CancelNumericSettingInput,
     AdjustNumericSettingByWheel(f32),
     AutoScrollTtsChanged(bool),
+    LookupWordsOnClickChanged(bool),
+    LookupWord(String),
+    CloseDefinition,
     CenterSpokenSentenceChanged(bool),
+    SmoothScrollChanged(bool),
+    ReadingRulerChanged(bool),
+    HyphenateChanged(bool),
+    /// Collapse the top bar into the overflow menu to give the text more room.
+    CompactControlsChanged(bool),
+    AccessibilityModeChanged(bool),
+    /// Pointer pressed on the reading ruler's handle.
+    ReadingRulerDragStarted,
+    /// Pointer moved while dragging the ruler, in pixels from the top of the text area.
+    ReadingRulerDragged(f32),
+    ReadingRulerDragEnded,
+    UndoSettings,
+    RedoSettings,
     Play,
     Pause,
     PlayFromPageStart,
     PlayFromCursor(usize),
+    /// Mark a sentence in the sentence list, extending any selection on this page.
+    SelectSentence(usize),
+    ClearSentenceSelection,
+    /// Play sentences `start..=end` of the current page, then stop.
+    PlaySelection {
+        start: usize,
+        end: usize,
+    },
     SetTtsSpeed(f32),
     SetTtsVolume(f32),
+    /// Switch to the voice with this id.
+    SetTtsVoice(String),
+    /// Speak arbitrary text without touching the reading position, e.g. a voice preview.
+    SpeakSnippet(String),
     SeekForward,
     SeekBackward,
+    /// Step the silent reading cursor, turning pages at the edges; TTS is left alone.
+    NextSentence,
+    PreviousSentence,
+    /// Audio scrubber dragged to this many seconds into the page's queued audio.
+    AudioScrubbed(f32),
+    /// Audio scrubber let go; playback seeks to the nearest sentence.
+    AudioScrubReleased,
     SentenceClicked(usize),
+    /// Follow an in-text cross-reference to the given page index.
+    FollowCrossRef(usize),
+    ShowFootnote(usize),
+    CloseFootnote,
     WindowResized {
         width: f32,
         height: f32,
@@ -97,6 +163,7 @@ pub enum Message {
         x: f32,
         y: f32,
     },
+    WindowFocusChanged(bool),
     KeyPressed {
         key: Key,
         modifiers: Modifiers,
@@ -113,21 +180,160 @@ pub enum Message {
         start_idx: usize,
         request_id: u64,
         files: Vec<(PathBuf, Duration)>,
+        /// Chapter-title clip to play before `files` under `announce_chapters`.
+        preamble: Option<(PathBuf, Duration)>,
+        elapsed: Duration,
     },
     TtsAppendPrepared {
         page: usize,
         start_idx: usize,
         request_id: u64,
         files: Vec<(PathBuf, Duration)>,
+        elapsed: Duration,
     },
     TtsPlanReady {
         page: usize,
         requested_display_idx: usize,
         request_id: u64,
         plan: PageNormalization,
+        elapsed: Duration,
+    },
+    BookmarkNameChanged(String),
+    AddBookmark(String),
+    JumpToBookmark(String),
+    DeleteBookmark(String),
+    ExportBookmarks,
+    NoteInputChanged(String),
+    AddNote,
+    JumpToNote(usize),
+    ExportProgress,
+    ImportProgress,
+    ProgressImportRead(Option<String>),
+    /// Ask to delete cached data for the open book; nothing is removed until confirmed.
+    ClearBookCache(CacheClearScope),
+    ConfirmClearBookCache,
+    CancelClearBookCache,
+    BookCacheCleared {
+        scope: CacheClearScope,
+        /// Number of audio clips removed.
+        result: Result<usize, String>,
+    },
+    /// Draft new location for the open book after its file went missing.
+    RelocatePathChanged(String),
+    RelocateSource,
+    ProfilePathChanged(String),
+    ExportProfile,
+    ImportProfile,
+    LogLevelChanged(LogLevel),
+    CopyDiagnostics,
+    /// Check the open book's chapters, encoding and TOC and show the report.
+    ShowDiagnostics,
+    DiagnosticsReady {
+        path: PathBuf,
+        result: Result<BookDiagnostics, String>,
+    },
+    CloseDiagnostics,
+    CacheChapterAudio(usize),
+    /// Synthesize the whole book's audio into the cache.
+    CacheBookAudio,
+    /// Stop a running chapter or book audio cache job.
+    CancelAudioCache,
+    ChapterAudioPageCached {
+        request_id: u64,
+        page: usize,
+        result: Result<CachedPageAudio, String>,
+    },
+    ExportAudio,
+    CancelAudioExport,
+    AudioExportPageReady {
+        request_id: u64,
+        page: usize,
+        result: Result<Vec<PathBuf>, String>,
+    },
+    AudioExportFinished {
+        request_id: u64,
+        result: Result<PathBuf, String>,
+    },
+    TtsPagePrefetched {
+        request_id: u64,
+        page: usize,
+        /// The page's clips in order and how many were already cached.
+        result: Result<(Vec<PathBuf>, usize), String>,
+    },
+    SnippetSynthesized {
+        request_id: u64,
+        result: Result<Vec<PathBuf>, String>,
     },
     Tick(Instant),
+    /// Animation frame of a `smooth_scroll` glide.
+    ScrollGlideFrame(Instant),
+    /// Periodic check of `auto_theme_schedule` against the local clock.
+    ThemeScheduleTick,
     PollSystemSignals,
+    NormalizerReloaded(Result<TextNormalizer, String>),
+}
+
+impl Message {
+    /// Whether this message edits reading settings and belongs in the undo history.
+    pub(in crate::app) fn is_settings_change(&self) -> bool {
+        matches!(
+            self,
+            Message::FontSizeChanged(_)
+                | Message::ToggleTheme
+                | Message::ThemeChanged(_)
+                | Message::CustomThemeBackgroundChanged(..)
+                | Message::CustomThemeForegroundChanged(..)
+                | Message::FontFamilyChanged(_)
+                | Message::CustomFontChanged(_)
+                | Message::FontWeightChanged(_)
+                | Message::TextDirectionChanged(_)
+                | Message::ReadingModeChanged(_)
+                | Message::LineSpacingChanged(_)
+                | Message::MarginHorizontalChanged(_)
+                | Message::MarginVerticalChanged(_)
+                | Message::WordSpacingChanged(_)
+                | Message::LetterSpacingChanged(_)
+                | Message::ParagraphSpacingChanged(_)
+                | Message::LinesPerPageChanged(_)
+                | Message::PauseAfterSentenceChanged(_)
+                | Message::PauseAfterParagraphChanged(_)
+                | Message::DayHighlightChanged(..)
+                | Message::NightHighlightChanged(..)
+                | Message::DaySearchHighlightChanged(..)
+                | Message::NightSearchHighlightChanged(..)
+                | Message::HighlightStyleChanged(_)
+                | Message::LogLevelChanged(_)
+                | Message::CommitNumericSettingInput
+                | Message::AdjustNumericSettingByWheel(_)
+                | Message::AutoScrollTtsChanged(_)
+                | Message::LookupWordsOnClickChanged(_)
+                | Message::CenterSpokenSentenceChanged(_)
+                | Message::SmoothScrollChanged(_)
+                | Message::ReadingRulerChanged(_)
+                | Message::HyphenateChanged(_)
+                | Message::CompactControlsChanged(_)
+                | Message::AccessibilityModeChanged(_)
+                | Message::SetTtsSpeed(_)
+                | Message::SetTtsVolume(_)
+        )
+    }
+}
+
+/// Entry in the font picker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontChoice {
+    Builtin(FontFamily),
+    /// Family loaded from the fonts directory.
+    Custom(&'static str),
+}
+
+impl std::fmt::Display for FontChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FontChoice::Builtin(family) => write!(f, "{family}"),
+            FontChoice::Custom(name) => f.write_str(name),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -138,13 +344,35 @@ pub enum Component {
     A,
 }
 
+/// One page rendered by a chapter or book audio cache job.
+#[derive(Debug, Clone, Default)]
+pub struct CachedPageAudio {
+    pub files: Vec<PathBuf>,
+    /// Clips that were already on disk.
+    pub reused: usize,
+    /// Size of `files` on disk.
+    pub bytes: u64,
+}
+
+/// What [`Message::ClearBookCache`] removes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheClearScope {
+    /// The book's sentence audio; bookmarks and settings stay.
+    Audio,
+    /// Sentence audio plus the book's cache directory with its settings,
+    /// bookmarks and reading stats.
+    Everything,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NumericSetting {
     LineSpacing,
     PauseAfterSentence,
+    PauseAfterParagraph,
     LinesPerPage,
     MarginHorizontal,
     MarginVertical,
     WordSpacing,
     LetterSpacing,
+    ParagraphSpacing,
 }
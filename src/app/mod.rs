@@ -4,12 +4,14 @@ mod topbar_layout;
 mod update;
 mod view;
 
-pub use state::App;
+pub use state::{App, StartTarget};
 
 use crate::cache::Bookmark;
-use crate::config::AppConfig;
+use crate::config::{AppConfig, ThemeMode, fonts_dir};
 use crate::epub_loader::LoadedBook;
-use iced::{Point, Size, Theme, window};
+use crate::fonts::{font_families, load_custom_fonts};
+use iced::theme::Palette;
+use iced::{Color, Point, Size, Theme, window};
 
 /// Helper to launch the app with the provided text.
 pub fn run_app(
@@ -17,6 +19,7 @@ pub fn run_app(
     config: AppConfig,
     epub_path: std::path::PathBuf,
     bookmark: Option<Bookmark>,
+    start: Option<StartTarget>,
 ) -> iced::Result {
     let window_settings = window::Settings {
         size: Size::new(config.window_width, config.window_height),
@@ -29,17 +32,29 @@ pub fn run_app(
         ..window::Settings::default()
     };
 
-    iced::application("EPUB Viewer", App::update, App::view)
+    update::detect_reduced_motion();
+    let fonts = load_custom_fonts(&fonts_dir());
+    let families = font_families(&fonts);
+    let application = iced::application(App::title, App::update, App::view)
         .window(window_settings)
         .subscription(App::subscription)
-        .theme(|app: &App| {
-            if matches!(app.config.theme, crate::config::ThemeMode::Night) {
-                Theme::Dark
-            } else {
-                Theme::Light
+        .theme(|app: &App| app_theme(app.config.theme));
+    fonts
+        .into_iter()
+        .fold(application, |application, font| {
+            application.font(font.bytes)
+        })
+        .run_with(move || {
+            let (mut app, task) = App::bootstrap(book, config, epub_path, bookmark);
+            app.set_custom_fonts(families);
+            match start.and_then(|target| app.apply_start_target(&target)) {
+                Some(offset) => (
+                    app,
+                    iced::widget::scrollable::snap_to(state::TEXT_SCROLL_ID.clone(), offset),
+                ),
+                None => (app, task),
             }
         })
-        .run_with(move || App::bootstrap(book, config, epub_path, bookmark))
 }
 
 /// Helper to launch the app in starter mode (no book path yet).
@@ -55,15 +70,53 @@ pub fn run_app_starter(config: AppConfig) -> iced::Result {
         ..window::Settings::default()
     };
 
-    iced::application("EPUB Viewer", App::update, App::view)
+    update::detect_reduced_motion();
+    let fonts = load_custom_fonts(&fonts_dir());
+    let families = font_families(&fonts);
+    let application = iced::application(App::title, App::update, App::view)
         .window(window_settings)
         .subscription(App::subscription)
-        .theme(|app: &App| {
-            if matches!(app.config.theme, crate::config::ThemeMode::Night) {
-                Theme::Dark
-            } else {
-                Theme::Light
-            }
+        .theme(|app: &App| app_theme(app.config.theme));
+    fonts
+        .into_iter()
+        .fold(application, |application, font| {
+            application.font(font.bytes)
         })
-        .run_with(move || App::bootstrap_starter(config))
+        .run_with(move || {
+            let (mut app, task) = App::bootstrap_starter(config);
+            app.set_custom_fonts(families);
+            (app, task)
+        })
+}
+
+fn app_theme(mode: ThemeMode) -> Theme {
+    match mode {
+        ThemeMode::Day => Theme::Light,
+        ThemeMode::Night => Theme::Dark,
+        ThemeMode::Sepia => Theme::custom(
+            "Sepia".to_string(),
+            Palette {
+                background: Color::from_rgb(0.96, 0.92, 0.83),
+                text: Color::from_rgb(0.36, 0.26, 0.16),
+                primary: Color::from_rgb(0.60, 0.42, 0.22),
+                ..Palette::LIGHT
+            },
+        ),
+        ThemeMode::Custom { bg, fg } => {
+            // Accent colors come from the built-in palette matching the background's tone.
+            let base = if mode.is_dark() {
+                Palette::DARK
+            } else {
+                Palette::LIGHT
+            };
+            Theme::custom(
+                "Custom".to_string(),
+                Palette {
+                    background: Color::from_rgb(bg[0], bg[1], bg[2]),
+                    text: Color::from_rgb(fg[0], fg[1], fg[2]),
+                    ..base
+                },
+            )
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use crate::cache::{Bookmark, Note};
 use iced::widget::scrollable::RelativeOffset;
 use std::time::Instant;
 
@@ -12,6 +13,40 @@ pub struct BookmarkState {
     pub(in crate::app) pending_sentence_snap: Option<usize>,
     pub(in crate::app) defer_sentence_snap_until_scroll: bool,
     pub(in crate::app) last_scroll_bookmark_save_at: Option<Instant>,
+    /// User-named bookmarks, kept apart from the automatic resume position.
+    pub(in crate::app) named: Vec<(String, Bookmark)>,
+    pub(in crate::app) name_input: String,
+    /// Outcome of the last bookmark export, shown under the bookmark list.
+    pub(in crate::app) export_status: Option<String>,
+    /// Reader notes for the open book, in the order they were written.
+    pub(in crate::app) notes: Vec<Note>,
+    pub(in crate::app) note_input: String,
+    /// Page opened via a start target; the saved resume point is left alone
+    /// until the reader moves off it.
+    pub(in crate::app) hold_resume_page: Option<usize>,
+    /// Auto-scroll glide in flight under `smooth_scroll`.
+    pub(in crate::app) scroll_glide: Option<ScrollGlide>,
+}
+
+/// An animated auto-scroll toward a sentence, advanced on each animation frame.
+///
+/// The target is recomputed every frame, so layout settling mid-glide is
+/// followed; a new sentence replaces the glide rather than queueing another.
+#[derive(Debug, Clone, Copy)]
+pub struct ScrollGlide {
+    pub(in crate::app) page: usize,
+    pub(in crate::app) sentence: usize,
+    pub(in crate::app) from: RelativeOffset,
+    pub(in crate::app) started_at: Instant,
+}
+
+/// Where to open a book instead of the saved resume point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StartTarget {
+    /// 1-based page number.
+    Page(usize),
+    /// TOC title, or a 1-based chapter number.
+    Chapter(String),
 }
 
 pub struct TextOnlyPreview {
@@ -20,3 +55,15 @@ pub struct TextOnlyPreview {
     pub(in crate::app) display_to_audio: Vec<Option<usize>>,
     pub(in crate::app) audio_to_display: Vec<usize>,
 }
+
+/// Soft-hyphen breaks for the sentences one page shows, worked out when the
+/// page changes rather than on every redraw.
+pub struct PageHyphenation {
+    pub(in crate::app) page: usize,
+    pub(in crate::app) text_only: bool,
+    /// Word and letter spacing the displayed sentences were formatted with.
+    pub(in crate::app) spacing: (u32, u32),
+    pub(in crate::app) patterns: Option<&'static hyphenation::Standard>,
+    /// Byte offsets of the breaks in each displayed sentence.
+    pub(in crate::app) breaks: Vec<Vec<usize>>,
+}
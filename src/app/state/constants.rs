@@ -1,21 +1,53 @@
-use crate::config::{FontFamily, FontWeight};
+use crate::config::{FontFamily, FontWeight, HighlightStyle, LogLevel, ReadingMode, TextDirection};
 use iced::widget::scrollable::Id as ScrollId;
 use once_cell::sync::Lazy;
 
+pub(crate) const APP_TITLE: &str = "EPUB Viewer";
+/// Upper bound on collected search hits so short queries stay responsive.
+pub(crate) const MAX_SEARCH_HITS: usize = 5000;
+/// Largest gap between ticks credited to the word clock, so stalls and resumes don't skip words.
+pub(crate) const MAX_WORD_CLOCK_STEP: std::time::Duration = std::time::Duration::from_millis(250);
+/// Wall-clock gap between system signal polls that means the machine was asleep.
+pub(crate) const SYSTEM_SLEEP_GAP: std::time::Duration = std::time::Duration::from_secs(5);
+/// How often the clock is checked against `auto_theme_schedule`.
+pub(crate) const THEME_SCHEDULE_POLL: std::time::Duration = std::time::Duration::from_secs(30);
+/// Length of a `smooth_scroll` glide to the spoken sentence.
+pub(crate) const SCROLL_GLIDE_DURATION: std::time::Duration = std::time::Duration::from_millis(250);
+/// Frame interval while a scroll glide is running.
+pub(crate) const SCROLL_GLIDE_FRAME: std::time::Duration = std::time::Duration::from_millis(16);
+
 /// Limits and defaults for reader controls.
 pub(crate) const MAX_HORIZONTAL_MARGIN: u16 = 1000;
 pub(crate) const MAX_VERTICAL_MARGIN: u16 = 100;
+/// Narrowest `max_text_width` accepted, so a typo can't squeeze the text to a sliver.
+pub(crate) const MIN_TEXT_WIDTH_CAP: u32 = 200;
 pub(crate) const MAX_WORD_SPACING: u32 = 5;
 pub(crate) const MAX_LETTER_SPACING: u32 = 3;
+pub(crate) const MAX_PARAGRAPH_SPACING: f32 = 48.0;
 pub(crate) const MIN_TTS_SPEED: f32 = 0.1;
 pub(crate) const MAX_TTS_SPEED: f32 = 3.0;
 pub(crate) const MIN_TTS_VOLUME: f32 = 0.0;
 pub(crate) const MAX_TTS_VOLUME: f32 = 2.0;
+pub(crate) const MAX_TTS_LOOKAHEAD: usize = 16;
+/// Spoken by the voice preview button.
+pub(crate) const VOICE_PREVIEW_TEXT: &str =
+    "This is how the selected voice sounds at the current speed.";
 pub(crate) const IMAGE_PREVIEW_HEIGHT_PX: f32 = 240.0;
 pub(crate) const IMAGE_LABEL_FONT_SIZE_PX: f32 = 14.0;
 pub(crate) const IMAGE_LABEL_LINE_HEIGHT: f32 = 1.0;
 pub(crate) const IMAGE_BLOCK_SPACING_PX: f32 = 6.0;
 pub(crate) const PAGE_FLOW_SPACING_PX: f32 = 12.0;
+/// Most pages shown at once in continuous reading; longer chapters are split.
+pub(crate) const MAX_CONTINUOUS_PAGES: usize = 40;
+pub(crate) const PAGE_JUMP_INPUT_WIDTH_PX: f32 = 72.0;
+pub(crate) const CHAPTER_PICKER_WIDTH_PX: f32 = 220.0;
+/// Title characters shown per chapter in the top-bar picker before truncating.
+pub(crate) const CHAPTER_PICKER_TITLE_CHARS: usize = 28;
+pub(crate) const CHAPTER_RULER_WIDTH_PX: f32 = 12.0;
+/// Height of the reading ruler band, in text lines.
+pub(crate) const READING_RULER_LINES: f32 = 1.6;
+/// Opacity of the reading ruler tint.
+pub(crate) const READING_RULER_ALPHA: f32 = 0.18;
 pub(crate) const IMAGE_FOOTER_FONT_SIZE_PX: f32 = 13.0;
 pub(crate) const IMAGE_FOOTER_LINE_HEIGHT: f32 = 1.0;
 pub(crate) static TEXT_SCROLL_ID: Lazy<ScrollId> = Lazy::new(|| ScrollId::new("text-scroll"));
@@ -36,3 +68,18 @@ pub(crate) const FONT_FAMILIES: [FontFamily; 13] = [
 ];
 pub(crate) const FONT_WEIGHTS: [FontWeight; 3] =
     [FontWeight::Light, FontWeight::Normal, FontWeight::Bold];
+pub(crate) const TEXT_DIRECTIONS: [TextDirection; 3] =
+    [TextDirection::Auto, TextDirection::Ltr, TextDirection::Rtl];
+pub(crate) const READING_MODES: [ReadingMode; 2] = [ReadingMode::Paged, ReadingMode::Continuous];
+pub(crate) const HIGHLIGHT_STYLES: [HighlightStyle; 3] = [
+    HighlightStyle::Background,
+    HighlightStyle::Underline,
+    HighlightStyle::Box,
+];
+pub(crate) const LOG_LEVELS: [LogLevel; 5] = [
+    LogLevel::Error,
+    LogLevel::Warn,
+    LogLevel::Info,
+    LogLevel::Debug,
+    LogLevel::Trace,
+];
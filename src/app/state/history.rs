@@ -0,0 +1,132 @@
+use crate::app::messages::Message;
+use crate::config::AppConfig;
+use std::collections::VecDeque;
+use std::mem::Discriminant;
+use std::time::{Duration, Instant};
+
+/// Most settings snapshots kept for undo.
+const SETTINGS_HISTORY_DEPTH: usize = 20;
+/// Repeated changes from the same control within this window (slider drags,
+/// wheel adjustments) collapse into a single undo step.
+const SETTINGS_HISTORY_COALESCE: Duration = Duration::from_secs(1);
+
+/// Bounded undo/redo stacks of configuration snapshots.
+#[derive(Debug, Default)]
+pub struct SettingsHistory {
+    undo: VecDeque<AppConfig>,
+    redo: Vec<AppConfig>,
+    last_change: Option<(Discriminant<Message>, Instant)>,
+}
+
+impl SettingsHistory {
+    /// Remember `before` as the state prior to a settings change made by `control`.
+    pub(in crate::app) fn record(
+        &mut self,
+        before: AppConfig,
+        control: Discriminant<Message>,
+        now: Instant,
+    ) {
+        let coalesce = self.last_change.is_some_and(|(last, at)| {
+            last == control && now.saturating_duration_since(at) < SETTINGS_HISTORY_COALESCE
+        });
+        self.last_change = Some((control, now));
+        self.redo.clear();
+        if coalesce && !self.undo.is_empty() {
+            return;
+        }
+        self.undo.push_back(before);
+        while self.undo.len() > SETTINGS_HISTORY_DEPTH {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Step back one change, returning the snapshot to apply.
+    pub(in crate::app) fn undo(&mut self, current: AppConfig) -> Option<AppConfig> {
+        let snapshot = self.undo.pop_back()?;
+        self.redo.push(current);
+        self.last_change = None;
+        Some(snapshot)
+    }
+
+    /// Re-apply the most recently undone change.
+    pub(in crate::app) fn redo(&mut self, current: AppConfig) -> Option<AppConfig> {
+        let snapshot = self.redo.pop()?;
+        self.undo.push_back(current);
+        self.last_change = None;
+        Some(snapshot)
+    }
+
+    pub(in crate::app) fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub(in crate::app) fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub(in crate::app) fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_font(size: u32) -> AppConfig {
+        AppConfig {
+            font_size: size,
+            ..AppConfig::default()
+        }
+    }
+
+    #[test]
+    fn undo_and_redo_walk_the_history() {
+        let mut history = SettingsHistory::default();
+        let control = std::mem::discriminant(&Message::FontSizeChanged(0));
+        let start = Instant::now();
+        history.record(config_with_font(16), control, start);
+        history.record(
+            config_with_font(18),
+            control,
+            start + Duration::from_secs(5),
+        );
+
+        let undone = history.undo(config_with_font(20)).expect("undo");
+        assert_eq!(undone.font_size, 18);
+        let undone = history.undo(undone).expect("undo");
+        assert_eq!(undone.font_size, 16);
+        assert!(!history.can_undo());
+
+        let redone = history.redo(undone).expect("redo");
+        assert_eq!(redone.font_size, 18);
+        assert!(history.can_redo());
+    }
+
+    #[test]
+    fn rapid_changes_coalesce_and_depth_is_bounded() {
+        let mut history = SettingsHistory::default();
+        let font = std::mem::discriminant(&Message::FontSizeChanged(0));
+        let start = Instant::now();
+        history.record(config_with_font(16), font, start);
+        history.record(
+            config_with_font(17),
+            font,
+            start + Duration::from_millis(200),
+        );
+        assert_eq!(history.undo.len(), 1);
+
+        for step in 0..(SETTINGS_HISTORY_DEPTH as u64 + 5) {
+            history.record(
+                config_with_font(20),
+                font,
+                start + Duration::from_secs(10 + step * 2),
+            );
+        }
+        assert_eq!(history.undo.len(), SETTINGS_HISTORY_DEPTH);
+        assert_eq!(
+            history.undo(config_with_font(30)).map(|c| c.font_size),
+            Some(20)
+        );
+    }
+}
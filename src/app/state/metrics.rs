@@ -0,0 +1,116 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Number of recent samples kept per operation.
+const MAX_TIMING_SAMPLES: usize = 64;
+
+/// Operations whose wall-clock time is recorded for the stats panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimedOperation {
+    Pagination,
+    NormalizerPlan,
+    TtsBatchPrep,
+}
+
+impl TimedOperation {
+    pub(in crate::app) fn label(self) -> &'static str {
+        match self {
+            TimedOperation::Pagination => "Pagination",
+            TimedOperation::NormalizerPlan => "Normalizer plan",
+            TimedOperation::TtsBatchPrep => "TTS batch prep",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimingSummary {
+    pub(in crate::app) count: usize,
+    pub(in crate::app) min: Duration,
+    pub(in crate::app) avg: Duration,
+    pub(in crate::app) max: Duration,
+}
+
+#[derive(Debug, Default)]
+pub struct TimingSamples {
+    samples: VecDeque<Duration>,
+    total_count: usize,
+}
+
+impl TimingSamples {
+    pub(in crate::app) fn record(&mut self, elapsed: Duration) {
+        if self.samples.len() == MAX_TIMING_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(elapsed);
+        self.total_count += 1;
+    }
+
+    /// Min/avg/max over the retained window; `count` covers every recorded sample.
+    pub(in crate::app) fn summary(&self) -> Option<TimingSummary> {
+        let min = self.samples.iter().min().copied()?;
+        let max = self.samples.iter().max().copied()?;
+        let sum: Duration = self.samples.iter().sum();
+        let avg = sum / self.samples.len() as u32;
+        Some(TimingSummary {
+            count: self.total_count,
+            min,
+            avg,
+            max,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct TimingMetrics {
+    pagination: TimingSamples,
+    normalizer_plan: TimingSamples,
+    tts_batch_prep: TimingSamples,
+}
+
+impl TimingMetrics {
+    pub(in crate::app) fn samples_mut(&mut self, operation: TimedOperation) -> &mut TimingSamples {
+        match operation {
+            TimedOperation::Pagination => &mut self.pagination,
+            TimedOperation::NormalizerPlan => &mut self.normalizer_plan,
+            TimedOperation::TtsBatchPrep => &mut self.tts_batch_prep,
+        }
+    }
+
+    pub(in crate::app) fn summary(&self, operation: TimedOperation) -> Option<TimingSummary> {
+        match operation {
+            TimedOperation::Pagination => self.pagination.summary(),
+            TimedOperation::NormalizerPlan => self.normalizer_plan.summary(),
+            TimedOperation::TtsBatchPrep => self.tts_batch_prep.summary(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_reports_min_avg_max() {
+        let mut samples = TimingSamples::default();
+        assert!(samples.summary().is_none());
+        for ms in [30, 10, 20] {
+            samples.record(Duration::from_millis(ms));
+        }
+        let summary = samples.summary().expect("summary");
+        assert_eq!(summary.count, 3);
+        assert_eq!(summary.min, Duration::from_millis(10));
+        assert_eq!(summary.avg, Duration::from_millis(20));
+        assert_eq!(summary.max, Duration::from_millis(30));
+    }
+
+    #[test]
+    fn samples_window_is_bounded() {
+        let mut samples = TimingSamples::default();
+        for ms in 0..(MAX_TIMING_SAMPLES as u64 + 10) {
+            samples.record(Duration::from_millis(ms));
+        }
+        let summary = samples.summary().expect("summary");
+        assert_eq!(summary.count, MAX_TIMING_SAMPLES + 10);
+        assert_eq!(summary.min, Duration::from_millis(10));
+    }
+}
@@ -1,80 +1,243 @@
 mod bookmark;
 mod constants;
+mod history;
+mod metrics;
 mod reader;
+mod stats;
 mod tts;
 mod ui;
 
-use crate::cache::{Bookmark, list_recent_books, save_epub_config};
+use crate::cache::{
+    Bookmark, list_recent_books, load_named_bookmarks, load_notes, load_stats, save_epub_config,
+};
 use crate::calibre::{CalibreColumn, CalibreConfig};
-use crate::config::{AppConfig, FontFamily, FontWeight, HighlightColor, ThemeMode};
-use crate::epub_loader::LoadedBook;
-use crate::normalizer::TextNormalizer;
-use crate::pagination::{MAX_LINES_PER_PAGE, MIN_LINES_PER_PAGE, paginate};
+use crate::config::{
+    AppConfig, FontFamily, FontWeight, HighlightColor, ReadingMode, SpeedRamp, ThemeMode,
+    save_config, saved_settings_path,
+};
+use crate::crossref::CrossRefTarget;
+use crate::dictionary::{DictionarySource, LocalDictionary};
+use crate::epub_loader::{BookDiagnostics, EpubMetadata, LoadedBook};
+use crate::hyphenate::{hyphenate_text, hyphenator_for, soft_hyphen_breaks};
+use crate::normalizer::{CODE_BLOCK_BACKTICK, TextNormalizer};
+use crate::pagination::{
+    MAX_LINES_PER_PAGE, MIN_LINES_PER_PAGE, chars_per_line, collapse_duplicate_pages,
+    paginate_with_breaks,
+};
 use crate::text_utils::split_sentences;
 use crate::tts::TtsEngine;
 use iced::font::{Family, Weight};
+use iced::widget::image;
 use iced::widget::scrollable::RelativeOffset;
 use iced::{Color, Font, Task};
-use regex::Regex;
-use std::path::PathBuf;
-use std::time::Instant;
+use regex::RegexBuilder;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
-use super::messages::{Component, Message, NumericSetting};
+use super::messages::{CacheClearScope, Component, Message, NumericSetting};
 
-pub(in crate::app) use bookmark::{BookmarkState, TextOnlyPreview};
+pub use bookmark::StartTarget;
+pub(in crate::app) use bookmark::{BookmarkState, PageHyphenation, ScrollGlide, TextOnlyPreview};
 pub(crate) use constants::*;
-pub(in crate::app) use reader::ReaderState;
+pub(in crate::app) use history::SettingsHistory;
+pub(crate) use metrics::TimedOperation;
+pub(in crate::app) use metrics::TimingMetrics;
+use reader::continuous_window;
+pub(in crate::app) use reader::{
+    ChapterProgress, ReaderState, SentenceEmphasis, SentenceFootnote, SentenceHeading, TextCounts,
+};
+pub(in crate::app) use stats::ReadingStatsState;
 pub(crate) use tts::TtsLifecycle;
-pub(in crate::app) use tts::{PendingAppendBatch, TtsState};
-pub(in crate::app) use ui::{CalibreState, RecentState, SearchState};
+pub(in crate::app) use tts::{
+    AudioExportJob, ChapterAudioJob, PendingAppendBatch, SentencePauses, SnippetPlayback, TtsState,
+    WordClock, grouped_member_for_elapsed, nearest_sentence_start, word_index_for_elapsed,
+};
+pub(in crate::app) use ui::{
+    CalibreState, RecentState, SearchHit, SearchState, SentenceSelection, WordDefinition,
+};
 
-fn tts_engine_from_config(config: &AppConfig) -> Option<TtsEngine> {
+/// The TTS engine for the configured model and voice, or why it can't start.
+fn tts_engine_from_config(config: &AppConfig) -> Result<TtsEngine, String> {
     TtsEngine::new(
         config.tts_model_path.clone().into(),
         config.tts_espeak_path.clone().into(),
+        &config.tts_voice,
     )
-    .ok()
+    .map_err(|err| {
+        tracing::warn!("TTS unavailable: {err:#}");
+        format!("{err:#}")
+    })
 }
+
+pub(super) fn dictionary_from_config(config: &AppConfig) -> Option<Arc<dyn DictionarySource>> {
+    let path = config.dictionary_path.trim();
+    if path.is_empty() {
+        return None;
+    }
+    match LocalDictionary::load(Path::new(path)) {
+        Ok(dictionary) => Some(Arc::new(dictionary)),
+        Err(err) => {
+            tracing::warn!(path, "Failed to load dictionary: {err:#}");
+            None
+        }
+    }
+}
+
+/// Modification time of the normalizer config, if it exists.
+pub(super) fn normalizer_config_modified() -> Option<SystemTime> {
+    std::fs::metadata(crate::config::normalizer_config_path())
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
 /// Core application state composed of sub-models.
 pub struct App {
     pub(super) starter_mode: bool,
     pub(super) show_stats: bool,
+    pub(super) show_contents: bool,
+    /// Sidebar listing the current page's sentences for click-to-play.
+    pub(super) show_sentence_list: bool,
+    pub(super) show_notes: bool,
+    pub(super) sentence_selection: Option<SentenceSelection>,
+    /// User stopped the `auto_page_seconds` timer from the controls.
+    pub(super) auto_page_paused: bool,
     pub(super) active_numeric_setting: Option<NumericSetting>,
     pub(super) numeric_setting_input: String,
     pub(super) reader: ReaderState,
     pub(super) tts: TtsState,
+    /// Why the TTS engine couldn't start; playback is disabled while set.
+    pub(super) tts_unavailable: Option<String>,
     pub(super) bookmark: BookmarkState,
     pub(super) config: AppConfig,
     pub(super) epub_path: PathBuf,
     pub(super) normalizer: TextNormalizer,
+    /// Modification time of the normalizer config the current rules came from.
+    pub(super) normalizer_modified: Option<SystemTime>,
+    pub(super) normalizer_checked_at: Option<Instant>,
     pub(super) text_only_mode: bool,
     pub(super) text_only_preview: Option<TextOnlyPreview>,
+    pub(super) page_hyphenation: Option<PageHyphenation>,
     pub(super) search: SearchState,
     pub(super) recent: RecentState,
     pub(super) calibre: CalibreState,
     pub(super) open_path_input: String,
     pub(super) book_loading: bool,
     pub(super) book_loading_error: Option<String>,
+    /// The open book's file was moved or deleted after it was loaded.
+    pub(super) source_missing: bool,
+    pub(super) source_checked_at: Option<Instant>,
+    /// Draft new location of a missing book file.
+    pub(super) relocate_path_input: String,
+    /// Outcome of the last relocation attempt, shown in the missing-file banner.
+    pub(super) relocate_status: Option<String>,
     pub(super) pending_window_resize: bool,
     pub(super) pending_window_move: bool,
     pub(super) window_geometry_changed_at: Option<Instant>,
+    pub(super) metrics: TimingMetrics,
+    /// Outcome of the last progress export/import, shown in the stats panel.
+    pub(super) progress_status: Option<String>,
+    /// Cache clear waiting for the user to confirm it.
+    pub(super) pending_cache_clear: Option<CacheClearScope>,
+    /// Outcome of the last cache clear, shown in the stats panel.
+    pub(super) cache_status: Option<String>,
+    /// Last structural check of the open book; the report panel shows while set.
+    pub(super) book_diagnostics: Option<Result<BookDiagnostics, String>>,
+    pub(super) book_diagnostics_running: bool,
+    /// Draft path of the config profile to import or export from settings.
+    pub(super) profile_path_input: String,
+    /// Outcome of the last profile export/import, shown in the settings panel.
+    pub(super) profile_status: Option<String>,
+    /// Draft text of the jump-to-page box; empty shows the current page.
+    pub(super) page_input: String,
+    pub(super) reading_stats: ReadingStatsState,
+    pub(super) settings_history: SettingsHistory,
+    /// Per-line character budget the current pages were laid out with.
+    pub(super) page_chars_per_line: usize,
+    /// Set when the book paginates past `continuous_above_pages`.
+    pub(super) large_book_continuous: bool,
+    /// The reading ruler's handle is held and the ruler follows the pointer.
+    pub(super) reading_ruler_dragging: bool,
+    /// Theme the `auto_theme_schedule` last switched to; a manual change holds
+    /// until the schedule calls for a different one.
+    pub(super) scheduled_theme: Option<ThemeMode>,
+    /// Last window resize not yet reflected in pagination.
+    pub(super) resize_repaginate_at: Option<Instant>,
+    pub(super) dictionary: Option<Arc<dyn DictionarySource>>,
+    /// Families loaded from the fonts directory at startup.
+    pub(super) custom_fonts: Vec<&'static str>,
+    /// Word lookup shown in the definition popup.
+    pub(super) definition: Option<WordDefinition>,
+    /// Footnote shown in the note popup, as an index into `reader.footnotes`.
+    pub(super) open_footnote: Option<usize>,
 }
 
 impl App {
+    /// Text column width used for pagination.
+    ///
+    /// Margins and side panels are left out so adjusting them doesn't reshuffle pages.
+    /// `max_text_width` caps it, matching the column the view draws.
+    pub(super) fn pagination_text_width(&self) -> f32 {
+        // Reader content applies 16px horizontal padding on each side.
+        let width = (self.config.window_width - 32.0).max(0.0);
+        match self.config.max_text_width {
+            Some(cap) => width.min(cap as f32),
+            None => width,
+        }
+    }
+
     /// Re-run pagination after a state change (e.g., font size).
     pub(super) fn repaginate(&mut self) {
-        self.reader.pages = paginate(
+        let started = Instant::now();
+        let chapter_breaks: Vec<usize> = self
+            .reader
+            .toc
+            .iter()
+            .map(|entry| entry.char_offset)
+            .collect();
+        self.reader.pages = paginate_with_breaks(
             &self.reader.full_text,
             self.config.font_size,
             self.config.lines_per_page,
+            self.pagination_text_width(),
+            &chapter_breaks,
+            self.config.fold_orphan_pages,
         );
+        self.page_chars_per_line =
+            chars_per_line(self.pagination_text_width(), self.config.font_size);
+        if self.config.collapse_duplicate_pages {
+            let before = self.reader.pages.len();
+            self.reader.pages = collapse_duplicate_pages(std::mem::take(&mut self.reader.pages));
+            if self.reader.pages.len() != before {
+                tracing::debug!(
+                    removed = before - self.reader.pages.len(),
+                    "Collapsed blank or duplicate pages"
+                );
+            }
+        }
         self.text_only_preview = None;
+        self.page_hyphenation = None;
+        self.tts.prefetched_page = None;
+        self.tts.prefetched_clips.clear();
         if self.reader.pages.is_empty() {
             self.reader
                 .pages
                 .push(String::from("This EPUB appears to contain no text."));
         }
+        let threshold = self.config.continuous_above_pages;
+        let large_book = threshold > 0 && self.reader.pages.len() > threshold;
+        if large_book && !self.large_book_continuous {
+            tracing::warn!(
+                pages = self.reader.pages.len(),
+                threshold,
+                "Book exceeds the paged page limit; switching to continuous reading"
+            );
+        }
+        self.large_book_continuous = large_book;
         self.reader.set_page_clamped(self.reader.current_page);
+        // Sentence indices shift with the page breaks.
+        self.reader.cursor_sentence_idx = None;
         self.reader.page_sentences = self
             .reader
             .pages
@@ -83,12 +246,47 @@ impl App {
             .collect();
         self.reader.page_sentence_counts =
             self.reader.page_sentences.iter().map(Vec::len).collect();
+        self.reader.page_text_counts = self
+            .reader
+            .pages
+            .iter()
+            .map(|page| TextCounts::of(page))
+            .collect();
+        self.reader.book_text_counts = self.reader.page_text_counts.iter().copied().sum();
+        self.reader.rebuild_sentence_markup();
+        self.reader.rebuild_toc_pages();
+        if self.search.visible {
+            self.update_search_matches();
+        }
         tracing::debug!(
             pages = self.reader.pages.len(),
             font_size = self.config.font_size,
             lines_per_page = self.config.lines_per_page,
             "Repaginated content"
         );
+        self.record_timing(
+            TimedOperation::Pagination,
+            started.elapsed(),
+            self.reader.pages.len(),
+        );
+    }
+
+    /// Store a timing sample and, when enabled, log it with structured fields.
+    pub(super) fn record_timing(
+        &mut self,
+        operation: TimedOperation,
+        elapsed: Duration,
+        items: usize,
+    ) {
+        self.metrics.samples_mut(operation).record(elapsed);
+        if self.config.log_timing_metrics {
+            tracing::info!(
+                operation = operation.label(),
+                elapsed_ms = elapsed.as_secs_f64() * 1000.0,
+                items,
+                "Timing sample"
+            );
+        }
     }
 
     pub(super) fn stop_playback(&mut self) {
@@ -99,27 +297,87 @@ impl App {
             playback.stop();
         }
         self.tts.lifecycle = TtsLifecycle::Idle;
+        self.tts.auto_paused = false;
         self.tts.started_at = None;
         self.tts.total_sources = 0;
+        self.tts.preamble = None;
         self.tts.pending_append = false;
         self.tts.pending_append_batch = None;
     }
 
+    /// Drop any voice preview or other snippet along with its temporary audio.
+    pub(super) fn stop_snippet(&mut self) {
+        if let Some(snippet) = self.tts.snippet.take() {
+            if let Some(playback) = snippet.playback {
+                playback.stop();
+            }
+            crate::tts::remove_files(&snippet.files);
+        }
+    }
+
+    /// Window title: the book's metadata title, else its file stem.
+    pub fn title(&self) -> String {
+        if self.starter_mode {
+            return APP_TITLE.to_string();
+        }
+        let book_title = self.reader.metadata.title.clone().or_else(|| {
+            self.epub_path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().trim().to_string())
+                .filter(|stem| !stem.is_empty())
+        });
+        match book_title {
+            Some(title) => format!("{title} - {APP_TITLE}"),
+            None => APP_TITLE.to_string(),
+        }
+    }
+
+    /// Record the fonts loaded at startup, falling back to Sans when the
+    /// configured custom font isn't among them.
+    pub(super) fn set_custom_fonts(&mut self, families: Vec<&'static str>) {
+        self.custom_fonts = families;
+        self.drop_missing_custom_font();
+    }
+
+    pub(super) fn drop_missing_custom_font(&mut self) {
+        let Some(name) = &self.config.custom_font else {
+            return;
+        };
+        if self.active_custom_font().is_none() {
+            tracing::warn!(font = %name, "Custom font not found; falling back to Sans");
+            self.config.custom_font = None;
+            self.config.font_family = FontFamily::Sans;
+        }
+    }
+
+    /// The configured custom font, if it was loaded.
+    pub(super) fn active_custom_font(&self) -> Option<&'static str> {
+        let name = self.config.custom_font.as_deref()?;
+        self.custom_fonts
+            .iter()
+            .find(|family| **family == name)
+            .copied()
+    }
+
     pub(super) fn current_font(&self) -> Font {
-        let family = match self.config.font_family {
-            FontFamily::Sans => Family::SansSerif,
-            FontFamily::Serif => Family::Serif,
-            FontFamily::Monospace => Family::Monospace,
-            FontFamily::Lexend => Family::Name("Lexend"),
-            FontFamily::FiraCode => Family::Name("Fira Code"),
-            FontFamily::AtkinsonHyperlegible => Family::Name("Atkinson Hyperlegible"),
-            FontFamily::AtkinsonHyperlegibleNext => Family::Name("Atkinson Hyperlegible Next"),
-            FontFamily::LexicaUltralegible => Family::Name("Lexica Ultralegible"),
-            FontFamily::Courier => Family::Name("Courier"),
-            FontFamily::FrankGothic => Family::Name("Frank Gothic"),
-            FontFamily::Hermit => Family::Name("Hermit"),
-            FontFamily::Hasklug => Family::Name("Hasklug"),
-            FontFamily::NotoSans => Family::Name("Noto Sans"),
+        let family = if let Some(name) = self.active_custom_font() {
+            Family::Name(name)
+        } else {
+            match self.config.font_family {
+                FontFamily::Sans => Family::SansSerif,
+                FontFamily::Serif => Family::Serif,
+                FontFamily::Monospace => Family::Monospace,
+                FontFamily::Lexend => Family::Name("Lexend"),
+                FontFamily::FiraCode => Family::Name("Fira Code"),
+                FontFamily::AtkinsonHyperlegible => Family::Name("Atkinson Hyperlegible"),
+                FontFamily::AtkinsonHyperlegibleNext => Family::Name("Atkinson Hyperlegible Next"),
+                FontFamily::LexicaUltralegible => Family::Name("Lexica Ultralegible"),
+                FontFamily::Courier => Family::Name("Courier"),
+                FontFamily::FrankGothic => Family::Name("Frank Gothic"),
+                FontFamily::Hermit => Family::Name("Hermit"),
+                FontFamily::Hasklug => Family::Name("Hasklug"),
+                FontFamily::NotoSans => Family::Name("Noto Sans"),
+            }
         };
 
         Font {
@@ -130,13 +388,25 @@ impl App {
     }
 
     pub(super) fn formatted_page_content(&self) -> String {
+        self.formatted_page_text(self.reader.current_page)
+    }
+
+    /// Text of `page` with the configured word and letter spacing applied,
+    /// blank-line runs collapsed under `collapse_blank_lines`, and long words
+    /// hyphenated under `hyphenate`.
+    pub(super) fn formatted_page_text(&self, page: usize) -> String {
         let base = self
             .reader
             .pages
-            .get(self.reader.current_page)
+            .get(page)
             .map(String::as_str)
-            .unwrap_or("")
-            .to_string();
+            .unwrap_or("");
+        let base = if self.config.collapse_blank_lines {
+            collapse_blank_lines(base)
+        } else {
+            base.to_string()
+        };
+        let base = self.hyphenated(base);
 
         if self.config.word_spacing == 0 && self.config.letter_spacing == 0 {
             return base;
@@ -178,6 +448,39 @@ impl App {
             .unwrap_or_default()
     }
 
+    /// Sentences of `page` as handed to the normalizer. Unless `speak_code_blocks`
+    /// is set, code is cut out of each sentence; a sentence of nothing but code is
+    /// left blank so display indices stay aligned.
+    pub(super) fn speech_sentences_for_page(&self, page: usize) -> Vec<String> {
+        let mut sentences = self.raw_sentences_for_page(page);
+        for (idx, sentence) in sentences.iter_mut().enumerate() {
+            for range in self.reader.preformatted_ranges(page, idx).iter().rev() {
+                if self.config.speak_code_blocks {
+                    // Backticks in a code block are code, not inline-code markup.
+                    let code =
+                        sentence[range.clone()].replace('`', &CODE_BLOCK_BACKTICK.to_string());
+                    sentence.replace_range(range.clone(), &code);
+                } else {
+                    sentence.replace_range(range.clone(), "\n");
+                }
+            }
+            if sentence.trim().is_empty() {
+                sentence.clear();
+            }
+        }
+        sentences
+    }
+
+    /// Code ranges of sentence `idx` on `page` to render preformatted; empty
+    /// when `preformatted_blocks` is off.
+    pub(super) fn shown_preformatted(&self, page: usize, idx: usize) -> &[Range<usize>] {
+        if self.config.preformatted_blocks {
+            self.reader.preformatted_ranges(page, idx)
+        } else {
+            &[]
+        }
+    }
+
     pub(super) fn find_audio_start_for_display_sentence(
         &self,
         display_idx: usize,
@@ -206,15 +509,51 @@ impl App {
     }
 
     pub(super) fn display_sentences_for_current_page(&self) -> Vec<String> {
-        if self.config.word_spacing == 0 && self.config.letter_spacing == 0 {
-            return self.raw_sentences_for_page(self.reader.current_page);
+        self.display_sentences_for_page(self.reader.current_page)
+    }
+
+    /// Sentences of `page` as shown: blank-line runs collapsed under
+    /// `collapse_blank_lines`, then word and letter spacing applied.
+    pub(super) fn display_sentences_for_page(&self, page: usize) -> Vec<String> {
+        if self.config.word_spacing == 0
+            && self.config.letter_spacing == 0
+            && !self.config.collapse_blank_lines
+        {
+            return self.raw_sentences_for_page(page);
         }
-        self.raw_sentences_for_page(self.reader.current_page)
+        self.raw_sentences_for_page(page)
             .into_iter()
-            .map(|sentence| self.format_sentence_for_display(&sentence))
+            .enumerate()
+            .map(|(idx, sentence)| {
+                // Code keeps its own spacing so columns still line up.
+                if !self.shown_preformatted(page, idx).is_empty() {
+                    return sentence;
+                }
+                let runs = self.collapsed_blank_lines(page, idx);
+                let sentence = if runs.is_empty() {
+                    sentence
+                } else {
+                    collapse_runs(&sentence, &runs)
+                };
+                self.format_sentence_for_display(&sentence)
+            })
             .collect()
     }
 
+    /// Blank-line runs in sentence `idx` of `page` that `collapse_blank_lines`
+    /// shortens, as byte ranges of the raw sentence. Code is left alone.
+    pub(super) fn collapsed_blank_lines(&self, page: usize, idx: usize) -> Vec<Range<usize>> {
+        if !self.config.collapse_blank_lines || !self.shown_preformatted(page, idx).is_empty() {
+            return Vec::new();
+        }
+        self.reader
+            .page_sentences
+            .get(page)
+            .and_then(|sentences| sentences.get(idx))
+            .map(|sentence| blank_line_runs(sentence))
+            .unwrap_or_default()
+    }
+
     pub(super) fn text_only_preview_for_current_page(&self) -> Option<&TextOnlyPreview> {
         self.text_only_preview
             .as_ref()
@@ -222,27 +561,15 @@ impl App {
     }
 
     pub(super) fn text_only_highlight_audio_idx_for_current_page(&self) -> Option<usize> {
+        if self.tts.preamble.is_some() {
+            return None;
+        }
         let display_idx = self.tts.current_sentence_idx?;
         let preview = self.text_only_preview_for_current_page()?;
         Self::nearest_audio_idx_for_display(display_idx, &preview.display_to_audio)
             .map(|idx| idx.min(preview.audio_sentences.len().saturating_sub(1)))
     }
 
-    pub(super) fn text_only_display_idx_for_audio_idx(&self, audio_idx: usize) -> Option<usize> {
-        self.text_only_preview_for_current_page()
-            .and_then(|preview| preview.audio_to_display.get(audio_idx).copied())
-    }
-
-    pub(super) fn search_sentences_for_current_page(&self) -> Vec<String> {
-        if self.text_only_mode {
-            return self
-                .text_only_preview_for_current_page()
-                .map(|preview| preview.audio_sentences.clone())
-                .unwrap_or_default();
-        }
-        self.display_sentences_for_current_page()
-    }
-
     pub(super) fn refresh_recent_books(&mut self) {
         self.recent.books = list_recent_books(64);
     }
@@ -257,7 +584,7 @@ impl App {
             return;
         }
 
-        let display_sentences = self.raw_sentences_for_page(page);
+        let display_sentences = self.speech_sentences_for_page(page);
         let preview = if display_sentences.is_empty() {
             TextOnlyPreview {
                 page,
@@ -266,9 +593,15 @@ impl App {
                 audio_to_display: Vec::new(),
             }
         } else {
+            let started = Instant::now();
             let plan = self
                 .normalizer
                 .plan_page_cached(&self.epub_path, page, &display_sentences);
+            self.record_timing(
+                TimedOperation::NormalizerPlan,
+                started.elapsed(),
+                plan.audio_sentences.len(),
+            );
             if plan.audio_sentences.is_empty() {
                 TextOnlyPreview {
                     page,
@@ -300,6 +633,114 @@ impl App {
         page.min(total_pages.saturating_sub(1))
     }
 
+    /// Chapter containing `page`.
+    ///
+    /// Without chapter boundaries from the loader each page stands in for its own
+    /// chapter.
+    pub(super) fn chapter_index_for_page(&self, page: usize) -> usize {
+        if self.reader.chapter_starts.is_empty() {
+            return page.min(self.reader.pages.len().saturating_sub(1));
+        }
+        self.reader
+            .chapter_starts
+            .partition_point(|start| *start <= page)
+            .saturating_sub(1)
+    }
+
+    /// Half-open page range covered by `chapter_idx`, if it exists.
+    pub(super) fn chapter_page_range(&self, chapter_idx: usize) -> Option<Range<usize>> {
+        if self.reader.chapter_starts.is_empty() {
+            return (chapter_idx < self.reader.pages.len()).then(|| chapter_idx..chapter_idx + 1);
+        }
+        let start = *self.reader.chapter_starts.get(chapter_idx)?;
+        let end = self
+            .reader
+            .chapter_starts
+            .get(chapter_idx + 1)
+            .copied()
+            .unwrap_or(self.reader.pages.len());
+        (start < end).then_some(start..end)
+    }
+
+    /// Page index a cross-reference resolves to, if it can be resolved.
+    ///
+    /// Chapter numbers need real chapter boundaries. Printed page numbers only
+    /// resolve through the EPUB page-list, since the reader's own pages have
+    /// nothing to do with the print edition's.
+    pub(super) fn crossref_page(&self, target: CrossRefTarget) -> Option<usize> {
+        match target {
+            CrossRefTarget::Chapter(number) => self
+                .reader
+                .chapter_starts
+                .get(number.checked_sub(1)?)
+                .copied(),
+            CrossRefTarget::Page(number) => {
+                let label = number.to_string();
+                let printed = self
+                    .reader
+                    .page_list
+                    .iter()
+                    .find(|printed| printed.label == label)?;
+                Some(self.reader.page_at_offset(printed.char_offset))
+            }
+        }
+    }
+
+    /// Zero-based page requested by the jump-to-page box, clamped to the book.
+    pub(super) fn page_input_target(&self) -> Option<usize> {
+        let number: usize = self.page_input.trim().parse().ok()?;
+        let last_page = self.reader.pages.len().checked_sub(1)?;
+        Some(number.saturating_sub(1).min(last_page))
+    }
+
+    /// Speed the next sentence to finish is played at.
+    pub(in crate::app) fn current_tts_speed(&self) -> f32 {
+        self.config.tts_speed_at(self.tts.spoken_sentences)
+    }
+
+    /// Speeds for `count` sentences queued after `ahead` still waiting to finish.
+    pub(in crate::app) fn queued_tts_speeds(&self, ahead: usize, count: usize) -> Vec<f32> {
+        let first = self.tts.spoken_sentences + ahead;
+        (first..first + count)
+            .map(|spoken| self.config.tts_speed_at(spoken))
+            .collect()
+    }
+
+    /// Time left to hear the rest of the book at the current speed.
+    ///
+    /// Built from the running average of sentences actually spoken, so it is
+    /// `None` until the first sentence finishes and sharpens as playback goes on.
+    pub(super) fn estimated_time_remaining(&self) -> Option<Duration> {
+        let sentence = self.tts.average_spoken_sentence(self.current_tts_speed())?;
+        let pause = Duration::from_secs_f32(self.config.pause_after_sentence.max(0.0));
+        let page = self.reader.current_page;
+        let on_page = self.sentence_count_for_page(page);
+        let current = self.tts.current_sentence_idx.unwrap_or(0).min(on_page);
+        let later_pages: usize = self.reader.page_sentence_counts.iter().skip(page + 1).sum();
+        let remaining = on_page - current + later_pages;
+        Some((sentence + pause).mul_f64(remaining as f64))
+    }
+
+    /// Fraction of the book read, from 0.0 to 1.0.
+    ///
+    /// Whole pages count first; within the current page the spoken sentence is
+    /// used while audio is active, otherwise the scroll position.
+    pub(super) fn reading_progress(&self) -> f32 {
+        let total_pages = self.reader.pages.len().max(1);
+        let page = self.reader.current_page.min(total_pages - 1);
+        let sentence_count = self.sentence_count_for_page(page);
+        let within_page = match self.tts.current_sentence_idx {
+            Some(idx) if self.tts.playback.is_some() && sentence_count > 0 => {
+                idx.min(sentence_count - 1) as f32 / sentence_count as f32
+            }
+            _ if self.bookmark.last_scroll_offset.y.is_finite() => {
+                self.bookmark.last_scroll_offset.y.clamp(0.0, 1.0)
+            }
+            _ => 0.0,
+        };
+        ((page as f32 + within_page) / total_pages as f32).clamp(0.0, 1.0)
+    }
+
     pub(super) fn sentence_count_for_page(&self, page: usize) -> usize {
         self.reader
             .page_sentence_counts
@@ -314,8 +755,31 @@ impl App {
             })
     }
 
+    /// Words on the current page, by Unicode word boundaries.
+    pub(super) fn page_word_count(&self) -> usize {
+        self.page_text_counts(self.reader.current_page).words
+    }
+
+    /// Words in the whole book, cached at pagination.
+    pub(super) fn book_word_count(&self) -> usize {
+        self.reader.book_text_counts.words
+    }
+
+    pub(super) fn page_text_counts(&self, page: usize) -> TextCounts {
+        self.reader
+            .page_text_counts
+            .get(page)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Height of the reading ruler band in pixels.
+    pub(super) fn reading_ruler_height(&self) -> f32 {
+        self.config.font_size as f32 * self.config.line_spacing * READING_RULER_LINES
+    }
+
     pub(super) fn highlight_color(&self) -> Color {
-        let base = if matches!(self.config.theme, ThemeMode::Night) {
+        let base = if self.config.theme.is_dark() {
             self.config.night_highlight
         } else {
             self.config.day_highlight
@@ -328,6 +792,63 @@ impl App {
         }
     }
 
+    /// Rebuild the TTS engine from the config, recording why if it can't start.
+    pub(super) fn reload_tts_engine(&mut self) {
+        match tts_engine_from_config(&self.config) {
+            Ok(engine) => {
+                self.tts.replace_engine(Some(engine));
+                self.tts_unavailable = None;
+            }
+            Err(err) => {
+                self.tts.replace_engine(None);
+                self.tts_unavailable = Some(err);
+            }
+        }
+    }
+
+    /// Where silent sentence stepping continues from: the reading cursor, or
+    /// the TTS sentence when the cursor hasn't been moved on this page.
+    pub(super) fn reading_cursor(&self) -> Option<usize> {
+        self.reader
+            .cursor_on_current_page()
+            .or(self.tts.current_sentence_idx)
+    }
+
+    /// Sentence marked in the text: the spoken one while TTS runs, otherwise
+    /// the reading cursor.
+    pub(super) fn highlighted_sentence_idx(&self) -> Option<usize> {
+        let speaking = self.tts.is_preparing()
+            || self
+                .tts
+                .playback
+                .as_ref()
+                .is_some_and(|playback| !playback.is_paused());
+        if speaking {
+            // The chapter title plays before any sentence is spoken.
+            if self.tts.preamble.is_some() {
+                return None;
+            }
+            self.tts.current_sentence_idx
+        } else {
+            self.reading_cursor()
+        }
+    }
+
+    /// Color search matches are marked with, kept apart from the TTS highlight.
+    pub(super) fn search_highlight_color(&self) -> Color {
+        let base = if self.config.theme.is_dark() {
+            self.config.night_search_highlight
+        } else {
+            self.config.day_search_highlight
+        };
+        Color {
+            r: base.r,
+            g: base.g,
+            b: base.b,
+            a: base.a,
+        }
+    }
+
     fn push_formatted_char(ch: char, word_gap: &str, letter_gap: &str, output: &mut String) {
         match ch {
             ' ' => output.push_str(word_gap),
@@ -362,11 +883,144 @@ impl App {
             })
     }
 
+    /// Interval for automatic page turns, when configured and currently allowed.
+    ///
+    /// Turning stops while TTS runs (it advances pages itself), while the
+    /// window is unfocused and on the last page.
+    pub(super) fn auto_page_interval(&self) -> Option<Duration> {
+        let secs = self.config.auto_page_seconds?;
+        let allowed = !self.starter_mode
+            && !self.auto_page_paused
+            && self.reading_stats.focused
+            && !self.tts.is_playing()
+            && !self.tts.is_preparing()
+            && !self.continuous_reading()
+            && self.reader.current_page + 1 < self.reader.pages.len();
+        allowed.then(|| Duration::from_secs_f32(secs))
+    }
+
+    /// Last TOC entry starting at or before the current page.
+    pub(super) fn current_toc_entry(&self) -> Option<usize> {
+        self.reader
+            .toc_pages
+            .iter()
+            .rposition(|page| *page <= self.reader.current_page)
+    }
+
+    /// Whether the reading view scrolls through whole chapters instead of single pages.
+    pub(super) fn continuous_reading(&self) -> bool {
+        self.config.reading_mode == ReadingMode::Continuous || self.large_book_continuous
+    }
+
+    /// Pages rendered together in the reading view.
+    ///
+    /// Text-only mode keeps to the current page since its preview is built per page.
+    pub(super) fn displayed_pages(&self) -> Range<usize> {
+        let page = self.reader.current_page;
+        if !self.continuous_reading() || self.text_only_mode {
+            return page..page + 1;
+        }
+        self.continuous_section(page)
+    }
+
+    /// Block of pages around `page` that continuous reading shows at once.
+    pub(super) fn continuous_section(&self, page: usize) -> Range<usize> {
+        continuous_window(
+            &self.reader.chapter_starts,
+            self.reader.pages.len(),
+            page,
+            MAX_CONTINUOUS_PAGES,
+        )
+    }
+
+    /// Whether the book reads right-to-left, from config or its EPUB language.
+    pub(super) fn is_rtl(&self) -> bool {
+        self.config
+            .text_direction
+            .is_rtl(self.reader.metadata.language.as_deref())
+    }
+
+    /// `tts_cache_max_mb` in bytes, or `None` when the audio cache is unlimited.
+    pub(super) fn tts_cache_max_bytes(&self) -> Option<u64> {
+        (self.config.tts_cache_max_mb > 0)
+            .then(|| self.config.tts_cache_max_mb.saturating_mul(1024 * 1024))
+    }
+
+    /// `text` as displayed: with soft hyphens in long words under `hyphenate`.
+    pub(super) fn hyphenated(&self, text: String) -> String {
+        match self
+            .page_hyphenation
+            .as_ref()
+            .and_then(|hyph| hyph.patterns)
+        {
+            Some(patterns) => hyphenate_text(&text, patterns),
+            None => text,
+        }
+    }
+
+    /// Soft-hyphen breaks in displayed sentence `idx` of the current page.
+    pub(super) fn sentence_hyphen_breaks(&self, idx: usize) -> &[usize] {
+        self.page_hyphenation
+            .as_ref()
+            .filter(|hyph| hyph.page == self.reader.current_page)
+            .and_then(|hyph| hyph.breaks.get(idx))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Work out the soft-hyphen breaks for the sentences `page` shows, unless
+    /// they are already known; cleared while `hyphenate` is off.
+    pub(super) fn ensure_hyphenation_for_page(&mut self, page: usize) {
+        if !self.config.hyphenate {
+            self.page_hyphenation = None;
+            return;
+        }
+        let spacing = (self.config.word_spacing, self.config.letter_spacing);
+        if self.page_hyphenation.as_ref().is_some_and(|hyph| {
+            hyph.page == page && hyph.text_only == self.text_only_mode && hyph.spacing == spacing
+        }) {
+            return;
+        }
+
+        let patterns = hyphenator_for(self.reader.metadata.language.as_deref());
+        let breaks = match patterns {
+            Some(patterns) => {
+                let sentences = if self.text_only_mode {
+                    self.text_only_preview
+                        .as_ref()
+                        .filter(|preview| preview.page == page)
+                        .map(|preview| preview.audio_sentences.clone())
+                        .unwrap_or_default()
+                } else {
+                    self.display_sentences_for_page(page)
+                };
+                sentences
+                    .iter()
+                    .map(|sentence| soft_hyphen_breaks(sentence, patterns))
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+        self.page_hyphenation = Some(PageHyphenation {
+            page,
+            text_only: self.text_only_mode,
+            spacing,
+            patterns,
+            breaks,
+        });
+    }
+
+    /// Persist the current config where the next load will look for it: the
+    /// book's cache directory, or the saved settings without `per_book_overrides`.
     pub(super) fn save_epub_config(&self) {
         if self.starter_mode {
             return;
         }
-        save_epub_config(&self.epub_path, &self.config);
+        if self.config.per_book_overrides {
+            save_epub_config(&self.epub_path, &self.config);
+        } else if let Err(err) = save_config(&self.config, saved_settings_path()) {
+            tracing::warn!("Failed to save settings: {err:#}");
+        }
     }
 
     pub(super) fn apply_loaded_book(
@@ -382,11 +1036,22 @@ impl App {
         self.starter_mode = false;
         self.book_loading = false;
         self.book_loading_error = None;
+        self.source_missing = false;
+        self.source_checked_at = None;
+        self.relocate_path_input.clear();
+        self.relocate_status = None;
+        self.progress_status = None;
+        self.pending_cache_clear = None;
+        self.cache_status = None;
+        self.book_diagnostics = None;
+        self.book_diagnostics_running = false;
+        self.page_input.clear();
         self.pending_window_resize = false;
         self.pending_window_move = false;
         self.window_geometry_changed_at = None;
         self.text_only_mode = false;
         self.text_only_preview = None;
+        self.page_hyphenation = None;
         self.open_path_input.clear();
         self.search.visible = false;
         self.search.query.clear();
@@ -397,72 +1062,64 @@ impl App {
         self.calibre.visible = false;
         self.calibre.error = None;
         self.show_stats = false;
+        self.show_contents = false;
+        self.show_sentence_list = false;
+        self.show_notes = false;
+        self.sentence_selection = None;
         self.active_numeric_setting = None;
         self.numeric_setting_input.clear();
+        self.settings_history.clear();
+        self.large_book_continuous = false;
         self.config = config;
+        self.normalizer
+            .set_sentence_grouping(self.config.tts_group_sentences);
+        // The book's saved theme stands until the schedule's next switch.
+        self.prime_theme_schedule();
         self.epub_path = epub_path;
         self.reader.full_text = book.text;
         self.reader.images = book.images;
+        self.reader.emphasis = book.emphasis;
+        self.reader.footnotes = book.footnotes;
+        self.reader.headings = book.headings;
+        self.reader.preformatted = book.preformatted;
+        self.reader.toc = book.toc;
+        self.reader.page_list = book.page_list;
+        self.reader.metadata = book.metadata;
+        self.reader.cover = book.cover.map(image::Handle::from_bytes);
         self.reader.set_page_clamped(0);
         self.bookmark.last_scroll_offset = RelativeOffset::START;
         self.bookmark.viewport_fraction = 0.25;
         self.bookmark.pending_sentence_snap = None;
         self.bookmark.defer_sentence_snap_until_scroll = false;
         self.bookmark.last_scroll_bookmark_save_at = None;
-        self.tts = TtsState::new(tts_engine_from_config(&self.config));
+        self.bookmark.named = load_named_bookmarks(&self.epub_path);
+        self.bookmark.name_input.clear();
+        self.bookmark.export_status = None;
+        self.bookmark.notes = load_notes(&self.epub_path);
+        self.bookmark.note_input.clear();
+        self.bookmark.hold_resume_page = None;
+        self.reading_stats =
+            ReadingStatsState::start_session(load_stats(&self.epub_path), Instant::now());
+        self.tts = TtsState::new(None);
+        self.reload_tts_engine();
+        self.dictionary = dictionary_from_config(&self.config);
+        self.drop_missing_custom_font();
+        self.definition = None;
+        self.open_footnote = None;
 
         self.repaginate();
-        let mut initial_scroll: Option<RelativeOffset> = None;
-        if let Some(bookmark) = bookmark {
-            self.reader.set_page_clamped(bookmark.page);
-            let scroll_y = if bookmark.scroll_y.is_finite() {
-                bookmark.scroll_y.clamp(0.0, 1.0)
-            } else {
-                0.0
-            };
-            self.bookmark.last_scroll_offset = RelativeOffset {
-                x: 0.0,
-                y: scroll_y,
-            };
-
-            self.tts.last_sentences = self.raw_sentences_for_page(self.reader.current_page);
-            let restored_idx = bookmark
-                .sentence_text
-                .as_ref()
-                .and_then(|target| self.tts.last_sentences.iter().position(|s| s == target))
-                .or(bookmark.sentence_idx)
-                .map(|idx| idx.min(self.tts.last_sentences.len().saturating_sub(1)));
-            if let Some(idx) = restored_idx {
-                self.tts
-                    .set_current_sentence_clamped(idx, self.tts.last_sentences.len());
-            } else {
-                self.tts.current_sentence_idx = None;
-            }
-            self.bookmark.pending_sentence_snap = self.tts.current_sentence_idx;
-
-            if self.bookmark.last_scroll_offset.y > 0.0 {
-                initial_scroll = Some(self.bookmark.last_scroll_offset);
-            } else if let Some(idx) = restored_idx {
-                if let Some(offset) = self.scroll_offset_for_sentence(idx) {
-                    self.bookmark.last_scroll_offset = offset;
-                    initial_scroll = Some(offset);
-                }
+        let initial_scroll = match bookmark {
+            Some(bookmark) => self.restore_bookmark_position(&bookmark),
+            None => {
+                self.skip_leading_pages();
+                None
             }
-
-            tracing::info!(
-                page = self.reader.current_page + 1,
-                sentence_idx = ?self.tts.current_sentence_idx,
-                scroll = self.bookmark.last_scroll_offset.y,
-                "Restored bookmark from cache"
-            );
-        } else {
-            tracing::info!("Starting from first page");
-        }
+        };
 
         tracing::info!(
             path = %self.epub_path.display(),
             font_size = self.config.font_size,
-            night_mode = matches!(self.config.theme, ThemeMode::Night),
+            theme = %self.config.theme,
             "Loaded book into reader state"
         );
 
@@ -470,65 +1127,207 @@ impl App {
         initial_scroll
     }
 
+    /// Scan every page for the search query (a case-insensitive regex).
+    ///
+    /// The selection moves to the first hit at or after the current page.
     pub(super) fn update_search_matches(&mut self) {
+        self.search.matches.clear();
+        self.search.truncated = false;
+        self.search.selected_match = 0;
         let query = self.search.query.trim();
         if query.is_empty() {
             self.search.error = None;
-            self.search.matches.clear();
-            self.search.selected_match = 0;
             return;
         }
 
-        let regex = match Regex::new(query) {
+        let regex = match RegexBuilder::new(query).case_insensitive(true).build() {
             Ok(regex) => regex,
             Err(err) => {
                 self.search.error = Some(err.to_string());
-                self.search.matches.clear();
-                self.search.selected_match = 0;
                 return;
             }
         };
 
         self.search.error = None;
-        let sentences = self.search_sentences_for_current_page();
-        self.search.matches = sentences
+        'pages: for (page, sentences) in self.reader.page_sentences.iter().enumerate() {
+            for (sentence_idx, sentence) in sentences.iter().enumerate() {
+                for found in regex.find_iter(sentence).filter(|found| !found.is_empty()) {
+                    if self.search.matches.len() == MAX_SEARCH_HITS {
+                        self.search.truncated = true;
+                        break 'pages;
+                    }
+                    self.search.matches.push(SearchHit {
+                        page,
+                        sentence_idx,
+                        char_range: found.range(),
+                    });
+                }
+            }
+        }
+        let current_page = self.reader.current_page;
+        self.search.selected_match = self
+            .search
+            .matches
             .iter()
+            .position(|hit| hit.page >= current_page)
+            .unwrap_or(0);
+    }
+
+    pub(super) fn selected_search_hit(&self) -> Option<&SearchHit> {
+        self.search.matches.get(self.search.selected_match)
+    }
+
+    /// Byte ranges of the search matches in each sentence of `page`, flagged
+    /// when the match is the selected one. Empty while the search bar is closed.
+    pub(super) fn search_marks_for_page(&self, page: usize) -> Vec<Vec<(Range<usize>, bool)>> {
+        let sentence_count = self.sentence_count_for_page(page);
+        let mut marks = vec![Vec::new(); sentence_count];
+        if !self.search.visible {
+            return marks;
+        }
+        // Hits are collected page by page, so the page's hits are contiguous.
+        let first = self.search.matches.partition_point(|hit| hit.page < page);
+        for (offset, hit) in self.search.matches[first..]
+            .iter()
+            .take_while(|hit| hit.page == page)
             .enumerate()
-            .filter_map(|(idx, sentence)| regex.is_match(sentence).then_some(idx))
-            .collect();
-        if self.search.matches.is_empty() {
-            self.search.selected_match = 0;
-        } else {
-            self.search.selected_match = self
-                .search
-                .selected_match
-                .min(self.search.matches.len().saturating_sub(1));
+        {
+            if let Some(sentence) = marks.get_mut(hit.sentence_idx) {
+                let selected = first + offset == self.search.selected_match;
+                sentence.push((hit.char_range.clone(), selected));
+            }
         }
+        marks
     }
 
-    pub(super) fn display_idx_for_search_sentence_idx(&self, sentence_idx: usize) -> Option<usize> {
-        if self.text_only_mode {
-            self.text_only_display_idx_for_audio_idx(sentence_idx)
+    /// Restore page, sentence and scroll from a cached bookmark.
+    ///
+    /// Returns the offset the text view should snap to once it is laid out.
+    pub(super) fn restore_bookmark_position(
+        &mut self,
+        bookmark: &Bookmark,
+    ) -> Option<RelativeOffset> {
+        // The saved page index goes stale whenever the layout changes between
+        // sessions, so follow the saved sentence to wherever it landed.
+        let located = bookmark.sentence_text.as_deref().and_then(|target| {
+            self.reader
+                .locate_sentence(target, bookmark.page, bookmark.sentence_idx.unwrap_or(0))
+        });
+        let relocated = located.is_some_and(|(page, _)| page != bookmark.page);
+        self.reader
+            .set_page_clamped(located.map_or(bookmark.page, |(page, _)| page));
+        // A scroll fraction saved against another page points nowhere useful.
+        let scroll_y = if bookmark.scroll_y.is_finite() && !relocated {
+            bookmark.scroll_y.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self.bookmark.last_scroll_offset = RelativeOffset {
+            x: 0.0,
+            y: scroll_y,
+        };
+
+        self.tts.last_sentences = self.raw_sentences_for_page(self.reader.current_page);
+        let sentence_count = self.tts.last_sentences.len();
+        let snap_to_sentence = self.config.snap_resume_to_sentence;
+        let restored_idx = located
+            .map(|(_, idx)| idx)
+            .or(bookmark.sentence_idx)
+            .or_else(|| {
+                // Older bookmarks only carry a scroll fraction; derive the sentence
+                // it points into so the snap below can land on its start.
+                (snap_to_sentence && scroll_y > 0.0)
+                    .then(|| Self::sentence_idx_for_scroll_fraction(scroll_y, sentence_count))
+                    .flatten()
+            })
+            .map(|idx| idx.min(sentence_count.saturating_sub(1)));
+        if let Some(idx) = restored_idx {
+            self.tts.set_current_sentence_clamped(idx, sentence_count);
         } else {
-            let count = self.sentence_count_for_page(self.reader.current_page);
-            if count == 0 {
-                None
-            } else {
-                Some(sentence_idx.min(count.saturating_sub(1)))
+            self.tts.current_sentence_idx = None;
+        }
+        self.bookmark.pending_sentence_snap = self.tts.current_sentence_idx;
+
+        let mut initial_scroll = None;
+        if let Some(idx) = self.tts.current_sentence_idx {
+            // Without sentence snapping, prefer persisted scroll for initial layout and
+            // rely on the one-time geometry-aware snap after the first viewport update.
+            if self.bookmark.last_scroll_offset.y > 0.0 && !snap_to_sentence {
+                initial_scroll = Some(self.bookmark.last_scroll_offset);
+            } else if let Some(offset) = self.scroll_offset_for_sentence(idx) {
+                self.bookmark.last_scroll_offset = offset;
+                initial_scroll = Some(offset);
             }
+        } else if self.bookmark.last_scroll_offset.y > 0.0 {
+            initial_scroll = Some(self.bookmark.last_scroll_offset);
         }
+
+        tracing::info!(
+            page = self.reader.current_page + 1,
+            sentence_idx = ?self.tts.current_sentence_idx,
+            scroll = self.bookmark.last_scroll_offset.y,
+            snap_to_sentence,
+            "Restored bookmark from cache"
+        );
+        initial_scroll
     }
 
-    pub(super) fn selected_search_sentence_idx(&self) -> Option<usize> {
-        if self.search.matches.is_empty() {
-            None
-        } else {
-            self.search
-                .matches
-                .get(self.search.selected_match)
-                .copied()
-                .or_else(|| self.search.matches.first().copied())
+    /// Start a book that has no resume bookmark, past `skip_leading_pages`
+    /// pages of front matter.
+    pub(super) fn skip_leading_pages(&mut self) {
+        let skip = self.config.skip_leading_pages;
+        if skip == 0 {
+            tracing::info!("Starting from first page");
+            return;
         }
+        self.reader.set_page_clamped(skip);
+        self.tts.last_sentences = self.raw_sentences_for_page(self.reader.current_page);
+        tracing::info!(
+            skip,
+            page = self.reader.current_page + 1,
+            "Skipped leading pages on first open"
+        );
+    }
+
+    /// Jump to a start target in place of the restored bookmark.
+    ///
+    /// Returns the scroll offset to apply, or `None` when the target does not
+    /// resolve and the restored position should stand.
+    pub(super) fn apply_start_target(&mut self, target: &StartTarget) -> Option<RelativeOffset> {
+        let last_page = self.reader.pages.len().saturating_sub(1);
+        let page = match target {
+            StartTarget::Page(number) => number.saturating_sub(1).min(last_page),
+            StartTarget::Chapter(name) => {
+                let by_number = name
+                    .trim()
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|number| self.crossref_page(CrossRefTarget::Chapter(number)));
+                match by_number.or_else(|| self.reader.toc_page_for_title(name)) {
+                    Some(page) => page,
+                    None => {
+                        tracing::warn!(
+                            chapter = %name,
+                            "Start chapter not found in table of contents"
+                        );
+                        return None;
+                    }
+                }
+            }
+        };
+
+        self.reader.set_page_clamped(page);
+        self.tts.last_sentences = self.raw_sentences_for_page(self.reader.current_page);
+        self.tts.current_sentence_idx = None;
+        self.bookmark.last_scroll_offset = RelativeOffset::START;
+        self.bookmark.pending_sentence_snap = None;
+        self.bookmark.hold_resume_page = Some(self.reader.current_page);
+        tracing::info!(
+            page = self.reader.current_page + 1,
+            ?target,
+            "Opened at start target"
+        );
+        Some(RelativeOffset::START)
     }
 
     pub(super) fn bootstrap(
@@ -538,18 +1337,46 @@ impl App {
         bookmark: Option<Bookmark>,
     ) -> (App, Task<Message>) {
         clamp_config(&mut config);
+        let dictionary = dictionary_from_config(&config);
+        let tts_engine = tts_engine_from_config(&config);
+        let mut normalizer = TextNormalizer::load_default();
+        normalizer.set_sentence_grouping(config.tts_group_sentences);
         let mut app = App {
             starter_mode: false,
             show_stats: false,
+            show_contents: false,
+            show_sentence_list: false,
+            show_notes: false,
+            sentence_selection: None,
+            auto_page_paused: false,
             active_numeric_setting: None,
             numeric_setting_input: String::new(),
             reader: ReaderState {
                 pages: Vec::new(),
                 page_sentences: Vec::new(),
                 page_sentence_counts: Vec::new(),
+                page_text_counts: Vec::new(),
+                book_text_counts: TextCounts::default(),
                 full_text: book.text,
                 images: book.images,
+                metadata: book.metadata,
+                cover: book.cover.map(image::Handle::from_bytes),
+                emphasis: book.emphasis,
+                sentence_emphasis: Vec::new(),
+                footnotes: book.footnotes,
+                sentence_footnotes: Vec::new(),
+                headings: book.headings,
+                sentence_headings: Vec::new(),
+                preformatted: book.preformatted,
+                sentence_preformatted: Vec::new(),
+                toc: book.toc,
+                page_list: book.page_list,
+                page_starts: Vec::new(),
+                toc_pages: Vec::new(),
+                chapter_starts: Vec::new(),
                 current_page: 0,
+                cursor_sentence_idx: None,
+                cursor_page: 0,
             },
             bookmark: BookmarkState {
                 last_scroll_offset: RelativeOffset::START,
@@ -561,18 +1388,30 @@ impl App {
                 pending_sentence_snap: None,
                 defer_sentence_snap_until_scroll: false,
                 last_scroll_bookmark_save_at: None,
+                named: load_named_bookmarks(&epub_path),
+                name_input: String::new(),
+                export_status: None,
+                notes: load_notes(&epub_path),
+                note_input: String::new(),
+                hold_resume_page: None,
+                scroll_glide: None,
             },
             epub_path,
-            tts: TtsState::new(tts_engine_from_config(&config)),
+            tts_unavailable: tts_engine.as_ref().err().cloned(),
+            tts: TtsState::new(tts_engine.ok()),
             config,
-            normalizer: TextNormalizer::load_default(),
+            normalizer,
+            normalizer_modified: normalizer_config_modified(),
+            normalizer_checked_at: None,
             text_only_mode: false,
             text_only_preview: None,
+            page_hyphenation: None,
             search: SearchState {
                 visible: false,
                 query: String::new(),
                 error: None,
                 matches: Vec::new(),
+                truncated: false,
                 selected_match: 0,
             },
             recent: RecentState {
@@ -592,74 +1431,66 @@ impl App {
             open_path_input: String::new(),
             book_loading: false,
             book_loading_error: None,
+            source_missing: false,
+            source_checked_at: None,
+            relocate_path_input: String::new(),
+            relocate_status: None,
             pending_window_resize: false,
             pending_window_move: false,
             window_geometry_changed_at: None,
+            metrics: TimingMetrics::default(),
+            progress_status: None,
+            pending_cache_clear: None,
+            cache_status: None,
+            book_diagnostics: None,
+            book_diagnostics_running: false,
+            profile_path_input: String::new(),
+            profile_status: None,
+            page_input: String::new(),
+            reading_stats: ReadingStatsState::default(),
+            settings_history: SettingsHistory::default(),
+            page_chars_per_line: 0,
+            large_book_continuous: false,
+            reading_ruler_dragging: false,
+            scheduled_theme: None,
+            resize_repaginate_at: None,
+            dictionary,
+            custom_fonts: Vec::new(),
+            definition: None,
+            open_footnote: None,
         };
 
+        app.reading_stats =
+            ReadingStatsState::start_session(load_stats(&app.epub_path), Instant::now());
+        app.prime_theme_schedule();
         app.repaginate();
-        let mut init_task = Task::none();
-        match bookmark {
+        let init_task = match bookmark {
             Some(bookmark) => {
-                app.reader.set_page_clamped(bookmark.page);
-                let scroll_y = if bookmark.scroll_y.is_finite() {
-                    bookmark.scroll_y.clamp(0.0, 1.0)
-                } else {
-                    0.0
-                };
-                app.bookmark.last_scroll_offset = RelativeOffset {
-                    x: 0.0,
-                    y: scroll_y,
-                };
-
-                app.tts.last_sentences = app.raw_sentences_for_page(app.reader.current_page);
-                let restored_idx = bookmark
-                    .sentence_text
-                    .as_ref()
-                    .and_then(|target| app.tts.last_sentences.iter().position(|s| s == target))
-                    .or(bookmark.sentence_idx)
-                    .map(|idx| idx.min(app.tts.last_sentences.len().saturating_sub(1)));
-                if let Some(idx) = restored_idx {
-                    app.tts
-                        .set_current_sentence_clamped(idx, app.tts.last_sentences.len());
-                } else {
-                    app.tts.current_sentence_idx = None;
-                }
-                app.bookmark.pending_sentence_snap = app.tts.current_sentence_idx;
-
-                if let Some(idx) = app.tts.current_sentence_idx {
-                    // Prefer persisted scroll for initial layout, then do a one-time
-                    // geometry-aware sentence snap after the first viewport update.
-                    if app.bookmark.last_scroll_offset.y > 0.0 {
-                        init_task = iced::widget::scrollable::snap_to(
-                            TEXT_SCROLL_ID.clone(),
-                            app.bookmark.last_scroll_offset,
+                let scroll = app
+                    .restore_bookmark_position(&bookmark)
+                    .map(|offset| iced::widget::scrollable::snap_to(TEXT_SCROLL_ID.clone(), offset))
+                    .unwrap_or_else(Task::none);
+                let resume = match app.tts_resume_point() {
+                    Some(sentence_idx) => {
+                        tracing::info!(
+                            page = app.reader.current_page + 1,
+                            sentence_idx,
+                            "Preparing paused TTS at the resume point"
                         );
-                    } else if let Some(offset) = app.scroll_offset_for_sentence(idx) {
-                        app.bookmark.last_scroll_offset = offset;
-                        init_task =
-                            iced::widget::scrollable::snap_to(TEXT_SCROLL_ID.clone(), offset);
+                        app.prepare_playback_from(app.reader.current_page, sentence_idx)
                     }
-                } else if app.bookmark.last_scroll_offset.y > 0.0 {
-                    init_task = iced::widget::scrollable::snap_to(
-                        TEXT_SCROLL_ID.clone(),
-                        app.bookmark.last_scroll_offset,
-                    );
-                }
-                tracing::info!(
-                    page = app.reader.current_page + 1,
-                    sentence_idx = ?app.tts.current_sentence_idx,
-                    scroll = app.bookmark.last_scroll_offset.y,
-                    "Restored bookmark from cache"
-                );
+                    None => Task::none(),
+                };
+                Task::batch([scroll, resume])
             }
             None => {
-                tracing::info!("Starting from first page");
+                app.skip_leading_pages();
+                Task::none()
             }
         };
         tracing::info!(
             font_size = app.config.font_size,
-            night_mode = matches!(app.config.theme, ThemeMode::Night),
+            theme = %app.config.theme,
             "Initialized app state"
         );
 
@@ -670,20 +1501,47 @@ impl App {
 
     pub(super) fn bootstrap_starter(mut config: AppConfig) -> (App, Task<Message>) {
         clamp_config(&mut config);
+        let mut normalizer = TextNormalizer::load_default();
+        normalizer.set_sentence_grouping(config.tts_group_sentences);
         let app = App {
             starter_mode: true,
             show_stats: false,
+            show_contents: false,
+            show_sentence_list: false,
+            show_notes: false,
+            sentence_selection: None,
+            auto_page_paused: false,
             active_numeric_setting: None,
             numeric_setting_input: String::new(),
             reader: ReaderState {
                 pages: vec![String::new()],
                 page_sentences: vec![Vec::new()],
                 page_sentence_counts: vec![0],
+                page_text_counts: vec![TextCounts::default()],
+                book_text_counts: TextCounts::default(),
                 full_text: String::new(),
                 images: Vec::new(),
+                metadata: EpubMetadata::default(),
+                cover: None,
+                emphasis: Vec::new(),
+                sentence_emphasis: Vec::new(),
+                footnotes: Vec::new(),
+                sentence_footnotes: Vec::new(),
+                headings: Vec::new(),
+                sentence_headings: Vec::new(),
+                preformatted: Vec::new(),
+                sentence_preformatted: Vec::new(),
+                toc: Vec::new(),
+                page_list: Vec::new(),
+                page_starts: Vec::new(),
+                toc_pages: Vec::new(),
+                chapter_starts: Vec::new(),
                 current_page: 0,
+                cursor_sentence_idx: None,
+                cursor_page: 0,
             },
             tts: TtsState::new(None),
+            tts_unavailable: None,
             bookmark: BookmarkState {
                 last_scroll_offset: RelativeOffset::START,
                 viewport_fraction: 0.25,
@@ -694,17 +1552,28 @@ impl App {
                 pending_sentence_snap: None,
                 defer_sentence_snap_until_scroll: false,
                 last_scroll_bookmark_save_at: None,
+                named: Vec::new(),
+                name_input: String::new(),
+                export_status: None,
+                notes: Vec::new(),
+                note_input: String::new(),
+                hold_resume_page: None,
+                scroll_glide: None,
             },
             config,
             epub_path: PathBuf::new(),
-            normalizer: TextNormalizer::load_default(),
+            normalizer,
+            normalizer_modified: normalizer_config_modified(),
+            normalizer_checked_at: None,
             text_only_mode: false,
             text_only_preview: None,
+            page_hyphenation: None,
             search: SearchState {
                 visible: false,
                 query: String::new(),
                 error: None,
                 matches: Vec::new(),
+                truncated: false,
                 selected_match: 0,
             },
             recent: RecentState {
@@ -724,11 +1593,36 @@ impl App {
             open_path_input: String::new(),
             book_loading: false,
             book_loading_error: None,
+            source_missing: false,
+            source_checked_at: None,
+            relocate_path_input: String::new(),
+            relocate_status: None,
             pending_window_resize: false,
             pending_window_move: false,
             window_geometry_changed_at: None,
+            metrics: TimingMetrics::default(),
+            progress_status: None,
+            pending_cache_clear: None,
+            cache_status: None,
+            book_diagnostics: None,
+            book_diagnostics_running: false,
+            profile_path_input: String::new(),
+            profile_status: None,
+            page_input: String::new(),
+            reading_stats: ReadingStatsState::default(),
+            settings_history: SettingsHistory::default(),
+            page_chars_per_line: 0,
+            large_book_continuous: false,
+            reading_ruler_dragging: false,
+            scheduled_theme: None,
+            resize_repaginate_at: None,
+            dictionary: None,
+            custom_fonts: Vec::new(),
+            definition: None,
+            open_footnote: None,
         };
 
+        app.prime_theme_schedule();
         let init_task = if app.calibre.config.enabled {
             Task::done(Message::PrimeCalibreLoad)
         } else {
@@ -748,6 +1642,17 @@ impl FontWeight {
     }
 }
 
+pub(crate) fn apply_rgb_component(mut rgb: [f32; 3], component: Component, value: f32) -> [f32; 3] {
+    let clamped = value.clamp(0.0, 1.0);
+    match component {
+        Component::R => rgb[0] = clamped,
+        Component::G => rgb[1] = clamped,
+        Component::B => rgb[2] = clamped,
+        Component::A => {}
+    }
+    rgb
+}
+
 pub(crate) fn apply_component(
     mut color: HighlightColor,
     component: Component,
@@ -763,7 +1668,66 @@ pub(crate) fn apply_component(
     color
 }
 
-fn clamp_config(config: &mut AppConfig) {
+/// Shrink whitespace runs spanning three or more line breaks to one blank line.
+fn collapse_blank_lines(text: &str) -> String {
+    collapse_runs(text, &blank_line_runs(text))
+}
+
+/// Whitespace runs holding three or more line breaks, as byte ranges.
+fn blank_line_runs(text: &str) -> Vec<Range<usize>> {
+    let mut runs = Vec::new();
+    let mut run_start = None;
+    let mut newlines = 0;
+    for (idx, ch) in text.char_indices() {
+        if ch.is_whitespace() {
+            run_start.get_or_insert(idx);
+            newlines += usize::from(ch == '\n');
+            continue;
+        }
+        if let Some(start) = run_start.take()
+            && newlines >= 3
+        {
+            runs.push(start..idx);
+        }
+        newlines = 0;
+    }
+    if let Some(start) = run_start
+        && newlines >= 3
+    {
+        runs.push(start..text.len());
+    }
+    runs
+}
+
+/// `text` with each of `runs` replaced by a single blank line.
+fn collapse_runs(text: &str, runs: &[Range<usize>]) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+    for run in runs {
+        output.push_str(&text[last..run.start]);
+        output.push_str("\n\n");
+        last = run.end;
+    }
+    output.push_str(&text[last..]);
+    output
+}
+
+/// Where byte `offset` of the raw text lands once `runs` are collapsed.
+pub(super) fn offset_after_collapse(offset: usize, runs: &[Range<usize>]) -> usize {
+    let mut removed = 0;
+    for run in runs {
+        if offset <= run.start {
+            break;
+        }
+        if offset < run.end {
+            return run.start - removed + (offset - run.start).min(2);
+        }
+        removed += run.len() - 2;
+    }
+    offset - removed
+}
+
+pub(super) fn clamp_config(config: &mut AppConfig) {
     use crate::pagination::{MAX_FONT_SIZE, MIN_FONT_SIZE};
 
     fn normalize_key_binding(value: &mut String, fallback: String) {
@@ -779,27 +1743,242 @@ fn clamp_config(config: &mut AppConfig) {
     config.line_spacing = config.line_spacing.clamp(0.8, 2.5);
     config.margin_horizontal = config.margin_horizontal.min(MAX_HORIZONTAL_MARGIN);
     config.margin_vertical = config.margin_vertical.min(MAX_VERTICAL_MARGIN);
+    config.max_text_width = config
+        .max_text_width
+        .map(|width| width.max(MIN_TEXT_WIDTH_CAP));
     config.window_width = config.window_width.clamp(320.0, 7680.0);
     config.window_height = config.window_height.clamp(240.0, 4320.0);
     config.window_pos_x = config.window_pos_x.filter(|v| v.is_finite());
     config.window_pos_y = config.window_pos_y.filter(|v| v.is_finite());
+    config.auto_page_seconds = config
+        .auto_page_seconds
+        .filter(|secs| secs.is_finite() && *secs > 0.0)
+        .map(|secs| secs.max(1.0));
     config.word_spacing = config.word_spacing.min(MAX_WORD_SPACING);
     config.letter_spacing = config.letter_spacing.min(MAX_LETTER_SPACING);
+    config.auto_theme_schedule = config
+        .auto_theme_schedule
+        .filter(|&(night, day)| night < 24 && day < 24 && night != day);
+    config.reading_ruler_position = if config.reading_ruler_position.is_finite() {
+        config.reading_ruler_position.clamp(0.0, 1.0)
+    } else {
+        0.4
+    };
+    config.paragraph_spacing = if config.paragraph_spacing.is_finite() {
+        config.paragraph_spacing.clamp(0.0, MAX_PARAGRAPH_SPACING)
+    } else {
+        0.0
+    };
     config.lines_per_page = config
         .lines_per_page
         .clamp(MIN_LINES_PER_PAGE, MAX_LINES_PER_PAGE);
     config.pause_after_sentence = config.pause_after_sentence.clamp(0.0, 2.0);
+    config.pause_after_clause = config
+        .pause_after_clause
+        .filter(|secs| secs.is_finite())
+        .map(|secs| secs.clamp(0.0, 2.0));
+    config.pause_after_paragraph = if config.pause_after_paragraph.is_finite() {
+        config.pause_after_paragraph.clamp(0.0, 2.0)
+    } else {
+        0.0
+    };
     config.tts_speed = config.tts_speed.clamp(MIN_TTS_SPEED, MAX_TTS_SPEED);
+    config.speed_ramp = config
+        .speed_ramp
+        .filter(|ramp| ramp.start.is_finite() && ramp.end.is_finite())
+        .map(|ramp| SpeedRamp {
+            start: ramp.start.clamp(MIN_TTS_SPEED, MAX_TTS_SPEED),
+            end: ramp.end.clamp(MIN_TTS_SPEED, MAX_TTS_SPEED),
+            ..ramp
+        });
     config.tts_volume = config.tts_volume.clamp(MIN_TTS_VOLUME, MAX_TTS_VOLUME);
     config.tts_threads = config.tts_threads.max(1);
+    config.tts_lookahead = config.tts_lookahead.clamp(1, MAX_TTS_LOOKAHEAD);
     config.tts_progress_log_interval_secs = config.tts_progress_log_interval_secs.clamp(0.1, 60.0);
     normalize_key_binding(&mut config.key_toggle_play_pause, "space".to_string());
     normalize_key_binding(&mut config.key_safe_quit, "q".to_string());
-    normalize_key_binding(&mut config.key_next_sentence, "f".to_string());
-    normalize_key_binding(&mut config.key_prev_sentence, "s".to_string());
+    normalize_key_binding(&mut config.key_next_sentence, "f,j".to_string());
+    normalize_key_binding(&mut config.key_prev_sentence, "s,k".to_string());
+    normalize_key_binding(&mut config.key_next_page, "right".to_string());
+    normalize_key_binding(&mut config.key_prev_page, "left".to_string());
     normalize_key_binding(&mut config.key_repeat_sentence, "r".to_string());
     normalize_key_binding(&mut config.key_toggle_search, "ctrl+f".to_string());
     normalize_key_binding(&mut config.key_toggle_settings, "ctrl+t".to_string());
     normalize_key_binding(&mut config.key_toggle_stats, "ctrl+g".to_string());
     normalize_key_binding(&mut config.key_toggle_tts, "ctrl+y".to_string());
 }
+
+/// Book path for a test app, unique per call so tests never share cache files.
+#[cfg(test)]
+pub(in crate::app) fn test_book_path() -> PathBuf {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let id = NEXT.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join(format!("ebup-test-{}-{id}.epub", std::process::id()))
+}
+
+/// An app reading `text` with the default configuration, for tests.
+#[cfg(test)]
+pub(in crate::app) fn test_app(text: impl Into<String>) -> App {
+    test_app_with(
+        LoadedBook {
+            text: text.into(),
+            ..Default::default()
+        },
+        AppConfig::default(),
+    )
+}
+
+/// An app reading `book` with `config`, for tests.
+#[cfg(test)]
+pub(in crate::app) fn test_app_with(book: LoadedBook, config: AppConfig) -> App {
+    App::bootstrap(book, config, test_book_path(), None).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::epub_loader::PrintedPage;
+
+    #[test]
+    fn blank_line_runs_collapse_to_one_blank_line() {
+        assert_eq!(
+            collapse_blank_lines("One.\n\n\n\nTwo.\n \n\t\nThree."),
+            "One.\n\nTwo.\n\nThree."
+        );
+        // Paragraph breaks and verse line breaks are left alone.
+        assert_eq!(
+            collapse_blank_lines("Roses are red,\nviolets blue.\n\nNext."),
+            "Roses are red,\nviolets blue.\n\nNext."
+        );
+        assert_eq!(collapse_blank_lines("Tail.\n\n\n"), "Tail.\n\n");
+    }
+
+    #[test]
+    fn shown_sentences_collapse_blank_lines_and_keep_their_emphasis() {
+        let text = "Chapter One\n\n\n\nIt began quietly. Then it ended.";
+        let start = text.find("quietly").expect("word");
+        let book = || LoadedBook {
+            text: text.to_string(),
+            emphasis: vec![crate::epub_loader::EmphasisRun {
+                start,
+                end: start + "quietly".len(),
+                style: crate::epub_loader::EmphasisStyle::Italic,
+            }],
+            ..Default::default()
+        };
+
+        let plain = test_app_with(book(), AppConfig::default());
+        assert!(
+            plain
+                .display_sentences_for_current_page()
+                .concat()
+                .contains("Chapter One\n\n\n\nIt began")
+        );
+
+        let app = test_app_with(
+            book(),
+            AppConfig {
+                collapse_blank_lines: true,
+                ..AppConfig::default()
+            },
+        );
+        let shown = app.display_sentences_for_current_page();
+        assert!(shown.concat().contains("Chapter One\n\nIt began"));
+        assert!(!shown.concat().contains("\n\n\n"));
+
+        let idx = shown
+            .iter()
+            .position(|sentence| sentence.contains("quietly"))
+            .expect("sentence");
+        let runs = app.collapsed_blank_lines(0, idx);
+        let emphasis = &app.reader.sentence_emphasis[0][idx][0].range;
+        let range = offset_after_collapse(emphasis.start, &runs)
+            ..offset_after_collapse(emphasis.end, &runs);
+        assert_eq!(&shown[idx][range], "quietly");
+    }
+
+    #[test]
+    fn code_blocks_keep_their_spacing_and_can_go_unspoken() {
+        let text = "Run this first.\n\nfn main() {}\n\nThen it prints. Done.";
+        let code_start = text.find("fn main").expect("code");
+        let book = LoadedBook {
+            text: text.to_string(),
+            preformatted: vec![code_start..code_start + "fn main() {}".len()],
+            ..Default::default()
+        };
+        let config = AppConfig {
+            word_spacing: 2,
+            preformatted_blocks: true,
+            speak_code_blocks: false,
+            ..AppConfig::default()
+        };
+        let app = test_app_with(book, config);
+
+        let raw = app.raw_sentences_for_page(0);
+        assert_eq!(raw.len(), 3);
+        let display = app.display_sentences_for_current_page();
+        assert_ne!(display[0], raw[0]);
+        assert_eq!(display[1], raw[1]);
+
+        let speech = app.speech_sentences_for_page(0);
+        assert_eq!(speech[0], raw[0]);
+        assert_eq!(speech[1].trim(), "Then it prints.");
+        assert_eq!(speech[2], raw[2]);
+    }
+
+    #[test]
+    fn printed_page_links_need_the_page_list() {
+        let text = (0..200)
+            .map(|i| format!("Sentence number {i} fills the printed edition."))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let offset = text.find("Sentence number 150").expect("sentence");
+        let book = LoadedBook {
+            text,
+            page_list: vec![PrintedPage {
+                label: "12".to_string(),
+                char_offset: offset,
+            }],
+            ..Default::default()
+        };
+        let config = AppConfig {
+            lines_per_page: 8,
+            ..AppConfig::default()
+        };
+        let app = test_app_with(book, config);
+        assert!(app.reader.pages.len() > 2);
+
+        assert_eq!(
+            app.crossref_page(CrossRefTarget::Page(12)),
+            Some(app.reader.page_at_offset(offset))
+        );
+        // The viewer's own page 2 exists, but the print edition's isn't listed.
+        assert_eq!(app.crossref_page(CrossRefTarget::Page(2)), None);
+    }
+
+    #[test]
+    fn hyphen_breaks_follow_the_page_and_the_toggle() {
+        let book = LoadedBook {
+            text: "Extraordinary circumstances. Short words.".to_string(),
+            metadata: EpubMetadata {
+                language: Some("en".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let config = AppConfig {
+            hyphenate: true,
+            ..AppConfig::default()
+        };
+        let mut app = test_app_with(book, config);
+        app.ensure_hyphenation_for_page(0);
+        assert!(!app.sentence_hyphen_breaks(0).is_empty());
+        assert!(app.sentence_hyphen_breaks(1).is_empty());
+
+        app.config.hyphenate = false;
+        app.ensure_hyphenation_for_page(0);
+        assert!(app.page_hyphenation.is_none());
+        assert!(app.sentence_hyphen_breaks(0).is_empty());
+    }
+}
@@ -1,4 +1,72 @@
-use crate::epub_loader::BookImage;
+use crate::epub_loader::{
+    BookImage, EmphasisRun, EmphasisStyle, EpubMetadata, Footnote, Heading, PrintedPage, TocEntry,
+};
+use iced::widget::image;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Emphasized byte range within one page sentence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentenceEmphasis {
+    pub(in crate::app) range: Range<usize>,
+    pub(in crate::app) style: EmphasisStyle,
+}
+
+/// Footnote marker byte range within one page sentence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentenceFootnote {
+    pub(in crate::app) range: Range<usize>,
+    /// Index into [`ReaderState::footnotes`].
+    pub(in crate::app) note: usize,
+}
+
+/// Heading byte range within one page sentence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentenceHeading {
+    pub(in crate::app) range: Range<usize>,
+    pub(in crate::app) level: u8,
+}
+
+/// Word and character totals for a span of text.
+///
+/// Words follow Unicode word boundaries, so each CJK ideograph counts as one
+/// word; characters are non-whitespace grapheme clusters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextCounts {
+    pub(in crate::app) words: usize,
+    pub(in crate::app) chars: usize,
+}
+
+impl TextCounts {
+    pub(in crate::app) fn of(text: &str) -> Self {
+        Self {
+            words: text.unicode_words().count(),
+            chars: text
+                .graphemes(true)
+                .filter(|grapheme| !grapheme.chars().all(char::is_whitespace))
+                .count(),
+        }
+    }
+}
+
+impl std::iter::Sum for TextCounts {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |total, counts| Self {
+            words: total.words + counts.words,
+            chars: total.chars + counts.chars,
+        })
+    }
+}
+
+/// Where a TOC entry stands against the reading position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChapterProgress {
+    /// Ends at or before the furthest page reached.
+    Read,
+    /// Contains the current page.
+    Current,
+    Unread,
+}
 
 /// Reader-related model.
 pub struct ReaderState {
@@ -6,11 +74,54 @@ pub struct ReaderState {
     pub(in crate::app) pages: Vec<String>,
     pub(in crate::app) page_sentences: Vec<Vec<String>>,
     pub(in crate::app) page_sentence_counts: Vec<usize>,
+    /// Word and character counts per page, refreshed with pagination.
+    pub(in crate::app) page_text_counts: Vec<TextCounts>,
+    /// Sum of `page_text_counts`, kept so the stats panel doesn't re-add every page.
+    pub(in crate::app) book_text_counts: TextCounts,
     pub(in crate::app) images: Vec<BookImage>,
+    pub(in crate::app) metadata: EpubMetadata,
+    /// Decoded lazily by the image widget; `None` when the book has no cover.
+    pub(in crate::app) cover: Option<image::Handle>,
+    pub(in crate::app) emphasis: Vec<EmphasisRun>,
+    /// Emphasis per page, per sentence; empty when the book has no emphasis runs.
+    pub(in crate::app) sentence_emphasis: Vec<Vec<Vec<SentenceEmphasis>>>,
+    pub(in crate::app) footnotes: Vec<Footnote>,
+    /// Footnote markers per page, per sentence; empty when the book has no footnotes.
+    pub(in crate::app) sentence_footnotes: Vec<Vec<Vec<SentenceFootnote>>>,
+    pub(in crate::app) headings: Vec<Heading>,
+    /// Headings per page, per sentence; empty when the book has no headings.
+    pub(in crate::app) sentence_headings: Vec<Vec<Vec<SentenceHeading>>>,
+    /// Code block byte ranges within `full_text`.
+    pub(in crate::app) preformatted: Vec<Range<usize>>,
+    /// Code block byte ranges per page, per sentence; empty when the book has none.
+    pub(in crate::app) sentence_preformatted: Vec<Vec<Vec<Range<usize>>>>,
+    pub(in crate::app) toc: Vec<TocEntry>,
+    /// Printed page numbers from the EPUB page-list.
+    pub(in crate::app) page_list: Vec<PrintedPage>,
+    /// Byte offset in `full_text` where each page begins.
+    pub(in crate::app) page_starts: Vec<usize>,
+    /// Page containing each TOC entry's start, parallel to `toc`.
+    pub(in crate::app) toc_pages: Vec<usize>,
+    /// First page of each top-level chapter; empty when the book has no TOC.
+    pub(in crate::app) chapter_starts: Vec<usize>,
     pub(in crate::app) current_page: usize,
+    /// Sentence stepped to while reading silently, on `cursor_page`.
+    pub(in crate::app) cursor_sentence_idx: Option<usize>,
+    pub(in crate::app) cursor_page: usize,
 }
 
 impl ReaderState {
+    /// The silent reading cursor, if it is on the current page.
+    pub(in crate::app) fn cursor_on_current_page(&self) -> Option<usize> {
+        self.cursor_sentence_idx
+            .filter(|_| self.cursor_page == self.current_page)
+    }
+
+    pub(in crate::app) fn set_cursor(&mut self, sentence_idx: usize) {
+        self.cursor_page = self.current_page;
+        self.cursor_sentence_idx = Some(sentence_idx);
+    }
+
     pub(in crate::app) fn set_page_clamped(&mut self, page: usize) {
         if self.pages.is_empty() {
             self.current_page = 0;
@@ -18,4 +129,493 @@ impl ReaderState {
             self.current_page = page.min(self.pages.len().saturating_sub(1));
         }
     }
+
+    /// Code block byte ranges within sentence `idx` of `page`.
+    pub(in crate::app) fn preformatted_ranges(&self, page: usize, idx: usize) -> &[Range<usize>] {
+        self.sentence_preformatted
+            .get(page)
+            .and_then(|page| page.get(idx))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Project book-level emphasis runs, footnote markers, headings and code
+    /// blocks onto the current page sentences.
+    ///
+    /// Sentences are located in `full_text` in reading order; a sentence that cannot
+    /// be found (for example after duplicate pages were collapsed) simply stays plain.
+    pub(in crate::app) fn rebuild_sentence_markup(&mut self) {
+        self.sentence_emphasis.clear();
+        self.sentence_footnotes.clear();
+        self.sentence_headings.clear();
+        self.sentence_preformatted.clear();
+        if self.emphasis.is_empty()
+            && self.footnotes.is_empty()
+            && self.headings.is_empty()
+            && self.preformatted.is_empty()
+        {
+            return;
+        }
+
+        let mut cursor = 0usize;
+        let mut run_idx = 0usize;
+        let mut note_idx = 0usize;
+        let mut heading_idx = 0usize;
+        let mut block_idx = 0usize;
+        for page_sentences in &self.page_sentences {
+            let mut page_emphasis = Vec::with_capacity(page_sentences.len());
+            let mut page_footnotes = Vec::with_capacity(page_sentences.len());
+            let mut page_headings = Vec::with_capacity(page_sentences.len());
+            let mut page_preformatted = Vec::with_capacity(page_sentences.len());
+            for sentence in page_sentences {
+                let trimmed = sentence.trim_start();
+                let lead = sentence.len() - trimmed.len();
+                let trimmed = trimmed.trim_end();
+                let Some(found) = self
+                    .full_text
+                    .get(cursor..)
+                    .and_then(|rest| rest.find(trimmed))
+                else {
+                    page_emphasis.push(Vec::new());
+                    page_footnotes.push(Vec::new());
+                    page_headings.push(Vec::new());
+                    page_preformatted.push(Vec::new());
+                    continue;
+                };
+                let abs_start = cursor + found;
+                let abs_end = abs_start + trimmed.len();
+                cursor = abs_end;
+
+                while run_idx < self.emphasis.len() && self.emphasis[run_idx].end <= abs_start {
+                    run_idx += 1;
+                }
+                let mut spans = Vec::new();
+                for run in self.emphasis[run_idx..]
+                    .iter()
+                    .take_while(|run| run.start < abs_end)
+                {
+                    let start = run.start.max(abs_start) - abs_start + lead;
+                    let end = run.end.min(abs_end) - abs_start + lead;
+                    if start < end {
+                        spans.push(SentenceEmphasis {
+                            range: start..end,
+                            style: run.style,
+                        });
+                    }
+                }
+                page_emphasis.push(spans);
+
+                while note_idx < self.footnotes.len() && self.footnotes[note_idx].end <= abs_start {
+                    note_idx += 1;
+                }
+                let markers = self.footnotes[note_idx..]
+                    .iter()
+                    .enumerate()
+                    .take_while(|(_, note)| note.start < abs_end)
+                    .filter(|(_, note)| abs_start <= note.start && note.end <= abs_end)
+                    .map(|(offset, note)| SentenceFootnote {
+                        range: note.start - abs_start + lead..note.end - abs_start + lead,
+                        note: note_idx + offset,
+                    })
+                    .collect();
+                page_footnotes.push(markers);
+
+                while heading_idx < self.headings.len()
+                    && self.headings[heading_idx].end <= abs_start
+                {
+                    heading_idx += 1;
+                }
+                let headings = self.headings[heading_idx..]
+                    .iter()
+                    .take_while(|heading| heading.start < abs_end)
+                    .filter_map(|heading| {
+                        let start = heading.start.max(abs_start) - abs_start + lead;
+                        let end = heading.end.min(abs_end) - abs_start + lead;
+                        (start < end).then_some(SentenceHeading {
+                            range: start..end,
+                            level: heading.level,
+                        })
+                    })
+                    .collect();
+                page_headings.push(headings);
+
+                while block_idx < self.preformatted.len()
+                    && self.preformatted[block_idx].end <= abs_start
+                {
+                    block_idx += 1;
+                }
+                // Sentences only split at sentence punctuation, so code usually
+                // shares one with the prose around it.
+                let blocks = self.preformatted[block_idx..]
+                    .iter()
+                    .take_while(|block| block.start < abs_end)
+                    .filter_map(|block| {
+                        let start = block.start.max(abs_start) - abs_start + lead;
+                        let end = block.end.min(abs_end) - abs_start + lead;
+                        (start < end).then_some(start..end)
+                    })
+                    .collect();
+                page_preformatted.push(blocks);
+            }
+            self.sentence_emphasis.push(page_emphasis);
+            self.sentence_footnotes.push(page_footnotes);
+            self.sentence_headings.push(page_headings);
+            self.sentence_preformatted.push(page_preformatted);
+        }
+    }
+
+    /// Map TOC offsets onto the current pagination and derive chapter start pages.
+    pub(in crate::app) fn rebuild_toc_pages(&mut self) {
+        self.page_starts = page_start_offsets(&self.full_text, &self.page_sentences);
+        self.toc_pages.clear();
+        self.chapter_starts.clear();
+        if self.toc.is_empty() {
+            return;
+        }
+
+        self.toc_pages = self
+            .toc
+            .iter()
+            .map(|entry| self.page_at_offset(entry.char_offset))
+            .collect();
+
+        let top_depth = self.toc.iter().map(|entry| entry.depth).min().unwrap_or(0);
+        self.chapter_starts = self
+            .toc
+            .iter()
+            .zip(&self.toc_pages)
+            .filter(|(entry, _)| entry.depth == top_depth)
+            .map(|(_, page)| *page)
+            .collect();
+        self.chapter_starts.sort_unstable();
+        self.chapter_starts.dedup();
+    }
+
+    /// Page of the TOC entry whose title best matches `query`.
+    pub(in crate::app) fn toc_page_for_title(&self, query: &str) -> Option<usize> {
+        let idx = find_toc_entry(&self.toc, query)?;
+        self.toc_pages.get(idx).copied()
+    }
+
+    /// Page where the first top-level TOC entry that isn't front matter begins.
+    pub(in crate::app) fn first_chapter_page(&self) -> Option<usize> {
+        let idx = first_chapter_entry(&self.toc)?;
+        self.toc_pages.get(idx).copied()
+    }
+
+    /// Page holding byte `offset` of `full_text` under the current pagination.
+    pub(in crate::app) fn page_at_offset(&self, offset: usize) -> usize {
+        self.page_starts
+            .partition_point(|start| *start <= offset)
+            .saturating_sub(1)
+    }
+
+    /// Where `page` begins in `full_text`.
+    pub(in crate::app) fn page_start(&self, page: usize) -> usize {
+        self.page_starts.get(page).copied().unwrap_or(0)
+    }
+
+    /// Progress of every TOC entry, with chapters before `furthest_offset`
+    /// staying read when the current page is earlier.
+    pub(in crate::app) fn chapter_progress(&self, furthest_offset: usize) -> Vec<ChapterProgress> {
+        chapter_progress(
+            &self.toc,
+            &self.toc_pages,
+            self.pages.len(),
+            self.current_page,
+            self.page_at_offset(furthest_offset).max(self.current_page),
+        )
+    }
+
+    /// Page and sentence index of the occurrence of `target` closest to
+    /// `(page, sentence_idx)`, so positions survive layout changes.
+    pub(in crate::app) fn locate_sentence(
+        &self,
+        target: &str,
+        page: usize,
+        sentence_idx: usize,
+    ) -> Option<(usize, usize)> {
+        nearest_sentence(&self.page_sentences, target, page, sentence_idx)
+    }
+}
+
+/// Closest exact match for `target`, preferring the same page over the same index.
+fn nearest_sentence(
+    page_sentences: &[Vec<String>],
+    target: &str,
+    page: usize,
+    sentence_idx: usize,
+) -> Option<(usize, usize)> {
+    page_sentences
+        .iter()
+        .enumerate()
+        .flat_map(|(page_idx, sentences)| {
+            sentences
+                .iter()
+                .enumerate()
+                .filter(|(_, candidate)| *candidate == target)
+                .map(move |(idx, _)| (page_idx, idx))
+        })
+        .min_by_key(|&(page_idx, idx)| (page_idx.abs_diff(page), idx.abs_diff(sentence_idx)))
+}
+
+/// Title words marking TOC entries that come before the book proper.
+const FRONT_MATTER_TITLES: &[&str] = &[
+    "cover",
+    "title",
+    "title page",
+    "half title",
+    "copyright",
+    "dedication",
+    "contents",
+    "table of contents",
+    "epigraph",
+    "also by",
+    "praise for",
+    "acknowledgments",
+    "acknowledgements",
+];
+
+/// Index of the first top-level TOC entry whose title isn't front matter.
+fn first_chapter_entry(toc: &[TocEntry]) -> Option<usize> {
+    let top_depth = toc.iter().map(|entry| entry.depth).min()?;
+    toc.iter().position(|entry| {
+        let title = entry.title.trim().to_lowercase();
+        entry.depth == top_depth
+            && !title.is_empty()
+            && !FRONT_MATTER_TITLES
+                .iter()
+                .any(|front| title == *front || title.starts_with(&format!("{front} ")))
+    })
+}
+
+/// Index of the TOC entry titled `query`, ignoring case; falls back to the
+/// first title containing it.
+fn find_toc_entry(toc: &[TocEntry], query: &str) -> Option<usize> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+    let titles: Vec<String> = toc
+        .iter()
+        .map(|entry| entry.title.trim().to_lowercase())
+        .collect();
+    titles
+        .iter()
+        .position(|title| *title == query)
+        .or_else(|| titles.iter().position(|title| title.contains(&query)))
+}
+
+/// Byte offset in `full_text` where each page's first sentence begins.
+///
+/// Pages whose opening sentence cannot be located inherit the previous start so
+/// the result stays sorted.
+fn page_start_offsets(full_text: &str, page_sentences: &[Vec<String>]) -> Vec<usize> {
+    let mut starts = Vec::with_capacity(page_sentences.len());
+    let mut cursor = 0usize;
+    for sentences in page_sentences {
+        let first = sentences
+            .iter()
+            .map(|sentence| sentence.trim())
+            .find(|sentence| !sentence.is_empty());
+        if let Some(found) = first.and_then(|first| full_text.get(cursor..)?.find(first)) {
+            cursor += found;
+        }
+        starts.push(cursor);
+    }
+    starts
+}
+
+/// An entry spans from its start page to the next entry at the same or a
+/// shallower depth, so a chapter stays current while one of its sections is.
+fn chapter_progress(
+    toc: &[TocEntry],
+    toc_pages: &[usize],
+    total_pages: usize,
+    current_page: usize,
+    furthest_page: usize,
+) -> Vec<ChapterProgress> {
+    toc.iter()
+        .zip(toc_pages)
+        .enumerate()
+        .map(|(idx, (entry, &start))| {
+            let end = toc
+                .iter()
+                .zip(toc_pages)
+                .skip(idx + 1)
+                .find(|(next, _)| next.depth <= entry.depth)
+                .map_or(total_pages, |(_, &page)| page);
+            if (start..end.max(start + 1)).contains(&current_page) {
+                ChapterProgress::Current
+            } else if end <= furthest_page {
+                ChapterProgress::Read
+            } else {
+                ChapterProgress::Unread
+            }
+        })
+        .collect()
+}
+
+/// Pages shown together in continuous reading: the chapter holding `page`,
+/// split into blocks of at most `max_pages`.
+///
+/// Pages before the first chapter start form their own leading section.
+pub(super) fn continuous_window(
+    chapter_starts: &[usize],
+    total_pages: usize,
+    page: usize,
+    max_pages: usize,
+) -> Range<usize> {
+    let page = page.min(total_pages.saturating_sub(1));
+    let idx = chapter_starts.partition_point(|start| *start <= page);
+    let start = idx.checked_sub(1).map_or(0, |prev| chapter_starts[prev]);
+    let end = chapter_starts
+        .get(idx)
+        .copied()
+        .unwrap_or(total_pages)
+        .clamp(page + 1, total_pages.max(page + 1));
+    let max_pages = max_pages.max(1);
+    let block_start = start + (page - start) / max_pages * max_pages;
+    block_start..(block_start + max_pages).min(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_counts_use_unicode_word_boundaries() {
+        assert_eq!(
+            TextCounts::of("Call me  Ishmael, don't."),
+            TextCounts {
+                words: 4,
+                chars: 20
+            }
+        );
+        // No spaces between Japanese words; each ideograph and kana is a word.
+        assert_eq!(
+            TextCounts::of("吾輩は猫である。"),
+            TextCounts { words: 7, chars: 8 }
+        );
+        assert_eq!(
+            [TextCounts::of("one two"), TextCounts::of("three")]
+                .into_iter()
+                .sum::<TextCounts>(),
+            TextCounts {
+                words: 3,
+                chars: 11
+            }
+        );
+    }
+
+    #[test]
+    fn chapters_before_the_furthest_page_stay_read() {
+        use ChapterProgress::{Current, Read, Unread};
+        let entry = |depth| TocEntry {
+            title: String::new(),
+            depth,
+            char_offset: 0,
+        };
+        let toc = [entry(0), entry(1), entry(1), entry(0), entry(0)];
+        let pages = [0, 0, 3, 6, 9];
+
+        assert_eq!(
+            chapter_progress(&toc, &pages, 12, 4, 4),
+            [Current, Read, Current, Unread, Unread]
+        );
+        assert_eq!(
+            chapter_progress(&toc, &pages, 12, 1, 10),
+            [Current, Current, Read, Read, Unread]
+        );
+    }
+
+    #[test]
+    fn continuous_window_follows_chapters_and_caps_length() {
+        let starts = [2, 5, 30];
+        assert_eq!(continuous_window(&starts, 40, 0, 10), 0..2);
+        assert_eq!(continuous_window(&starts, 40, 3, 10), 2..5);
+        assert_eq!(continuous_window(&starts, 40, 5, 10), 5..15);
+        assert_eq!(continuous_window(&starts, 40, 27, 10), 25..30);
+        assert_eq!(continuous_window(&starts, 40, 39, 10), 30..40);
+        assert_eq!(continuous_window(&[], 25, 12, 10), 10..20);
+        assert_eq!(continuous_window(&[], 1, 0, 10), 0..1);
+    }
+
+    #[test]
+    fn nearest_sentence_prefers_the_closest_page() {
+        let pages = vec![
+            vec!["Hello.".to_string(), " Again.".to_string()],
+            vec!["Other.".to_string()],
+            vec![
+                "Other.".to_string(),
+                " Hello.".to_string(),
+                "Hello.".to_string(),
+            ],
+        ];
+        assert_eq!(nearest_sentence(&pages, "Hello.", 0, 1), Some((0, 0)));
+        assert_eq!(nearest_sentence(&pages, "Hello.", 2, 0), Some((2, 2)));
+        assert_eq!(nearest_sentence(&pages, "Other.", 0, 0), Some((1, 0)));
+        assert_eq!(nearest_sentence(&pages, "Missing.", 1, 0), None);
+    }
+
+    #[test]
+    fn page_starts_follow_first_sentences() {
+        let full_text = "One. Two.\n\nThree. Four.\n\nFive.";
+        let pages = vec![
+            vec!["One.".to_string(), " Two.".to_string()],
+            vec!["Three.".to_string(), " Four.".to_string()],
+            vec!["Missing.".to_string()],
+            vec!["Five.".to_string()],
+        ];
+        let starts = page_start_offsets(full_text, &pages);
+        assert_eq!(
+            starts,
+            vec![
+                0,
+                full_text.find("Three.").unwrap(),
+                full_text.find("Three.").unwrap(),
+                full_text.find("Five.").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn toc_titles_match_exactly_before_partially() {
+        let entry = |title: &str| TocEntry {
+            title: title.to_string(),
+            depth: 0,
+            char_offset: 0,
+        };
+        let toc = vec![
+            entry("Chapter 15: Endings"),
+            entry("Chapter 1"),
+            entry("Chapter 5"),
+        ];
+        assert_eq!(find_toc_entry(&toc, "chapter 5"), Some(2));
+        assert_eq!(find_toc_entry(&toc, " CHAPTER 1 "), Some(1));
+        assert_eq!(find_toc_entry(&toc, "endings"), Some(0));
+        assert_eq!(find_toc_entry(&toc, "Epilogue"), None);
+        assert_eq!(find_toc_entry(&toc, "  "), None);
+    }
+
+    #[test]
+    fn first_chapter_skips_front_matter() {
+        let entry = |title: &str, depth: usize| TocEntry {
+            title: title.to_string(),
+            depth,
+            char_offset: 0,
+        };
+        let toc = vec![
+            entry("Cover", 0),
+            entry("Title Page", 0),
+            entry("Copyright", 0),
+            entry("Dedication", 0),
+            entry("Also by the Author", 1),
+            entry("Chapter 1: Titles", 0),
+            entry("Chapter 2", 0),
+        ];
+        assert_eq!(first_chapter_entry(&toc), Some(5));
+        assert_eq!(first_chapter_entry(&toc[..4]), None);
+        assert_eq!(first_chapter_entry(&[]), None);
+    }
 }
@@ -0,0 +1,249 @@
+use crate::cache::ReadingStats;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Longest gap between polls credited as reading time, so a suspended machine
+/// or stalled event loop doesn't inflate the total.
+const MAX_READING_STEP: Duration = Duration::from_secs(2);
+/// Minimum spacing between stats writes.
+const STATS_SAVE_INTERVAL: Duration = Duration::from_secs(30);
+/// Timed pages behind the rolling reading speed.
+const ROLLING_SPEED_PAGES: usize = 10;
+/// Pages turned faster than this were flipped past rather than read.
+const MIN_TIMED_PAGE: Duration = Duration::from_secs(3);
+
+/// Per-book reading statistics and the bookkeeping needed to accumulate them.
+#[derive(Debug, Default)]
+pub struct ReadingStatsState {
+    pub(in crate::app) stats: ReadingStats,
+    pub(in crate::app) focused: bool,
+    last_tick: Option<Instant>,
+    pending: Duration,
+    dirty: bool,
+    last_saved_at: Option<Instant>,
+    /// When silent reading of the current page began; `None` once listening
+    /// or leaving the window makes the page's time meaningless.
+    page_started: Option<Instant>,
+    /// Word count and time of the most recent timed pages, oldest first.
+    recent_pages: VecDeque<(usize, Duration)>,
+    silent_pending: Duration,
+}
+
+impl ReadingStatsState {
+    /// Begin a new reading session on top of previously saved totals.
+    pub(in crate::app) fn start_session(mut stats: ReadingStats, now: Instant) -> Self {
+        stats.sessions += 1;
+        Self {
+            stats,
+            focused: true,
+            last_tick: Some(now),
+            pending: Duration::ZERO,
+            dirty: true,
+            last_saved_at: None,
+            page_started: Some(now),
+            recent_pages: VecDeque::new(),
+            silent_pending: Duration::ZERO,
+        }
+    }
+
+    /// Credit time since the previous call while the window is focused.
+    pub(in crate::app) fn record_time(&mut self, now: Instant) {
+        if let (Some(last), true) = (self.last_tick, self.focused) {
+            self.pending += now.saturating_duration_since(last).min(MAX_READING_STEP);
+            let whole_secs = self.pending.as_secs();
+            if whole_secs > 0 {
+                self.stats.total_seconds += whole_secs;
+                self.pending -= Duration::from_secs(whole_secs);
+                self.dirty = true;
+            }
+        }
+        self.last_tick = Some(now);
+    }
+
+    pub(in crate::app) fn set_focused(&mut self, focused: bool, now: Instant) {
+        self.record_time(now);
+        self.focused = focused;
+        if !focused {
+            self.stop_page_timer();
+        }
+    }
+
+    /// Stop timing the current page; it is left out of the reading speed.
+    pub(in crate::app) fn stop_page_timer(&mut self) {
+        self.page_started = None;
+    }
+
+    /// Time the next page from `now`, or not at all while unfocused.
+    pub(in crate::app) fn restart_page_timer(&mut self, now: Instant) {
+        self.page_started = self.focused.then_some(now);
+    }
+
+    /// Credit `words` to the reading speed for the page just finished, then
+    /// start timing the next one. Pages flipped past or left open longer than
+    /// `idle_timeout` are discarded.
+    pub(in crate::app) fn record_silent_page(
+        &mut self,
+        words: usize,
+        now: Instant,
+        idle_timeout: Duration,
+    ) {
+        let started = self.page_started;
+        self.restart_page_timer(now);
+        let Some(started) = started else {
+            return;
+        };
+        let elapsed = now.saturating_duration_since(started);
+        if words == 0 || elapsed < MIN_TIMED_PAGE || elapsed > idle_timeout {
+            return;
+        }
+        if self.recent_pages.len() == ROLLING_SPEED_PAGES {
+            self.recent_pages.pop_front();
+        }
+        self.recent_pages.push_back((words, elapsed));
+
+        self.stats.silent_words += words as u64;
+        self.silent_pending += elapsed;
+        let whole_secs = self.silent_pending.as_secs();
+        self.stats.silent_seconds += whole_secs;
+        self.silent_pending -= Duration::from_secs(whole_secs);
+        self.dirty = true;
+    }
+
+    /// Words per minute over the last few timed pages of this session.
+    pub(in crate::app) fn rolling_wpm(&self) -> Option<f64> {
+        let words = self
+            .recent_pages
+            .iter()
+            .map(|(words, _)| *words as u64)
+            .sum();
+        let elapsed = self.recent_pages.iter().map(|(_, elapsed)| *elapsed).sum();
+        words_per_minute(words, elapsed)
+    }
+
+    /// Words per minute over every timed page of this book.
+    pub(in crate::app) fn average_wpm(&self) -> Option<f64> {
+        words_per_minute(
+            self.stats.silent_words,
+            Duration::from_secs(self.stats.silent_seconds) + self.silent_pending,
+        )
+    }
+
+    /// Timed pages behind [`Self::rolling_wpm`].
+    pub(in crate::app) fn rolling_pages(&self) -> usize {
+        self.recent_pages.len()
+    }
+
+    /// Count a page turn. `reached` is where the new page starts when the turn
+    /// was to the next page, which is what extends the furthest point read;
+    /// jumps through the contents or search do not.
+    pub(in crate::app) fn record_page_turn(&mut self, reached: Option<usize>) {
+        self.stats.pages_turned += 1;
+        if let Some(offset) = reached {
+            self.stats.furthest_offset = self.stats.furthest_offset.max(offset);
+        }
+        self.dirty = true;
+    }
+
+    /// Stats to write now, if anything changed and the last write is old enough.
+    pub(in crate::app) fn take_due_save(&mut self, now: Instant) -> Option<ReadingStats> {
+        if !self.dirty {
+            return None;
+        }
+        if let Some(saved_at) = self.last_saved_at {
+            if now.saturating_duration_since(saved_at) < STATS_SAVE_INTERVAL {
+                return None;
+            }
+        }
+        self.dirty = false;
+        self.last_saved_at = Some(now);
+        Some(self.stats)
+    }
+
+    /// Unsaved stats regardless of debounce, for shutdown and book switches.
+    pub(in crate::app) fn take_unsaved(&mut self) -> Option<ReadingStats> {
+        std::mem::take(&mut self.dirty).then_some(self.stats)
+    }
+}
+
+fn words_per_minute(words: u64, elapsed: Duration) -> Option<f64> {
+    (words > 0 && elapsed >= MIN_TIMED_PAGE).then(|| words as f64 * 60.0 / elapsed.as_secs_f64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accumulates_focused_time_only() {
+        let start = Instant::now();
+        let mut state = ReadingStatsState::start_session(ReadingStats::default(), start);
+        assert_eq!(state.stats.sessions, 1);
+
+        state.record_time(start + Duration::from_millis(1500));
+        assert_eq!(state.stats.total_seconds, 1);
+        state.set_focused(false, start + Duration::from_millis(1900));
+        state.record_time(start + Duration::from_secs(60));
+        assert_eq!(state.stats.total_seconds, 1);
+
+        state.set_focused(true, start + Duration::from_secs(61));
+        // Long gaps are capped rather than credited in full.
+        state.record_time(start + Duration::from_secs(600));
+        assert_eq!(state.stats.total_seconds, 3);
+    }
+
+    #[test]
+    fn saves_are_debounced() {
+        let start = Instant::now();
+        let mut state = ReadingStatsState::start_session(ReadingStats::default(), start);
+        assert!(state.take_due_save(start).is_some());
+        assert!(state.take_due_save(start).is_none());
+
+        state.record_page_turn(Some(700));
+        assert!(
+            state
+                .take_due_save(start + Duration::from_secs(5))
+                .is_none()
+        );
+        let saved = state
+            .take_due_save(start + STATS_SAVE_INTERVAL)
+            .expect("due");
+        assert_eq!(saved.pages_turned, 1);
+        assert_eq!(saved.furthest_offset, 700);
+
+        state.record_page_turn(Some(300));
+        state.record_page_turn(None);
+        assert_eq!(
+            state
+                .take_unsaved()
+                .map(|s| (s.pages_turned, s.furthest_offset)),
+            Some((3, 700))
+        );
+        assert!(state.take_unsaved().is_none());
+    }
+
+    #[test]
+    fn reading_speed_skips_idle_and_flipped_pages() {
+        let start = Instant::now();
+        let idle = Duration::from_secs(300);
+        let mut state = ReadingStatsState::start_session(ReadingStats::default(), start);
+        assert_eq!(state.rolling_wpm(), None);
+
+        // 250 words in a minute, then a page flipped past and one left open overnight.
+        state.record_silent_page(250, start + Duration::from_secs(60), idle);
+        state.record_silent_page(250, start + Duration::from_secs(61), idle);
+        state.record_silent_page(250, start + Duration::from_secs(10_000), idle);
+        assert_eq!(state.rolling_pages(), 1);
+        assert_eq!(state.rolling_wpm(), Some(250.0));
+
+        // Listening in between leaves the next page untimed.
+        state.stop_page_timer();
+        state.record_silent_page(250, start + Duration::from_secs(10_030), idle);
+        state.record_silent_page(200, start + Duration::from_secs(10_090), idle);
+        assert_eq!(state.rolling_wpm(), Some(225.0));
+        assert_eq!(
+            (state.stats.silent_words, state.stats.silent_seconds),
+            (450, 120)
+        );
+        assert_eq!(state.average_wpm(), Some(225.0));
+    }
+}
@@ -1,6 +1,9 @@
-use crate::tts::{TtsEngine, TtsPlayback};
+use crate::config::AppConfig;
+use crate::tts::{TtsEngine, TtsPlayback, VoiceInfo};
 use std::path::PathBuf;
-use std::time::{Duration, Instant};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant, SystemTime};
 
 /// Runtime TTS model (configuration lives in `AppConfig`).
 pub struct PendingAppendBatch {
@@ -10,6 +13,68 @@ pub struct PendingAppendBatch {
     pub(in crate::app) audio_sentences: Vec<String>,
 }
 
+/// Background job that renders a chapter's, or the whole book's, audio into the disk
+/// cache without playing it.
+pub struct ChapterAudioJob {
+    /// `None` when caching the whole book.
+    pub(in crate::app) chapter_idx: Option<usize>,
+    pub(in crate::app) request_id: u64,
+    pub(in crate::app) next_page: usize,
+    pub(in crate::app) end_page: usize,
+    pub(in crate::app) pages_done: usize,
+    pub(in crate::app) page_total: usize,
+    pub(in crate::app) synthesized: usize,
+    pub(in crate::app) reused: usize,
+    /// Set on cancel so the page in flight stops dispatching sentences.
+    pub(in crate::app) cancel: Arc<AtomicBool>,
+    /// Clips cached so far, spared by `tts_cache_max_mb` eviction.
+    pub(in crate::app) clips: Vec<PathBuf>,
+    /// Size of `clips` on disk.
+    pub(in crate::app) bytes: u64,
+    /// Page interrupted by playback, retried once playback's batch is prepared.
+    pub(in crate::app) deferred_page: Option<usize>,
+}
+
+impl ChapterAudioJob {
+    /// What is being cached, for status lines.
+    pub(in crate::app) fn label(&self) -> String {
+        match self.chapter_idx {
+            Some(idx) => format!("chapter {}", idx + 1),
+            None => "the book".to_string(),
+        }
+    }
+}
+
+/// Background job that renders every page's audio and joins it into one file.
+pub struct AudioExportJob {
+    pub(in crate::app) request_id: u64,
+    pub(in crate::app) output: PathBuf,
+    pub(in crate::app) next_page: usize,
+    pub(in crate::app) page_total: usize,
+    pub(in crate::app) files: Vec<PathBuf>,
+    /// Set on cancel so the page or write in flight stops early.
+    pub(in crate::app) cancel: Arc<AtomicBool>,
+}
+
+/// Throwaway audio played outside the book, e.g. a voice preview.
+pub struct SnippetPlayback {
+    pub(in crate::app) request_id: u64,
+    /// `None` while the snippet is still being synthesized.
+    pub(in crate::app) playback: Option<TtsPlayback>,
+    /// Temporary clips, deleted once the snippet finishes or is replaced.
+    pub(in crate::app) files: Vec<PathBuf>,
+    /// Book playback was paused for the snippet and resumes after it.
+    pub(in crate::app) resume_book: bool,
+}
+
+/// Time spent inside the audio sentence that is currently playing.
+#[derive(Debug, Clone, Copy)]
+pub struct WordClock {
+    pub(in crate::app) audio_idx: usize,
+    pub(in crate::app) elapsed: Duration,
+    pub(in crate::app) last_tick: Instant,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TtsLifecycle {
     Idle,
@@ -24,6 +89,8 @@ pub enum TtsLifecycle {
 
 pub struct TtsState {
     pub(in crate::app) engine: Option<TtsEngine>,
+    /// Voices the engine can switch to, listed once when the engine is created.
+    pub(in crate::app) voices: Vec<VoiceInfo>,
     pub(in crate::app) playback: Option<TtsPlayback>,
     pub(in crate::app) lifecycle: TtsLifecycle,
     pub(in crate::app) pending_append: bool,
@@ -31,20 +98,56 @@ pub struct TtsState {
     pub(in crate::app) resume_after_prepare: bool,
     pub(in crate::app) last_sentences: Vec<String>,
     pub(in crate::app) current_sentence_idx: Option<usize>,
+    pub(in crate::app) current_word_idx: Option<usize>,
+    pub(in crate::app) word_clock: Option<WordClock>,
     pub(in crate::app) sentence_offset: usize,
     pub(in crate::app) track: Vec<(PathBuf, Duration)>,
     pub(in crate::app) started_at: Option<Instant>,
     pub(in crate::app) elapsed: Duration,
+    /// Where the audio scrubber is held while it is being dragged.
+    pub(in crate::app) scrub_position: Option<Duration>,
     pub(in crate::app) request_id: u64,
-    pub(in crate::app) sources_per_sentence: usize,
     pub(in crate::app) total_sources: usize,
     pub(in crate::app) display_to_audio: Vec<Option<usize>>,
     pub(in crate::app) audio_to_display: Vec<usize>,
+    /// Silence after each audio sentence of the page, parallel to `audio_to_display`.
+    pub(in crate::app) audio_pauses: Vec<Duration>,
+    pub(in crate::app) chapter_cache: Option<ChapterAudioJob>,
+    pub(in crate::app) chapter_cache_status: Option<String>,
+    pub(in crate::app) chapter_cache_request_id: u64,
+    pub(in crate::app) audio_export: Option<AudioExportJob>,
+    pub(in crate::app) audio_export_status: Option<String>,
+    pub(in crate::app) audio_export_request_id: u64,
+    /// Page whose audio has been requested ahead of playback.
+    pub(in crate::app) prefetched_page: Option<usize>,
+    /// Clips of `prefetched_page`, spared by audio cache eviction.
+    pub(in crate::app) prefetched_clips: Vec<PathBuf>,
+    /// Audio sentence last seen playing, used to detect finished sentences.
+    pub(in crate::app) spoken_cursor: Option<usize>,
+    /// Running total of finished sentence lengths, scaled to 1x speed.
+    pub(in crate::app) spoken_unit_secs: f64,
+    pub(in crate::app) spoken_sentences: usize,
+    /// Paused by focus loss or system sleep rather than by the user.
+    pub(in crate::app) auto_paused: bool,
+    /// Last display sentence of a "play selection" run; playback stops after it.
+    pub(in crate::app) selection_end: Option<usize>,
+    /// Wall-clock time of the last system signal poll; monotonic time stops during sleep.
+    pub(in crate::app) last_poll_wall_time: Option<SystemTime>,
+    pub(in crate::app) snippet: Option<SnippetPlayback>,
+    pub(in crate::app) snippet_request_id: u64,
+    /// Page and chapter title to speak before playback next starts at that page.
+    pub(in crate::app) announcement: Option<(usize, String)>,
+    /// Length of the chapter-title clip queued ahead of `track` while it plays.
+    pub(in crate::app) preamble: Option<Duration>,
 }
 
 impl TtsState {
     pub(in crate::app) fn new(engine: Option<TtsEngine>) -> Self {
         Self {
+            voices: engine
+                .as_ref()
+                .map(TtsEngine::list_voices)
+                .unwrap_or_default(),
             engine,
             playback: None,
             lifecycle: TtsLifecycle::Idle,
@@ -53,18 +156,51 @@ impl TtsState {
             resume_after_prepare: true,
             last_sentences: Vec::new(),
             current_sentence_idx: None,
+            current_word_idx: None,
+            word_clock: None,
             sentence_offset: 0,
             track: Vec::new(),
             started_at: None,
             elapsed: Duration::ZERO,
+            scrub_position: None,
             request_id: 0,
-            sources_per_sentence: 1,
             total_sources: 0,
             display_to_audio: Vec::new(),
             audio_to_display: Vec::new(),
+            audio_pauses: Vec::new(),
+            chapter_cache: None,
+            chapter_cache_status: None,
+            chapter_cache_request_id: 0,
+            audio_export: None,
+            audio_export_status: None,
+            audio_export_request_id: 0,
+            prefetched_page: None,
+            prefetched_clips: Vec::new(),
+            spoken_cursor: None,
+            spoken_unit_secs: 0.0,
+            spoken_sentences: 0,
+            auto_paused: false,
+            selection_end: None,
+            last_poll_wall_time: None,
+            snippet: None,
+            snippet_request_id: 0,
+            announcement: None,
+            preamble: None,
         }
     }
 
+    /// Swap in a new engine, cancelling any batch the old one was preparing.
+    pub(in crate::app) fn replace_engine(&mut self, engine: Option<TtsEngine>) {
+        if let Some(old) = &self.engine {
+            old.cancel_preparation();
+        }
+        self.voices = engine
+            .as_ref()
+            .map(TtsEngine::list_voices)
+            .unwrap_or_default();
+        self.engine = engine;
+    }
+
     pub(in crate::app) fn is_preparing(&self) -> bool {
         matches!(self.lifecycle, TtsLifecycle::Preparing { .. })
     }
@@ -89,8 +225,12 @@ impl TtsState {
         self.track.clear();
         self.started_at = None;
         self.elapsed = Duration::ZERO;
-        self.sources_per_sentence = 1;
+        self.scrub_position = None;
+        self.current_word_idx = None;
+        self.word_clock = None;
+        self.spoken_cursor = None;
         self.total_sources = 0;
+        self.preamble = None;
         self.pending_append = false;
         self.pending_append_batch = None;
     }
@@ -107,6 +247,68 @@ impl TtsState {
         }
     }
 
+    /// Note that `audio_idx` is now playing, crediting every sentence that
+    /// finished since the last call to the running average.
+    pub(in crate::app) fn record_spoken_progress(&mut self, audio_idx: usize, speed: f32) {
+        if let Some(previous) = self.spoken_cursor.filter(|previous| *previous < audio_idx) {
+            for finished in previous..audio_idx {
+                let Some((_, duration)) = finished
+                    .checked_sub(self.sentence_offset)
+                    .and_then(|idx| self.track.get(idx))
+                else {
+                    continue;
+                };
+                self.spoken_unit_secs += duration.as_secs_f64() * f64::from(speed.max(0.1));
+                self.spoken_sentences += 1;
+            }
+        }
+        self.spoken_cursor = Some(audio_idx);
+    }
+
+    /// Average spoken sentence length at `speed`, once any sentence has finished.
+    pub(in crate::app) fn average_spoken_sentence(&self, speed: f32) -> Option<Duration> {
+        if self.spoken_sentences == 0 {
+            return None;
+        }
+        let unit_secs = self.spoken_unit_secs / self.spoken_sentences as f64;
+        Some(Duration::from_secs_f64(
+            unit_secs / f64::from(speed.max(0.1)),
+        ))
+    }
+
+    /// Pauses for `count` audio sentences from `start`; `fallback` past the planned page.
+    pub(in crate::app) fn pauses_from(
+        &self,
+        start: usize,
+        count: usize,
+        fallback: Duration,
+    ) -> Vec<Duration> {
+        (start..start + count)
+            .map(|idx| self.audio_pauses.get(idx).copied().unwrap_or(fallback))
+            .collect()
+    }
+
+    /// Start of each sentence in `track` on the playback clock, pauses
+    /// included, followed by the end of the track.
+    pub(in crate::app) fn track_marks(&self, fallback_pause: Duration) -> Vec<Duration> {
+        let pauses = self.pauses_from(self.sentence_offset, self.track.len(), fallback_pause);
+        let mut at = Duration::ZERO;
+        let mut marks = vec![at];
+        for ((_, speech), pause) in self.track.iter().zip(pauses) {
+            at += *speech + pause;
+            marks.push(at);
+        }
+        marks
+    }
+
+    /// Time played into `track`, not counting paused stretches.
+    pub(in crate::app) fn track_position(&self, now: Instant) -> Duration {
+        self.elapsed
+            + self.started_at.map_or(Duration::ZERO, |started| {
+                now.saturating_duration_since(started)
+            })
+    }
+
     pub(in crate::app) fn set_mappings_checked(
         &mut self,
         display_to_audio: Vec<Option<usize>>,
@@ -124,9 +326,148 @@ impl TtsState {
     }
 }
 
+/// Silence lengths chosen by how a spoken sentence ends.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SentencePauses {
+    pub(in crate::app) sentence: Duration,
+    pub(in crate::app) clause: Duration,
+    /// Added on top of the sentence pause at a paragraph break.
+    pub(in crate::app) paragraph_extra: Duration,
+}
+
+impl SentencePauses {
+    pub(in crate::app) fn from_config(config: &AppConfig) -> Self {
+        let secs = |secs: f32| Duration::from_secs_f32(secs.max(0.0));
+        let sentence = secs(config.pause_after_sentence);
+        Self {
+            sentence,
+            clause: config.pause_after_clause.map_or(sentence, secs),
+            paragraph_extra: secs(config.pause_after_paragraph),
+        }
+    }
+
+    /// Silence after each audio sentence of a page, parallel to `audio_to_display`.
+    ///
+    /// A piece of a long sentence split for synthesis, or a sentence ending in
+    /// `,`, `;` or `:`, gets the clause pause; the last piece of a sentence
+    /// followed by a blank line in `page_text` also gets the paragraph extra.
+    pub(in crate::app) fn plan(
+        &self,
+        page_text: &str,
+        display_sentences: &[String],
+        audio_to_display: &[usize],
+    ) -> Vec<Duration> {
+        let paragraph_ends = paragraph_ends(page_text, display_sentences);
+        audio_to_display
+            .iter()
+            .enumerate()
+            .map(|(idx, &display)| {
+                if audio_to_display.get(idx + 1) == Some(&display) {
+                    return self.clause;
+                }
+                let sentence = display_sentences.get(display).map_or("", String::as_str);
+                let pause = if ends_with_clause_mark(sentence) {
+                    self.clause
+                } else {
+                    self.sentence
+                };
+                if paragraph_ends.get(display).copied().unwrap_or(false) {
+                    pause + self.paragraph_extra
+                } else {
+                    pause
+                }
+            })
+            .collect()
+    }
+}
+
+/// Whether each sentence is followed by a paragraph break in `page_text`.
+///
+/// Sentences are found in order; one that can't be located is never a paragraph end.
+fn paragraph_ends(page_text: &str, sentences: &[String]) -> Vec<bool> {
+    let mut cursor = 0;
+    sentences
+        .iter()
+        .map(|sentence| {
+            let sentence = sentence.trim();
+            if sentence.is_empty() {
+                return false;
+            }
+            let Some(found) = page_text[cursor..].find(sentence) else {
+                return false;
+            };
+            cursor += found + sentence.len();
+            page_text[cursor..]
+                .chars()
+                .take_while(|ch| ch.is_whitespace())
+                .filter(|ch| *ch == '\n')
+                .count()
+                >= 2
+        })
+        .collect()
+}
+
+fn ends_with_clause_mark(sentence: &str) -> bool {
+    sentence
+        .trim_end()
+        .trim_end_matches(['"', '\'', ')', ']', '\u{201d}', '\u{2019}', '\u{bb}'])
+        .ends_with([',', ';', ':'])
+}
+
+/// Word active after `elapsed` of a sentence lasting `total`, assuming words are evenly paced.
+pub(in crate::app) fn word_index_for_elapsed(
+    word_count: usize,
+    elapsed: Duration,
+    total: Duration,
+) -> Option<usize> {
+    if word_count == 0 {
+        return None;
+    }
+    if total.is_zero() {
+        return Some(0);
+    }
+    let fraction = (elapsed.as_secs_f64() / total.as_secs_f64()).clamp(0.0, 1.0);
+    Some(((fraction * word_count as f64) as usize).min(word_count - 1))
+}
+
+/// Member of a grouped clip spoken after `elapsed` of `total`, with the time
+/// before it and its own share, splitting the clip by each member's `weights`.
+pub(in crate::app) fn grouped_member_for_elapsed(
+    weights: &[usize],
+    elapsed: Duration,
+    total: Duration,
+) -> (usize, Duration, Duration) {
+    let sum = weights.iter().sum::<usize>().max(1) as f64;
+    let mut before = Duration::ZERO;
+    for (member, weight) in weights.iter().enumerate() {
+        let share = total.mul_f64(*weight as f64 / sum);
+        if elapsed < before + share || member + 1 == weights.len() {
+            return (member, before, share);
+        }
+        before += share;
+    }
+    (0, Duration::ZERO, total)
+}
+
+/// Sentence of [`TtsState::track_marks`] whose start is closest to `position`.
+pub(in crate::app) fn nearest_sentence_start(
+    marks: &[Duration],
+    position: Duration,
+) -> Option<usize> {
+    let starts = marks.get(..marks.len().saturating_sub(1))?;
+    starts
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, start)| start.abs_diff(position))
+        .map(|(idx, _)| idx)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::TtsState;
+    use super::{SentencePauses, TtsState, word_index_for_elapsed};
+    use crate::config::AppConfig;
+    use std::path::PathBuf;
+    use std::time::Duration;
 
     #[test]
     fn clamps_current_sentence() {
@@ -142,4 +483,105 @@ mod tests {
         assert_eq!(tts.display_to_audio, vec![Some(0), None, None]);
         assert_eq!(tts.audio_to_display, vec![2, 1]);
     }
+
+    #[test]
+    fn word_index_spreads_duration_across_words() {
+        let total = Duration::from_secs(4);
+        let at = |ms| word_index_for_elapsed(4, Duration::from_millis(ms), total);
+        assert_eq!(at(0), Some(0));
+        assert_eq!(at(999), Some(0));
+        assert_eq!(at(1000), Some(1));
+        assert_eq!(at(3500), Some(3));
+        assert_eq!(at(9000), Some(3));
+        assert_eq!(word_index_for_elapsed(0, Duration::ZERO, total), None);
+        assert_eq!(
+            word_index_for_elapsed(3, Duration::from_millis(5), Duration::ZERO),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn grouped_clips_split_by_sentence_length() {
+        let total = Duration::from_secs(4);
+        let at = |ms| grouped_member_for_elapsed(&[10, 30], Duration::from_millis(ms), total);
+        assert_eq!(at(0), (0, Duration::ZERO, Duration::from_secs(1)));
+        assert_eq!(
+            at(1500),
+            (1, Duration::from_secs(1), Duration::from_secs(3))
+        );
+        assert_eq!(at(9000).0, 1);
+        assert_eq!(grouped_member_for_elapsed(&[], Duration::ZERO, total).0, 0);
+    }
+
+    #[test]
+    fn pauses_follow_clauses_and_paragraph_breaks() {
+        let pauses = SentencePauses {
+            sentence: Duration::from_millis(100),
+            clause: Duration::from_millis(40),
+            paragraph_extra: Duration::from_millis(500),
+        };
+        let page = "First part, second part. Then this:\n\nA new paragraph.";
+        let sentences =
+            ["First part, second part.", "Then this:", "A new paragraph."].map(String::from);
+        // The first sentence is synthesized in two pieces.
+        let plan = pauses.plan(page, &sentences, &[0, 0, 1, 2]);
+        assert_eq!(plan, [40, 100, 540, 100].map(Duration::from_millis));
+
+        let defaults = SentencePauses::from_config(&AppConfig::default());
+        assert_eq!(defaults.clause, defaults.sentence);
+        assert_eq!(defaults.paragraph_extra, Duration::ZERO);
+    }
+
+    #[test]
+    fn scrubbing_lands_on_the_nearest_sentence_start() {
+        let mut tts = TtsState::new(None);
+        tts.sentence_offset = 2;
+        tts.track = [2, 4, 6]
+            .into_iter()
+            .map(|secs| (PathBuf::new(), Duration::from_secs(secs)))
+            .collect();
+        tts.audio_pauses = vec![Duration::from_secs(1); 4];
+        let marks = tts.track_marks(Duration::ZERO);
+        // The last sentence falls back to no pause past the planned pauses.
+        assert_eq!(marks, [0, 3, 8, 14].map(Duration::from_secs));
+
+        let at = |millis| nearest_sentence_start(&marks, Duration::from_millis(millis));
+        assert_eq!(at(0), Some(0));
+        assert_eq!(at(1400), Some(0));
+        assert_eq!(at(1600), Some(1));
+        assert_eq!(at(13_900), Some(2));
+        assert_eq!(
+            nearest_sentence_start(&[Duration::ZERO], Duration::ZERO),
+            None
+        );
+    }
+
+    #[test]
+    fn spoken_average_accumulates_and_rescales_with_speed() {
+        let mut tts = TtsState::new(None);
+        tts.sentence_offset = 4;
+        tts.track = [2, 4, 6]
+            .into_iter()
+            .map(|secs| (PathBuf::new(), Duration::from_secs(secs)))
+            .collect();
+        assert_eq!(tts.average_spoken_sentence(1.0), None);
+
+        tts.record_spoken_progress(4, 2.0);
+        assert_eq!(tts.average_spoken_sentence(1.0), None);
+        tts.record_spoken_progress(6, 2.0);
+        // 2s and 4s at 2x are 4s and 8s at 1x.
+        assert_eq!(
+            tts.average_spoken_sentence(1.0),
+            Some(Duration::from_secs(6))
+        );
+        assert_eq!(
+            tts.average_spoken_sentence(3.0),
+            Some(Duration::from_secs(2))
+        );
+
+        // A backwards seek only moves the cursor; the replayed sentence counts again.
+        tts.record_spoken_progress(5, 2.0);
+        tts.record_spoken_progress(6, 2.0);
+        assert_eq!(tts.spoken_sentences, 3);
+    }
 }
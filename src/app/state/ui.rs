@@ -1,11 +1,45 @@
 use crate::cache::RecentBook;
 use crate::calibre::{CalibreBook, CalibreColumn, CalibreConfig};
+use std::ops::Range;
+
+/// A search match; `char_range` is the matched byte range within the sentence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHit {
+    pub(in crate::app) page: usize,
+    pub(in crate::app) sentence_idx: usize,
+    pub(in crate::app) char_range: Range<usize>,
+}
+
+/// A looked-up word; `definition` is `None` when no source knew it.
+#[derive(Debug, Clone)]
+pub struct WordDefinition {
+    pub(in crate::app) word: String,
+    pub(in crate::app) definition: Option<String>,
+}
+
+/// Sentences marked in the sentence list for playing just that passage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SentenceSelection {
+    pub(in crate::app) page: usize,
+    /// First sentence marked; the selection spans from here to `focus`.
+    pub(in crate::app) anchor: usize,
+    pub(in crate::app) focus: usize,
+}
+
+impl SentenceSelection {
+    /// First and last selected sentence, inclusive.
+    pub(in crate::app) fn bounds(&self) -> (usize, usize) {
+        (self.anchor.min(self.focus), self.anchor.max(self.focus))
+    }
+}
 
 pub struct SearchState {
     pub(in crate::app) visible: bool,
     pub(in crate::app) query: String,
     pub(in crate::app) error: Option<String>,
-    pub(in crate::app) matches: Vec<usize>,
+    pub(in crate::app) matches: Vec<SearchHit>,
+    /// Set when the hit list stopped at `MAX_SEARCH_HITS`.
+    pub(in crate::app) truncated: bool,
     pub(in crate::app) selected_match: usize,
 }
 
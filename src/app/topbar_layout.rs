@@ -1,8 +1,15 @@
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) struct TopBarPlan {
+    /// Theme, Close Book, Settings and Stats; only compact controls hide them.
+    pub(crate) show_essentials: bool,
     pub(crate) show_text_mode: bool,
     pub(crate) show_tts: bool,
     pub(crate) show_search: bool,
+    pub(crate) show_contents: bool,
+    pub(crate) show_notes: bool,
+    pub(crate) show_auto_page: bool,
+    /// The overflow menu button, holding whatever the bar leaves out.
+    pub(crate) show_overflow: bool,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -13,38 +20,169 @@ pub(crate) struct TopBarLabels<'a> {
     pub(crate) text_mode: &'a str,
     pub(crate) tts: &'a str,
     pub(crate) search: &'a str,
+    pub(crate) contents: &'a str,
+    pub(crate) notes: &'a str,
+    /// Auto page-turn toggle; `None` when no interval is configured.
+    pub(crate) auto_page: Option<&'a str>,
 }
 
+/// A top-bar button that can be moved into the overflow menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TopBarAction {
+    Theme,
+    CloseBook,
+    Settings,
+    Stats,
+    TextMode,
+    Tts,
+    Search,
+    Contents,
+    Notes,
+    AutoPage,
+}
+
+/// Entry in the overflow menu, shown under the label its button would have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct OverflowItem<'a> {
+    pub(crate) action: TopBarAction,
+    pub(crate) label: &'a str,
+}
+
+impl std::fmt::Display for OverflowItem<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label)
+    }
+}
+
+pub(crate) const OVERFLOW_LABEL: &str = "⋯";
+
 const CONTROLS_SPACING_PX: f32 = 10.0;
 const CONTROLS_PADDING_BUDGET_PX: f32 = 12.0;
+/// Label size and padding of control buttons in accessibility mode.
+pub(crate) const ACCESSIBLE_BUTTON_TEXT_SIZE: f32 = 20.0;
+pub(crate) const ACCESSIBLE_BUTTON_PADDING: [u16; 2] = [12, 20];
 
-pub(crate) fn estimate_button_width_px(label: &str) -> f32 {
+/// Width of a control button showing `label`; `accessible` sizes it for the
+/// larger text and padding of accessibility mode.
+pub(crate) fn estimate_button_width_px(label: &str, accessible: bool) -> f32 {
     let chars = label.chars().count() as f32;
-    (chars * 8.4) + 36.0
+    if accessible {
+        (chars * 10.5) + 56.0
+    } else {
+        (chars * 8.4) + 36.0
+    }
 }
 
-pub(crate) fn topbar_plan(available_width: f32, labels: TopBarLabels<'_>) -> TopBarPlan {
-    let controls_budget = (available_width - CONTROLS_PADDING_BUDGET_PX).max(0.0);
-
-    let mandatory_labels = [
-        "Previous",
-        "Next",
-        labels.theme,
-        "Close Book",
-        labels.settings,
-        labels.stats,
-    ];
-    let mandatory_width = mandatory_labels
+/// Which top-bar buttons fit in `available_width`. Buttons that don't fit go
+/// into the overflow menu, whose width is reserved once anything overflows;
+/// `compact` keeps only page navigation in the bar, and `accessible` measures
+/// buttons at their accessibility-mode size.
+pub(crate) fn topbar_plan(
+    available_width: f32,
+    labels: TopBarLabels<'_>,
+    compact: bool,
+    accessible: bool,
+) -> TopBarPlan {
+    let budget = (available_width - CONTROLS_PADDING_BUDGET_PX).max(0.0);
+    let overflow_width = CONTROLS_SPACING_PX + estimate_button_width_px(OVERFLOW_LABEL, accessible);
+    if compact {
+        let navigation_width = row_width(&["Previous", "Next"], accessible);
+        return TopBarPlan {
+            show_essentials: false,
+            show_text_mode: false,
+            show_tts: false,
+            show_search: false,
+            show_contents: false,
+            show_notes: false,
+            show_auto_page: false,
+            show_overflow: navigation_width + overflow_width <= budget,
+        };
+    }
+    let plan = plan_within(budget, labels, accessible);
+    if overflow_items(plan, labels).is_empty() {
+        return plan;
+    }
+    TopBarPlan {
+        show_overflow: mandatory_width(labels, accessible) + overflow_width <= budget,
+        ..plan_within((budget - overflow_width).max(0.0), labels, accessible)
+    }
+}
+
+/// Buttons the plan leaves out of the bar, in top-bar order.
+pub(crate) fn overflow_items<'a>(
+    plan: TopBarPlan,
+    labels: TopBarLabels<'a>,
+) -> Vec<OverflowItem<'a>> {
+    let mut items = Vec::new();
+    if !plan.show_essentials {
+        items.extend([
+            (TopBarAction::Theme, labels.theme),
+            (TopBarAction::CloseBook, "Close Book"),
+            (TopBarAction::Settings, labels.settings),
+            (TopBarAction::Stats, labels.stats),
+        ]);
+    }
+    if !plan.show_text_mode {
+        items.push((TopBarAction::TextMode, labels.text_mode));
+    }
+    if !plan.show_tts {
+        items.push((TopBarAction::Tts, labels.tts));
+    }
+    if !plan.show_search {
+        items.push((TopBarAction::Search, labels.search));
+    }
+    if !plan.show_contents {
+        items.push((TopBarAction::Contents, labels.contents));
+    }
+    if !plan.show_notes {
+        items.push((TopBarAction::Notes, labels.notes));
+    }
+    if let (false, Some(label)) = (plan.show_auto_page, labels.auto_page) {
+        items.push((TopBarAction::AutoPage, label));
+    }
+    items
+        .into_iter()
+        .map(|(action, label)| OverflowItem { action, label })
+        .collect()
+}
+
+/// Width of the buttons that stay in the bar outside compact mode.
+fn mandatory_width(labels: TopBarLabels<'_>, accessible: bool) -> f32 {
+    row_width(
+        &[
+            "Previous",
+            "Next",
+            labels.theme,
+            "Close Book",
+            labels.settings,
+            labels.stats,
+        ],
+        accessible,
+    )
+}
+
+fn row_width(labels: &[&str], accessible: bool) -> f32 {
+    labels
         .iter()
-        .map(|label| estimate_button_width_px(label))
+        .map(|label| estimate_button_width_px(label, accessible))
         .sum::<f32>()
-        + (CONTROLS_SPACING_PX * (mandatory_labels.len().saturating_sub(1) as f32));
+        + (CONTROLS_SPACING_PX * (labels.len().saturating_sub(1) as f32))
+}
 
+/// Optional buttons that fit in `controls_budget`, in priority order; the
+/// overflow flag is left for the caller.
+fn plan_within(controls_budget: f32, labels: TopBarLabels<'_>, accessible: bool) -> TopBarPlan {
+    let mandatory_width = mandatory_width(labels, accessible);
     if mandatory_width >= controls_budget {
         return TopBarPlan {
+            show_essentials: true,
             show_text_mode: false,
             show_tts: false,
             show_search: false,
+            show_contents: false,
+            show_notes: false,
+            show_auto_page: false,
+            show_overflow: false,
         };
     }
 
@@ -52,9 +190,12 @@ pub(crate) fn topbar_plan(available_width: f32, labels: TopBarLabels<'_>) -> Top
     let mut show_text_mode = false;
     let mut show_tts = false;
     let mut show_search = false;
+    let mut show_contents = false;
+    let mut show_notes = false;
+    let mut show_auto_page = false;
 
     let add_optional = |used: &mut f32, label: &str| -> bool {
-        let extra = CONTROLS_SPACING_PX + estimate_button_width_px(label);
+        let extra = CONTROLS_SPACING_PX + estimate_button_width_px(label, accessible);
         if *used + extra <= controls_budget {
             *used += extra;
             true
@@ -73,11 +214,25 @@ pub(crate) fn topbar_plan(available_width: f32, labels: TopBarLabels<'_>) -> Top
     if add_optional(&mut used, labels.search) {
         show_search = true;
     }
+    if add_optional(&mut used, labels.contents) {
+        show_contents = true;
+    }
+    if add_optional(&mut used, labels.notes) {
+        show_notes = true;
+    }
+    if let Some(auto_page) = labels.auto_page {
+        show_auto_page = add_optional(&mut used, auto_page);
+    }
 
     TopBarPlan {
+        show_essentials: true,
         show_text_mode,
         show_tts,
         show_search,
+        show_contents,
+        show_notes,
+        show_auto_page,
+        show_overflow: false,
     }
 }
 
@@ -93,15 +248,32 @@ mod tests {
             text_mode: "Text Only",
             tts: "Show TTS",
             search: "Search",
+            contents: "Contents",
+            notes: "Notes",
+            auto_page: Some("Auto Turn"),
         }
     }
 
     #[test]
     fn shows_all_optional_with_large_width() {
-        let plan = topbar_plan(5000.0, labels());
+        let plan = topbar_plan(5000.0, labels(), false, false);
         assert!(plan.show_text_mode);
         assert!(plan.show_tts);
         assert!(plan.show_search);
+        assert!(plan.show_contents);
+        assert!(plan.show_notes);
+        assert!(plan.show_auto_page);
+        assert!(!plan.show_overflow);
+        let without_timer = topbar_plan(
+            5000.0,
+            TopBarLabels {
+                auto_page: None,
+                ..labels()
+            },
+            false,
+            false,
+        );
+        assert!(!without_timer.show_auto_page);
     }
 
     #[test]
@@ -116,14 +288,17 @@ mod tests {
             l.stats,
         ]
         .iter()
-        .map(|label| estimate_button_width_px(label))
+        .map(|label| estimate_button_width_px(label, false))
         .sum::<f32>()
             + 10.0 * 5.0;
-        let width = mandatory + 10.0 + estimate_button_width_px(l.text_mode) + 5.0;
-        let plan = topbar_plan(width + 12.0, l);
+        let overflow = 10.0 + estimate_button_width_px(OVERFLOW_LABEL, false);
+        let width =
+            mandatory + overflow + 10.0 + estimate_button_width_px(l.text_mode, false) + 5.0;
+        let plan = topbar_plan(width + 12.0, l, false, false);
         assert!(plan.show_text_mode);
         assert!(!plan.show_tts);
         assert!(!plan.show_search);
+        assert!(plan.show_overflow);
     }
 
     #[test]
@@ -138,55 +313,163 @@ mod tests {
             l.stats,
         ]
         .iter()
-        .map(|label| estimate_button_width_px(label))
+        .map(|label| estimate_button_width_px(label, false))
         .sum::<f32>()
             + 10.0 * 5.0;
 
-        let text_extra = 10.0 + estimate_button_width_px(l.text_mode);
-        let tts_extra = 10.0 + estimate_button_width_px(l.tts);
-        let search_extra = 10.0 + estimate_button_width_px(l.search);
+        let text_extra = 10.0 + estimate_button_width_px(l.text_mode, false);
+        let tts_extra = 10.0 + estimate_button_width_px(l.tts, false);
+        let search_extra = 10.0 + estimate_button_width_px(l.search, false);
+        let overflow = 10.0 + estimate_button_width_px(OVERFLOW_LABEL, false);
 
-        let only_mandatory = topbar_plan(mandatory + 12.0 + 1.0, l);
+        let only_mandatory = topbar_plan(mandatory + overflow + 12.0 + 1.0, l, false, false);
         assert_eq!(
             only_mandatory,
             TopBarPlan {
+                show_essentials: true,
                 show_text_mode: false,
                 show_tts: false,
-                show_search: false
+                show_search: false,
+                show_contents: false,
+                show_notes: false,
+                show_auto_page: false,
+                show_overflow: true,
             }
         );
 
-        let with_text = topbar_plan(mandatory + text_extra + 12.0 + 1.0, l);
+        let with_text = topbar_plan(
+            mandatory + overflow + text_extra + 12.0 + 1.0,
+            l,
+            false,
+            false,
+        );
         assert_eq!(
             with_text,
             TopBarPlan {
+                show_essentials: true,
                 show_text_mode: true,
                 show_tts: false,
-                show_search: false
+                show_search: false,
+                show_contents: false,
+                show_notes: false,
+                show_auto_page: false,
+                show_overflow: true,
             }
         );
 
-        let with_tts = topbar_plan(mandatory + text_extra + tts_extra + 12.0 + 1.0, l);
+        let with_tts = topbar_plan(
+            mandatory + overflow + text_extra + tts_extra + 12.0 + 1.0,
+            l,
+            false,
+            false,
+        );
         assert_eq!(
             with_tts,
             TopBarPlan {
+                show_essentials: true,
                 show_text_mode: true,
                 show_tts: true,
-                show_search: false
+                show_search: false,
+                show_contents: false,
+                show_notes: false,
+                show_auto_page: false,
+                show_overflow: true,
             }
         );
 
         let with_search = topbar_plan(
-            mandatory + text_extra + tts_extra + search_extra + 12.0 + 1.0,
+            mandatory + overflow + text_extra + tts_extra + search_extra + 12.0 + 1.0,
             l,
+            false,
+            false,
         );
         assert_eq!(
             with_search,
             TopBarPlan {
+                show_essentials: true,
                 show_text_mode: true,
                 show_tts: true,
-                show_search: true
+                show_search: true,
+                show_contents: false,
+                show_notes: false,
+                show_auto_page: false,
+                show_overflow: true,
             }
         );
     }
+
+    #[test]
+    fn accessibility_mode_measures_wider_buttons() {
+        assert!(estimate_button_width_px("Next", true) > estimate_button_width_px("Next", false));
+
+        // A width that fits every button at the normal size overflows at the larger one.
+        let l = labels();
+        let everything = [
+            "Previous",
+            "Next",
+            l.theme,
+            "Close Book",
+            l.settings,
+            l.stats,
+            l.text_mode,
+            l.tts,
+            l.search,
+            l.contents,
+            l.notes,
+            "Auto Turn",
+        ];
+        let width = everything
+            .iter()
+            .map(|label| estimate_button_width_px(label, false))
+            .sum::<f32>()
+            + 10.0 * 11.0
+            + 12.0;
+        assert!(!topbar_plan(width, l, false, false).show_overflow);
+        let accessible = topbar_plan(width, l, false, true);
+        assert!(accessible.show_overflow);
+        assert!(!accessible.show_auto_page);
+    }
+
+    #[test]
+    fn hidden_buttons_move_into_overflow_menu() {
+        let everything = topbar_plan(5000.0, labels(), false, false);
+        assert!(overflow_items(everything, labels()).is_empty());
+
+        let compact = topbar_plan(5000.0, labels(), true, false);
+        let actions: Vec<TopBarAction> = overflow_items(compact, labels())
+            .into_iter()
+            .map(|item| item.action)
+            .collect();
+        assert_eq!(actions.first(), Some(&TopBarAction::Theme));
+        assert_eq!(actions.last(), Some(&TopBarAction::AutoPage));
+        assert_eq!(actions.len(), 10);
+        assert!(compact.show_overflow);
+
+        // The menu's own width can push out a button that would otherwise fit.
+        let l = labels();
+        let mandatory = [
+            "Previous",
+            "Next",
+            l.theme,
+            "Close Book",
+            l.settings,
+            l.stats,
+        ]
+        .iter()
+        .map(|label| estimate_button_width_px(label, false))
+        .sum::<f32>()
+            + 10.0 * 5.0;
+        let text_extra = 10.0 + estimate_button_width_px(l.text_mode, false);
+        let tight = topbar_plan(mandatory + text_extra + 12.0 + 1.0, l, false, false);
+        assert!(!tight.show_text_mode);
+        assert!(tight.show_overflow);
+        let items = overflow_items(tight, l);
+        assert_eq!(items.first().map(|item| item.label), Some("Text Only"));
+        assert!(items.iter().all(|item| item.action != TopBarAction::Theme));
+
+        // Without room for the menu button the hidden buttons stay hidden.
+        let cramped = topbar_plan(mandatory + 12.0 + 1.0, l, false, false);
+        assert!(!cramped.show_text_mode);
+        assert!(!cramped.show_overflow);
+    }
 }
@@ -1,9 +1,10 @@
 use super::super::messages::{Component, NumericSetting};
 use super::super::state::{
-    App, MAX_HORIZONTAL_MARGIN, MAX_LETTER_SPACING, MAX_VERTICAL_MARGIN, MAX_WORD_SPACING,
-    apply_component,
+    App, MAX_HORIZONTAL_MARGIN, MAX_LETTER_SPACING, MAX_PARAGRAPH_SPACING, MAX_VERTICAL_MARGIN,
+    MAX_WORD_SPACING, apply_component, apply_rgb_component,
 };
 use super::Effect;
+use crate::config::{AppConfig, ThemeMode};
 use crate::pagination::{MAX_FONT_SIZE, MAX_LINES_PER_PAGE, MIN_FONT_SIZE, MIN_LINES_PER_PAGE};
 use std::time::{Duration, Instant};
 use tracing::{debug, info};
@@ -12,65 +13,57 @@ impl App {
     pub(super) fn handle_font_size_changed(&mut self, size: u32, effects: &mut Vec<Effect>) {
         let clamped = size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE);
         if clamped != self.config.font_size {
-            let old_page = self.reader.current_page;
-            let old_sentence_idx = self.tts.current_sentence_idx.unwrap_or(0);
-            let active_sentence = self
-                .raw_sentences_for_page(old_page)
-                .get(old_sentence_idx)
-                .cloned()
-                .or_else(|| self.raw_sentences_for_page(old_page).into_iter().next());
-            let had_tts = self.tts.playback.is_some() || self.tts.is_preparing();
-            let was_playing = self
-                .tts
-                .playback
-                .as_ref()
-                .map(|p| !p.is_paused())
-                .unwrap_or(self.tts.is_playing());
-
             debug!(
                 old = self.config.font_size,
                 new = clamped,
                 "Font size changed"
             );
             self.config.font_size = clamped;
-            self.repaginate();
-            self.remap_current_sentence_after_relayout(
-                old_page,
-                old_sentence_idx,
-                active_sentence.as_deref(),
-            );
-            if had_tts {
-                if let Some(sentence_idx) = self.tts.current_sentence_idx {
-                    // Invalidate any in-flight work from the old pagination before restart.
-                    self.tts.request_id = self.tts.request_id.wrapping_add(1);
-                    self.tts.lifecycle = super::super::state::TtsLifecycle::Idle;
-                    self.tts.pending_append = false;
-                    self.tts.pending_append_batch = None;
-                    self.tts.resume_after_prepare = was_playing;
-                    effects.push(Effect::StartTts {
-                        page: self.reader.current_page,
-                        sentence_idx,
-                    });
-                }
-            }
+            self.repaginate_keeping_position(effects);
             self.schedule_highlight_snap_after_layout_change(effects);
             effects.push(Effect::SaveConfig);
         }
     }
 
     pub(super) fn handle_toggle_theme(&mut self, effects: &mut Vec<Effect>) {
-        let next = match self.config.theme {
-            crate::config::ThemeMode::Night => crate::config::ThemeMode::Day,
-            crate::config::ThemeMode::Day => crate::config::ThemeMode::Night,
-        };
-        info!(
-            night_mode = matches!(next, crate::config::ThemeMode::Night),
-            "Toggled theme"
-        );
+        let next = self.config.theme.next(self.config.custom_colors);
+        info!(theme = %next, "Toggled theme");
+        self.remember_custom_colors();
         self.config.theme = next;
         effects.push(Effect::SaveConfig);
     }
 
+    pub(super) fn handle_theme_changed(&mut self, mode: ThemeMode, effects: &mut Vec<Effect>) {
+        info!(theme = %mode, "Theme changed");
+        self.remember_custom_colors();
+        self.config.theme = mode;
+        effects.push(Effect::SaveConfig);
+    }
+
+    /// Keep the `Custom` theme's colors so switching back restores them.
+    pub(super) fn remember_custom_colors(&mut self) {
+        if let Some(colors) = self.config.theme.custom_colors() {
+            self.config.custom_colors = Some(colors);
+        }
+    }
+
+    pub(super) fn handle_custom_theme_color_changed(
+        &mut self,
+        background: bool,
+        component: Component,
+        value: f32,
+        effects: &mut Vec<Effect>,
+    ) {
+        let ThemeMode::Custom { bg, fg } = &mut self.config.theme else {
+            return;
+        };
+        let target = if background { bg } else { fg };
+        *target = apply_rgb_component(*target, component, value);
+        self.remember_custom_colors();
+        debug!(background, ?component, value, "Custom theme color updated");
+        effects.push(Effect::SaveConfig);
+    }
+
     pub(super) fn handle_toggle_settings(&mut self, effects: &mut Vec<Effect>) {
         debug!("Toggled settings panel");
         let next = !self.config.show_settings;
@@ -85,6 +78,21 @@ impl App {
         effects.push(Effect::SaveConfig);
     }
 
+    pub(super) fn handle_toggle_contents(&mut self, effects: &mut Vec<Effect>) {
+        self.show_contents = !self.show_contents;
+        self.schedule_highlight_snap_after_layout_change(effects);
+    }
+
+    pub(super) fn handle_toggle_sentence_list(&mut self, effects: &mut Vec<Effect>) {
+        self.show_sentence_list = !self.show_sentence_list;
+        self.schedule_highlight_snap_after_layout_change(effects);
+    }
+
+    pub(super) fn handle_toggle_notes(&mut self, effects: &mut Vec<Effect>) {
+        self.show_notes = !self.show_notes;
+        self.schedule_highlight_snap_after_layout_change(effects);
+    }
+
     pub(super) fn handle_toggle_stats(&mut self, effects: &mut Vec<Effect>) {
         self.show_stats = !self.show_stats;
         let mut changed_settings_visibility = false;
@@ -118,6 +126,14 @@ impl App {
     ) {
         debug!(?family, "Font family changed");
         self.config.font_family = family;
+        self.config.custom_font = None;
+        self.schedule_highlight_snap_after_layout_change(effects);
+        effects.push(Effect::SaveConfig);
+    }
+
+    pub(super) fn handle_custom_font_changed(&mut self, name: String, effects: &mut Vec<Effect>) {
+        debug!(font = %name, "Custom font changed");
+        self.config.custom_font = Some(name);
         self.schedule_highlight_snap_after_layout_change(effects);
         effects.push(Effect::SaveConfig);
     }
@@ -133,6 +149,32 @@ impl App {
         effects.push(Effect::SaveConfig);
     }
 
+    pub(super) fn handle_text_direction_changed(
+        &mut self,
+        direction: crate::config::TextDirection,
+        effects: &mut Vec<Effect>,
+    ) {
+        self.config.text_direction = direction;
+        debug!(?direction, rtl = self.is_rtl(), "Text direction changed");
+        self.schedule_highlight_snap_after_layout_change(effects);
+        effects.push(Effect::SaveConfig);
+    }
+
+    pub(super) fn handle_reading_mode_changed(
+        &mut self,
+        mode: crate::config::ReadingMode,
+        effects: &mut Vec<Effect>,
+    ) {
+        self.config.reading_mode = mode;
+        debug!(
+            ?mode,
+            continuous = self.continuous_reading(),
+            "Reading mode changed"
+        );
+        self.schedule_highlight_snap_after_layout_change(effects);
+        effects.push(Effect::SaveConfig);
+    }
+
     pub(super) fn handle_line_spacing_changed(&mut self, spacing: f32, effects: &mut Vec<Effect>) {
         self.config.line_spacing = spacing.clamp(0.8, 2.5);
         debug!(
@@ -195,6 +237,20 @@ impl App {
         effects.push(Effect::SaveConfig);
     }
 
+    pub(super) fn handle_paragraph_spacing_changed(
+        &mut self,
+        spacing: f32,
+        effects: &mut Vec<Effect>,
+    ) {
+        self.config.paragraph_spacing = spacing.clamp(0.0, MAX_PARAGRAPH_SPACING);
+        debug!(
+            paragraph_spacing = self.config.paragraph_spacing,
+            "Paragraph spacing changed"
+        );
+        self.schedule_highlight_snap_after_layout_change(effects);
+        effects.push(Effect::SaveConfig);
+    }
+
     pub(super) fn handle_begin_numeric_setting_edit(&mut self, setting: NumericSetting) {
         self.active_numeric_setting = Some(setting);
         self.numeric_setting_input = self.numeric_setting_value_string(setting);
@@ -279,6 +335,172 @@ impl App {
         effects.push(Effect::SaveConfig);
     }
 
+    pub(super) fn handle_search_highlight_changed(
+        &mut self,
+        night: bool,
+        component: Component,
+        value: f32,
+        effects: &mut Vec<Effect>,
+    ) {
+        let color = if night {
+            &mut self.config.night_search_highlight
+        } else {
+            &mut self.config.day_search_highlight
+        };
+        *color = apply_component(*color, component, value);
+        debug!(night, ?component, value, "Search highlight updated");
+        effects.push(Effect::SaveConfig);
+    }
+
+    pub(super) fn handle_highlight_style_changed(
+        &mut self,
+        style: crate::config::HighlightStyle,
+        effects: &mut Vec<Effect>,
+    ) {
+        self.config.highlight_style = style;
+        debug!(?style, "Highlight style changed");
+        effects.push(Effect::SaveConfig);
+    }
+
+    pub(super) fn handle_log_level_changed(
+        &mut self,
+        level: crate::config::LogLevel,
+        effects: &mut Vec<Effect>,
+    ) {
+        self.config.log_level = level;
+        crate::logging::set_level(level.as_filter_str());
+        effects.push(Effect::SaveConfig);
+    }
+
+    pub(super) fn handle_reading_ruler_changed(
+        &mut self,
+        enabled: bool,
+        effects: &mut Vec<Effect>,
+    ) {
+        self.config.reading_ruler = enabled;
+        self.reading_ruler_dragging = false;
+        debug!(enabled, "Reading ruler toggled");
+        effects.push(Effect::SaveConfig);
+    }
+
+    pub(super) fn handle_hyphenate_changed(&mut self, enabled: bool, effects: &mut Vec<Effect>) {
+        self.config.hyphenate = enabled;
+        self.page_hyphenation = None;
+        debug!(enabled, "Hyphenation toggled");
+        self.schedule_highlight_snap_after_layout_change(effects);
+        effects.push(Effect::SaveConfig);
+    }
+
+    pub(super) fn handle_compact_controls_changed(
+        &mut self,
+        enabled: bool,
+        effects: &mut Vec<Effect>,
+    ) {
+        self.config.compact_controls = enabled;
+        debug!(enabled, "Compact controls toggled");
+        self.schedule_highlight_snap_after_layout_change(effects);
+        effects.push(Effect::SaveConfig);
+    }
+
+    pub(super) fn handle_accessibility_mode_changed(
+        &mut self,
+        enabled: bool,
+        effects: &mut Vec<Effect>,
+    ) {
+        self.config.accessibility_mode = enabled;
+        debug!(enabled, "Accessibility mode toggled");
+        self.schedule_highlight_snap_after_layout_change(effects);
+        effects.push(Effect::SaveConfig);
+    }
+
+    /// Centre the ruler band on the pointer, `y` pixels below the top of the text area.
+    pub(super) fn handle_reading_ruler_dragged(&mut self, y: f32) {
+        if !self.reading_ruler_dragging {
+            return;
+        }
+        let band = self.reading_ruler_height();
+        let travel = self.bookmark.viewport_height - band;
+        if travel <= 0.0 || !y.is_finite() {
+            return;
+        }
+        self.config.reading_ruler_position = ((y - band / 2.0) / travel).clamp(0.0, 1.0);
+    }
+
+    pub(super) fn handle_reading_ruler_drag_ended(&mut self, effects: &mut Vec<Effect>) {
+        if !std::mem::take(&mut self.reading_ruler_dragging) {
+            return;
+        }
+        debug!(
+            position = self.config.reading_ruler_position,
+            "Reading ruler moved"
+        );
+        effects.push(Effect::SaveConfig);
+    }
+
+    pub(super) fn handle_undo_settings(&mut self, effects: &mut Vec<Effect>) {
+        if let Some(snapshot) = self.settings_history.undo(self.config.clone()) {
+            info!("Undid settings change");
+            self.apply_settings_snapshot(snapshot, effects);
+        }
+    }
+
+    pub(super) fn handle_redo_settings(&mut self, effects: &mut Vec<Effect>) {
+        if let Some(snapshot) = self.settings_history.redo(self.config.clone()) {
+            info!("Redid settings change");
+            self.apply_settings_snapshot(snapshot, effects);
+        }
+    }
+
+    /// Restore a settings snapshot from the undo history.
+    ///
+    /// Only reading settings are rolled back; window geometry, panel visibility
+    /// and the TTS engine paths stay as they are now.
+    fn apply_settings_snapshot(&mut self, mut snapshot: AppConfig, effects: &mut Vec<Effect>) {
+        snapshot.window_width = self.config.window_width;
+        snapshot.window_height = self.config.window_height;
+        snapshot.window_pos_x = self.config.window_pos_x;
+        snapshot.window_pos_y = self.config.window_pos_y;
+        snapshot.show_settings = self.config.show_settings;
+        snapshot.show_tts = self.config.show_tts;
+        snapshot.tts_model_path = self.config.tts_model_path.clone();
+        snapshot.tts_espeak_path = self.config.tts_espeak_path.clone();
+        snapshot.tts_voice = self.config.tts_voice.clone();
+
+        let relayout = snapshot.font_size != self.config.font_size
+            || snapshot.lines_per_page != self.config.lines_per_page;
+        let restart_tts = snapshot.tts_speed != self.config.tts_speed
+            || snapshot.speed_ramp != self.config.speed_ramp
+            || snapshot.pause_after_sentence != self.config.pause_after_sentence
+            || snapshot.pause_after_clause != self.config.pause_after_clause
+            || snapshot.pause_after_paragraph != self.config.pause_after_paragraph;
+        let volume_changed = snapshot.tts_volume != self.config.tts_volume;
+        let log_level_changed = snapshot.log_level != self.config.log_level;
+        self.config = snapshot;
+        self.active_numeric_setting = None;
+        self.numeric_setting_input.clear();
+        if log_level_changed {
+            crate::logging::set_level(self.config.log_level.as_filter_str());
+        }
+
+        if relayout && !self.starter_mode {
+            self.repaginate_keeping_position(effects);
+        }
+        self.schedule_highlight_snap_after_layout_change(effects);
+        if let Some(playback) = &self.tts.playback {
+            if volume_changed {
+                playback.set_volume(self.config.tts_volume);
+            }
+            if restart_tts {
+                self.tts.resume_after_prepare = !playback.is_paused();
+                effects.push(Effect::StartTts {
+                    page: self.reader.current_page,
+                    sentence_idx: self.tts.current_sentence_idx.unwrap_or(0),
+                });
+            }
+        }
+        effects.push(Effect::SaveConfig);
+    }
+
     pub(super) fn handle_window_resized(
         &mut self,
         width: f32,
@@ -302,6 +524,7 @@ impl App {
                 self.bookmark.defer_sentence_snap_until_scroll = true;
             }
             self.window_geometry_changed_at = Some(Instant::now());
+            self.resize_repaginate_at = self.window_geometry_changed_at;
         }
     }
 
@@ -382,34 +605,99 @@ impl App {
         effects.push(Effect::SaveBookmark);
     }
 
+    /// Capture the reading position before a change that repaginates the book.
+    ///
+    /// Without an active sentence the first sentence of the current page is used
+    /// so the reader still lands on the text they were looking at.
+    pub(super) fn capture_relayout_anchor(&self) -> RelayoutAnchor {
+        let page = self.reader.current_page;
+        let book_fraction = page as f64 / self.reader.pages.len().max(1) as f64;
+        let sentence_idx = self.tts.current_sentence_idx.unwrap_or(0);
+        let sentences = self.raw_sentences_for_page(page);
+        let sentence = sentences
+            .get(sentence_idx)
+            .or_else(|| sentences.first())
+            .cloned();
+        RelayoutAnchor {
+            page,
+            sentence_idx,
+            sentence,
+            book_fraction,
+            had_tts: self.tts.playback.is_some() || self.tts.is_preparing(),
+            was_playing: self
+                .tts
+                .playback
+                .as_ref()
+                .map(|p| !p.is_paused())
+                .unwrap_or(self.tts.is_playing()),
+        }
+    }
+
+    /// Relocate the anchored sentence after repaginating and restart TTS there if
+    /// it was active. Returns `true` when the sentence was found again.
+    ///
+    /// When the sentence is gone (an empty page, or text the normalizer now
+    /// drops) the reader lands at the same fraction of the book instead.
+    pub(super) fn restore_relayout_anchor(
+        &mut self,
+        anchor: RelayoutAnchor,
+        effects: &mut Vec<Effect>,
+    ) -> bool {
+        let found = self.remap_current_sentence_after_relayout(
+            anchor.page,
+            anchor.sentence_idx,
+            anchor.sentence.as_deref(),
+        );
+        if !found {
+            let page = (anchor.book_fraction * self.reader.pages.len() as f64).floor() as usize;
+            self.reader.set_page_clamped(page);
+            if self.tts.current_sentence_idx.is_some() {
+                self.tts.current_sentence_idx = Some(0);
+                self.tts.last_sentences = self.raw_sentences_for_page(self.reader.current_page);
+            }
+            debug!(
+                fraction = anchor.book_fraction,
+                page = self.reader.current_page,
+                "Anchor sentence not found; restored position by book fraction"
+            );
+        }
+        if anchor.had_tts {
+            if let Some(sentence_idx) = self.tts.current_sentence_idx {
+                // Invalidate any in-flight work from the old pagination before restart.
+                self.tts.request_id = self.tts.request_id.wrapping_add(1);
+                self.tts.lifecycle = super::super::state::TtsLifecycle::Idle;
+                self.tts.pending_append = false;
+                self.tts.pending_append_batch = None;
+                self.tts.resume_after_prepare = anchor.was_playing;
+                effects.push(Effect::StartTts {
+                    page: self.reader.current_page,
+                    sentence_idx,
+                });
+            }
+        }
+        found
+    }
+
     fn remap_current_sentence_after_relayout(
         &mut self,
         old_page: usize,
         old_sentence_idx: usize,
         active_sentence: Option<&str>,
-    ) {
+    ) -> bool {
         let Some(target) = active_sentence else {
-            return;
+            return false;
         };
-        let mut best: Option<(usize, usize, usize)> = None;
-        for (page_idx, page_sentences) in self.reader.page_sentences.iter().enumerate() {
-            for (sentence_idx, candidate) in page_sentences.iter().enumerate() {
-                if candidate == target {
-                    let distance = page_idx.abs_diff(old_page) * 10_000
-                        + sentence_idx.abs_diff(old_sentence_idx);
-                    match best {
-                        Some((best_distance, _, _)) if best_distance <= distance => {}
-                        _ => best = Some((distance, page_idx, sentence_idx)),
-                    }
-                }
-            }
-        }
-        if let Some((_, page_idx, sentence_idx)) = best {
+        let best = self
+            .reader
+            .locate_sentence(target, old_page, old_sentence_idx);
+        if let Some((page_idx, sentence_idx)) = best {
             self.reader.current_page = page_idx;
             self.tts.current_sentence_idx = Some(sentence_idx);
             self.tts.last_sentences = self.raw_sentences_for_page(page_idx);
             self.bookmark.pending_sentence_snap = Some(sentence_idx);
+            return true;
         }
+        false
     }
 
     fn apply_numeric_setting_value(
@@ -423,6 +711,9 @@ impl App {
             NumericSetting::PauseAfterSentence => {
                 self.handle_pause_after_sentence_changed(value, effects);
             }
+            NumericSetting::PauseAfterParagraph => {
+                self.handle_pause_after_paragraph_changed(value, effects);
+            }
             NumericSetting::LinesPerPage => {
                 self.handle_lines_per_page_changed(value.round() as u32, effects);
             }
@@ -438,6 +729,9 @@ impl App {
             NumericSetting::LetterSpacing => {
                 self.handle_letter_spacing_changed(value.round() as u32, effects);
             }
+            NumericSetting::ParagraphSpacing => {
+                self.handle_paragraph_spacing_changed(value, effects);
+            }
         }
     }
 
@@ -445,11 +739,13 @@ impl App {
         match setting {
             NumericSetting::LineSpacing => self.config.line_spacing,
             NumericSetting::PauseAfterSentence => self.config.pause_after_sentence,
+            NumericSetting::PauseAfterParagraph => self.config.pause_after_paragraph,
             NumericSetting::LinesPerPage => self.config.lines_per_page as f32,
             NumericSetting::MarginHorizontal => self.config.margin_horizontal as f32,
             NumericSetting::MarginVertical => self.config.margin_vertical as f32,
             NumericSetting::WordSpacing => self.config.word_spacing as f32,
             NumericSetting::LetterSpacing => self.config.letter_spacing as f32,
+            NumericSetting::ParagraphSpacing => self.config.paragraph_spacing,
         }
     }
 
@@ -512,46 +808,60 @@ impl App {
     fn numeric_setting_bounds_update(setting: NumericSetting) -> (f32, f32) {
         match setting {
             NumericSetting::LineSpacing => (0.8, 2.5),
-            NumericSetting::PauseAfterSentence => (0.0, 2.0),
+            NumericSetting::PauseAfterSentence | NumericSetting::PauseAfterParagraph => (0.0, 2.0),
             NumericSetting::LinesPerPage => (MIN_LINES_PER_PAGE as f32, MAX_LINES_PER_PAGE as f32),
             NumericSetting::MarginHorizontal => (0.0, MAX_HORIZONTAL_MARGIN as f32),
             NumericSetting::MarginVertical => (0.0, MAX_VERTICAL_MARGIN as f32),
             NumericSetting::WordSpacing => (0.0, MAX_WORD_SPACING as f32),
             NumericSetting::LetterSpacing => (0.0, MAX_LETTER_SPACING as f32),
+            NumericSetting::ParagraphSpacing => (0.0, MAX_PARAGRAPH_SPACING),
         }
     }
 
     fn numeric_setting_step_update(setting: NumericSetting) -> f32 {
         match setting {
             NumericSetting::LineSpacing => 0.05,
-            NumericSetting::PauseAfterSentence => 0.01,
+            NumericSetting::PauseAfterSentence | NumericSetting::PauseAfterParagraph => 0.01,
             NumericSetting::LinesPerPage => 1.0,
             NumericSetting::MarginHorizontal => 1.0,
             NumericSetting::MarginVertical => 1.0,
             NumericSetting::WordSpacing => 1.0,
             NumericSetting::LetterSpacing => 1.0,
+            NumericSetting::ParagraphSpacing => 1.0,
         }
     }
 
     fn numeric_setting_decimals_update(setting: NumericSetting) -> u8 {
         match setting {
             NumericSetting::LineSpacing => 2,
-            NumericSetting::PauseAfterSentence => 2,
+            NumericSetting::PauseAfterSentence | NumericSetting::PauseAfterParagraph => 2,
             NumericSetting::LinesPerPage
             | NumericSetting::MarginHorizontal
             | NumericSetting::MarginVertical
             | NumericSetting::WordSpacing
-            | NumericSetting::LetterSpacing => 0,
+            | NumericSetting::LetterSpacing
+            | NumericSetting::ParagraphSpacing => 0,
         }
     }
 }
 
+/// Reading position captured by [`App::capture_relayout_anchor`].
+pub(super) struct RelayoutAnchor {
+    page: usize,
+    sentence_idx: usize,
+    sentence: Option<String>,
+    /// Share of the book before the anchored page, for when the sentence is gone.
+    book_fraction: f64,
+    had_tts: bool,
+    was_playing: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::state::test_app_with;
     use crate::config::AppConfig;
     use crate::epub_loader::LoadedBook;
-    use std::path::PathBuf;
 
     fn sample_text(sentence_count: usize) -> String {
         (0..sentence_count)
@@ -567,7 +877,7 @@ mod tests {
     fn build_test_app(sentence_count: usize) -> App {
         let book = LoadedBook {
             text: sample_text(sentence_count),
-            images: Vec::new(),
+            ..Default::default()
         };
 
         let mut config = AppConfig::default();
@@ -575,12 +885,7 @@ mod tests {
         config.auto_scroll_tts = true;
         config.font_size = 16;
         config.lines_per_page = 16;
-        let epub_path = PathBuf::from(format!(
-            "/tmp/ebup-appearance-test-{}-{}.epub",
-            std::process::id(),
-            sentence_count
-        ));
-        let (mut app, _task) = App::bootstrap(book, config, epub_path, None);
+        let mut app = test_app_with(book, config);
         app.reader.current_page = 0;
         app
     }
@@ -629,6 +934,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn font_size_change_without_active_sentence_keeps_page_anchor() {
+        let mut app = build_test_app(180);
+        assert!(
+            app.reader.pages.len() > 3,
+            "fixture should span several pages"
+        );
+        app.reader.current_page = 3;
+        app.tts.current_sentence_idx = None;
+        let first_on_page = app
+            .raw_sentences_for_page(3)
+            .first()
+            .cloned()
+            .expect("first sentence on page");
+
+        let mut effects = Vec::new();
+        app.handle_font_size_changed(28, &mut effects);
+
+        let mapped = app
+            .tts
+            .current_sentence_idx
+            .and_then(|idx| {
+                app.raw_sentences_for_page(app.reader.current_page)
+                    .get(idx)
+                    .cloned()
+            })
+            .expect("anchored sentence");
+        assert_eq!(first_on_page, mapped);
+    }
+
+    #[test]
+    fn font_size_change_mid_book_keeps_anchor_on_current_page() {
+        let mut app = build_test_app(400);
+        let middle = app.reader.pages.len() / 2;
+        app.reader.current_page = middle;
+        app.tts.current_sentence_idx = Some(2);
+        let anchor = app.raw_sentences_for_page(middle)[2].clone();
+
+        let mut effects = Vec::new();
+        app.handle_font_size_changed(30, &mut effects);
+        assert!(
+            app.raw_sentences_for_page(app.reader.current_page)
+                .contains(&anchor)
+        );
+
+        app.handle_font_size_changed(12, &mut effects);
+        assert!(
+            app.raw_sentences_for_page(app.reader.current_page)
+                .contains(&anchor)
+        );
+    }
+
+    #[test]
+    fn lost_anchor_falls_back_to_book_fraction() {
+        let mut app = build_test_app(400);
+        let old_pages = app.reader.pages.len();
+        app.reader.current_page = old_pages * 3 / 4;
+        app.tts.current_sentence_idx = Some(1);
+        let mut anchor = app.capture_relayout_anchor();
+        anchor.sentence = Some("A sentence that is not in the book.".to_string());
+
+        app.config.font_size = 28;
+        app.repaginate();
+        let mut effects = Vec::new();
+        assert!(!app.restore_relayout_anchor(anchor, &mut effects));
+
+        let new_pages = app.reader.pages.len();
+        assert!(app.reader.current_page.abs_diff(new_pages * 3 / 4) <= 1);
+        assert_eq!(app.tts.current_sentence_idx, Some(0));
+    }
+
     #[test]
     fn margin_change_requests_snap_when_auto_scroll_enabled() {
         let mut app = build_test_app(120);
@@ -678,4 +1054,33 @@ mod tests {
                 .all(|effect| !matches!(effect, Effect::AutoScrollToCurrent))
         );
     }
+
+    #[test]
+    fn missing_custom_font_falls_back_to_sans() {
+        let mut app = build_test_app(10);
+        let mut effects = Vec::new();
+        app.set_custom_fonts(vec!["Literata"]);
+        app.handle_custom_font_changed("Literata".to_string(), &mut effects);
+        assert_eq!(app.active_custom_font(), Some("Literata"));
+        assert_eq!(
+            app.current_font().family,
+            iced::font::Family::Name("Literata")
+        );
+
+        app.config.font_family = crate::config::FontFamily::Serif;
+        app.set_custom_fonts(Vec::new());
+        assert_eq!(app.config.custom_font, None);
+        assert_eq!(app.config.font_family, crate::config::FontFamily::Sans);
+    }
+
+    #[test]
+    fn reading_mode_switches_from_the_settings_panel() {
+        let mut app = build_test_app(10);
+        assert!(!app.continuous_reading());
+
+        let mut effects = Vec::new();
+        app.handle_reading_mode_changed(crate::config::ReadingMode::Continuous, &mut effects);
+        assert!(app.continuous_reading());
+        assert!(matches!(effects.last(), Some(Effect::SaveConfig)));
+    }
 }
@@ -0,0 +1,116 @@
+use super::super::messages::CacheClearScope;
+use super::super::state::{App, ReadingStatsState};
+use super::Effect;
+use crate::cache::{ReadingStats, load_named_bookmarks, load_notes};
+use crate::config::load_app_config;
+use std::time::Instant;
+use tracing::{info, warn};
+
+impl App {
+    pub(super) fn handle_clear_book_cache(&mut self, scope: CacheClearScope) {
+        if self.starter_mode {
+            return;
+        }
+        self.pending_cache_clear = Some(scope);
+        self.cache_status = None;
+    }
+
+    pub(super) fn handle_confirm_clear_book_cache(&mut self, effects: &mut Vec<Effect>) {
+        let Some(scope) = self.pending_cache_clear.take() else {
+            return;
+        };
+        // Playback reads the clips about to be deleted.
+        if self.tts.playback.is_some() || self.tts.is_preparing() {
+            effects.push(Effect::StopTts);
+        }
+        info!(path = %self.epub_path.display(), ?scope, "Clearing book cache");
+        self.cache_status = Some("Clearing cache...".to_string());
+        effects.push(Effect::ClearBookCache(scope));
+    }
+
+    pub(super) fn handle_book_cache_cleared(
+        &mut self,
+        scope: CacheClearScope,
+        result: Result<usize, String>,
+        effects: &mut Vec<Effect>,
+    ) {
+        match result {
+            Ok(clips) => {
+                info!(?scope, clips, "Cleared book cache");
+                if scope == CacheClearScope::Everything {
+                    self.forget_cleared_book_state(effects);
+                }
+                self.cache_status = Some(match scope {
+                    CacheClearScope::Audio => format!("Removed {clips} cached audio clips."),
+                    CacheClearScope::Everything => {
+                        format!("Cleared the book cache and {clips} cached audio clips.")
+                    }
+                });
+            }
+            Err(err) => {
+                warn!(?scope, "Failed to clear book cache: {err}");
+                self.cache_status = Some(format!("Clearing cache failed: {err}"));
+            }
+        }
+    }
+
+    /// Drop what the deleted cache directory held from memory too, so the next
+    /// save or quit does not write it straight back.
+    fn forget_cleared_book_state(&mut self, effects: &mut Vec<Effect>) {
+        self.bookmark.named = load_named_bookmarks(&self.epub_path);
+        self.bookmark.notes = load_notes(&self.epub_path);
+        // The resume position is only saved again once the reader moves on.
+        self.bookmark.hold_resume_page = Some(self.reader.current_page);
+        self.reading_stats =
+            ReadingStatsState::start_session(ReadingStats::default(), Instant::now());
+        if self.config.per_book_overrides {
+            self.apply_profile(load_app_config(), effects);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::{test_app, test_app_with};
+    use crate::config::AppConfig;
+    use crate::epub_loader::LoadedBook;
+
+    #[test]
+    fn cache_clear_waits_for_confirmation() {
+        let mut app = test_app("One sentence. Another sentence.");
+
+        app.handle_clear_book_cache(CacheClearScope::Audio);
+        assert_eq!(app.pending_cache_clear, Some(CacheClearScope::Audio));
+
+        let mut effects = Vec::new();
+        app.handle_confirm_clear_book_cache(&mut effects);
+        assert_eq!(app.pending_cache_clear, None);
+        assert!(matches!(
+            effects.as_slice(),
+            [Effect::ClearBookCache(CacheClearScope::Audio)]
+        ));
+
+        effects.clear();
+        app.handle_confirm_clear_book_cache(&mut effects);
+        assert!(effects.is_empty());
+    }
+
+    #[test]
+    fn clearing_everything_forgets_the_books_state_in_memory() {
+        let book = LoadedBook {
+            text: "One sentence. Another sentence.".to_string(),
+            ..Default::default()
+        };
+        let mut config = AppConfig::default();
+        config.per_book_overrides = false;
+        let mut app = test_app_with(book, config);
+        app.reading_stats.stats.total_seconds = 3_600;
+
+        let mut effects = Vec::new();
+        app.handle_book_cache_cleared(CacheClearScope::Everything, Ok(0), &mut effects);
+        assert_eq!(app.reading_stats.stats.total_seconds, 0);
+        assert_eq!(app.bookmark.hold_resume_page, Some(app.reader.current_page));
+        assert!(effects.is_empty());
+    }
+}
@@ -0,0 +1,115 @@
+use super::super::state::App;
+use super::Effect;
+use crate::cache::{
+    bookmarks_export_path, delete_named_bookmark, export_bookmarks, load_named_bookmarks,
+    save_named_bookmark,
+};
+use tracing::{info, warn};
+
+impl App {
+    pub(super) fn handle_add_bookmark(&mut self, name: String) {
+        if self.starter_mode {
+            return;
+        }
+        let bookmark = self.current_bookmark();
+        let name = match name.trim() {
+            "" => match bookmark.sentence_idx {
+                Some(idx) => format!("Page {}, sentence {}", bookmark.page + 1, idx + 1),
+                None => format!("Page {}", bookmark.page + 1),
+            },
+            trimmed => trimmed.to_string(),
+        };
+        if let Err(err) = save_named_bookmark(&self.epub_path, &name, &bookmark) {
+            warn!(name, "Failed to save named bookmark: {err}");
+            return;
+        }
+        info!(name, page = bookmark.page + 1, "Added named bookmark");
+        self.bookmark.name_input.clear();
+        self.bookmark.named = load_named_bookmarks(&self.epub_path);
+    }
+
+    pub(super) fn handle_jump_to_bookmark(&mut self, name: &str, effects: &mut Vec<Effect>) {
+        let Some(bookmark) = self
+            .bookmark
+            .named
+            .iter()
+            .find(|(candidate, _)| candidate == name)
+            .map(|(_, bookmark)| bookmark.clone())
+        else {
+            return;
+        };
+        let (page, sentence_idx) = self.jump_to_anchor(
+            bookmark.page,
+            bookmark.sentence_idx,
+            bookmark.sentence_text.as_deref(),
+            effects,
+        );
+        info!(
+            name,
+            page = page + 1,
+            sentence_idx,
+            "Jumping to named bookmark"
+        );
+    }
+
+    /// Move to a saved page and sentence, returning where the jump landed.
+    pub(super) fn jump_to_anchor(
+        &mut self,
+        page: usize,
+        sentence_idx: Option<usize>,
+        sentence_text: Option<&str>,
+        effects: &mut Vec<Effect>,
+    ) -> (usize, usize) {
+        // Prefer the saved sentence text so the jump survives layout changes.
+        let (page, sentence_idx) = sentence_text
+            .and_then(|target| {
+                self.reader
+                    .locate_sentence(target, page, sentence_idx.unwrap_or(0))
+            })
+            .unwrap_or((page, sentence_idx.unwrap_or(0)));
+        let page = page.min(self.reader.pages.len().saturating_sub(1));
+
+        if page != self.reader.current_page {
+            effects.extend(self.go_to_page_at(page, sentence_idx));
+        } else {
+            let sentence_count = self.sentence_count_for_page(page);
+            self.tts
+                .set_current_sentence_clamped(sentence_idx, sentence_count);
+            effects.push(Effect::AutoScrollToCurrent);
+            effects.push(Effect::SaveBookmark);
+        }
+        (page, sentence_idx)
+    }
+
+    pub(super) fn handle_delete_bookmark(&mut self, name: &str) {
+        if let Err(err) = delete_named_bookmark(&self.epub_path, name) {
+            warn!(name, "Failed to delete named bookmark: {err}");
+            return;
+        }
+        self.bookmark
+            .named
+            .retain(|(candidate, _)| candidate != name);
+    }
+
+    /// Write the resume point and named bookmarks, with their sentences, to a markdown file.
+    pub(super) fn handle_export_bookmarks(&mut self) {
+        if self.starter_mode {
+            return;
+        }
+        // Export the position on screen now rather than the last periodic save.
+        self.persist_bookmark();
+        let output = bookmarks_export_path(&self.epub_path);
+        let title = self.reader.metadata.title.as_deref();
+        self.bookmark.export_status =
+            Some(match export_bookmarks(&self.epub_path, title, &output) {
+                Ok(count) => {
+                    info!(count, path = %output.display(), "Exported bookmarks");
+                    format!("Exported {count} bookmark(s) to {}.", output.display())
+                }
+                Err(err) => {
+                    warn!("Failed to export bookmarks: {err}");
+                    format!("Export failed: {err}")
+                }
+            });
+    }
+}
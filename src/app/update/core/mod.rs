@@ -3,7 +3,7 @@ mod runtime;
 mod shortcuts;
 
 use super::super::messages::Message;
-use super::super::state::App;
+use super::super::state::{App, SCROLL_GLIDE_FRAME, THEME_SCHEDULE_POLL};
 use iced::event;
 use iced::time;
 use iced::{Subscription, Task};
@@ -16,9 +16,19 @@ impl App {
         subscriptions
             .push(time::every(Duration::from_millis(120)).map(|_| Message::PollSystemSignals));
 
-        if app.tts.is_playing() {
+        if app.tts.is_playing() || app.tts.snippet.is_some() {
             subscriptions.push(time::every(Duration::from_millis(80)).map(Message::Tick));
         }
+        if app.bookmark.scroll_glide.is_some() {
+            subscriptions.push(time::every(SCROLL_GLIDE_FRAME).map(Message::ScrollGlideFrame));
+        }
+        if app.config.auto_theme_schedule.is_some() {
+            subscriptions
+                .push(time::every(THEME_SCHEDULE_POLL).map(|_| Message::ThemeScheduleTick));
+        }
+        if let Some(interval) = app.auto_page_interval() {
+            subscriptions.push(time::every(interval).map(|_| Message::NextPage));
+        }
 
         Subscription::batch(subscriptions)
     }
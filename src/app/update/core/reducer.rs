@@ -1,6 +1,7 @@
 use super::super::super::messages::Message;
-use super::super::super::state::App;
+use super::super::super::state::{App, TimedOperation};
 use super::super::Effect;
+use super::super::source::missing_source_error;
 use crate::calibre::{CalibreBook, CalibreColumn};
 use std::cmp::Ordering;
 use tracing::{info, warn};
@@ -8,6 +9,9 @@ use tracing::{info, warn};
 impl App {
     pub(super) fn reduce(&mut self, message: Message) -> Vec<Effect> {
         let mut effects = Vec::new();
+        let settings_before = message
+            .is_settings_change()
+            .then(|| (std::mem::discriminant(&message), self.config.clone()));
 
         match message {
             Message::NextPage => self.handle_next_page(&mut effects),
@@ -15,8 +19,22 @@ impl App {
             Message::CloseReadingSession => self.handle_close_reading_session(&mut effects),
             Message::FontSizeChanged(size) => self.handle_font_size_changed(size, &mut effects),
             Message::ToggleTheme => self.handle_toggle_theme(&mut effects),
+            Message::ThemeChanged(mode) => self.handle_theme_changed(mode, &mut effects),
+            Message::CustomThemeBackgroundChanged(component, value) => {
+                self.handle_custom_theme_color_changed(true, component, value, &mut effects);
+            }
+            Message::CustomThemeForegroundChanged(component, value) => {
+                self.handle_custom_theme_color_changed(false, component, value, &mut effects);
+            }
             Message::ToggleSettings => self.handle_toggle_settings(&mut effects),
             Message::ToggleStats => self.handle_toggle_stats(&mut effects),
+            Message::ToggleContents => self.handle_toggle_contents(&mut effects),
+            Message::ToggleSentenceList => self.handle_toggle_sentence_list(&mut effects),
+            Message::ToggleNotes => self.handle_toggle_notes(&mut effects),
+            Message::OpenTocEntry(idx) => self.handle_open_toc_entry(idx, &mut effects),
+            Message::SkipToFirstChapter => self.handle_skip_to_first_chapter(&mut effects),
+            Message::PageInputChanged(value) => self.handle_page_input_changed(value),
+            Message::GoToPage(page) => self.handle_go_to_page(page, &mut effects),
             Message::ToggleSearch => self.handle_toggle_search(&mut effects),
             Message::SearchQueryChanged(query) => self.handle_search_query_changed(query),
             Message::SearchSubmit => self.handle_search_submit(&mut effects),
@@ -24,6 +42,7 @@ impl App {
             Message::SearchPrev => self.handle_search_prev(&mut effects),
             Message::ToggleRecentBooks => self.handle_toggle_recent_books(),
             Message::OpenRecentBook(path) => self.handle_open_recent_book(path, &mut effects),
+            Message::OpenBook(path) => self.handle_open_book(path, &mut effects),
             Message::DeleteRecentBook(path) => self.handle_delete_recent_book(path),
             Message::ToggleCalibreBrowser => self.handle_toggle_calibre_browser(&mut effects),
             Message::PrimeCalibreLoad => self.handle_prime_calibre_load(&mut effects),
@@ -55,12 +74,20 @@ impl App {
             } => self.handle_book_loaded(path, book, config, bookmark, &mut effects),
             Message::BookLoadFailed { path, error } => self.handle_book_load_failed(path, error),
             Message::ToggleTextOnly => self.handle_toggle_text_only(&mut effects),
+            Message::ToggleAutoPage => self.handle_toggle_auto_page(),
             Message::FontFamilyChanged(family) => {
                 self.handle_font_family_changed(family, &mut effects);
             }
+            Message::CustomFontChanged(name) => self.handle_custom_font_changed(name, &mut effects),
             Message::FontWeightChanged(weight) => {
                 self.handle_font_weight_changed(weight, &mut effects);
             }
+            Message::TextDirectionChanged(direction) => {
+                self.handle_text_direction_changed(direction, &mut effects);
+            }
+            Message::ReadingModeChanged(mode) => {
+                self.handle_reading_mode_changed(mode, &mut effects);
+            }
             Message::LineSpacingChanged(spacing) => {
                 self.handle_line_spacing_changed(spacing, &mut effects);
             }
@@ -76,6 +103,9 @@ impl App {
             Message::LetterSpacingChanged(spacing) => {
                 self.handle_letter_spacing_changed(spacing, &mut effects);
             }
+            Message::ParagraphSpacingChanged(spacing) => {
+                self.handle_paragraph_spacing_changed(spacing, &mut effects);
+            }
             Message::LinesPerPageChanged(lines) => {
                 self.handle_lines_per_page_changed(lines, &mut effects);
             }
@@ -85,9 +115,21 @@ impl App {
             Message::PauseAfterSentenceChanged(pause) => {
                 self.handle_pause_after_sentence_changed(pause, &mut effects);
             }
+            Message::PauseAfterParagraphChanged(pause) => {
+                self.handle_pause_after_paragraph_changed(pause, &mut effects);
+            }
             Message::NightHighlightChanged(component, value) => {
                 self.handle_night_highlight_changed(component, value, &mut effects);
             }
+            Message::DaySearchHighlightChanged(component, value) => {
+                self.handle_search_highlight_changed(false, component, value, &mut effects);
+            }
+            Message::NightSearchHighlightChanged(component, value) => {
+                self.handle_search_highlight_changed(true, component, value, &mut effects);
+            }
+            Message::HighlightStyleChanged(style) => {
+                self.handle_highlight_style_changed(style, &mut effects);
+            }
             Message::BeginNumericSettingEdit(setting) => {
                 self.handle_begin_numeric_setting_edit(setting);
             }
@@ -106,9 +148,34 @@ impl App {
             Message::AutoScrollTtsChanged(enabled) => {
                 self.handle_auto_scroll_tts_changed(enabled, &mut effects);
             }
+            Message::LookupWordsOnClickChanged(enabled) => {
+                self.handle_lookup_words_on_click_changed(enabled, &mut effects);
+            }
+            Message::LookupWord(word) => self.handle_lookup_word(&word),
+            Message::CloseDefinition => self.definition = None,
+            Message::UndoSettings => self.handle_undo_settings(&mut effects),
+            Message::RedoSettings => self.handle_redo_settings(&mut effects),
             Message::CenterSpokenSentenceChanged(centered) => {
                 self.handle_center_spoken_sentence_changed(centered, &mut effects);
             }
+            Message::SmoothScrollChanged(enabled) => {
+                self.handle_smooth_scroll_changed(enabled, &mut effects);
+            }
+            Message::ReadingRulerChanged(enabled) => {
+                self.handle_reading_ruler_changed(enabled, &mut effects);
+            }
+            Message::HyphenateChanged(enabled) => {
+                self.handle_hyphenate_changed(enabled, &mut effects)
+            }
+            Message::CompactControlsChanged(enabled) => {
+                self.handle_compact_controls_changed(enabled, &mut effects);
+            }
+            Message::AccessibilityModeChanged(enabled) => {
+                self.handle_accessibility_mode_changed(enabled, &mut effects);
+            }
+            Message::ReadingRulerDragStarted => self.reading_ruler_dragging = true,
+            Message::ReadingRulerDragged(y) => self.handle_reading_ruler_dragged(y),
+            Message::ReadingRulerDragEnded => self.handle_reading_ruler_drag_ended(&mut effects),
             Message::ToggleTtsControls => self.handle_toggle_tts_controls(&mut effects),
             Message::JumpToCurrentAudio => self.handle_jump_to_current_audio(&mut effects),
             Message::TogglePlayPause => self.handle_toggle_play_pause(&mut effects),
@@ -117,18 +184,41 @@ impl App {
             Message::Play => self.handle_play(&mut effects),
             Message::PlayFromPageStart => self.handle_play_from_page_start(&mut effects),
             Message::PlayFromCursor(idx) => self.handle_play_from_cursor(idx, &mut effects),
+            Message::SelectSentence(idx) => self.handle_select_sentence(idx),
+            Message::ClearSentenceSelection => self.sentence_selection = None,
+            Message::PlaySelection { start, end } => {
+                self.handle_play_selection(start, end, &mut effects);
+            }
             Message::Pause => self.handle_pause(&mut effects),
             Message::SetTtsSpeed(speed) => self.handle_set_tts_speed(speed, &mut effects),
             Message::SetTtsVolume(volume) => self.handle_set_tts_volume(volume, &mut effects),
+            Message::SetTtsVoice(voice) => self.handle_set_tts_voice(voice, &mut effects),
+            Message::SpeakSnippet(text) => self.handle_speak_snippet(text, &mut effects),
+            Message::SnippetSynthesized { request_id, result } => {
+                self.handle_snippet_synthesized(request_id, result, &mut effects)
+            }
             Message::SeekForward => self.handle_seek_forward(&mut effects),
+            Message::NextSentence => self.handle_next_sentence(&mut effects),
+            Message::PreviousSentence => self.handle_previous_sentence(&mut effects),
+            Message::AudioScrubbed(secs) => self.handle_audio_scrubbed(secs),
+            Message::AudioScrubReleased => self.handle_audio_scrub_released(&mut effects),
             Message::SeekBackward => self.handle_seek_backward(&mut effects),
             Message::SentenceClicked(idx) => self.handle_sentence_clicked(idx, &mut effects),
+            Message::FollowCrossRef(page) => self.handle_follow_crossref(page, &mut effects),
+            Message::ShowFootnote(note) => {
+                self.definition = None;
+                self.open_footnote = Some(note);
+            }
+            Message::CloseFootnote => self.open_footnote = None,
             Message::WindowResized { width, height } => {
                 self.handle_window_resized(width, height, &mut effects);
             }
             Message::WindowMoved { x, y } => {
                 self.handle_window_moved(x, y, &mut effects);
             }
+            Message::WindowFocusChanged(focused) => {
+                self.handle_window_focus_changed(focused, &mut effects);
+            }
             Message::KeyPressed { key, modifiers } => {
                 if let Some(shortcut) = self.shortcut_message_for_key(key, modifiers) {
                     effects.extend(self.reduce(shortcut));
@@ -153,33 +243,119 @@ impl App {
                 start_idx,
                 request_id,
                 files,
-            } => self.handle_tts_prepared(page, start_idx, request_id, files, &mut effects),
+                preamble,
+                elapsed,
+            } => {
+                self.record_timing(TimedOperation::TtsBatchPrep, elapsed, files.len());
+                self.handle_tts_prepared(page, start_idx, request_id, files, preamble, &mut effects)
+            }
             Message::TtsAppendPrepared {
                 page,
                 start_idx,
                 request_id,
                 files,
-            } => self.handle_tts_append_prepared(page, start_idx, request_id, files),
+                elapsed,
+            } => {
+                self.record_timing(TimedOperation::TtsBatchPrep, elapsed, files.len());
+                self.handle_tts_append_prepared(page, start_idx, request_id, files)
+            }
             Message::TtsPlanReady {
                 page,
                 requested_display_idx,
                 request_id,
                 plan,
-            } => self.handle_tts_plan_ready(
+                elapsed,
+            } => {
+                self.record_timing(
+                    TimedOperation::NormalizerPlan,
+                    elapsed,
+                    plan.audio_sentences.len(),
+                );
+                self.handle_tts_plan_ready(
+                    page,
+                    requested_display_idx,
+                    request_id,
+                    plan,
+                    &mut effects,
+                )
+            }
+            Message::BookmarkNameChanged(name) => self.bookmark.name_input = name,
+            Message::AddBookmark(name) => self.handle_add_bookmark(name),
+            Message::JumpToBookmark(name) => self.handle_jump_to_bookmark(&name, &mut effects),
+            Message::DeleteBookmark(name) => self.handle_delete_bookmark(&name),
+            Message::ExportBookmarks => self.handle_export_bookmarks(),
+            Message::NoteInputChanged(text) => self.bookmark.note_input = text,
+            Message::AddNote => self.handle_add_note(),
+            Message::JumpToNote(idx) => self.handle_jump_to_note(idx, &mut effects),
+            Message::ExportProgress => self.handle_export_progress(&mut effects),
+            Message::ImportProgress => self.handle_import_progress(&mut effects),
+            Message::ProgressImportRead(contents) => {
+                self.handle_progress_import_read(contents, &mut effects)
+            }
+            Message::ClearBookCache(scope) => self.handle_clear_book_cache(scope),
+            Message::ConfirmClearBookCache => self.handle_confirm_clear_book_cache(&mut effects),
+            Message::CancelClearBookCache => self.pending_cache_clear = None,
+            Message::BookCacheCleared { scope, result } => {
+                self.handle_book_cache_cleared(scope, result, &mut effects)
+            }
+            Message::RelocatePathChanged(path) => self.relocate_path_input = path,
+            Message::RelocateSource => self.handle_relocate_source(&mut effects),
+            Message::ProfilePathChanged(path) => self.profile_path_input = path,
+            Message::ExportProfile => self.handle_export_profile(),
+            Message::LogLevelChanged(level) => self.handle_log_level_changed(level, &mut effects),
+            Message::CopyDiagnostics => self.handle_copy_diagnostics(&mut effects),
+            Message::ShowDiagnostics => self.handle_show_diagnostics(&mut effects),
+            Message::DiagnosticsReady { path, result } => {
+                self.handle_diagnostics_ready(path, result)
+            }
+            Message::CloseDiagnostics => self.book_diagnostics = None,
+            Message::ImportProfile => self.handle_import_profile(&mut effects),
+            Message::CacheChapterAudio(chapter_idx) => {
+                self.handle_cache_chapter_audio(chapter_idx, &mut effects);
+            }
+            Message::CacheBookAudio => self.handle_cache_book_audio(&mut effects),
+            Message::CancelAudioCache => self.handle_cancel_audio_cache(),
+            Message::ChapterAudioPageCached {
+                request_id,
                 page,
-                requested_display_idx,
+                result,
+            } => self.handle_chapter_audio_page_cached(request_id, page, result, &mut effects),
+            Message::ExportAudio => self.handle_export_audio(&mut effects),
+            Message::CancelAudioExport => self.handle_cancel_audio_export(),
+            Message::AudioExportPageReady {
                 request_id,
-                plan,
-                &mut effects,
-            ),
+                page,
+                result,
+            } => self.handle_audio_export_page_ready(request_id, page, result, &mut effects),
+            Message::AudioExportFinished { request_id, result } => {
+                self.handle_audio_export_finished(request_id, result)
+            }
+            Message::TtsPagePrefetched {
+                request_id,
+                page,
+                result,
+            } => self.handle_tts_page_prefetched(request_id, page, result),
             Message::Tick(now) => self.handle_tick(now, &mut effects),
+            Message::ScrollGlideFrame(now) => self.handle_scroll_glide_frame(now, &mut effects),
+            Message::ThemeScheduleTick => self.handle_theme_schedule_tick(&mut effects),
             Message::PollSystemSignals => self.handle_poll_system_signals(&mut effects),
+            Message::NormalizerReloaded(result) => {
+                self.handle_normalizer_reloaded(result, &mut effects)
+            }
+        }
+
+        if let Some((control, before)) = settings_before
+            && before != self.config
+        {
+            self.settings_history
+                .record(before, control, std::time::Instant::now());
         }
 
         if self.text_only_mode {
             self.ensure_text_only_preview_for_page(self.reader.current_page);
         }
-        self.update_search_matches();
+        self.ensure_hyphenation_for_page(self.reader.current_page);
+        self.resume_deferred_audio_cache(&mut effects);
 
         effects
     }
@@ -190,6 +366,7 @@ impl App {
         } else {
             self.search.error = None;
             self.search.matches.clear();
+            self.search.truncated = false;
             self.search.selected_match = 0;
         }
         effects.push(Effect::SaveBookmark);
@@ -206,7 +383,12 @@ impl App {
         if crate::take_sigint_requested() {
             effects.push(Effect::QuitSafely);
         }
+        self.maybe_repaginate_after_resize(effects);
         self.maybe_flush_window_geometry_updates(effects);
+        self.track_reading_time(effects);
+        self.pause_tts_after_system_sleep(effects);
+        self.maybe_reload_normalizer(effects);
+        self.maybe_check_source();
     }
 
     fn handle_search_query_changed(&mut self, query: String) {
@@ -239,15 +421,10 @@ impl App {
     }
 
     fn jump_to_selected_search_match(&mut self, effects: &mut Vec<Effect>) {
-        let Some(sentence_idx) = self.selected_search_sentence_idx() else {
-            return;
-        };
-        let Some(display_idx) = self.display_idx_for_search_sentence_idx(sentence_idx) else {
+        let Some(hit) = self.selected_search_hit().cloned() else {
             return;
         };
-        self.tts.current_sentence_idx = Some(display_idx);
-        effects.push(Effect::AutoScrollToCurrent);
-        effects.push(Effect::SaveBookmark);
+        self.go_to_search_hit(&hit, effects);
     }
 
     fn handle_toggle_recent_books(&mut self) {
@@ -264,12 +441,36 @@ impl App {
         if self.book_loading {
             return;
         }
+        if let Some(err) = missing_source_error(&path) {
+            self.book_loading_error = Some(err);
+            return;
+        }
         self.book_loading = true;
         self.book_loading_error = None;
         info!(path = %path.display(), "Opening recent book");
         effects.push(Effect::LoadBook(path));
     }
 
+    fn handle_open_book(&mut self, path: std::path::PathBuf, effects: &mut Vec<Effect>) {
+        if self.book_loading {
+            return;
+        }
+        if let Some(err) = missing_source_error(&path) {
+            self.book_loading_error = Some(err);
+            return;
+        }
+        if !self.starter_mode {
+            // Keep the current book's place before its state is replaced.
+            self.stop_playback();
+            self.stop_snippet();
+            effects.push(Effect::SaveBookmark);
+        }
+        self.book_loading = true;
+        self.book_loading_error = None;
+        info!(path = %path.display(), "Opening another book");
+        effects.push(Effect::LoadBook(path));
+    }
+
     fn handle_delete_recent_book(&mut self, path: std::path::PathBuf) {
         if self.book_loading {
             return;
@@ -447,6 +648,8 @@ impl App {
         bookmark: Option<crate::cache::Bookmark>,
         effects: &mut Vec<Effect>,
     ) {
+        self.persist_reading_stats();
+        let resuming = bookmark.is_some();
         let initial_scroll = self.apply_loaded_book(book, config, path.clone(), bookmark);
         self.refresh_recent_books();
         if let Some(offset) = initial_scroll {
@@ -454,6 +657,17 @@ impl App {
         } else if self.tts.current_sentence_idx.is_some() {
             effects.push(Effect::AutoScrollToCurrent);
         }
+        if let Some(sentence_idx) = self.tts_resume_point().filter(|_| resuming) {
+            info!(
+                page = self.reader.current_page + 1,
+                sentence_idx, "Preparing paused TTS at the resume point"
+            );
+            self.tts.resume_after_prepare = false;
+            effects.push(Effect::StartTts {
+                page: self.reader.current_page,
+                sentence_idx,
+            });
+        }
         info!(path = %path.display(), "Book loaded in-process");
     }
 
@@ -1,17 +1,25 @@
-use super::super::super::messages::Message;
+use super::super::super::messages::{CacheClearScope, CachedPageAudio, Message};
 use super::super::super::state::{App, PendingAppendBatch, TEXT_SCROLL_ID, TtsLifecycle};
 use super::super::Effect;
-use crate::cache::{load_bookmark, load_epub_config, remember_source_path};
-use crate::config::load_config;
-use crate::epub_loader::load_book_content;
+use crate::cache::{
+    clear_book_cache, clips_used_by_other_books, enforce_tts_cache_limit, load_book_config,
+    load_bookmark, push_recent, record_book_clips, remember_source_path,
+};
+use crate::config::{load_app_config, normalizer_config_path};
+use crate::epub_loader::{LoadOptions, diagnose_book, load_book_content};
+use crate::normalizer::TextNormalizer;
+use crate::tts::remove_cached_clips;
 use iced::Event;
 use iced::Task;
 use iced::event;
 use iced::keyboard;
 use iced::mouse;
 use iced::window;
-use std::path::Path;
-use std::time::Duration;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant, SystemTime};
 use tracing::info;
 
 impl App {
@@ -25,7 +33,27 @@ impl App {
                 self.persist_bookmark();
                 Task::none()
             }
-            Effect::StartTts { page, sentence_idx } => self.start_playback_from(page, sentence_idx),
+            Effect::SaveReadingStats(stats) => {
+                crate::cache::save_stats(&self.epub_path, &stats);
+                Task::none()
+            }
+            Effect::TouchSentenceClip(path) => {
+                crate::cache::touch_sentence_clip(&path);
+                Task::none()
+            }
+            Effect::StartTts { page, sentence_idx } => {
+                self.stop_snippet();
+                // Playback takes over from the silent reading cursor.
+                self.reader.cursor_sentence_idx = None;
+                self.tts.selection_end = None;
+                self.start_playback_from(page, sentence_idx)
+            }
+            Effect::StartTtsSelection { page, start, end } => {
+                self.stop_snippet();
+                self.reader.cursor_sentence_idx = None;
+                self.tts.selection_end = Some(end);
+                self.start_playback_from(page, start)
+            }
             Effect::PrepareTtsBatches {
                 page,
                 request_id,
@@ -37,7 +65,6 @@ impl App {
                     self.tts.pending_append = false;
                     return Task::none();
                 };
-                let cache_root = crate::cache::tts_dir(&self.epub_path);
                 let threads = self.config.tts_threads.max(1);
                 let progress_log_interval =
                     Duration::from_secs_f32(self.config.tts_progress_log_interval_secs);
@@ -49,7 +76,8 @@ impl App {
                     clamped_start_idx
                 };
                 let remaining = audio_sentences.len().saturating_sub(start_idx);
-                let initial_count = remaining.min(1);
+                // Playback waits for this first batch; the rest is appended in the background.
+                let initial_count = remaining.min(self.config.tts_lookahead.max(1));
                 let initial_sentences = audio_sentences
                     .iter()
                     .skip(start_idx)
@@ -86,28 +114,55 @@ impl App {
                     "Split TTS generation into initial playback batch and background append batch"
                 );
                 let initial_engine = engine.clone();
-                let initial_cache = cache_root.clone();
+                let cache_limit = self.tts_cache_limit();
+                let announcement = self.take_chapter_announcement(page, start_idx);
+                let epub_path = self.epub_path.clone();
                 let initial_task = Task::perform(
                     async move {
-                        initial_engine
-                            .prepare_batch(
-                                initial_cache,
-                                initial_sentences,
-                                0,
-                                threads,
-                                progress_log_interval,
-                            )
+                        let started = Instant::now();
+                        // The chapter title is its own clip, queued ahead of the sentences.
+                        let preamble = announcement.and_then(|title| {
+                            initial_engine
+                                .prepare_batch(vec![title], 0, 1, progress_log_interval)
+                                .ok()?
+                                .pop()
+                        });
+                        let prepared = initial_engine.prepare_batch(
+                            initial_sentences,
+                            0,
+                            threads,
+                            progress_log_interval,
+                        );
+                        if let Ok(files) = &prepared {
+                            record_book_clips(
+                                &epub_path,
+                                files.iter().chain(&preamble).map(|(path, _)| path),
+                            );
+                        }
+                        TtsCacheLimit::enforce(
+                            cache_limit,
+                            prepared
+                                .iter()
+                                .flatten()
+                                .chain(&preamble)
+                                .map(|(path, _)| path),
+                        );
+                        prepared
                             .map(|files| Message::TtsPrepared {
                                 page,
                                 start_idx,
                                 request_id,
                                 files,
+                                preamble,
+                                elapsed: started.elapsed(),
                             })
                             .unwrap_or_else(|_| Message::TtsPrepared {
                                 page,
                                 start_idx,
                                 request_id,
                                 files: Vec::new(),
+                                preamble: None,
+                                elapsed: started.elapsed(),
                             })
                     },
                     |msg| msg,
@@ -125,31 +180,41 @@ impl App {
                     self.tts.pending_append_batch = None;
                     return Task::none();
                 };
-                let cache_root = crate::cache::tts_dir(&self.epub_path);
                 let threads = self.config.tts_threads.max(1);
                 let progress_log_interval =
                     Duration::from_secs_f32(self.config.tts_progress_log_interval_secs);
+                let cache_limit = self.tts_cache_limit();
+                let epub_path = self.epub_path.clone();
                 Task::perform(
                     async move {
-                        engine
-                            .prepare_batch(
-                                cache_root,
-                                audio_sentences,
-                                0,
-                                threads,
-                                progress_log_interval,
-                            )
+                        let started = Instant::now();
+                        let prepared = engine.prepare_batch(
+                            audio_sentences,
+                            0,
+                            threads,
+                            progress_log_interval,
+                        );
+                        if let Ok(files) = &prepared {
+                            record_book_clips(&epub_path, files.iter().map(|(path, _)| path));
+                        }
+                        TtsCacheLimit::enforce(
+                            cache_limit,
+                            prepared.iter().flatten().map(|(path, _)| path),
+                        );
+                        prepared
                             .map(|files| Message::TtsAppendPrepared {
                                 page,
                                 start_idx,
                                 request_id,
                                 files,
+                                elapsed: started.elapsed(),
                             })
                             .unwrap_or_else(|_| Message::TtsAppendPrepared {
                                 page,
                                 start_idx,
                                 request_id,
                                 files: Vec::new(),
+                                elapsed: started.elapsed(),
                             })
                     },
                     |msg| msg,
@@ -159,6 +224,56 @@ impl App {
                 self.stop_playback();
                 Task::none()
             }
+            Effect::CacheChapterAudioPage { page, request_id } => {
+                let cancel = self
+                    .tts
+                    .chapter_cache
+                    .as_ref()
+                    .map(|job| job.cancel.clone())
+                    .unwrap_or_default();
+                self.prepare_page_audio(page, cancel, move |result| {
+                    Message::ChapterAudioPageCached {
+                        request_id,
+                        page,
+                        result: result.map(|(files, reused)| CachedPageAudio {
+                            bytes: files
+                                .iter()
+                                .filter_map(|file| std::fs::metadata(file).ok())
+                                .map(|meta| meta.len())
+                                .sum(),
+                            files,
+                            reused,
+                        }),
+                    }
+                })
+            }
+            Effect::ExportAudioPage { page, request_id } => {
+                let cancel = self
+                    .tts
+                    .audio_export
+                    .as_ref()
+                    .map(|job| job.cancel.clone())
+                    .unwrap_or_default();
+                self.prepare_page_audio(page, cancel, move |result| Message::AudioExportPageReady {
+                    request_id,
+                    page,
+                    result: result.map(|(files, _)| files),
+                })
+            }
+            Effect::ExportAudio { output } => self.write_audio_export(output),
+            Effect::PrefetchTtsPage { page, request_id } => {
+                self.prepare_page_audio(page, Arc::default(), move |result| {
+                    Message::TtsPagePrefetched {
+                        request_id,
+                        page,
+                        result,
+                    }
+                })
+            }
+            Effect::SynthesizeSnippet {
+                request_id,
+                sentences,
+            } => self.synthesize_snippet(request_id, sentences),
             Effect::ScrollTo(offset) => {
                 self.bookmark.last_scroll_offset = offset;
                 iced::widget::scrollable::snap_to(TEXT_SCROLL_ID.clone(), offset)
@@ -173,6 +288,9 @@ impl App {
                     {
                         return Task::none();
                     }
+                    if self.start_scroll_glide(idx) {
+                        return Task::none();
+                    }
                     if let Some(offset) = self.scroll_offset_for_sentence(idx) {
                         self.bookmark.last_scroll_offset = offset;
                         return iced::widget::scrollable::snap_to(TEXT_SCROLL_ID.clone(), offset);
@@ -219,41 +337,44 @@ impl App {
                 },
                 |message| message,
             ),
+            Effect::ReloadNormalizer => Task::perform(
+                async { TextNormalizer::try_load(normalizer_config_path()) },
+                Message::NormalizerReloaded,
+            ),
             Effect::ReadClipboard => iced::clipboard::read().map(Message::ClipboardRead),
+            Effect::WriteClipboard(contents) => iced::clipboard::write(contents),
+            Effect::ReadProgressClipboard => {
+                iced::clipboard::read().map(Message::ProgressImportRead)
+            }
+            Effect::ClearBookCache(scope) => self.clear_book_cache_task(scope),
+            Effect::DiagnoseBook(path) => Task::perform(
+                async move {
+                    let result = diagnose_book(&path).map_err(|err| format!("{err:#}"));
+                    Message::DiagnosticsReady { path, result }
+                },
+                |msg| msg,
+            ),
             Effect::LoadBook(path) => {
                 self.book_loading = true;
                 self.book_loading_error = None;
                 let requested_path = path.clone();
                 Task::perform(
                     async move {
-                        let base_config = load_config(Path::new("conf/config.toml"));
+                        let base_config = load_app_config();
                         remember_source_path(&requested_path);
-                        let mut config = base_config.clone();
-                        if let Some(mut overrides) = load_epub_config(&requested_path) {
-                            overrides.log_level = base_config.log_level;
-                            overrides.tts_threads = base_config.tts_threads;
-                            overrides.tts_progress_log_interval_secs =
-                                base_config.tts_progress_log_interval_secs;
-                            overrides.key_toggle_play_pause =
-                                base_config.key_toggle_play_pause.clone();
-                            overrides.key_safe_quit = base_config.key_safe_quit.clone();
-                            overrides.key_next_sentence = base_config.key_next_sentence.clone();
-                            overrides.key_prev_sentence = base_config.key_prev_sentence.clone();
-                            overrides.key_repeat_sentence = base_config.key_repeat_sentence.clone();
-                            overrides.key_toggle_search = base_config.key_toggle_search.clone();
-                            overrides.key_toggle_settings = base_config.key_toggle_settings.clone();
-                            overrides.key_toggle_stats = base_config.key_toggle_stats.clone();
-                            overrides.key_toggle_tts = base_config.key_toggle_tts.clone();
-                            config = overrides;
-                        }
+                        let config = load_book_config(&base_config, &requested_path);
                         let bookmark = load_bookmark(&requested_path);
-                        match load_book_content(&requested_path) {
-                            Ok(book) => Message::BookLoaded {
-                                path: requested_path,
-                                book,
-                                config,
-                                bookmark,
-                            },
+                        let options = LoadOptions::from_config(&config);
+                        match load_book_content(&requested_path, &options) {
+                            Ok(book) => {
+                                push_recent(&requested_path, book.metadata.title.as_deref());
+                                Message::BookLoaded {
+                                    path: requested_path,
+                                    book,
+                                    config,
+                                    bookmark,
+                                }
+                            }
                             Err(err) => Message::BookLoadFailed {
                                 path: requested_path,
                                 error: err.to_string(),
@@ -266,6 +387,7 @@ impl App {
             Effect::ReturnToStarter => {
                 self.save_epub_config();
                 self.persist_bookmark();
+                self.persist_reading_stats();
                 self.stop_playback();
                 let (next, init_task) = App::bootstrap_starter(self.config.clone());
                 *self = next;
@@ -274,11 +396,162 @@ impl App {
             Effect::QuitSafely => {
                 self.save_epub_config();
                 self.persist_bookmark();
+                self.persist_reading_stats();
                 self.stop_playback();
+                self.stop_snippet();
                 iced::exit()
             }
         }
     }
+
+    /// Synthesize (or reuse) every audio sentence on `page` off the UI thread, reporting
+    /// the cached files in order along with how many were already on disk. Setting
+    /// `cancel` stops the page early.
+    fn prepare_page_audio(
+        &self,
+        page: usize,
+        cancel: Arc<AtomicBool>,
+        on_done: impl FnOnce(Result<(Vec<PathBuf>, usize), String>) -> Message + Send + 'static,
+    ) -> Task<Message> {
+        let Some(engine) = self.tts.engine.clone() else {
+            return Task::done(on_done(Err("TTS engine unavailable".to_string())));
+        };
+        let normalizer = self.normalizer.clone();
+        let epub_path = self.epub_path.clone();
+        let display_sentences = self.speech_sentences_for_page(page);
+        let threads = self.config.tts_threads.max(1);
+        let progress_log_interval =
+            Duration::from_secs_f32(self.config.tts_progress_log_interval_secs);
+        let cache_limit = self.tts_cache_limit();
+        Task::perform(
+            async move {
+                let plan = normalizer.plan_page_cached(&epub_path, page, &display_sentences);
+                let reused = plan
+                    .audio_sentences
+                    .iter()
+                    .filter(|sentence| engine.is_cached(sentence))
+                    .count();
+                let prepared = engine.prepare_batch_cancellable(
+                    plan.audio_sentences,
+                    0,
+                    threads,
+                    progress_log_interval,
+                    &cancel,
+                );
+                if let Ok(files) = &prepared {
+                    record_book_clips(&epub_path, files.iter().map(|(path, _)| path));
+                }
+                TtsCacheLimit::enforce(
+                    cache_limit,
+                    prepared.iter().flatten().map(|(path, _)| path),
+                );
+                let result = prepared
+                    .map(|files| (files.into_iter().map(|(path, _)| path).collect(), reused))
+                    .map_err(|err| err.to_string());
+                on_done(result)
+            },
+            |msg| msg,
+        )
+    }
+
+    /// What `tts_cache_max_mb` eviction must respect once a batch is prepared,
+    /// or `None` when the audio cache is unlimited.
+    fn tts_cache_limit(&self) -> Option<TtsCacheLimit> {
+        let max_bytes = self.tts_cache_max_bytes()?;
+        let mut keep: HashSet<PathBuf> = self
+            .tts
+            .track
+            .iter()
+            .map(|(path, _)| path)
+            .chain(self.tts.chapter_cache.iter().flat_map(|job| &job.clips))
+            .chain(&self.tts.prefetched_clips)
+            .cloned()
+            .collect();
+        if let (Some(engine), Some(batch)) = (&self.tts.engine, &self.tts.pending_append_batch) {
+            keep.extend(
+                batch
+                    .audio_sentences
+                    .iter()
+                    .map(|sentence| engine.clip_path(sentence)),
+            );
+        }
+        Some(TtsCacheLimit {
+            max_bytes,
+            keep,
+            since: SystemTime::now(),
+        })
+    }
+
+    /// Delete the open book's sentence audio for every voice and, for
+    /// [`CacheClearScope::Everything`], its cache directory too. Clips another
+    /// book has played are shared, so they stay.
+    fn clear_book_cache_task(&self, scope: CacheClearScope) -> Task<Message> {
+        let normalizer = self.normalizer.clone();
+        let epub_path = self.epub_path.clone();
+        let pages: Vec<Vec<String>> = (0..self.reader.page_sentences.len())
+            .map(|page| self.speech_sentences_for_page(page))
+            .collect();
+        Task::perform(
+            async move {
+                // Clips are keyed by normalized text, so re-plan each page to find them.
+                let shared = clips_used_by_other_books(&epub_path);
+                let clips: usize = pages
+                    .iter()
+                    .map(|sentences| {
+                        remove_cached_clips(
+                            &normalizer.plan_page(sentences).audio_sentences,
+                            &shared,
+                        )
+                    })
+                    .sum();
+                let result = match scope {
+                    CacheClearScope::Audio => Ok(clips),
+                    CacheClearScope::Everything => clear_book_cache(&epub_path).map(|()| clips),
+                };
+                Message::BookCacheCleared { scope, result }
+            },
+            |msg| msg,
+        )
+    }
+
+    /// Render snippet audio into the temp dir, where it stays only until played.
+    fn synthesize_snippet(&self, request_id: u64, sentences: Vec<String>) -> Task<Message> {
+        let Some(engine) = self.tts.engine.clone() else {
+            return Task::done(Message::SnippetSynthesized {
+                request_id,
+                result: Err("TTS engine unavailable".to_string()),
+            });
+        };
+        let threads = self.config.tts_threads.max(1);
+        let dir = std::env::temp_dir().join("ebup-viewer-snippets");
+        let prefix = format!("{}-{request_id}", std::process::id());
+        Task::perform(
+            async move {
+                engine
+                    .synthesize_uncached(&sentences, &dir, &prefix, threads)
+                    .map_err(|err| err.to_string())
+            },
+            move |result| Message::SnippetSynthesized { request_id, result },
+        )
+    }
+
+    fn write_audio_export(&self, output: PathBuf) -> Task<Message> {
+        let Some(job) = self.tts.audio_export.as_ref() else {
+            return Task::none();
+        };
+        let request_id = job.request_id;
+        let files = job.files.clone();
+        let cancel = job.cancel.clone();
+        let pause_after = Duration::from_secs_f32(self.config.pause_after_sentence.max(0.0));
+        Task::perform(
+            async move {
+                crate::tts::concat_wav_files(&files, pause_after, &output, &cancel)
+                    .map(|()| output)
+                    .map_err(|err| err.to_string())
+            },
+            move |result| Message::AudioExportFinished { request_id, result },
+        )
+    }
 }
 
 pub(super) fn runtime_event_to_message(
@@ -303,6 +576,9 @@ pub(super) fn runtime_event_to_message(
             x: position.x,
             y: position.y,
         }),
+        Event::Window(iced::window::Event::Focused) => Some(Message::WindowFocusChanged(true)),
+        Event::Window(iced::window::Event::Unfocused) => Some(Message::WindowFocusChanged(false)),
+        Event::Window(iced::window::Event::FileDropped(path)) => Some(Message::OpenBook(path)),
         Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
             Some(Message::KeyPressed { key, modifiers })
         }
@@ -313,6 +589,28 @@ pub(super) fn runtime_event_to_message(
     }
 }
 
+/// Size limit for the shared sentence audio, with the clips queued or
+/// pending when the batch started: the playing track, the lookahead page and
+/// append batch, and the clips an audio cache job has made.
+struct TtsCacheLimit {
+    max_bytes: u64,
+    keep: HashSet<PathBuf>,
+    /// Clips written after this belong to work still in flight.
+    since: SystemTime,
+}
+
+impl TtsCacheLimit {
+    /// Evict down to the limit after a batch, also sparing the clips it
+    /// prepared.
+    fn enforce<'a>(limit: Option<Self>, prepared: impl IntoIterator<Item = &'a PathBuf>) {
+        let Some(mut limit) = limit else {
+            return;
+        };
+        limit.keep.extend(prepared.into_iter().cloned());
+        enforce_tts_cache_limit(limit.max_bytes, &limit.keep, limit.since);
+    }
+}
+
 fn wheel_delta_y(delta: mouse::ScrollDelta) -> f32 {
     match delta {
         mouse::ScrollDelta::Lines { y, .. } => y,
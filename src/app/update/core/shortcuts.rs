@@ -1,5 +1,6 @@
 use super::super::super::messages::Message;
 use super::super::super::state::App;
+use crate::config::KeyAction;
 use iced::keyboard::{Key, Modifiers, key};
 
 impl App {
@@ -8,65 +9,101 @@ impl App {
         key: Key,
         modifiers: Modifiers,
     ) -> Option<Message> {
-        let pressed = match key.as_ref() {
-            Key::Named(key::Named::Space) => "space".to_string(),
-            Key::Character(ch) => ch.to_ascii_lowercase(),
-            _ => return None,
-        };
+        let pressed = Self::pressed_key_name(&key)?;
+        let config = &self.config;
+        let builtin = [
+            (&config.key_toggle_play_pause, "space", KeyAction::PlayPause),
+            (&config.key_safe_quit, "q", KeyAction::SafeQuit),
+            (&config.key_next_sentence, "f,j", KeyAction::NextSentence),
+            (&config.key_prev_sentence, "s,k", KeyAction::PrevSentence),
+            (&config.key_next_page, "right", KeyAction::NextPage),
+            (&config.key_prev_page, "left", KeyAction::PrevPage),
+            (&config.key_repeat_sentence, "r", KeyAction::RepeatSentence),
+            (&config.key_toggle_search, "ctrl+f", KeyAction::ToggleSearch),
+            (
+                &config.key_toggle_settings,
+                "ctrl+t",
+                KeyAction::ToggleSettings,
+            ),
+            (&config.key_toggle_stats, "ctrl+g", KeyAction::ToggleStats),
+            (&config.key_toggle_tts, "ctrl+y", KeyAction::ToggleTts),
+        ];
+        // Bindings from the key map win over the per-action keys.
+        let action = config
+            .key_map
+            .iter()
+            .find(|(binding, _)| Self::shortcut_matches(binding, "", &pressed, modifiers))
+            .map(|(_, action)| *action)
+            .or_else(|| {
+                builtin
+                    .into_iter()
+                    .find(|(raw, fallback, _)| {
+                        Self::shortcut_matches(raw, fallback, &pressed, modifiers)
+                    })
+                    .map(|(_, _, action)| action)
+            })?;
+        // Arrow keys point the way a right-to-left book's pages turn; other
+        // keys keep their meaning.
+        let mirrored = self.is_rtl() && matches!(pressed.as_str(), "left" | "right");
+        Some(self.key_action_message(action, mirrored))
+    }
 
-        if Self::shortcut_matches(
-            &self.config.key_toggle_play_pause,
-            "space",
-            &pressed,
-            modifiers,
-        ) {
-            Some(Message::TogglePlayPause)
-        } else if Self::shortcut_matches(&self.config.key_safe_quit, "q", &pressed, modifiers) {
-            Some(Message::SafeQuit)
-        } else if Self::shortcut_matches(&self.config.key_next_sentence, "f", &pressed, modifiers) {
-            Some(Message::SeekForward)
-        } else if Self::shortcut_matches(&self.config.key_prev_sentence, "s", &pressed, modifiers) {
-            Some(Message::SeekBackward)
-        } else if Self::shortcut_matches(&self.config.key_repeat_sentence, "r", &pressed, modifiers)
-        {
-            Some(Message::RepeatCurrentSentence)
-        } else if Self::shortcut_matches(
-            &self.config.key_toggle_search,
-            "ctrl+f",
-            &pressed,
-            modifiers,
-        ) {
-            Some(Message::ToggleSearch)
-        } else if Self::shortcut_matches(
-            &self.config.key_toggle_settings,
-            "ctrl+t",
-            &pressed,
-            modifiers,
-        ) {
-            Some(Message::ToggleSettings)
-        } else if Self::shortcut_matches(
-            &self.config.key_toggle_stats,
-            "ctrl+g",
-            &pressed,
-            modifiers,
-        ) {
-            Some(Message::ToggleStats)
-        } else if Self::shortcut_matches(&self.config.key_toggle_tts, "ctrl+y", &pressed, modifiers)
-        {
-            Some(Message::ToggleTtsControls)
-        } else {
-            None
+    fn key_action_message(&self, action: KeyAction, mirrored: bool) -> Message {
+        match action {
+            KeyAction::PlayPause => Message::TogglePlayPause,
+            KeyAction::SafeQuit => Message::SafeQuit,
+            KeyAction::NextSentence if self.tts_engaged() => Message::SeekForward,
+            KeyAction::NextSentence => Message::NextSentence,
+            KeyAction::PrevSentence if self.tts_engaged() => Message::SeekBackward,
+            KeyAction::PrevSentence => Message::PreviousSentence,
+            KeyAction::NextPage if mirrored => Message::PreviousPage,
+            KeyAction::NextPage => Message::NextPage,
+            KeyAction::PrevPage if mirrored => Message::NextPage,
+            KeyAction::PrevPage => Message::PreviousPage,
+            KeyAction::RepeatSentence => Message::RepeatCurrentSentence,
+            KeyAction::ToggleSearch => Message::ToggleSearch,
+            KeyAction::ToggleSettings => Message::ToggleSettings,
+            KeyAction::ToggleStats => Message::ToggleStats,
+            KeyAction::ToggleTts => Message::ToggleTtsControls,
+            KeyAction::ToggleTheme => Message::ToggleTheme,
+            KeyAction::ToggleContents => Message::ToggleContents,
+            KeyAction::ToggleTextOnly => Message::ToggleTextOnly,
         }
     }
 
+    /// Whether sentence keys should seek the narration rather than step the
+    /// silent reading cursor.
+    fn tts_engaged(&self) -> bool {
+        self.tts.playback.is_some() || self.tts.is_preparing()
+    }
+
+    pub(super) fn pressed_key_name(key: &Key) -> Option<String> {
+        match key.as_ref() {
+            Key::Named(key::Named::Space) => Some("space".to_string()),
+            Key::Named(key::Named::ArrowLeft) => Some("left".to_string()),
+            Key::Named(key::Named::ArrowRight) => Some("right".to_string()),
+            Key::Named(key::Named::ArrowUp) => Some("up".to_string()),
+            Key::Named(key::Named::ArrowDown) => Some("down".to_string()),
+            Key::Character(ch) => Some(ch.to_ascii_lowercase()),
+            _ => None,
+        }
+    }
+
+    /// `raw` may list several comma-separated bindings; any of them matching is enough.
     pub(super) fn shortcut_matches(
         raw: &str,
         fallback: &str,
         pressed: &str,
         modifiers: Modifiers,
     ) -> bool {
-        let normalized = Self::normalize_shortcut_token(raw, fallback);
+        Self::normalize_shortcut_token(raw, fallback)
+            .split(',')
+            .map(str::trim)
+            .filter(|binding| !binding.is_empty())
+            .any(|binding| Self::binding_matches(binding, pressed, modifiers))
+    }
 
+    fn binding_matches(normalized: &str, pressed: &str, modifiers: Modifiers) -> bool {
         let mut required_ctrl = false;
         let mut required_alt = false;
         let mut required_logo = false;
@@ -87,8 +124,7 @@ impl App {
             }
         }
 
-        let required_key = required_key.unwrap_or(fallback);
-        if pressed != required_key {
+        if required_key != Some(pressed) {
             return false;
         }
 
@@ -111,6 +147,9 @@ impl App {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::state::test_app_with;
+    use crate::config::{AppConfig, TextDirection};
+    use crate::epub_loader::LoadedBook;
     use iced::keyboard::Modifiers;
 
     #[test]
@@ -132,4 +171,54 @@ mod tests {
             Modifiers::CTRL | Modifiers::SHIFT,
         ));
     }
+
+    #[test]
+    fn matches_any_comma_separated_alternative() {
+        assert!(App::shortcut_matches("f, j", "x", "j", Modifiers::empty()));
+        assert!(App::shortcut_matches("f,ctrl+j", "x", "j", Modifiers::CTRL));
+        assert!(!App::shortcut_matches("f,j", "x", "k", Modifiers::empty()));
+    }
+
+    #[test]
+    fn names_arrow_keys() {
+        let right = Key::Named(key::Named::ArrowRight);
+        assert_eq!(App::pressed_key_name(&right).as_deref(), Some("right"));
+        assert!(App::shortcut_matches(
+            "right",
+            "x",
+            "right",
+            Modifiers::empty()
+        ));
+    }
+
+    #[test]
+    fn right_to_left_mirrors_only_the_arrow_keys() {
+        let book = LoadedBook {
+            text: "One sentence.".to_string(),
+            ..Default::default()
+        };
+        let mut config = AppConfig::default();
+        config.text_direction = TextDirection::Rtl;
+        config.key_next_page = "right,n".to_string();
+        config.key_prev_page = "left,p".to_string();
+        let app = test_app_with(book, config);
+        let press = |key: Key| app.shortcut_message_for_key(key, Modifiers::empty());
+
+        assert!(matches!(
+            press(Key::Named(key::Named::ArrowLeft)),
+            Some(Message::NextPage)
+        ));
+        assert!(matches!(
+            press(Key::Named(key::Named::ArrowRight)),
+            Some(Message::PreviousPage)
+        ));
+        assert!(matches!(
+            press(Key::Character("n".into())),
+            Some(Message::NextPage)
+        ));
+        assert!(matches!(
+            press(Key::Character("p".into())),
+            Some(Message::PreviousPage)
+        ));
+    }
 }
@@ -0,0 +1,100 @@
+use super::super::state::App;
+use super::Effect;
+use crate::epub_loader::BookDiagnostics;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+impl App {
+    pub(super) fn handle_show_diagnostics(&mut self, effects: &mut Vec<Effect>) {
+        if self.starter_mode || self.book_diagnostics_running {
+            return;
+        }
+        info!(path = %self.epub_path.display(), "Running book diagnostics");
+        self.book_diagnostics_running = true;
+        effects.push(Effect::DiagnoseBook(self.epub_path.clone()));
+    }
+
+    pub(super) fn handle_diagnostics_ready(
+        &mut self,
+        path: PathBuf,
+        result: Result<BookDiagnostics, String>,
+    ) {
+        // A report for a book closed while it ran is stale.
+        if path != self.epub_path {
+            return;
+        }
+        self.book_diagnostics_running = false;
+        if let Err(err) = &result {
+            warn!("Book diagnostics failed: {err}");
+        }
+        self.book_diagnostics = Some(result);
+    }
+
+    pub(super) fn handle_copy_diagnostics(&mut self, effects: &mut Vec<Effect>) {
+        let report = self.diagnostics_report(&crate::logging::recent_lines());
+        info!(bytes = report.len(), "Copied diagnostics to the clipboard");
+        effects.push(Effect::WriteClipboard(report));
+    }
+
+    /// Plain-text summary for bug reports: versions, the open book and TTS
+    /// state, followed by `log_lines`.
+    fn diagnostics_report(&self, log_lines: &[String]) -> String {
+        let mut report = String::new();
+        let _ = writeln!(report, "ebup-viewer {}", env!("CARGO_PKG_VERSION"));
+        let _ = writeln!(
+            report,
+            "Platform: {} {}",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        );
+        let _ = writeln!(
+            report,
+            "Log filter: {}",
+            crate::logging::current_filter().unwrap_or_else(|| self.config.log_level.to_string())
+        );
+        if self.starter_mode {
+            let _ = writeln!(report, "Book: none (starter)");
+        } else {
+            let _ = writeln!(report, "Book: {}", self.epub_path.display());
+            let _ = writeln!(
+                report,
+                "Page: {} of {}",
+                self.reader.current_page + 1,
+                self.reader.pages.len()
+            );
+        }
+        let _ = writeln!(
+            report,
+            "TTS: {:?}, voice {}",
+            self.tts.lifecycle,
+            self.tts
+                .engine
+                .as_ref()
+                .map_or("unavailable", |engine| engine.voice_id())
+        );
+        let _ = writeln!(report, "\nRecent log lines:");
+        for line in log_lines {
+            let _ = writeln!(report, "{line}");
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::test_app;
+
+    #[test]
+    fn diagnostics_name_the_book_and_end_with_the_log() {
+        let app = test_app("One sentence. Another sentence.");
+
+        let report = app.diagnostics_report(&["INFO first".to_string()]);
+        assert!(report.starts_with("ebup-viewer "));
+        assert!(report.contains(&format!("Book: {}\n", app.epub_path.display())));
+        assert!(report.contains("Page: 1 of "));
+        assert!(report.contains("voice unavailable"));
+        assert!(report.ends_with("Recent log lines:\nINFO first\n"));
+    }
+}
@@ -0,0 +1,36 @@
+use super::super::state::{App, WordDefinition};
+use super::Effect;
+use crate::dictionary::normalize_word;
+use tracing::debug;
+
+impl App {
+    pub(super) fn handle_lookup_words_on_click_changed(
+        &mut self,
+        enabled: bool,
+        effects: &mut Vec<Effect>,
+    ) {
+        if self.config.lookup_words_on_click != enabled {
+            self.config.lookup_words_on_click = enabled;
+            if !enabled {
+                self.definition = None;
+            }
+            effects.push(Effect::SaveConfig);
+        }
+    }
+
+    pub(super) fn handle_lookup_word(&mut self, word: &str) {
+        let Some(normalized) = normalize_word(word) else {
+            return;
+        };
+        let definition = self
+            .dictionary
+            .as_ref()
+            .and_then(|dictionary| dictionary.lookup(&normalized));
+        debug!(word = %normalized, found = definition.is_some(), "Looked up word");
+        self.open_footnote = None;
+        self.definition = Some(WordDefinition {
+            word: normalized,
+            definition,
+        });
+    }
+}
@@ -1,20 +1,44 @@
+use super::messages::CacheClearScope;
+use crate::cache::ReadingStats;
 use crate::calibre::{CalibreBook, CalibreConfig};
 use iced::widget::scrollable::RelativeOffset;
 
 mod appearance;
+mod book_cache;
+mod bookmarks;
 mod core;
+mod diagnostics;
+mod dictionary;
 mod navigation;
+mod normalizer;
+mod notes;
+mod profile;
+mod progress;
 mod scroll;
+mod source;
+mod stats;
+mod theme_schedule;
 mod tts;
 
+pub(super) use scroll::detect_reduced_motion;
+
 /// Describes work that must be performed outside the pure reducer.
 pub(super) enum Effect {
     SaveConfig,
     SaveBookmark,
+    SaveReadingStats(ReadingStats),
+    /// Mark a cached clip as just played for LRU eviction.
+    TouchSentenceClip(std::path::PathBuf),
     StartTts {
         page: usize,
         sentence_idx: usize,
     },
+    /// Like `StartTts`, but stop after sentence `end` instead of moving on.
+    StartTtsSelection {
+        page: usize,
+        start: usize,
+        end: usize,
+    },
     PrepareTtsBatches {
         page: usize,
         request_id: u64,
@@ -28,6 +52,26 @@ pub(super) enum Effect {
         audio_sentences: Vec<String>,
     },
     StopTts,
+    CacheChapterAudioPage {
+        page: usize,
+        request_id: u64,
+    },
+    ExportAudioPage {
+        page: usize,
+        request_id: u64,
+    },
+    ExportAudio {
+        output: std::path::PathBuf,
+    },
+    PrefetchTtsPage {
+        page: usize,
+        request_id: u64,
+    },
+    /// Render a snippet into temporary files outside the sentence cache.
+    SynthesizeSnippet {
+        request_id: u64,
+        sentences: Vec<String>,
+    },
     ScrollTo(RelativeOffset),
     AutoScrollToCurrent,
     LoadCalibreBooks {
@@ -37,7 +81,14 @@ pub(super) enum Effect {
         book: CalibreBook,
         config: CalibreConfig,
     },
+    ReloadNormalizer,
     ReadClipboard,
+    WriteClipboard(String),
+    ReadProgressClipboard,
+    /// Delete cached data for the open book in the background.
+    ClearBookCache(CacheClearScope),
+    /// Run the book diagnostics in the background.
+    DiagnoseBook(std::path::PathBuf),
     LoadBook(std::path::PathBuf),
     ReturnToStarter,
     QuitSafely,
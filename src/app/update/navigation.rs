@@ -1,84 +1,102 @@
-use super::super::state::App;
+use super::super::state::{App, SearchHit};
 use super::Effect;
-use crate::pagination::{MAX_LINES_PER_PAGE, MIN_LINES_PER_PAGE};
+use crate::pagination::{MAX_LINES_PER_PAGE, MIN_LINES_PER_PAGE, chars_per_line};
 use iced::widget::scrollable::RelativeOffset;
-use tracing::debug;
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+/// Quiet period after the last resize event before pages are reflowed.
+const RESIZE_REPAGINATE_DEBOUNCE: Duration = Duration::from_millis(150);
 
 impl App {
+    /// Turn the page, or in continuous reading jump to the next chapter.
     pub(super) fn handle_next_page(&mut self, effects: &mut Vec<Effect>) {
-        effects.extend(self.go_to_page(self.reader.current_page + 1));
+        let next = if self.continuous_reading() {
+            self.displayed_pages().end
+        } else {
+            self.reader.current_page + 1
+        };
+        effects.extend(self.go_to_page(next));
     }
 
+    /// Turn back a page, or in continuous reading jump to the previous chapter.
     pub(super) fn handle_previous_page(&mut self, effects: &mut Vec<Effect>) {
-        if self.reader.current_page > 0 {
+        if self.continuous_reading() {
+            let start = self.displayed_pages().start;
+            if start > 0 {
+                let previous = self.continuous_section(start - 1).start;
+                effects.extend(self.go_to_page(previous));
+            }
+        } else if self.reader.current_page > 0 {
             effects.extend(self.go_to_page(self.reader.current_page - 1));
         }
     }
 
+    pub(super) fn handle_open_toc_entry(&mut self, idx: usize, effects: &mut Vec<Effect>) {
+        let Some(&page) = self.reader.toc_pages.get(idx) else {
+            return;
+        };
+        if let Some(entry) = self.reader.toc.get(idx) {
+            info!(title = %entry.title, page = page + 1, "Opening TOC entry");
+        }
+        let page_effects = self.go_to_page(page);
+        if page_effects.iter().any(|effect| {
+            matches!(
+                effect,
+                Effect::StartTts {
+                    sentence_idx: 0,
+                    ..
+                }
+            )
+        }) {
+            self.announce_chapter_at(page);
+        }
+        effects.extend(page_effects);
+    }
+
+    pub(super) fn handle_toggle_auto_page(&mut self) {
+        self.auto_page_paused = !self.auto_page_paused;
+        info!(
+            paused = self.auto_page_paused,
+            "Toggled automatic page turns"
+        );
+    }
+
+    pub(super) fn handle_skip_to_first_chapter(&mut self, effects: &mut Vec<Effect>) {
+        let Some(page) = self.reader.first_chapter_page() else {
+            info!("No chapter found past the front matter");
+            return;
+        };
+        if page != self.reader.current_page {
+            info!(page = page + 1, "Skipping to first chapter");
+            effects.extend(self.go_to_page(page));
+        }
+    }
+
+    pub(super) fn handle_page_input_changed(&mut self, value: String) {
+        self.page_input = value;
+    }
+
+    pub(super) fn handle_go_to_page(&mut self, page: usize, effects: &mut Vec<Effect>) {
+        self.page_input.clear();
+        if page != self.reader.current_page {
+            info!(page = page + 1, "Jumping to page");
+            effects.extend(self.go_to_page(page));
+        }
+    }
+
+    pub(super) fn handle_follow_crossref(&mut self, page: usize, effects: &mut Vec<Effect>) {
+        if page != self.reader.current_page {
+            info!(page = page + 1, "Following cross-reference");
+            effects.extend(self.go_to_page(page));
+        }
+    }
+
     pub(super) fn handle_lines_per_page_changed(&mut self, lines: u32, effects: &mut Vec<Effect>) {
         let clamped = lines.clamp(MIN_LINES_PER_PAGE as u32, MAX_LINES_PER_PAGE as u32) as usize;
         if clamped != self.config.lines_per_page {
-            let old_page = self.reader.current_page;
-            let old_sentence_idx = self.tts.current_sentence_idx.unwrap_or(0);
-            let active_sentence = self
-                .raw_sentences_for_page(old_page)
-                .get(old_sentence_idx)
-                .cloned()
-                .or_else(|| self.raw_sentences_for_page(old_page).into_iter().next());
-            let had_tts = self.tts.playback.is_some() || self.tts.is_preparing();
-            let was_playing = self
-                .tts
-                .playback
-                .as_ref()
-                .map(|p| !p.is_paused())
-                .unwrap_or(self.tts.is_playing());
-
-            let before = self.reader.current_page;
             self.config.lines_per_page = clamped;
-            self.repaginate();
-
-            if let Some(sentence) = active_sentence {
-                let mut best: Option<(usize, usize, usize)> = None;
-                for (page_idx, page_sentences) in self.reader.page_sentences.iter().enumerate() {
-                    for (sentence_idx, candidate) in page_sentences.iter().enumerate() {
-                        if candidate == &sentence {
-                            let distance = page_idx.abs_diff(old_page) * 10_000
-                                + sentence_idx.abs_diff(old_sentence_idx);
-                            match best {
-                                Some((best_distance, _, _)) if best_distance <= distance => {}
-                                _ => best = Some((distance, page_idx, sentence_idx)),
-                            }
-                        }
-                    }
-                }
-                if let Some((_, page_idx, sentence_idx)) = best {
-                    self.reader.current_page = page_idx;
-                    self.tts.current_sentence_idx = Some(sentence_idx);
-                    self.tts.last_sentences = self.raw_sentences_for_page(page_idx);
-                    self.bookmark.pending_sentence_snap = Some(sentence_idx);
-                    effects.push(Effect::AutoScrollToCurrent);
-
-                    if had_tts {
-                        // Invalidate any in-flight work from the old pagination before restart.
-                        self.tts.request_id = self.tts.request_id.wrapping_add(1);
-                        self.tts.lifecycle = super::super::state::TtsLifecycle::Idle;
-                        self.tts.pending_append = false;
-                        self.tts.pending_append_batch = None;
-                        self.tts.resume_after_prepare = was_playing;
-                        effects.push(Effect::StartTts {
-                            page: self.reader.current_page,
-                            sentence_idx,
-                        });
-                    }
-                }
-            }
-
-            if self.reader.current_page != before {
-                self.bookmark.last_scroll_offset = RelativeOffset::START;
-                effects.push(Effect::SaveBookmark);
-            } else if self.tts.current_sentence_idx.is_some() {
-                effects.push(Effect::SaveBookmark);
-            }
+            self.repaginate_keeping_position(effects);
             debug!(
                 lines_per_page = self.config.lines_per_page,
                 "Lines per page changed"
@@ -87,7 +105,130 @@ impl App {
         }
     }
 
+    /// Reflow pages once resizing has settled, if the new width changes the line budget.
+    pub(super) fn maybe_repaginate_after_resize(&mut self, effects: &mut Vec<Effect>) {
+        let Some(resized_at) = self.resize_repaginate_at else {
+            return;
+        };
+        if Instant::now().saturating_duration_since(resized_at) < RESIZE_REPAGINATE_DEBOUNCE {
+            return;
+        }
+        self.resize_repaginate_at = None;
+        let line_chars = chars_per_line(self.pagination_text_width(), self.config.font_size);
+        if line_chars == self.page_chars_per_line {
+            return;
+        }
+        debug!(
+            from = self.page_chars_per_line,
+            to = line_chars,
+            "Repaginating after window resize"
+        );
+        self.repaginate_keeping_position(effects);
+    }
+
+    /// Repaginate while keeping the anchored sentence in view.
+    ///
+    /// Every change that reflows pages (font size, lines per page, window width)
+    /// goes through here so they all restore the reading position the same way.
+    pub(super) fn repaginate_keeping_position(&mut self, effects: &mut Vec<Effect>) {
+        let anchor = self.capture_relayout_anchor();
+        let before = self.reader.current_page;
+        self.repaginate();
+
+        if self.restore_relayout_anchor(anchor, effects) {
+            effects.push(Effect::AutoScrollToCurrent);
+        }
+
+        if self.reader.current_page != before {
+            self.bookmark.last_scroll_offset = RelativeOffset::START;
+            effects.push(Effect::SaveBookmark);
+        } else if self.tts.current_sentence_idx.is_some() {
+            effects.push(Effect::SaveBookmark);
+        }
+    }
+
+    /// Show the page holding `hit` and scroll its sentence into view.
+    pub(super) fn go_to_search_hit(&mut self, hit: &SearchHit, effects: &mut Vec<Effect>) {
+        if hit.page != self.reader.current_page {
+            effects.extend(self.go_to_page_at(hit.page, hit.sentence_idx));
+        } else {
+            let sentence_count = self.sentence_count_for_page(hit.page);
+            self.tts
+                .set_current_sentence_clamped(hit.sentence_idx, sentence_count);
+            effects.push(Effect::SaveBookmark);
+        }
+        let Some(idx) = self.tts.current_sentence_idx else {
+            return;
+        };
+        self.bookmark.pending_sentence_snap = Some(idx);
+        if let Some(offset) = self.scroll_offset_for_sentence(idx) {
+            effects.push(Effect::ScrollTo(offset));
+        }
+    }
+
+    /// Step the reading cursor one sentence on, onto the next page at the end.
+    pub(super) fn handle_next_sentence(&mut self, effects: &mut Vec<Effect>) {
+        let target = self.reading_cursor().map_or(0, |idx| idx + 1);
+        if target < self.sentence_count_for_page(self.reader.current_page) {
+            self.move_reading_cursor(target, effects);
+        } else if self.reader.current_page + 1 < self.reader.pages.len() {
+            effects.extend(self.go_to_page(self.reader.current_page + 1));
+            self.reader.set_cursor(0);
+        }
+    }
+
+    /// Step the reading cursor one sentence back, onto the previous page's last
+    /// sentence at the start.
+    pub(super) fn handle_previous_sentence(&mut self, effects: &mut Vec<Effect>) {
+        match self.reading_cursor() {
+            Some(idx) if idx > 0 => self.move_reading_cursor(idx - 1, effects),
+            Some(_) if self.reader.current_page > 0 => {
+                let page = self.reader.current_page - 1;
+                let last = self.sentence_count_for_page(page).saturating_sub(1);
+                effects.extend(self.go_to_page_at(page, last));
+                self.reader.set_cursor(last);
+            }
+            Some(_) => {}
+            None => self.move_reading_cursor(0, effects),
+        }
+    }
+
+    fn move_reading_cursor(&mut self, sentence_idx: usize, effects: &mut Vec<Effect>) {
+        self.reader.set_cursor(sentence_idx);
+        debug!(sentence_idx, "Moved reading cursor");
+        if let Some(offset) = self.scroll_offset_for_sentence(sentence_idx) {
+            effects.push(Effect::ScrollTo(offset));
+        }
+    }
+
+    /// Feed the reading speed when the reader moves on to the next page by
+    /// themselves; jumps, turning back and timed auto turns only restart the timer.
+    fn time_silent_page(&mut self, new_page: usize) {
+        let now = Instant::now();
+        let finished = self.reader.current_page;
+        let read_on = new_page == finished + 1
+            && self.auto_page_interval().is_none()
+            && !self.tts.is_playing()
+            && !self.tts.is_preparing();
+        if !read_on {
+            self.reading_stats.restart_page_timer(now);
+            return;
+        }
+        let words = self
+            .reader
+            .page_text_counts
+            .get(finished)
+            .map_or(0, |counts| counts.words);
+        let idle_timeout = Duration::from_secs(self.config.reading_speed_idle_secs);
+        self.reading_stats
+            .record_silent_page(words, now, idle_timeout);
+    }
+
     fn go_to_page(&mut self, new_page: usize) -> Vec<Effect> {
+        self.go_to_page_at(new_page, 0)
+    }
+
+    pub(super) fn go_to_page_at(&mut self, new_page: usize, sentence_idx: usize) -> Vec<Effect> {
         let mut effects = Vec::new();
         if new_page < self.reader.pages.len() {
             let was_paused = self
@@ -105,9 +246,17 @@ impl App {
                 .as_ref()
                 .map(|p| !p.is_paused())
                 .unwrap_or_else(|| self.tts.is_playing() || self.tts.is_preparing());
+            if new_page != self.reader.current_page {
+                // Only reading on to the next page counts as having read it.
+                let reached = (new_page == self.reader.current_page + 1)
+                    .then(|| self.reader.page_start(new_page));
+                self.reading_stats.record_page_turn(reached);
+                self.time_silent_page(new_page);
+            }
             self.reader.current_page = new_page;
             let sentence_count = self.sentence_count_for_page(new_page);
-            self.tts.set_current_sentence_clamped(0, sentence_count);
+            self.tts
+                .set_current_sentence_clamped(sentence_idx, sentence_count);
             self.tts.last_sentences = self.raw_sentences_for_page(new_page);
             self.bookmark.last_scroll_offset = RelativeOffset::START;
             tracing::info!(page = self.reader.current_page + 1, "Navigated to page");
@@ -115,7 +264,7 @@ impl App {
                 self.tts.resume_after_prepare = true;
                 effects.push(Effect::StartTts {
                     page: self.reader.current_page,
-                    sentence_idx: 0,
+                    sentence_idx: self.tts.current_sentence_idx.unwrap_or(0),
                 });
             } else {
                 self.stop_playback();
@@ -129,3 +278,266 @@ impl App {
         effects
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::{MAX_CONTINUOUS_PAGES, test_app_with, test_book_path};
+    use crate::cache::Bookmark;
+    use crate::config::AppConfig;
+    use crate::epub_loader::LoadedBook;
+    use std::time::Duration;
+
+    fn build_test_app(sentence_count: usize) -> App {
+        let text = (0..sentence_count)
+            .map(|i| format!("Sentence number {i} is part of a long navigation test book."))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut config = AppConfig::default();
+        config.lines_per_page = 8;
+        test_app_with(
+            LoadedBook {
+                text,
+                ..Default::default()
+            },
+            config,
+        )
+    }
+
+    #[test]
+    fn search_scans_whole_book_and_jumps_to_hit_page() {
+        let mut app = build_test_app(300);
+        assert!(app.reader.pages.len() > 10);
+        app.search.visible = true;
+        app.search.query = "NUMBER 250 is".to_string();
+        app.update_search_matches();
+
+        assert_eq!(app.search.matches.len(), 1);
+        let hit = app.search.matches[0].clone();
+        assert!(hit.page > 0);
+
+        let mut effects = Vec::new();
+        app.go_to_search_hit(&hit, &mut effects);
+        assert_eq!(app.reader.current_page, hit.page);
+        assert_eq!(app.tts.current_sentence_idx, Some(hit.sentence_idx));
+        let sentence = &app.raw_sentences_for_page(hit.page)[hit.sentence_idx];
+        assert_eq!(&sentence[hit.char_range.clone()], "number 250 is");
+    }
+
+    #[test]
+    fn reading_progress_tracks_pages_and_scroll() {
+        let mut single = build_test_app(2);
+        assert_eq!(single.reader.pages.len(), 1);
+        assert_eq!(single.reading_progress(), 0.0);
+        single.bookmark.last_scroll_offset.y = 0.5;
+        assert!((single.reading_progress() - 0.5).abs() < 1e-6);
+        single.bookmark.last_scroll_offset.y = 1.0;
+        assert_eq!(single.reading_progress(), 1.0);
+
+        let mut book = build_test_app(300);
+        let pages = book.reader.pages.len();
+        let mut effects = Vec::new();
+        book.handle_next_page(&mut effects);
+        assert!((book.reading_progress() - 1.0 / pages as f32).abs() < 1e-6);
+    }
+
+    #[test]
+    fn large_books_switch_to_continuous_sections() {
+        let mut app = build_test_app(2000);
+        assert!(!app.continuous_reading());
+        app.config.continuous_above_pages = 10;
+        app.repaginate();
+        assert!(app.continuous_reading());
+        assert!(app.reader.pages.len() > MAX_CONTINUOUS_PAGES);
+
+        let mut effects = Vec::new();
+        app.handle_next_page(&mut effects);
+        assert_eq!(app.reader.current_page, MAX_CONTINUOUS_PAGES);
+        assert_eq!(
+            app.displayed_pages(),
+            MAX_CONTINUOUS_PAGES..(2 * MAX_CONTINUOUS_PAGES).min(app.reader.pages.len())
+        );
+        app.reader.current_page += 3;
+        app.handle_previous_page(&mut effects);
+        assert_eq!(app.reader.current_page, 0);
+    }
+
+    #[test]
+    fn settled_resize_repaginates_and_keeps_sentence() {
+        let mut app = build_test_app(300);
+        let wide_pages = app.reader.pages.len();
+        app.reader.current_page = 10;
+        app.tts.current_sentence_idx = Some(3);
+        let anchored = app.raw_sentences_for_page(10)[3].clone();
+
+        let mut effects = Vec::new();
+        app.handle_window_resized(480.0, 700.0, &mut effects);
+        app.maybe_repaginate_after_resize(&mut effects);
+        assert_eq!(app.reader.pages.len(), wide_pages, "still debouncing");
+
+        app.resize_repaginate_at = Some(Instant::now() - RESIZE_REPAGINATE_DEBOUNCE);
+        app.maybe_repaginate_after_resize(&mut effects);
+        assert!(app.reader.pages.len() > wide_pages);
+        assert!(app.resize_repaginate_at.is_none());
+        let idx = app.tts.current_sentence_idx.expect("sentence kept");
+        assert_eq!(
+            app.raw_sentences_for_page(app.reader.current_page)[idx],
+            anchored
+        );
+    }
+
+    #[test]
+    fn text_width_cap_governs_page_breaks() {
+        let mut app = build_test_app(300);
+        let uncapped_pages = app.reader.pages.len();
+        app.config.max_text_width = Some(400);
+        app.repaginate();
+        assert!(app.reader.pages.len() > uncapped_pages);
+        let capped_chars = app.page_chars_per_line;
+
+        let mut effects = Vec::new();
+        app.handle_window_resized(2400.0, 900.0, &mut effects);
+        app.resize_repaginate_at = Some(Instant::now() - RESIZE_REPAGINATE_DEBOUNCE);
+        app.maybe_repaginate_after_resize(&mut effects);
+        assert_eq!(app.pagination_text_width(), 400.0);
+        assert_eq!(app.page_chars_per_line, capped_chars);
+    }
+
+    #[test]
+    fn search_marks_cover_only_the_page_and_flag_the_selection() {
+        let mut app = build_test_app(300);
+        app.reader.current_page = 5;
+        app.search.query = "long navigation".to_string();
+        app.update_search_matches();
+        assert!(app.search_marks_for_page(5).iter().all(Vec::is_empty));
+
+        app.search.visible = true;
+        let marks = app.search_marks_for_page(5);
+        assert_eq!(marks.len(), app.sentence_count_for_page(5));
+        assert!(marks.iter().all(|sentence| sentence.len() == 1));
+        assert!(marks[0][0].1);
+        assert!(marks[1..].iter().all(|sentence| !sentence[0].1));
+        let sentence = &app.raw_sentences_for_page(5)[1];
+        assert_eq!(&sentence[marks[1][0].0.clone()], "long navigation");
+    }
+
+    #[test]
+    fn search_selection_starts_at_current_page() {
+        let mut app = build_test_app(300);
+        app.search.query = "long navigation".to_string();
+        app.reader.current_page = 5;
+        app.update_search_matches();
+
+        assert_eq!(app.search.matches.len(), 300);
+        let selected = app.selected_search_hit().expect("selected hit");
+        assert_eq!(selected.page, 5);
+        assert_eq!(selected.sentence_idx, 0);
+    }
+
+    #[test]
+    fn page_input_clamps_and_ignores_garbage() {
+        let mut app = build_test_app(300);
+        let last_page = app.reader.pages.len() - 1;
+        let target = |app: &mut App, raw: &str| {
+            app.page_input = raw.to_string();
+            app.page_input_target()
+        };
+        assert_eq!(target(&mut app, " 3 "), Some(2));
+        assert_eq!(target(&mut app, "0"), Some(0));
+        assert_eq!(target(&mut app, "99999"), Some(last_page));
+        assert_eq!(target(&mut app, "three"), None);
+        assert_eq!(target(&mut app, "-2"), None);
+
+        let mut effects = Vec::new();
+        app.handle_go_to_page(last_page, &mut effects);
+        assert_eq!(app.reader.current_page, last_page);
+        assert!(app.page_input.is_empty());
+    }
+
+    #[test]
+    fn time_remaining_scales_with_speed_and_position() {
+        let mut app = build_test_app(300);
+        app.config.pause_after_sentence = 0.0;
+        assert_eq!(app.estimated_time_remaining(), None);
+
+        app.tts.spoken_unit_secs = 4.0;
+        app.tts.spoken_sentences = 1;
+        app.config.tts_speed = 1.0;
+        let total: usize = app.reader.page_sentence_counts.iter().sum();
+        let at_start = app.estimated_time_remaining().expect("estimate");
+        assert_eq!(at_start, Duration::from_secs(4 * total as u64));
+
+        app.config.tts_speed = 2.0;
+        assert_eq!(app.estimated_time_remaining(), Some(at_start / 2));
+
+        app.reader.current_page = app.reader.pages.len() - 1;
+        app.tts.current_sentence_idx = Some(app.sentence_count_for_page(app.reader.current_page));
+        assert_eq!(app.estimated_time_remaining(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn leading_pages_are_skipped_only_without_a_bookmark() {
+        let open = |bookmark: Option<Bookmark>| {
+            let book = LoadedBook {
+                text: (0..300)
+                    .map(|i| format!("Sentence number {i} opens a front matter test book."))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                ..Default::default()
+            };
+            let mut config = AppConfig::default();
+            config.lines_per_page = 8;
+            config.skip_leading_pages = 3;
+            App::bootstrap(book, config, test_book_path(), bookmark).0
+        };
+
+        assert_eq!(open(None).reader.current_page, 3);
+        let bookmark = Bookmark {
+            page: 1,
+            sentence_idx: None,
+            sentence_text: None,
+            scroll_y: 0.0,
+        };
+        assert_eq!(open(Some(bookmark)).reader.current_page, 1);
+    }
+
+    #[test]
+    fn auto_page_timer_runs_only_while_reading_silently() {
+        let mut app = build_test_app(300);
+        assert_eq!(app.auto_page_interval(), None);
+
+        app.config.auto_page_seconds = Some(30.0);
+        assert_eq!(app.auto_page_interval(), Some(Duration::from_secs(30)));
+
+        app.handle_toggle_auto_page();
+        assert_eq!(app.auto_page_interval(), None);
+        app.handle_toggle_auto_page();
+
+        app.reading_stats.focused = false;
+        assert_eq!(app.auto_page_interval(), None);
+        app.reading_stats.focused = true;
+
+        app.reader.current_page = app.reader.pages.len() - 1;
+        assert_eq!(app.auto_page_interval(), None);
+    }
+
+    #[test]
+    fn sentence_stepping_crosses_pages_without_tts() {
+        let mut app = build_test_app(300);
+        let mut effects = Vec::new();
+        app.handle_next_sentence(&mut effects);
+        assert_eq!(app.reader.cursor_on_current_page(), Some(1));
+
+        let last = app.sentence_count_for_page(0) - 1;
+        app.reader.set_cursor(last);
+        app.handle_next_sentence(&mut effects);
+        assert_eq!(app.reader.current_page, 1);
+        assert_eq!(app.reader.cursor_on_current_page(), Some(0));
+        assert!(app.tts.playback.is_none());
+
+        app.handle_previous_sentence(&mut effects);
+        assert_eq!(app.reader.current_page, 0);
+        assert_eq!(app.reader.cursor_on_current_page(), Some(last));
+        assert_eq!(app.highlighted_sentence_idx(), Some(last));
+    }
+}
@@ -0,0 +1,57 @@
+use super::super::state::{App, normalizer_config_modified};
+use super::Effect;
+use crate::config::normalizer_config_path;
+use crate::normalizer::TextNormalizer;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// How often the normalizer config's modification time is checked.
+const NORMALIZER_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+impl App {
+    /// Schedule a normalizer reload when `conf/normalizer.toml` changed on disk.
+    pub(super) fn maybe_reload_normalizer(&mut self, effects: &mut Vec<Effect>) {
+        if self.starter_mode {
+            return;
+        }
+        let now = Instant::now();
+        if self
+            .normalizer_checked_at
+            .is_some_and(|at| now.saturating_duration_since(at) < NORMALIZER_POLL_INTERVAL)
+        {
+            return;
+        }
+        self.normalizer_checked_at = Some(now);
+        let modified = normalizer_config_modified();
+        // A deleted file keeps the current rules; recreating it reloads them.
+        if modified.is_some() && modified != self.normalizer_modified {
+            self.normalizer_modified = modified;
+            effects.push(Effect::ReloadNormalizer);
+        }
+    }
+
+    /// Swap in the reloaded rules and re-plan TTS from the current sentence.
+    ///
+    /// Normalization caches are keyed by a hash of the config, so plans made
+    /// under the old rules are simply not looked up again.
+    pub(super) fn handle_normalizer_reloaded(
+        &mut self,
+        result: Result<TextNormalizer, String>,
+        effects: &mut Vec<Effect>,
+    ) {
+        let mut normalizer = match result {
+            Ok(normalizer) => normalizer,
+            Err(err) => {
+                warn!("Keeping the previous normalizer config: {err}");
+                return;
+            }
+        };
+        info!(path = %normalizer_config_path().display(), "Reloaded normalizer config");
+        normalizer.set_sentence_grouping(self.config.tts_group_sentences);
+        self.normalizer = normalizer;
+        self.text_only_preview = None;
+        self.page_hyphenation = None;
+        let anchor = self.capture_relayout_anchor();
+        self.restore_relayout_anchor(anchor, effects);
+    }
+}
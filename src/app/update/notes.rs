@@ -0,0 +1,81 @@
+use super::super::state::App;
+use super::Effect;
+use crate::cache::{Note, save_note};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
+
+impl App {
+    /// Save the note being typed, anchored to the sentence being read.
+    pub(super) fn handle_add_note(&mut self) {
+        if self.starter_mode {
+            return;
+        }
+        let text = self.bookmark.note_input.trim();
+        if text.is_empty() {
+            return;
+        }
+        let anchor = self.current_bookmark();
+        let note = Note {
+            page: anchor.page,
+            sentence_idx: anchor.sentence_idx,
+            sentence_text: anchor.sentence_text,
+            text: text.to_string(),
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs()),
+        };
+        if let Err(err) = save_note(&self.epub_path, &note) {
+            warn!(page = note.page + 1, "Failed to save note: {err}");
+            return;
+        }
+        info!(page = note.page + 1, "Added note");
+        self.bookmark.note_input.clear();
+        self.bookmark.notes.push(note);
+    }
+
+    pub(super) fn handle_jump_to_note(&mut self, idx: usize, effects: &mut Vec<Effect>) {
+        let Some(note) = self.bookmark.notes.get(idx).cloned() else {
+            return;
+        };
+        let (page, sentence_idx) = self.jump_to_anchor(
+            note.page,
+            note.sentence_idx,
+            note.sentence_text.as_deref(),
+            effects,
+        );
+        info!(page = page + 1, sentence_idx, "Jumping to note");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::test_app;
+    use crate::cache::hash_dir;
+
+    #[test]
+    fn notes_anchor_to_the_current_sentence_and_jump_back() {
+        let mut app = test_app("First sentence. Second sentence. Third sentence.");
+        app.bookmark.notes.clear();
+
+        app.tts.current_sentence_idx = Some(1);
+        app.bookmark.note_input = "  Worth rereading ".to_string();
+        app.handle_add_note();
+        assert!(app.bookmark.note_input.is_empty());
+        let note = app.bookmark.notes.last().cloned().unwrap();
+        assert_eq!(note.text, "Worth rereading");
+        assert_eq!(note.sentence_idx, Some(1));
+        assert_eq!(note.sentence_text.as_deref(), Some("Second sentence."));
+
+        app.tts.current_sentence_idx = Some(2);
+        let mut effects = Vec::new();
+        app.handle_jump_to_note(0, &mut effects);
+        assert_eq!(app.tts.current_sentence_idx, Some(1));
+        assert!(matches!(
+            effects.as_slice(),
+            [Effect::AutoScrollToCurrent, Effect::SaveBookmark]
+        ));
+
+        let _ = std::fs::remove_dir_all(hash_dir(&app.epub_path));
+    }
+}
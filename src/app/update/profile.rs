@@ -0,0 +1,140 @@
+use super::super::state::{App, clamp_config, dictionary_from_config};
+use super::Effect;
+use crate::config::{AppConfig, export_profile, import_profile, save_config, saved_settings_path};
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+impl App {
+    pub(super) fn handle_export_profile(&mut self) {
+        let Some(path) = self.profile_path() else {
+            self.profile_status = Some("Enter a path to export the profile to.".to_string());
+            return;
+        };
+        self.profile_status = Some(match export_profile(&self.config, &path) {
+            Ok(()) => format!("Exported profile to {}.", path.display()),
+            Err(err) => {
+                warn!("Failed to export config profile: {err:#}");
+                format!("Export failed: {err:#}")
+            }
+        });
+    }
+
+    /// Import a profile and make it the base config that new books start from.
+    pub(super) fn handle_import_profile(&mut self, effects: &mut Vec<Effect>) {
+        let Some(path) = self.profile_path() else {
+            self.profile_status = Some("Enter the path of a profile to import.".to_string());
+            return;
+        };
+        let imported = match import_profile(&path) {
+            Ok(config) => config,
+            Err(err) => {
+                warn!("Rejected config profile: {err:#}");
+                self.profile_status = Some(format!("Import failed: {err:#}"));
+                return;
+            }
+        };
+
+        self.apply_profile(imported, effects);
+        effects.push(Effect::SaveConfig);
+        if let Err(err) = save_config(&self.config, saved_settings_path()) {
+            warn!("Failed to save imported profile as the saved settings: {err:#}");
+        }
+        info!(path = %path.display(), "Applied config profile");
+        self.profile_status = Some(format!("Imported profile from {}.", path.display()));
+    }
+
+    /// Swap in an imported config after range-checking it. Saving it is up to
+    /// the caller.
+    ///
+    /// Window size and position describe this machine rather than the reading
+    /// setup, so they are kept from the current config.
+    pub(super) fn apply_profile(&mut self, mut imported: AppConfig, effects: &mut Vec<Effect>) {
+        clamp_config(&mut imported);
+        imported.window_width = self.config.window_width;
+        imported.window_height = self.config.window_height;
+        imported.window_pos_x = self.config.window_pos_x;
+        imported.window_pos_y = self.config.window_pos_y;
+
+        let engine_changed = imported.tts_model_path != self.config.tts_model_path
+            || imported.tts_espeak_path != self.config.tts_espeak_path
+            || imported.tts_voice != self.config.tts_voice;
+        let dictionary_changed = imported.dictionary_path != self.config.dictionary_path;
+        self.config = imported;
+        self.normalizer
+            .set_sentence_grouping(self.config.tts_group_sentences);
+        self.drop_missing_custom_font();
+        self.settings_history.clear();
+
+        if engine_changed {
+            self.stop_playback();
+            self.reload_tts_engine();
+        }
+        if dictionary_changed {
+            self.dictionary = dictionary_from_config(&self.config);
+        }
+        if !self.starter_mode {
+            self.repaginate_keeping_position(effects);
+        }
+    }
+
+    fn profile_path(&self) -> Option<PathBuf> {
+        let trimmed = self.profile_path_input.trim();
+        (!trimmed.is_empty()).then(|| PathBuf::from(trimmed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::{MAX_TTS_LOOKAHEAD, test_app};
+    use crate::config::ThemeMode;
+    use crate::pagination::{MAX_FONT_SIZE, MIN_LINES_PER_PAGE};
+
+    #[test]
+    fn applied_profile_is_clamped_and_keeps_window_geometry() {
+        let text = (0..200)
+            .map(|i| format!("Profile test sentence {i} keeps the pages busy."))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut app = test_app(text);
+        let window_width = app.config.window_width;
+        let pages_before = app.reader.pages.len();
+
+        let mut imported = AppConfig::default();
+        imported.font_size = 10_000;
+        imported.line_spacing = 9.0;
+        imported.lines_per_page = MIN_LINES_PER_PAGE;
+        imported.theme = ThemeMode::Night;
+        imported.tts_lookahead = 1_000;
+        imported.window_width = window_width + 500.0;
+        let mut effects = Vec::new();
+        app.apply_profile(imported, &mut effects);
+
+        assert_eq!(app.config.font_size, MAX_FONT_SIZE);
+        assert_eq!(app.config.line_spacing, 2.5);
+        assert_eq!(app.config.theme, ThemeMode::Night);
+        assert_eq!(app.config.tts_lookahead, MAX_TTS_LOOKAHEAD);
+        assert_eq!(app.config.window_width, window_width);
+        assert_ne!(app.reader.pages.len(), pages_before);
+    }
+
+    #[test]
+    fn missing_tts_model_disables_playback_with_a_reason() {
+        let mut app = test_app("One sentence. Another sentence.");
+
+        let mut imported = app.config.clone();
+        imported.tts_model_path = "/nonexistent/ebup-viewer/voice.onnx".to_string();
+        let mut effects = Vec::new();
+        app.apply_profile(imported, &mut effects);
+        assert!(app.tts.engine.is_none());
+        assert!(
+            app.tts_unavailable
+                .as_deref()
+                .is_some_and(|reason| reason.contains("/nonexistent/ebup-viewer/voice.onnx"))
+        );
+
+        effects.clear();
+        app.handle_play(&mut effects);
+        assert!(effects.is_empty());
+    }
+}
@@ -0,0 +1,67 @@
+use super::super::state::App;
+use super::Effect;
+use crate::cache::{export_progress, format_location, import_progress, load_named_bookmarks};
+use tracing::{info, warn};
+
+impl App {
+    pub(super) fn handle_export_progress(&mut self, effects: &mut Vec<Effect>) {
+        if self.starter_mode {
+            return;
+        }
+        // Export what is on screen now rather than the last periodic save.
+        self.persist_bookmark();
+        match export_progress(&self.epub_path) {
+            Ok(json) => {
+                info!(path = %self.epub_path.display(), "Exported reading progress");
+                self.progress_status = Some("Progress copied to clipboard.".to_string());
+                effects.push(Effect::WriteClipboard(json));
+            }
+            Err(err) => {
+                warn!("Failed to export reading progress: {err}");
+                self.progress_status = Some(format!("Export failed: {err}"));
+            }
+        }
+    }
+
+    pub(super) fn handle_import_progress(&mut self, effects: &mut Vec<Effect>) {
+        if self.starter_mode {
+            return;
+        }
+        effects.push(Effect::ReadProgressClipboard);
+    }
+
+    pub(super) fn handle_progress_import_read(
+        &mut self,
+        contents: Option<String>,
+        effects: &mut Vec<Effect>,
+    ) {
+        let Some(json) = contents.filter(|json| !json.trim().is_empty()) else {
+            self.progress_status =
+                Some("Clipboard is empty; copy exported progress and try again.".to_string());
+            return;
+        };
+
+        let bookmark = match import_progress(&self.epub_path, &json) {
+            Ok(bookmark) => bookmark,
+            Err(err) => {
+                warn!("Rejected imported reading progress: {err}");
+                self.progress_status = Some(format!("Import failed: {err}"));
+                return;
+            }
+        };
+
+        self.bookmark.named = load_named_bookmarks(&self.epub_path);
+        if self.tts.playback.is_some() || self.tts.is_preparing() {
+            effects.push(Effect::StopTts);
+        }
+        let offset = self.restore_bookmark_position(&bookmark);
+        if let Some(offset) = offset {
+            effects.push(Effect::ScrollTo(offset));
+        }
+        info!(location = %format_location(&bookmark), "Imported reading progress");
+        self.progress_status = Some(format!(
+            "Moved to imported location {}.",
+            format_location(&bookmark)
+        ));
+    }
+}
@@ -1,11 +1,12 @@
 use super::super::state::{
     App, IMAGE_BLOCK_SPACING_PX, IMAGE_FOOTER_FONT_SIZE_PX, IMAGE_FOOTER_LINE_HEIGHT,
     IMAGE_LABEL_FONT_SIZE_PX, IMAGE_LABEL_LINE_HEIGHT, IMAGE_PREVIEW_HEIGHT_PX,
-    PAGE_FLOW_SPACING_PX,
+    PAGE_FLOW_SPACING_PX, SCROLL_GLIDE_DURATION, ScrollGlide,
 };
 use super::Effect;
 use crate::cache::{Bookmark, save_bookmark};
 use iced::widget::scrollable::RelativeOffset;
+use std::sync::OnceLock;
 use std::time::{Duration, Instant};
 use tracing::info;
 
@@ -88,36 +89,61 @@ impl App {
         }
     }
 
-    pub(super) fn persist_bookmark(&self) {
+    pub(super) fn persist_bookmark(&mut self) {
         if self.starter_mode {
             return;
         }
+        if let Some(page) = self.bookmark.hold_resume_page {
+            if page == self.reader.current_page {
+                return;
+            }
+            self.bookmark.hold_resume_page = None;
+        }
+        save_bookmark(&self.epub_path, &self.current_bookmark());
+    }
+
+    /// Snapshot of the current page, sentence and scroll position.
+    pub(super) fn current_bookmark(&self) -> Bookmark {
         let sentences = self.current_sentences();
 
         let sentence_idx = self
             .tts
             .current_sentence_idx
             .filter(|idx| *idx < sentences.len())
-            .or_else(|| {
-                if sentences.is_empty() {
-                    None
-                } else {
-                    let frac = Self::sanitize_offset(self.bookmark.last_scroll_offset).y;
-                    let idx = (frac * (sentences.len().saturating_sub(1) as f32)).round() as usize;
-                    Some(idx.min(sentences.len().saturating_sub(1)))
-                }
-            });
+            .or_else(|| self.sentence_at_scroll());
         let sentence_text = sentence_idx.and_then(|idx| sentences.get(idx).cloned());
         let scroll_y = Self::sanitize_offset(self.bookmark.last_scroll_offset).y;
 
-        let bookmark = Bookmark {
+        Bookmark {
             page: self.reader.current_page,
             sentence_idx,
             sentence_text,
             scroll_y,
-        };
+        }
+    }
 
-        save_bookmark(&self.epub_path, &bookmark);
+    /// Sentence the current scroll position points into.
+    pub(super) fn sentence_at_scroll(&self) -> Option<usize> {
+        let frac = Self::sanitize_offset(self.bookmark.last_scroll_offset).y;
+        Self::sentence_idx_for_scroll_fraction(frac, self.current_sentences().len())
+    }
+
+    /// Map a relative scroll fraction onto the sentence it most likely points into.
+    pub(crate) fn sentence_idx_for_scroll_fraction(
+        fraction: f32,
+        sentence_count: usize,
+    ) -> Option<usize> {
+        if sentence_count == 0 {
+            return None;
+        }
+        let last = sentence_count.saturating_sub(1);
+        let fraction = if fraction.is_finite() {
+            fraction.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let idx = (fraction * last as f32).round() as usize;
+        Some(idx.min(last))
     }
 
     pub(super) fn sanitize_offset(offset: RelativeOffset) -> RelativeOffset {
@@ -134,7 +160,7 @@ impl App {
         }
     }
 
-    fn current_sentences(&self) -> Vec<String> {
+    pub(crate) fn current_sentences(&self) -> Vec<String> {
         self.raw_sentences_for_page(self.reader.current_page)
     }
 
@@ -154,6 +180,56 @@ impl App {
         )
     }
 
+    /// Start (or retarget) a `smooth_scroll` glide to `sentence_idx`, returning
+    /// whether one is running; the caller snaps instead when it isn't.
+    pub(in crate::app::update) fn start_scroll_glide(&mut self, sentence_idx: usize) -> bool {
+        if !self.config.smooth_scroll || prefers_reduced_motion() {
+            return false;
+        }
+        self.glide_from_current_offset(sentence_idx);
+        true
+    }
+
+    fn glide_from_current_offset(&mut self, sentence_idx: usize) {
+        // Frames track the sentence's offset as layout settles, so the
+        // after-layout snap isn't needed.
+        self.bookmark.pending_sentence_snap = None;
+        self.bookmark.scroll_glide = Some(ScrollGlide {
+            page: self.reader.current_page,
+            sentence: sentence_idx,
+            from: self.bookmark.last_scroll_offset,
+            started_at: Instant::now(),
+        });
+    }
+
+    pub(super) fn handle_scroll_glide_frame(&mut self, now: Instant, effects: &mut Vec<Effect>) {
+        let Some(glide) = self.bookmark.scroll_glide else {
+            return;
+        };
+        let target = (glide.page == self.reader.current_page)
+            .then(|| self.scroll_offset_for_sentence(glide.sentence))
+            .flatten();
+        let Some(target) = target else {
+            self.bookmark.scroll_glide = None;
+            return;
+        };
+        let progress = now
+            .saturating_duration_since(glide.started_at)
+            .as_secs_f32()
+            / SCROLL_GLIDE_DURATION.as_secs_f32();
+        let offset = if progress >= 1.0 {
+            self.bookmark.scroll_glide = None;
+            effects.push(Effect::SaveBookmark);
+            target
+        } else {
+            RelativeOffset {
+                x: target.x,
+                y: glide_position(glide.from.y, target.y, progress),
+            }
+        };
+        effects.push(Effect::ScrollTo(offset));
+    }
+
     fn scroll_offset_for_sentence_jump(&self, sentence_idx: usize) -> Option<RelativeOffset> {
         // Jump actions should be stricter than passive tracking: force center behavior and
         // keep a visibility guard band so the highlighted sentence does not land outside view.
@@ -296,11 +372,21 @@ impl App {
             });
         }
 
-        let sentences = self.display_sentences_for_current_page();
-        if sentences.is_empty() {
+        let current = self.display_sentences_for_current_page();
+        if current.is_empty() {
             return None;
         }
-        let target_idx = display_sentence_idx.min(sentences.len().saturating_sub(1));
+        // In continuous reading the live page sits among the rest of its chapter.
+        let shown = self.displayed_pages();
+        let mut sentences: Vec<String> = (shown.start..self.reader.current_page)
+            .flat_map(|page| self.raw_sentences_for_page(page))
+            .collect();
+        let target_idx = sentences.len() + display_sentence_idx.min(current.len() - 1);
+        sentences.extend(current);
+        sentences.extend(
+            (self.reader.current_page + 1..shown.end)
+                .flat_map(|page| self.raw_sentences_for_page(page)),
+        );
         Some(ScrollTargetModel {
             sentences,
             target_idx,
@@ -388,17 +474,18 @@ impl App {
         lines.max(1.0)
     }
 
-    fn current_page_image_count(&self) -> usize {
+    fn displayed_image_count(&self) -> usize {
+        let shown = self.displayed_pages();
         self.reader
             .images
             .iter()
             .enumerate()
-            .filter(|(idx, _)| self.image_assigned_page(*idx) == self.reader.current_page)
+            .filter(|(idx, _)| shown.contains(&self.image_assigned_page(*idx)))
             .count()
     }
 
     fn estimated_non_text_tail_px(&self) -> f32 {
-        let image_count = self.current_page_image_count() as f32;
+        let image_count = self.displayed_image_count() as f32;
         if image_count <= 0.0 {
             return 0.0;
         }
@@ -425,9 +512,73 @@ struct SentenceProgress {
     middle: f32,
 }
 
+/// Offset `progress` (0 to 1) of the way from `from` to `to`, eased out so the
+/// glide starts quickly and settles gently.
+fn glide_position(from: f32, to: f32, progress: f32) -> f32 {
+    let t = progress.clamp(0.0, 1.0);
+    let eased = 1.0 - (1.0 - t).powi(3);
+    from + (to - from) * eased
+}
+
+static REDUCED_MOTION: OnceLock<bool> = OnceLock::new();
+
+/// Ask the desktop once, on a background thread, whether it prefers reduced
+/// motion, so neither startup nor auto-scroll waits on the answer.
+pub(in crate::app) fn detect_reduced_motion() {
+    std::thread::spawn(|| {
+        let reduced = desktop_prefers_reduced_motion();
+        if reduced {
+            info!("Desktop prefers reduced motion; auto-scroll will snap");
+        }
+        let _ = REDUCED_MOTION.set(reduced);
+    });
+}
+
+/// Whether the desktop asked for reduced motion; no until detection finishes.
+fn prefers_reduced_motion() -> bool {
+    REDUCED_MOTION.get().copied().unwrap_or(false)
+}
+
+/// macOS's Reduce Motion accessibility setting.
+#[cfg(target_os = "macos")]
+fn desktop_prefers_reduced_motion() -> bool {
+    command_prints(
+        "defaults",
+        &["read", "com.apple.universalaccess", "reduceMotion"],
+        "1",
+    )
+}
+
+/// GNOME's `enable-animations` turned off; other desktops count as no.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn desktop_prefers_reduced_motion() -> bool {
+    command_prints(
+        "gsettings",
+        &["get", "org.gnome.desktop.interface", "enable-animations"],
+        "false",
+    )
+}
+
+#[cfg(not(unix))]
+fn desktop_prefers_reduced_motion() -> bool {
+    false
+}
+
+/// Whether `program` runs successfully and prints `expected`.
+#[cfg(unix)]
+fn command_prints(program: &str, args: &[&str], expected: &str) -> bool {
+    std::process::Command::new(program)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .is_some_and(|output| String::from_utf8_lossy(&output.stdout).trim() == expected)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::state::{test_app_with, test_book_path};
     use crate::config::AppConfig;
     use crate::epub_loader::{BookImage, LoadedBook};
     use std::path::PathBuf;
@@ -454,6 +605,7 @@ mod tests {
         let book = LoadedBook {
             text: sample_text(sentence_count),
             images,
+            ..Default::default()
         };
 
         let mut config = AppConfig::default();
@@ -466,12 +618,7 @@ mod tests {
         config.font_size = 16;
         config.auto_scroll_tts = true;
 
-        let epub_path = PathBuf::from(format!(
-            "/tmp/ebup-scroll-test-{}-{}.epub",
-            std::process::id(),
-            sentence_count
-        ));
-        let (mut app, _task) = App::bootstrap(book, config, epub_path, None);
+        let mut app = test_app_with(book, config);
 
         app.reader.current_page = 0;
         app.bookmark.viewport_width = 920.0;
@@ -483,6 +630,28 @@ mod tests {
         app
     }
 
+    #[test]
+    fn play_can_start_at_the_scrolled_sentence() {
+        let mut app = build_test_app(140, 0);
+        app.tts_unavailable = None;
+        app.tts.current_sentence_idx = Some(0);
+        app.bookmark.last_scroll_offset = RelativeOffset { x: 0.0, y: 0.5 };
+        let started_at = |app: &mut App| {
+            let mut effects = Vec::new();
+            app.handle_play(&mut effects);
+            effects.iter().find_map(|effect| match effect {
+                Effect::StartTts { sentence_idx, .. } => Some(*sentence_idx),
+                _ => None,
+            })
+        };
+
+        assert_eq!(started_at(&mut app), Some(0));
+        app.config.play_starts_at_scroll = true;
+        let scrolled = app.sentence_at_scroll();
+        assert!(scrolled.is_some_and(|idx| idx > 0));
+        assert_eq!(started_at(&mut app), scrolled);
+    }
+
     #[test]
     fn text_only_center_differs_from_auto_scroll() {
         let mut app = build_test_app(140, 0);
@@ -651,4 +820,89 @@ mod tests {
                 .any(|effect| matches!(effect, Effect::ScrollTo(_)))
         );
     }
+
+    #[test]
+    fn resume_from_mid_sentence_scroll_snaps_to_sentence_offset() {
+        let book = LoadedBook {
+            text: sample_text(120),
+            ..Default::default()
+        };
+        let mut config = AppConfig::default();
+        config.lines_per_page = 200;
+        config.snap_resume_to_sentence = true;
+        let bookmark = Bookmark {
+            page: 0,
+            sentence_idx: None,
+            sentence_text: None,
+            scroll_y: 0.37,
+        };
+        let (app, _task) = App::bootstrap(book, config, test_book_path(), Some(bookmark));
+
+        let sentence_count = app.raw_sentences_for_page(app.reader.current_page).len();
+        let expected_idx = App::sentence_idx_for_scroll_fraction(0.37, sentence_count)
+            .expect("derived sentence index");
+        assert_eq!(app.tts.current_sentence_idx, Some(expected_idx));
+
+        let expected = app
+            .scroll_offset_for_sentence(expected_idx)
+            .expect("sentence-start offset");
+        assert_eq!(app.bookmark.last_scroll_offset, expected);
+        assert_ne!(app.bookmark.last_scroll_offset.y, 0.37);
+    }
+
+    #[test]
+    fn resume_follows_sentence_text_to_a_different_page() {
+        let mut app = build_test_app(180, 0);
+        app.config.lines_per_page = 20;
+        app.repaginate();
+        let pages = app.reader.page_sentences.len();
+        assert!(pages > 2, "sample should paginate");
+        let target_page = pages - 1;
+        let target_idx = app.reader.page_sentences[target_page].len() / 2;
+        let bookmark = Bookmark {
+            page: 0,
+            sentence_idx: Some(target_idx),
+            sentence_text: Some(app.reader.page_sentences[target_page][target_idx].clone()),
+            scroll_y: 0.8,
+        };
+
+        let offset = app.restore_bookmark_position(&bookmark);
+
+        assert_eq!(app.reader.current_page, target_page);
+        assert_eq!(app.tts.current_sentence_idx, Some(target_idx));
+        assert_eq!(offset, app.scroll_offset_for_sentence(target_idx));
+
+        let stale = Bookmark {
+            sentence_text: Some("Not in this book.".to_string()),
+            page: 1,
+            ..bookmark
+        };
+        app.restore_bookmark_position(&stale);
+        assert_eq!(app.reader.current_page, 1);
+    }
+    #[test]
+    fn scroll_glide_eases_to_the_target_and_retargets() {
+        assert!((glide_position(0.2, 0.6, 0.0) - 0.2).abs() < 1e-6);
+        assert!((glide_position(0.2, 0.6, 1.0) - 0.6).abs() < 1e-6);
+        assert!((glide_position(0.2, 0.6, 3.0) - 0.6).abs() < 1e-6);
+        let halfway = glide_position(0.0, 1.0, 0.5);
+        assert!(halfway > 0.5 && halfway < 1.0, "eases out: {halfway}");
+        assert!(glide_position(0.8, 0.4, 0.5) < 0.8);
+
+        let mut app = build_test_app(140, 0);
+        app.glide_from_current_offset(40);
+        app.glide_from_current_offset(60);
+        let glide = app.bookmark.scroll_glide.expect("one glide in flight");
+        assert_eq!(glide.sentence, 60);
+
+        let mut effects = Vec::new();
+        app.handle_scroll_glide_frame(glide.started_at + SCROLL_GLIDE_DURATION, &mut effects);
+        assert!(app.bookmark.scroll_glide.is_none());
+        let target = app.scroll_offset_for_sentence(60).expect("target offset");
+        assert!(
+            effects
+                .iter()
+                .any(|effect| matches!(effect, Effect::ScrollTo(offset) if *offset == target))
+        );
+    }
 }
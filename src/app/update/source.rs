@@ -0,0 +1,113 @@
+use super::super::state::App;
+use super::Effect;
+use crate::cache::migrate_book_cache;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// How often the open book's file is checked for still being in place.
+const SOURCE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+impl App {
+    /// Notice when the open book's file is moved or deleted, or comes back.
+    pub(super) fn maybe_check_source(&mut self) {
+        if self.starter_mode {
+            return;
+        }
+        let now = Instant::now();
+        if self
+            .source_checked_at
+            .is_some_and(|at| now.saturating_duration_since(at) < SOURCE_POLL_INTERVAL)
+        {
+            return;
+        }
+        self.source_checked_at = Some(now);
+        let missing = !self.epub_path.exists();
+        if missing == self.source_missing {
+            return;
+        }
+        if missing {
+            warn!(path = %self.epub_path.display(), "Open book's file is no longer there");
+        } else {
+            info!(path = %self.epub_path.display(), "Open book's file is back");
+            self.relocate_status = None;
+        }
+        self.source_missing = missing;
+    }
+
+    pub(super) fn handle_relocate_source(&mut self, effects: &mut Vec<Effect>) {
+        let input = self.relocate_path_input.trim();
+        if input.is_empty() {
+            return;
+        }
+        let new_path = PathBuf::from(input);
+        match migrate_book_cache(&self.epub_path, &new_path) {
+            Ok(()) => {
+                let new_path = std::fs::canonicalize(&new_path).unwrap_or(new_path);
+                info!(
+                    from = %self.epub_path.display(),
+                    to = %new_path.display(),
+                    "Relocated open book"
+                );
+                self.epub_path = new_path;
+                self.source_missing = false;
+                self.relocate_path_input.clear();
+                self.relocate_status = None;
+                self.refresh_recent_books();
+                effects.push(Effect::SaveBookmark);
+                effects.push(Effect::SaveConfig);
+            }
+            Err(err) => {
+                warn!(path = %new_path.display(), "Failed to relocate open book: {err}");
+                self.relocate_status = Some(format!("Relocating failed: {err}"));
+            }
+        }
+    }
+}
+
+/// Error shown instead of trying to open a book whose file is gone.
+pub(super) fn missing_source_error(path: &Path) -> Option<String> {
+    (!path.exists()).then(|| format!("{} no longer exists.", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::epub_loader::LoadedBook;
+
+    #[test]
+    fn missing_source_is_flagged_and_relocated() {
+        let book = LoadedBook {
+            text: "One sentence. Another sentence.".to_string(),
+            ..Default::default()
+        };
+        let dir = std::env::temp_dir().join(format!("ebup-relocate-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let old_path = dir.join("old.txt");
+        let new_path = dir.join("new.txt");
+        std::fs::write(&old_path, "One sentence. Another sentence.").unwrap();
+        let (mut app, _task) = App::bootstrap(book, AppConfig::default(), old_path.clone(), None);
+
+        app.maybe_check_source();
+        assert!(!app.source_missing);
+
+        std::fs::rename(&old_path, &new_path).unwrap();
+        app.source_checked_at = None;
+        app.maybe_check_source();
+        assert!(app.source_missing);
+        assert!(missing_source_error(&old_path).is_some());
+
+        let mut effects = Vec::new();
+        app.relocate_path_input = new_path.display().to_string();
+        app.handle_relocate_source(&mut effects);
+        assert!(!app.source_missing);
+        assert_eq!(app.epub_path, std::fs::canonicalize(&new_path).unwrap());
+        assert!(matches!(
+            effects.as_slice(),
+            [Effect::SaveBookmark, Effect::SaveConfig]
+        ));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
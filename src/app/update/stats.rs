@@ -0,0 +1,40 @@
+use super::super::state::App;
+use super::Effect;
+use crate::cache::save_stats;
+use std::time::Instant;
+use tracing::debug;
+
+impl App {
+    pub(super) fn handle_window_focus_changed(&mut self, focused: bool, effects: &mut Vec<Effect>) {
+        debug!(focused, "Window focus changed");
+        self.reading_stats.set_focused(focused, Instant::now());
+        self.pause_tts_for_focus_change(focused, effects);
+    }
+
+    /// Accumulate reading time and schedule a debounced stats write.
+    pub(super) fn track_reading_time(&mut self, effects: &mut Vec<Effect>) {
+        if self.starter_mode {
+            return;
+        }
+        let now = Instant::now();
+        self.reading_stats.record_time(now);
+        // Time spent listening says nothing about silent reading speed.
+        if self.tts.is_playing() || self.tts.is_preparing() {
+            self.reading_stats.stop_page_timer();
+        }
+        if let Some(stats) = self.reading_stats.take_due_save(now) {
+            effects.push(Effect::SaveReadingStats(stats));
+        }
+    }
+
+    /// Write any stats not yet on disk; used when the book is closed.
+    pub(super) fn persist_reading_stats(&mut self) {
+        if self.starter_mode {
+            return;
+        }
+        self.reading_stats.record_time(Instant::now());
+        if let Some(stats) = self.reading_stats.take_unsaved() {
+            save_stats(&self.epub_path, &stats);
+        }
+    }
+}
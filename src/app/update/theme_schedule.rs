@@ -0,0 +1,141 @@
+use super::super::state::App;
+use super::Effect;
+use crate::config::ThemeMode;
+use chrono::Timelike;
+use std::time::SystemTime;
+use tracing::info;
+
+impl App {
+    pub(super) fn handle_theme_schedule_tick(&mut self, effects: &mut Vec<Effect>) {
+        if self.sync_theme_schedule() {
+            effects.push(Effect::SaveConfig);
+        }
+    }
+
+    /// Switch the theme if the schedule has crossed into a new period, returning
+    /// whether the theme changed.
+    pub(in crate::app) fn sync_theme_schedule(&mut self) -> bool {
+        self.apply_theme_schedule_at(local_hour(SystemTime::now()))
+    }
+
+    /// Start following the schedule from the current period without touching
+    /// the saved theme, which holds until the next switch.
+    pub(in crate::app) fn prime_theme_schedule(&mut self) {
+        self.prime_theme_schedule_at(local_hour(SystemTime::now()));
+    }
+
+    fn prime_theme_schedule_at(&mut self, hour: u8) {
+        self.scheduled_theme = self
+            .config
+            .auto_theme_schedule
+            .map(|schedule| scheduled_theme(hour, schedule));
+    }
+
+    fn apply_theme_schedule_at(&mut self, hour: u8) -> bool {
+        let Some(schedule) = self.config.auto_theme_schedule else {
+            self.scheduled_theme = None;
+            return false;
+        };
+        let theme = scheduled_theme(hour, schedule);
+        // Within the same period a manual toggle stands.
+        if self.scheduled_theme == Some(theme) {
+            return false;
+        }
+        self.scheduled_theme = Some(theme);
+        if self.config.theme == theme {
+            return false;
+        }
+        info!(theme = %theme, hour, "Switching theme on schedule");
+        self.remember_custom_colors();
+        self.config.theme = theme;
+        true
+    }
+}
+
+/// Night from `night_hour` until `day_hour`, Day otherwise; the night may wrap
+/// past midnight. The hours must differ.
+fn scheduled_theme(hour: u8, (night_hour, day_hour): (u8, u8)) -> ThemeMode {
+    let night = if night_hour > day_hour {
+        hour >= night_hour || hour < day_hour
+    } else {
+        hour >= night_hour && hour < day_hour
+    };
+    if night {
+        ThemeMode::Night
+    } else {
+        ThemeMode::Day
+    }
+}
+
+/// Hour of the local day in the system time zone.
+fn local_hour(now: SystemTime) -> u8 {
+    chrono::DateTime::<chrono::Local>::from(now).hour() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::test_app_with;
+    use crate::config::AppConfig;
+    use crate::epub_loader::LoadedBook;
+
+    #[test]
+    fn schedule_wraps_past_midnight() {
+        let overnight = (20, 7);
+        assert_eq!(scheduled_theme(19, overnight), ThemeMode::Day);
+        assert_eq!(scheduled_theme(20, overnight), ThemeMode::Night);
+        assert_eq!(scheduled_theme(0, overnight), ThemeMode::Night);
+        assert_eq!(scheduled_theme(6, overnight), ThemeMode::Night);
+        assert_eq!(scheduled_theme(7, overnight), ThemeMode::Day);
+
+        let early_hours = (1, 5);
+        assert_eq!(scheduled_theme(0, early_hours), ThemeMode::Day);
+        assert_eq!(scheduled_theme(3, early_hours), ThemeMode::Night);
+        assert_eq!(scheduled_theme(5, early_hours), ThemeMode::Day);
+    }
+
+    #[test]
+    fn manual_theme_holds_until_the_next_boundary() {
+        let book = LoadedBook {
+            text: "One sentence.".to_string(),
+            ..Default::default()
+        };
+        let mut config = AppConfig::default();
+        config.auto_theme_schedule = Some((20, 7));
+        let mut app = test_app_with(book, config);
+
+        app.scheduled_theme = None;
+        app.config.theme = ThemeMode::Day;
+        assert!(app.apply_theme_schedule_at(21));
+        assert_eq!(app.config.theme, ThemeMode::Night);
+
+        app.config.theme = ThemeMode::Sepia;
+        assert!(!app.apply_theme_schedule_at(23));
+        assert!(!app.apply_theme_schedule_at(3));
+        assert_eq!(app.config.theme, ThemeMode::Sepia);
+
+        assert!(app.apply_theme_schedule_at(7));
+        assert_eq!(app.config.theme, ThemeMode::Day);
+    }
+
+    #[test]
+    fn saved_theme_holds_at_startup_until_the_next_boundary() {
+        let book = LoadedBook {
+            text: "One sentence.".to_string(),
+            ..Default::default()
+        };
+        let mut config = AppConfig::default();
+        config.auto_theme_schedule = Some((20, 7));
+        let mut app = test_app_with(book, config);
+
+        app.config.theme = ThemeMode::Day;
+        app.prime_theme_schedule_at(22);
+        assert_eq!(app.scheduled_theme, Some(ThemeMode::Night));
+        assert!(!app.apply_theme_schedule_at(23));
+        assert_eq!(app.config.theme, ThemeMode::Day);
+
+        app.config.theme = ThemeMode::Night;
+        assert!(app.apply_theme_schedule_at(7));
+        assert_eq!(app.config.theme, ThemeMode::Day);
+    }
+}
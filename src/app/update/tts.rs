@@ -1,5 +1,7 @@
 use super::super::state::{
-    App, MAX_TTS_SPEED, MAX_TTS_VOLUME, MIN_TTS_SPEED, MIN_TTS_VOLUME, TtsLifecycle,
+    App, MAX_TTS_SPEED, MAX_TTS_VOLUME, MAX_WORD_CLOCK_STEP, MIN_TTS_SPEED, MIN_TTS_VOLUME,
+    SentencePauses, TtsLifecycle, WordClock, grouped_member_for_elapsed, nearest_sentence_start,
+    word_index_for_elapsed,
 };
 use super::Effect;
 use iced::Task;
@@ -7,7 +9,15 @@ use iced::widget::scrollable::RelativeOffset;
 use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
+mod announce;
+mod audio_export;
+mod book_end;
+mod chapter_cache;
 mod effects;
+mod interruptions;
+mod prefetch;
+mod selection;
+mod snippet;
 mod transitions;
 
 impl App {
@@ -31,20 +41,43 @@ impl App {
             self.config.pause_after_sentence = clamped;
             info!(pause_secs = clamped, "Updated pause after sentence");
             effects.push(Effect::SaveConfig);
-            if let Some(playback) = &self.tts.playback {
-                self.tts.resume_after_prepare = !playback.is_paused();
-                let idx = self
-                    .tts
-                    .current_sentence_idx
-                    .or_else(|| self.display_index_for_audio_sentence(self.tts.sentence_offset))
-                    .unwrap_or(0);
-                effects.push(Effect::StartTts {
-                    page: self.reader.current_page,
-                    sentence_idx: idx,
-                });
-                effects.push(Effect::AutoScrollToCurrent);
-                effects.push(Effect::SaveBookmark);
-            }
+            self.restart_tts_for_new_pauses(effects);
+        }
+    }
+
+    pub(super) fn handle_pause_after_paragraph_changed(
+        &mut self,
+        pause: f32,
+        effects: &mut Vec<Effect>,
+    ) {
+        let clamped = if pause.is_finite() {
+            pause.clamp(0.0, 2.0)
+        } else {
+            self.config.pause_after_paragraph
+        };
+        if (clamped - self.config.pause_after_paragraph).abs() > f32::EPSILON {
+            self.config.pause_after_paragraph = clamped;
+            info!(pause_secs = clamped, "Updated pause after paragraph");
+            effects.push(Effect::SaveConfig);
+            self.restart_tts_for_new_pauses(effects);
+        }
+    }
+
+    /// Pauses are baked into queued audio, so re-queue from the current sentence.
+    fn restart_tts_for_new_pauses(&mut self, effects: &mut Vec<Effect>) {
+        if let Some(playback) = &self.tts.playback {
+            self.tts.resume_after_prepare = !playback.is_paused();
+            let idx = self
+                .tts
+                .current_sentence_idx
+                .or_else(|| self.display_index_for_audio_sentence(self.tts.sentence_offset))
+                .unwrap_or(0);
+            effects.push(Effect::StartTts {
+                page: self.reader.current_page,
+                sentence_idx: idx,
+            });
+            effects.push(Effect::AutoScrollToCurrent);
+            effects.push(Effect::SaveBookmark);
         }
     }
 
@@ -80,9 +113,27 @@ impl App {
         }
     }
 
+    pub(super) fn handle_smooth_scroll_changed(
+        &mut self,
+        enabled: bool,
+        effects: &mut Vec<Effect>,
+    ) {
+        if self.config.smooth_scroll != enabled {
+            self.config.smooth_scroll = enabled;
+            info!(enabled, "Updated smooth auto-scroll preference");
+            if !enabled {
+                self.bookmark.scroll_glide = None;
+            }
+            effects.push(Effect::SaveConfig);
+        }
+    }
+
     pub(super) fn handle_set_tts_speed(&mut self, speed: f32, effects: &mut Vec<Effect>) {
         let clamped = speed.clamp(MIN_TTS_SPEED, MAX_TTS_SPEED);
         self.config.tts_speed = clamped;
+        if self.config.speed_ramp.take().is_some() {
+            info!("Speed slider moved; speed ramp turned off");
+        }
         info!(speed = self.config.tts_speed, "Adjusted TTS speed");
         if let Some(playback) = &self.tts.playback {
             self.tts.resume_after_prepare = !playback.is_paused();
@@ -97,6 +148,32 @@ impl App {
         effects.push(Effect::SaveConfig);
     }
 
+    pub(super) fn handle_set_tts_voice(&mut self, voice: String, effects: &mut Vec<Effect>) {
+        if voice == self.config.tts_voice {
+            return;
+        }
+        self.config.tts_voice = voice;
+        self.reload_tts_engine();
+        info!(voice = %self.config.tts_voice, "Switched TTS voice");
+        let restart = match &self.tts.playback {
+            Some(playback) => {
+                self.tts.resume_after_prepare = !playback.is_paused();
+                true
+            }
+            None => self.tts.is_preparing(),
+        };
+        if restart {
+            let idx = self.tts.current_sentence_idx.unwrap_or(0);
+            effects.push(Effect::StartTts {
+                page: self.reader.current_page,
+                sentence_idx: idx,
+            });
+            effects.push(Effect::AutoScrollToCurrent);
+            effects.push(Effect::SaveBookmark);
+        }
+        effects.push(Effect::SaveConfig);
+    }
+
     pub(super) fn handle_set_tts_volume(&mut self, volume: f32, effects: &mut Vec<Effect>) {
         let clamped = volume.clamp(MIN_TTS_VOLUME, MAX_TTS_VOLUME);
         self.config.tts_volume = clamped;
@@ -109,6 +186,14 @@ impl App {
 
     pub(super) fn handle_play(&mut self, effects: &mut Vec<Effect>) {
         if let Some((page, sentence_idx, _)) = self.tts.preparing_context() {
+            if !self.tts.resume_after_prepare {
+                info!(
+                    page = page + 1,
+                    sentence_idx, "Playing paused preparation once it is ready"
+                );
+                self.tts.resume_after_prepare = true;
+                return;
+            }
             info!(
                 page = page + 1,
                 sentence_idx,
@@ -116,13 +201,31 @@ impl App {
             );
             return;
         }
+        if let Some(reason) = &self.tts_unavailable {
+            warn!("Ignoring play request; TTS unavailable: {reason}");
+            return;
+        }
+        self.stop_snippet();
+        self.tts.auto_paused = false;
         if let Some(playback) = &self.tts.playback {
             info!("Resuming TTS playback");
             playback.play();
+            let now = Instant::now();
             self.tts.lifecycle = TtsLifecycle::Playing;
-            self.tts.started_at = Some(Instant::now());
+            self.tts.started_at = Some(now);
+            // Time spent paused must not count towards the spoken word.
+            if let Some(clock) = &mut self.tts.word_clock {
+                clock.last_tick = now;
+            }
         } else {
-            let start_idx = self.tts.current_sentence_idx.unwrap_or(0);
+            let start_idx = if self.config.play_starts_at_scroll {
+                self.reader
+                    .cursor_on_current_page()
+                    .or_else(|| self.sentence_at_scroll())
+            } else {
+                self.reading_cursor()
+            }
+            .unwrap_or(0);
             self.tts.resume_after_prepare = true;
             info!(start_idx, "Starting TTS playback from cursor");
             effects.push(Effect::StartTts {
@@ -141,7 +244,8 @@ impl App {
             .as_ref()
             .map(|p| !p.is_paused())
             .unwrap_or(false);
-        if self.tts.is_preparing() || currently_playing {
+        let preparing_to_play = self.tts.is_preparing() && self.tts.resume_after_prepare;
+        if preparing_to_play || currently_playing {
             self.handle_pause(effects);
         } else {
             self.handle_play(effects);
@@ -163,6 +267,33 @@ impl App {
         self.begin_play_from_sentence(idx, effects, "Playing from cursor");
     }
 
+    pub(super) fn handle_audio_scrubbed(&mut self, secs: f32) {
+        if self.tts.track.is_empty() || !secs.is_finite() {
+            return;
+        }
+        self.tts.scrub_position = Some(Duration::from_secs_f32(secs.max(0.0)));
+    }
+
+    /// Seek to the sentence starting closest to where the scrubber was let go.
+    pub(super) fn handle_audio_scrub_released(&mut self, effects: &mut Vec<Effect>) {
+        let Some(position) = self.tts.scrub_position.take() else {
+            return;
+        };
+        let marks = self
+            .tts
+            .track_marks(SentencePauses::from_config(&self.config).sentence);
+        let Some(display_idx) = nearest_sentence_start(&marks, position)
+            .and_then(|idx| self.display_index_for_audio_sentence(self.tts.sentence_offset + idx))
+        else {
+            return;
+        };
+        self.begin_play_from_sentence(
+            display_idx,
+            effects,
+            "Scrubbed audio; playing from sentence",
+        );
+    }
+
     pub(super) fn handle_sentence_clicked(&mut self, idx: usize, effects: &mut Vec<Effect>) {
         self.begin_play_from_sentence(idx, effects, "Sentence clicked; playing from sentence");
     }
@@ -227,6 +358,8 @@ impl App {
             effects.push(Effect::SaveBookmark);
         } else if self.reader.current_page + 1 < self.reader.pages.len() {
             self.reader.current_page += 1;
+            self.reading_stats
+                .record_page_turn(Some(self.reader.page_start(self.reader.current_page)));
             info!("Seeking forward into next page");
             self.tts.last_sentences = self.raw_sentences_for_page(self.reader.current_page);
             if should_resume_playback {
@@ -286,6 +419,7 @@ impl App {
             effects.push(Effect::SaveBookmark);
         } else if self.reader.current_page > 0 {
             self.reader.current_page -= 1;
+            self.reading_stats.record_page_turn(None);
             let last_idx = self
                 .sentence_count_for_page(self.reader.current_page)
                 .saturating_sub(1);
@@ -311,7 +445,113 @@ impl App {
         }
     }
 
+    /// Track time spent in the playing audio sentence and derive the spoken word from it.
+    /// Returns the display sentence being spoken, which moves through the members of a
+    /// clip grouped under `tts_group_sentences`.
+    fn advance_word_clock(&mut self, audio_idx: usize, display_idx: usize, now: Instant) -> usize {
+        let members = self.grouped_members(audio_idx, display_idx);
+        if !self.config.word_level_highlight && members.len() < 2 {
+            self.tts.current_word_idx = None;
+            self.tts.word_clock = None;
+            return display_idx;
+        }
+        let clock = match self.tts.word_clock {
+            Some(mut clock) if clock.audio_idx == audio_idx => {
+                clock.elapsed += now
+                    .saturating_duration_since(clock.last_tick)
+                    .min(MAX_WORD_CLOCK_STEP);
+                clock.last_tick = now;
+                clock
+            }
+            _ => WordClock {
+                audio_idx,
+                elapsed: Duration::ZERO,
+                last_tick: now,
+            },
+        };
+        self.tts.word_clock = Some(clock);
+
+        if members.len() > 1 {
+            let clip = audio_idx
+                .checked_sub(self.tts.sentence_offset)
+                .and_then(|i| self.tts.track.get(i))
+                .map_or(Duration::ZERO, |(_, dur)| *dur);
+            let weights: Vec<usize> = members
+                .iter()
+                .map(|idx| self.page_sentence_chars(*idx).max(1))
+                .collect();
+            let (member, before, share) = grouped_member_for_elapsed(&weights, clock.elapsed, clip);
+            let display_idx = members[member];
+            self.tts.current_word_idx = if self.config.word_level_highlight {
+                word_index_for_elapsed(
+                    self.page_sentence_words(display_idx),
+                    clock.elapsed.saturating_sub(before),
+                    share,
+                )
+            } else {
+                None
+            };
+            return display_idx;
+        }
+
+        // Long sentences may be voiced as several audio chunks; pace words across all of them.
+        let mut before = Duration::ZERO;
+        let mut total = Duration::ZERO;
+        for (i, (_, dur)) in self.tts.track.iter().enumerate() {
+            let idx = self.tts.sentence_offset + i;
+            if self.display_index_for_audio_sentence(idx) != Some(display_idx) {
+                continue;
+            }
+            total += *dur;
+            if idx < audio_idx {
+                before += *dur;
+            }
+        }
+        self.tts.current_word_idx = word_index_for_elapsed(
+            self.page_sentence_words(display_idx),
+            before + clock.elapsed,
+            total,
+        );
+        display_idx
+    }
+
+    /// Display sentences voiced by the clip at `audio_idx`, starting at `display_idx`.
+    fn grouped_members(&self, audio_idx: usize, display_idx: usize) -> Vec<usize> {
+        let mut members = vec![display_idx];
+        for (idx, audio) in self
+            .tts
+            .display_to_audio
+            .iter()
+            .enumerate()
+            .skip(display_idx + 1)
+        {
+            match audio {
+                Some(audio) if *audio == audio_idx => members.push(idx),
+                None => continue,
+                Some(_) => break,
+            }
+        }
+        members
+    }
+
+    fn page_sentence_words(&self, display_idx: usize) -> usize {
+        self.reader
+            .page_sentences
+            .get(self.reader.current_page)
+            .and_then(|sentences| sentences.get(display_idx))
+            .map_or(0, |sentence| sentence.split_whitespace().count())
+    }
+
+    fn page_sentence_chars(&self, display_idx: usize) -> usize {
+        self.reader
+            .page_sentences
+            .get(self.reader.current_page)
+            .and_then(|sentences| sentences.get(display_idx))
+            .map_or(0, |sentence| sentence.chars().count())
+    }
+
     pub(super) fn handle_tick(&mut self, now: Instant, effects: &mut Vec<Effect>) {
+        self.poll_snippet(effects);
         if !self.tts.is_playing() {
             return;
         }
@@ -324,16 +564,22 @@ impl App {
         {
             return;
         }
+        self.maybe_prefetch_next_page(effects);
 
-        let _ = now;
         let mut target_idx = None;
         let offset = self.tts.sentence_offset;
         if let Some(playback) = &self.tts.playback {
             let total_sources = self.tts.total_sources;
             let remaining = playback.queued_sources();
-            let consumed = total_sources.saturating_sub(remaining);
-            let per_sentence = self.tts.sources_per_sentence.max(1);
-            let sentence_progress = consumed / per_sentence;
+            // Each queued source is one sentence with its pause included.
+            let mut sentence_progress = total_sources.saturating_sub(remaining);
+            if self.tts.preamble.is_some() {
+                if sentence_progress == 0 {
+                    return;
+                }
+                sentence_progress -= 1;
+                self.finish_chapter_preamble();
+            }
             if sentence_progress < self.tts.track.len() {
                 target_idx = Some(offset + sentence_progress);
             }
@@ -344,10 +590,21 @@ impl App {
             let Some(started) = self.tts.started_at else {
                 return;
             };
-            let elapsed = self.tts.elapsed + Instant::now().saturating_duration_since(started);
+            let mut elapsed = self.tts.elapsed + Instant::now().saturating_duration_since(started);
+            if let Some(preamble) = self.tts.preamble {
+                if elapsed <= preamble {
+                    return;
+                }
+                elapsed -= preamble;
+                self.finish_chapter_preamble();
+            }
             let mut acc = Duration::ZERO;
-            let pause = Duration::from_secs_f32(self.config.pause_after_sentence);
-            for (i, (_, dur)) in self.tts.track.iter().enumerate() {
+            let pauses = self.tts.pauses_from(
+                offset,
+                self.tts.track.len(),
+                SentencePauses::from_config(&self.config).sentence,
+            );
+            for (i, ((_, dur), pause)) in self.tts.track.iter().zip(pauses).enumerate() {
                 acc += *dur + pause;
                 if elapsed <= acc {
                     target_idx = Some(offset + i);
@@ -367,6 +624,16 @@ impl App {
                             .saturating_sub(1),
                     )
                 });
+            let display_idx = self.advance_word_clock(clamped_audio, display_idx, now);
+            if self.tts.spoken_cursor != Some(clamped_audio)
+                && let Some((clip, _)) = clamped_audio
+                    .checked_sub(offset)
+                    .and_then(|idx| self.tts.track.get(idx))
+            {
+                effects.push(Effect::TouchSentenceClip(clip.clone()));
+            }
+            self.tts
+                .record_spoken_progress(clamped_audio, self.current_tts_speed());
             if Some(display_idx) != self.tts.current_sentence_idx {
                 self.tts.current_sentence_idx = Some(display_idx);
                 effects.push(Effect::AutoScrollToCurrent);
@@ -377,10 +644,17 @@ impl App {
                 return;
             }
             effects.push(Effect::StopTts);
+            if self.tts.selection_end.take().is_some() {
+                info!("Finished playing selection");
+                return;
+            }
             if self.reader.current_page + 1 < self.reader.pages.len() {
                 self.reader.current_page += 1;
+                self.reading_stats
+                    .record_page_turn(Some(self.reader.page_start(self.reader.current_page)));
                 self.bookmark.last_scroll_offset = RelativeOffset::START;
                 info!("Playback finished page, advancing");
+                self.announce_chapter_at(self.reader.current_page);
                 effects.push(Effect::StartTts {
                     page: self.reader.current_page,
                     sentence_idx: 0,
@@ -388,7 +662,7 @@ impl App {
                 effects.push(Effect::AutoScrollToCurrent);
                 effects.push(Effect::SaveBookmark);
             } else {
-                info!("Playback finished at end of book");
+                self.finish_book_playback(effects);
             }
         }
     }
@@ -399,6 +673,7 @@ impl App {
         start_idx: usize,
         request_id: u64,
         files: Vec<(std::path::PathBuf, Duration)>,
+        preamble: Option<(std::path::PathBuf, Duration)>,
         effects: &mut Vec<Effect>,
     ) {
         if request_id != self.tts.request_id {
@@ -431,6 +706,19 @@ impl App {
             self.tts.current_sentence_idx = None;
             return;
         }
+        if !transitions::prepared_batch_fits_page(self, page, start_idx, files.len()) {
+            // Trimming cannot fix a batch whose first clip is already wrong;
+            // plan the page again from the sentence being read.
+            let sentence_idx = self
+                .tts
+                .current_sentence_idx
+                .unwrap_or(0)
+                .min(self.sentence_count_for_page(page).saturating_sub(1));
+            self.tts.pending_append = false;
+            self.tts.pending_append_batch = None;
+            effects.push(Effect::StartTts { page, sentence_idx });
+            return;
+        }
         let keep_pending_append = self.tts.pending_append;
         let keep_pending_append_batch = self.tts.pending_append_batch.take();
         self.stop_playback();
@@ -439,14 +727,28 @@ impl App {
         if let Some(engine) = &self.tts.engine {
             let file_paths: Vec<_> = files.iter().map(|(p, _)| p.clone()).collect();
             let start_paused = !self.tts.resume_after_prepare;
+            let sentence_pause = SentencePauses::from_config(&self.config).sentence;
+            let mut queued = file_paths.clone();
+            let mut pauses = self
+                .tts
+                .pauses_from(start_idx, file_paths.len(), sentence_pause);
+            let mut speeds = self.queued_tts_speeds(0, file_paths.len());
+            if let Some((title, _)) = &preamble {
+                queued.insert(0, title.clone());
+                pauses.insert(0, sentence_pause);
+                speeds.insert(0, self.current_tts_speed());
+            }
             if let Ok(playback) = engine.play_files(
-                &file_paths,
-                Duration::from_secs_f32(self.config.pause_after_sentence),
-                self.config.tts_speed,
+                &queued,
+                &pauses,
+                &speeds,
                 self.config.tts_volume,
                 start_paused,
             ) {
-                let played = playback.sentence_durations().to_vec();
+                let mut played = playback.sentence_durations().to_vec();
+                if preamble.is_some() && played.len() == queued.len() {
+                    self.tts.preamble = Some(played.remove(0) + sentence_pause);
+                }
                 self.tts.track = if played.len() == file_paths.len() {
                     file_paths.into_iter().zip(played.iter().copied()).collect()
                 } else {
@@ -464,12 +766,8 @@ impl App {
                         )
                     });
                 self.tts.current_sentence_idx = Some(display_idx);
-                self.tts.sources_per_sentence = if self.config.pause_after_sentence > f32::EPSILON {
-                    2
-                } else {
-                    1
-                };
-                self.tts.total_sources = self.tts.track.len() * self.tts.sources_per_sentence;
+                self.tts.total_sources =
+                    self.tts.track.len() + usize::from(self.tts.preamble.is_some());
                 self.tts.elapsed = Duration::ZERO;
                 if start_paused {
                     self.tts.started_at = None;
@@ -533,12 +831,19 @@ impl App {
             return;
         }
         let file_paths: Vec<_> = files.iter().map(|(p, _)| p.clone()).collect();
+        let pauses = self.tts.pauses_from(
+            start_idx,
+            file_paths.len(),
+            SentencePauses::from_config(&self.config).sentence,
+        );
+        let unfinished = self.tts.track.len().saturating_sub(
+            self.tts
+                .spoken_cursor
+                .map_or(0, |cursor| cursor.saturating_sub(self.tts.sentence_offset)),
+        );
+        let speeds = self.queued_tts_speeds(unfinished, file_paths.len());
         let appended = if let Some(playback) = self.tts.playback.as_mut() {
-            match playback.append_files(
-                &file_paths,
-                Duration::from_secs_f32(self.config.pause_after_sentence),
-                self.config.tts_speed,
-            ) {
+            match playback.append_files(&file_paths, &pauses, &speeds) {
                 Ok(durations) => durations,
                 Err(err) => {
                     warn!("Failed appending prepared TTS files: {err}");
@@ -555,7 +860,7 @@ impl App {
         } else {
             self.tts.track.extend(files);
         }
-        self.tts.total_sources = self.tts.track.len() * self.tts.sources_per_sentence.max(1);
+        self.tts.total_sources = self.tts.track.len() + usize::from(self.tts.preamble.is_some());
         info!(
             page = page + 1,
             start_idx,
@@ -596,6 +901,24 @@ impl App {
         effects::tasks_from_actions(self, actions)
     }
 
+    /// Like `start_playback_from`, but the prepared audio waits paused for Play.
+    pub(in crate::app) fn prepare_playback_from(
+        &mut self,
+        page: usize,
+        sentence_idx: usize,
+    ) -> Task<super::super::messages::Message> {
+        self.tts.resume_after_prepare = false;
+        self.start_playback_from(page, sentence_idx)
+    }
+
+    /// Restored sentence to prepare paused on open under `auto_resume_tts`.
+    pub(in crate::app) fn tts_resume_point(&self) -> Option<usize> {
+        if !self.config.auto_resume_tts || self.tts.engine.is_none() {
+            return None;
+        }
+        self.tts.current_sentence_idx
+    }
+
     fn begin_play_from_sentence(
         &mut self,
         idx: usize,
@@ -0,0 +1,100 @@
+use super::super::super::state::App;
+use std::time::Instant;
+use tracing::{debug, info};
+
+impl App {
+    /// Queue the chapter title for speaking when playback enters `page` at
+    /// the start of a TOC entry under `announce_chapters`.
+    pub(in crate::app::update) fn announce_chapter_at(&mut self, page: usize) {
+        self.tts.announcement = None;
+        if !self.config.announce_chapters {
+            return;
+        }
+        let Some(idx) = self
+            .reader
+            .toc_pages
+            .iter()
+            .rposition(|start| *start == page)
+        else {
+            return;
+        };
+        let Some(entry) = self.reader.toc.get(idx) else {
+            return;
+        };
+        let title = entry.title.trim();
+        if !title.is_empty() {
+            info!(title, page = page + 1, "Announcing chapter title");
+            self.tts.announcement = Some((page, title.to_string()));
+        }
+    }
+
+    /// Title to speak ahead of a batch starting at `start_idx` on `page`, if
+    /// one was queued for it. Any other batch drops the queued title.
+    pub(in crate::app::update) fn take_chapter_announcement(
+        &mut self,
+        page: usize,
+        start_idx: usize,
+    ) -> Option<String> {
+        self.tts
+            .announcement
+            .take()
+            .filter(|(announced, _)| *announced == page && start_idx == 0)
+            .map(|(_, title)| title)
+    }
+
+    /// Drop the finished title clip from the playback accounting so sentence
+    /// tracking counts from the first sentence again.
+    pub(in crate::app::update) fn finish_chapter_preamble(&mut self) {
+        let Some(length) = self.tts.preamble.take() else {
+            return;
+        };
+        debug!(?length, "Chapter title finished");
+        self.tts.total_sources = self.tts.total_sources.saturating_sub(1);
+        let from_elapsed = length.min(self.tts.elapsed);
+        self.tts.elapsed -= from_elapsed;
+        if let Some(started) = self.tts.started_at.as_mut() {
+            *started = (*started + (length - from_elapsed)).min(Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::test_app;
+    use crate::epub_loader::TocEntry;
+    use std::time::Duration;
+
+    #[test]
+    fn chapter_titles_are_announced_only_from_the_chapter_start() {
+        let mut app = test_app("Opening line. Chapter two begins.");
+        app.reader.toc = vec![TocEntry {
+            title: " Chapter Two ".to_string(),
+            depth: 0,
+            char_offset: 14,
+        }];
+        app.reader.toc_pages = vec![1];
+
+        app.announce_chapter_at(1);
+        assert_eq!(app.take_chapter_announcement(1, 0), None);
+
+        app.config.announce_chapters = true;
+        app.announce_chapter_at(0);
+        assert_eq!(app.take_chapter_announcement(0, 0), None);
+        app.announce_chapter_at(1);
+        assert_eq!(app.take_chapter_announcement(1, 2), None);
+        app.announce_chapter_at(1);
+        assert_eq!(
+            app.take_chapter_announcement(1, 0).as_deref(),
+            Some("Chapter Two")
+        );
+
+        app.tts.preamble = Some(Duration::from_secs(2));
+        app.tts.total_sources = 4;
+        app.tts.elapsed = Duration::from_secs(3);
+        app.finish_chapter_preamble();
+        assert_eq!(app.tts.preamble, None);
+        assert_eq!(app.tts.total_sources, 3);
+        assert_eq!(app.tts.elapsed, Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,131 @@
+use super::super::super::state::{App, AudioExportJob};
+use super::super::Effect;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use tracing::{info, warn};
+
+impl App {
+    pub(in crate::app::update) fn handle_export_audio(&mut self, effects: &mut Vec<Effect>) {
+        if self.tts.audio_export.is_some() {
+            return;
+        }
+        if self.tts.engine.is_none() {
+            self.tts.audio_export_status = Some("TTS engine unavailable".to_string());
+            return;
+        }
+        // Same constraint as chapter caching: playback preparation would cancel our batches.
+        if self.tts.is_preparing() || self.tts.pending_append {
+            self.tts.audio_export_status =
+                Some("Playback is preparing audio; try again shortly".to_string());
+            return;
+        }
+        let page_total = self.reader.pages.len();
+        if page_total == 0 {
+            return;
+        }
+
+        self.tts.audio_export_request_id = self.tts.audio_export_request_id.wrapping_add(1);
+        let request_id = self.tts.audio_export_request_id;
+        let output = crate::cache::audio_export_path(&self.epub_path);
+        info!(pages = page_total, output = %output.display(), "Exporting book audio");
+        self.tts.audio_export = Some(AudioExportJob {
+            request_id,
+            output,
+            next_page: 1,
+            page_total,
+            files: Vec::new(),
+            cancel: Default::default(),
+        });
+        self.tts.audio_export_status = Some(format!("Exporting audio: page 1/{page_total}"));
+        effects.push(Effect::ExportAudioPage {
+            page: 0,
+            request_id,
+        });
+    }
+
+    pub(in crate::app::update) fn handle_cancel_audio_export(&mut self) {
+        let Some(job) = self.tts.audio_export.take() else {
+            return;
+        };
+        // Stops the page or write in flight, which then deletes its partial file,
+        // and bumping the id makes it report back as stale.
+        job.cancel.store(true, Ordering::Release);
+        self.tts.audio_export_request_id = self.tts.audio_export_request_id.wrapping_add(1);
+        info!("Audio export cancelled");
+        self.tts.audio_export_status = Some("Audio export cancelled".to_string());
+    }
+
+    pub(in crate::app::update) fn handle_audio_export_page_ready(
+        &mut self,
+        request_id: u64,
+        page: usize,
+        result: Result<Vec<PathBuf>, String>,
+        effects: &mut Vec<Effect>,
+    ) {
+        let Some(job) = self.tts.audio_export.as_mut() else {
+            return;
+        };
+        if job.request_id != request_id {
+            return;
+        }
+
+        match result {
+            Ok(files) => job.files.extend(files),
+            Err(err) => {
+                warn!(page = page + 1, %err, "Audio export stopped");
+                self.tts.audio_export_status = Some(format!("Audio export stopped: {err}"));
+                self.tts.audio_export = None;
+                return;
+            }
+        }
+
+        if job.next_page < job.page_total {
+            let next = job.next_page;
+            job.next_page += 1;
+            self.tts.audio_export_status = Some(format!(
+                "Exporting audio: page {}/{}",
+                next + 1,
+                job.page_total
+            ));
+            effects.push(Effect::ExportAudioPage {
+                page: next,
+                request_id,
+            });
+            return;
+        }
+
+        self.tts.audio_export_status = Some(format!(
+            "Exporting audio: writing {} sentences",
+            job.files.len()
+        ));
+        effects.push(Effect::ExportAudio {
+            output: job.output.clone(),
+        });
+    }
+
+    pub(in crate::app::update) fn handle_audio_export_finished(
+        &mut self,
+        request_id: u64,
+        result: Result<PathBuf, String>,
+    ) {
+        if self
+            .tts
+            .audio_export
+            .as_ref()
+            .is_none_or(|job| job.request_id != request_id)
+        {
+            return;
+        }
+        self.tts.audio_export = None;
+        self.tts.audio_export_status = Some(match result {
+            Ok(output) => {
+                info!(output = %output.display(), "Finished exporting book audio");
+                format!("Audio exported to {}", output.display())
+            }
+            Err(err) => {
+                warn!(%err, "Audio export failed");
+                format!("Audio export failed: {err}")
+            }
+        });
+    }
+}
@@ -0,0 +1,59 @@
+use super::super::super::state::App;
+use super::super::Effect;
+use crate::config::BookEndAction;
+use iced::widget::scrollable::RelativeOffset;
+use tracing::info;
+
+impl App {
+    /// Apply `on_book_end` once playback has run past the last page.
+    pub(in crate::app::update) fn finish_book_playback(&mut self, effects: &mut Vec<Effect>) {
+        match self.config.on_book_end {
+            BookEndAction::Stop => info!("Playback finished at end of book"),
+            BookEndAction::RestartFromStart => {
+                info!("Playback finished at end of book; restarting from the first page");
+                self.reader.current_page = 0;
+                self.bookmark.last_scroll_offset = RelativeOffset::START;
+                effects.push(Effect::StartTts {
+                    page: 0,
+                    sentence_idx: 0,
+                });
+                effects.push(Effect::AutoScrollToCurrent);
+                effects.push(Effect::SaveBookmark);
+            }
+            BookEndAction::CloseApp => {
+                info!("Playback finished at end of book; closing");
+                effects.push(Effect::QuitSafely);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::test_app;
+
+    #[test]
+    fn book_end_follows_the_configured_action() {
+        let mut app = test_app("Only sentence.");
+
+        let mut effects = Vec::new();
+        app.finish_book_playback(&mut effects);
+        assert!(effects.is_empty());
+
+        app.config.on_book_end = BookEndAction::RestartFromStart;
+        app.finish_book_playback(&mut effects);
+        assert!(matches!(
+            effects.first(),
+            Some(Effect::StartTts {
+                page: 0,
+                sentence_idx: 0
+            })
+        ));
+
+        effects.clear();
+        app.config.on_book_end = BookEndAction::CloseApp;
+        app.finish_book_playback(&mut effects);
+        assert!(matches!(effects.as_slice(), [Effect::QuitSafely]));
+    }
+}
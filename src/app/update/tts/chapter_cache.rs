@@ -0,0 +1,341 @@
+use super::super::super::messages::CachedPageAudio;
+use super::super::super::state::{App, ChapterAudioJob};
+use super::super::Effect;
+use crate::tts::PREPARATION_CANCELLED;
+use std::ops::Range;
+use std::sync::atomic::Ordering;
+use tracing::{info, warn};
+
+impl App {
+    pub(in crate::app::update) fn handle_cache_chapter_audio(
+        &mut self,
+        chapter_idx: usize,
+        effects: &mut Vec<Effect>,
+    ) {
+        if !self.can_start_audio_cache() {
+            return;
+        }
+        let Some(pages) = self.chapter_page_range(chapter_idx) else {
+            warn!(chapter_idx, "Requested audio cache for unknown chapter");
+            return;
+        };
+        self.begin_audio_cache(Some(chapter_idx), pages, effects);
+    }
+
+    /// Synthesize every page of the book into the cache for offline listening.
+    pub(in crate::app::update) fn handle_cache_book_audio(&mut self, effects: &mut Vec<Effect>) {
+        if !self.can_start_audio_cache() || self.reader.pages.is_empty() {
+            return;
+        }
+        self.begin_audio_cache(None, 0..self.reader.pages.len(), effects);
+    }
+
+    pub(in crate::app::update) fn handle_cancel_audio_cache(&mut self) {
+        let Some(job) = self.tts.chapter_cache.take() else {
+            return;
+        };
+        // Stops the page in flight, and bumping the id makes it report back as stale.
+        job.cancel.store(true, Ordering::Release);
+        self.tts.chapter_cache_request_id = self.tts.chapter_cache_request_id.wrapping_add(1);
+        info!(
+            pages_done = job.pages_done,
+            page_total = job.page_total,
+            "Audio caching cancelled"
+        );
+        self.tts.chapter_cache_status = Some(format!(
+            "Caching {} cancelled after {}/{} pages",
+            job.label(),
+            job.pages_done,
+            job.page_total
+        ));
+    }
+
+    fn can_start_audio_cache(&mut self) -> bool {
+        if self.tts.chapter_cache.is_some() {
+            return false;
+        }
+        if self.tts.engine.is_none() {
+            self.tts.chapter_cache_status = Some("TTS engine unavailable".to_string());
+            return false;
+        }
+        // Live playback owns the worker pool; starting it also cancels any batch in flight,
+        // so only begin caching while nothing is being prepared for playback.
+        if self.tts.is_preparing() || self.tts.pending_append {
+            self.tts.chapter_cache_status =
+                Some("Playback is preparing audio; try again shortly".to_string());
+            return false;
+        }
+        true
+    }
+
+    fn begin_audio_cache(
+        &mut self,
+        chapter_idx: Option<usize>,
+        pages: Range<usize>,
+        effects: &mut Vec<Effect>,
+    ) {
+        // Kept separate from the playback request id so caching never invalidates playback.
+        self.tts.chapter_cache_request_id = self.tts.chapter_cache_request_id.wrapping_add(1);
+        let request_id = self.tts.chapter_cache_request_id;
+        let job = ChapterAudioJob {
+            chapter_idx,
+            request_id,
+            next_page: pages.start + 1,
+            end_page: pages.end,
+            pages_done: 0,
+            page_total: pages.len(),
+            synthesized: 0,
+            reused: 0,
+            cancel: Default::default(),
+            clips: Vec::new(),
+            bytes: 0,
+            deferred_page: None,
+        };
+        info!(
+            job = %job.label(),
+            first_page = pages.start + 1,
+            last_page = pages.end,
+            "Caching audio"
+        );
+        self.tts.chapter_cache_status = Some(format!("Caching {}...", job.label()));
+        self.tts.chapter_cache = Some(job);
+        effects.push(Effect::CacheChapterAudioPage {
+            page: pages.start,
+            request_id,
+        });
+    }
+
+    pub(in crate::app::update) fn handle_chapter_audio_page_cached(
+        &mut self,
+        request_id: u64,
+        page: usize,
+        result: Result<CachedPageAudio, String>,
+        effects: &mut Vec<Effect>,
+    ) {
+        let max_bytes = self.tts_cache_max_bytes();
+        let Some(job) = self.tts.chapter_cache.as_mut() else {
+            return;
+        };
+        if job.request_id != request_id {
+            return;
+        }
+
+        let cached = match result {
+            Ok(cached) => cached,
+            // Starting playback cancels every batch in flight; go again once its
+            // own batch is ready so the listener never waits on the cache job.
+            Err(err) if err == PREPARATION_CANCELLED => {
+                info!(
+                    page = page + 1,
+                    "Audio caching interrupted by playback; retrying page after it"
+                );
+                job.deferred_page = Some(page);
+                self.resume_deferred_audio_cache(effects);
+                return;
+            }
+            Err(err) => {
+                warn!(page = page + 1, %err, "Audio caching stopped");
+                self.tts.chapter_cache_status =
+                    Some(format!("Caching {} stopped: {err}", job.label()));
+                self.tts.chapter_cache = None;
+                return;
+            }
+        };
+        job.synthesized += cached.files.len().saturating_sub(cached.reused);
+        job.reused += cached.reused;
+        job.pages_done += 1;
+        job.bytes += cached.bytes;
+        job.clips.extend(cached.files);
+
+        // The job's clips are spared by eviction, so past the limit it would
+        // only push out other audio and still not fit.
+        if let Some(max_bytes) = max_bytes.filter(|max| job.bytes > *max) {
+            warn!(
+                job = %job.label(),
+                bytes = job.bytes,
+                max_bytes,
+                "Audio caching stopped at the cache size limit"
+            );
+            self.tts.chapter_cache_status = Some(format!(
+                "Stopped caching {} after {}/{} pages: it needs more than the {} MB audio cache limit (tts_cache_max_mb)",
+                job.label(),
+                job.pages_done,
+                job.page_total,
+                max_bytes / (1024 * 1024)
+            ));
+            self.tts.chapter_cache = None;
+            return;
+        }
+
+        if job.next_page < job.end_page {
+            let next = job.next_page;
+            job.next_page += 1;
+            self.tts.chapter_cache_status = Some(format!(
+                "Caching {}: page {}/{}",
+                job.label(),
+                job.pages_done + 1,
+                job.page_total
+            ));
+            effects.push(Effect::CacheChapterAudioPage {
+                page: next,
+                request_id,
+            });
+            return;
+        }
+
+        info!(
+            job = %job.label(),
+            synthesized = job.synthesized,
+            reused = job.reused,
+            "Finished caching audio"
+        );
+        self.tts.chapter_cache_status = Some(format!(
+            "Cached {}: {} new, {} reused",
+            job.label(),
+            job.synthesized,
+            job.reused
+        ));
+        self.tts.chapter_cache = None;
+    }
+
+    /// Send a page interrupted by playback back for caching once playback's own
+    /// audio is prepared.
+    pub(in crate::app::update) fn resume_deferred_audio_cache(
+        &mut self,
+        effects: &mut Vec<Effect>,
+    ) {
+        if self.tts.is_preparing() || self.tts.pending_append {
+            return;
+        }
+        let Some(job) = self.tts.chapter_cache.as_mut() else {
+            return;
+        };
+        let Some(page) = job.deferred_page.take() else {
+            return;
+        };
+        info!(page = page + 1, "Resuming audio caching");
+        effects.push(Effect::CacheChapterAudioPage {
+            page,
+            request_id: job.request_id,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::super::state::TtsLifecycle;
+    use super::*;
+    use crate::app::state::{test_app, test_app_with};
+    use crate::config::AppConfig;
+    use crate::epub_loader::LoadedBook;
+    use std::path::PathBuf;
+
+    fn cached_page(effects: &[Effect]) -> Option<(usize, u64)> {
+        effects.iter().find_map(|effect| match effect {
+            Effect::CacheChapterAudioPage { page, request_id } => Some((*page, *request_id)),
+            _ => None,
+        })
+    }
+
+    fn page_audio(clips: usize, reused: usize, bytes: u64) -> CachedPageAudio {
+        CachedPageAudio {
+            files: (0..clips)
+                .map(|idx| PathBuf::from(format!("clip-{idx}.wav")))
+                .collect(),
+            reused,
+            bytes,
+        }
+    }
+
+    #[test]
+    fn book_caching_walks_every_page_and_ignores_cancelled_results() {
+        let mut app = test_app("One sentence. Another sentence.");
+
+        let mut effects = Vec::new();
+        app.begin_audio_cache(None, 0..3, &mut effects);
+        let (page, request_id) = cached_page(&effects).expect("first page requested");
+        assert_eq!(page, 0);
+
+        // Playback cancelled the page; it waits until playback's batch is ready.
+        app.tts.lifecycle = TtsLifecycle::Preparing {
+            page: 0,
+            sentence_idx: 0,
+            request_id: 7,
+        };
+        effects.clear();
+        app.handle_chapter_audio_page_cached(
+            request_id,
+            0,
+            Err(PREPARATION_CANCELLED.to_string()),
+            &mut effects,
+        );
+        assert!(effects.is_empty());
+        app.tts.lifecycle = TtsLifecycle::Playing;
+        app.resume_deferred_audio_cache(&mut effects);
+        assert_eq!(cached_page(&effects), Some((0, request_id)));
+
+        effects.clear();
+        app.handle_chapter_audio_page_cached(
+            request_id,
+            0,
+            Ok(page_audio(3, 1, 300)),
+            &mut effects,
+        );
+        assert_eq!(cached_page(&effects), Some((1, request_id)));
+        assert_eq!(
+            app.tts.chapter_cache_status.as_deref(),
+            Some("Caching the book: page 2/3")
+        );
+        let job = app.tts.chapter_cache.as_ref().expect("job running");
+        assert_eq!((job.synthesized, job.reused, job.clips.len()), (2, 1, 3));
+        let cancel = job.cancel.clone();
+
+        app.handle_cancel_audio_cache();
+        assert!(app.tts.chapter_cache.is_none());
+        assert!(cancel.load(Ordering::Acquire));
+        effects.clear();
+        app.handle_chapter_audio_page_cached(
+            request_id,
+            1,
+            Ok(page_audio(1, 0, 100)),
+            &mut effects,
+        );
+        assert!(effects.is_empty());
+        assert_eq!(
+            app.tts.chapter_cache_status.as_deref(),
+            Some("Caching the book cancelled after 1/3 pages")
+        );
+    }
+
+    #[test]
+    fn caching_stops_when_the_job_outgrows_the_cache_limit() {
+        let config = AppConfig {
+            tts_cache_max_mb: 1,
+            ..AppConfig::default()
+        };
+        let book = LoadedBook {
+            text: "One sentence. Another sentence.".to_string(),
+            ..Default::default()
+        };
+        let mut app = test_app_with(book, config);
+
+        let mut effects = Vec::new();
+        app.begin_audio_cache(None, 0..3, &mut effects);
+        let (_, request_id) = cached_page(&effects).expect("first page requested");
+        effects.clear();
+        app.handle_chapter_audio_page_cached(
+            request_id,
+            0,
+            Ok(page_audio(4, 0, 2 * 1024 * 1024)),
+            &mut effects,
+        );
+        assert!(effects.is_empty());
+        assert!(app.tts.chapter_cache.is_none());
+        assert_eq!(
+            app.tts.chapter_cache_status.as_deref(),
+            Some(
+                "Stopped caching the book after 1/3 pages: it needs more than the 1 MB audio cache limit (tts_cache_max_mb)"
+            )
+        );
+    }
+}
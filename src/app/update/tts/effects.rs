@@ -3,6 +3,7 @@ use super::super::super::state::App;
 use super::super::Effect;
 use super::transitions::TtsAction;
 use iced::Task;
+use std::time::Instant;
 
 pub(super) fn append_effects_from_actions(actions: Vec<TtsAction>, effects: &mut Vec<Effect>) {
     for action in actions {
@@ -38,12 +39,14 @@ pub(super) fn tasks_from_actions(app: &App, actions: Vec<TtsAction>) -> Task<Mes
             let epub_path = app.epub_path.clone();
             tasks.push(Task::perform(
                 async move {
+                    let started = Instant::now();
                     let plan = normalizer.plan_page_cached(&epub_path, page, &display_sentences);
                     Message::TtsPlanReady {
                         page,
                         requested_display_idx,
                         request_id,
                         plan,
+                        elapsed: started.elapsed(),
                     }
                 },
                 |msg| msg,
@@ -0,0 +1,123 @@
+use super::super::super::state::{App, SYSTEM_SLEEP_GAP};
+use super::super::Effect;
+use std::time::SystemTime;
+use tracing::info;
+
+impl App {
+    /// Pause on focus loss when configured, and resume an automatic pause once focus returns.
+    pub(in crate::app::update) fn pause_tts_for_focus_change(
+        &mut self,
+        focused: bool,
+        effects: &mut Vec<Effect>,
+    ) {
+        if focused {
+            if self.tts.auto_paused && self.config.resume_on_focus {
+                info!("Window focused; resuming automatically paused TTS");
+                self.handle_play(effects);
+            }
+        } else if self.config.pause_on_focus_loss && self.tts_is_active() {
+            info!("Window lost focus; pausing TTS");
+            self.auto_pause_tts(effects);
+        }
+    }
+
+    /// Pause after the machine wakes from sleep, which shows up as a wall-clock jump
+    /// between polls.
+    pub(in crate::app::update) fn pause_tts_after_system_sleep(
+        &mut self,
+        effects: &mut Vec<Effect>,
+    ) {
+        let now = SystemTime::now();
+        let slept = self
+            .tts
+            .last_poll_wall_time
+            .replace(now)
+            .and_then(|last| now.duration_since(last).ok())
+            .is_some_and(|gap| gap >= SYSTEM_SLEEP_GAP);
+        if slept && self.tts_is_active() {
+            info!("System woke from sleep; pausing TTS");
+            self.auto_pause_tts(effects);
+        }
+    }
+
+    pub(super) fn tts_is_active(&self) -> bool {
+        self.tts.is_preparing()
+            || self
+                .tts
+                .playback
+                .as_ref()
+                .is_some_and(|playback| !playback.is_paused())
+    }
+
+    fn auto_pause_tts(&mut self, effects: &mut Vec<Effect>) {
+        self.handle_pause(effects);
+        self.tts.auto_paused = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::{TtsLifecycle, test_app_with};
+    use crate::config::AppConfig;
+    use crate::epub_loader::LoadedBook;
+    use std::time::Duration;
+
+    fn app_preparing_tts() -> App {
+        let book = LoadedBook {
+            text: "One sentence here. Another sentence there.".to_string(),
+            ..Default::default()
+        };
+        let mut config = AppConfig::default();
+        config.pause_on_focus_loss = true;
+        config.resume_on_focus = true;
+        let mut app = test_app_with(book, config);
+        app.tts.lifecycle = TtsLifecycle::Preparing {
+            page: 0,
+            sentence_idx: 1,
+            request_id: app.tts.request_id,
+        };
+        app.tts.current_sentence_idx = Some(1);
+        app
+    }
+
+    #[test]
+    fn focus_loss_pauses_and_focus_gain_resumes_from_the_same_sentence() {
+        let mut app = app_preparing_tts();
+        let mut effects = Vec::new();
+
+        app.pause_tts_for_focus_change(false, &mut effects);
+        assert!(app.tts.auto_paused);
+        assert!(!app.tts.is_preparing());
+
+        app.pause_tts_for_focus_change(true, &mut effects);
+        assert!(!app.tts.auto_paused);
+        assert!(effects.iter().any(|effect| matches!(
+            effect,
+            Effect::StartTts {
+                page: 0,
+                sentence_idx: 1
+            }
+        )));
+    }
+
+    #[test]
+    fn wall_clock_jump_between_polls_pauses_tts() {
+        let mut app = app_preparing_tts();
+        let mut effects = Vec::new();
+
+        app.pause_tts_after_system_sleep(&mut effects);
+        assert!(app.tts.is_preparing());
+
+        app.tts.last_poll_wall_time = Some(SystemTime::now() - SYSTEM_SLEEP_GAP * 2);
+        app.pause_tts_after_system_sleep(&mut effects);
+        assert!(app.tts.auto_paused);
+        assert!(!app.tts.is_preparing());
+        assert!(
+            app.tts
+                .last_poll_wall_time
+                .and_then(|time| SystemTime::now().duration_since(time).ok())
+                .is_some_and(|since| since < Duration::from_secs(1))
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use super::super::super::state::App;
+use super::super::Effect;
+use std::path::PathBuf;
+use tracing::{debug, info};
+
+impl App {
+    /// Warm the audio cache for the next page once the current page is fully queued,
+    /// so advancing at the end of the page doesn't wait on synthesis.
+    pub(super) fn maybe_prefetch_next_page(&mut self, effects: &mut Vec<Effect>) {
+        // Prefetching shares the worker pool, so stay out of the way of live preparation
+        // and of chapter cache or export jobs that are already walking ahead.
+        if self.tts.pending_append
+            || self.tts.chapter_cache.is_some()
+            || self.tts.audio_export.is_some()
+        {
+            return;
+        }
+        let next_page = self.reader.current_page + 1;
+        if next_page >= self.reader.pages.len() || self.tts.prefetched_page == Some(next_page) {
+            return;
+        }
+        self.tts.prefetched_page = Some(next_page);
+        debug!(page = next_page + 1, "Prefetching TTS audio for next page");
+        effects.push(Effect::PrefetchTtsPage {
+            page: next_page,
+            request_id: self.tts.request_id,
+        });
+    }
+
+    pub(in crate::app::update) fn handle_tts_page_prefetched(
+        &mut self,
+        request_id: u64,
+        page: usize,
+        result: Result<(Vec<PathBuf>, usize), String>,
+    ) {
+        let (files, reused) = match result {
+            Ok(prepared) => prepared,
+            Err(err) => {
+                // Usually cancelled by a playback restart; allow another attempt later.
+                if self.tts.prefetched_page == Some(page) {
+                    self.tts.prefetched_page = None;
+                }
+                debug!(page = page + 1, %err, "TTS prefetch did not finish");
+                return;
+            }
+        };
+        if request_id != self.tts.request_id {
+            debug!(
+                request_id,
+                current = self.tts.request_id,
+                "Ignoring stale TTS prefetch"
+            );
+            return;
+        }
+        info!(
+            page = page + 1,
+            synthesized = files.len().saturating_sub(reused),
+            reused,
+            "Prefetched TTS audio for next page"
+        );
+        if self.tts.prefetched_page == Some(page) {
+            self.tts.prefetched_clips = files;
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use super::super::super::state::{App, SentenceSelection};
+use super::super::Effect;
+use tracing::info;
+
+impl App {
+    /// Start a selection at `idx`, or extend the current page's selection to it.
+    /// Marking a lone selected sentence again clears the selection.
+    pub(in crate::app::update) fn handle_select_sentence(&mut self, idx: usize) {
+        let page = self.reader.current_page;
+        self.sentence_selection = match self.sentence_selection {
+            Some(selection) if selection.page == page && selection.bounds() == (idx, idx) => None,
+            Some(selection) if selection.page == page => Some(SentenceSelection {
+                focus: idx,
+                ..selection
+            }),
+            _ => Some(SentenceSelection {
+                page,
+                anchor: idx,
+                focus: idx,
+            }),
+        };
+    }
+
+    pub(in crate::app::update) fn handle_play_selection(
+        &mut self,
+        start: usize,
+        end: usize,
+        effects: &mut Vec<Effect>,
+    ) {
+        let page = self.reader.current_page;
+        let sentence_count = self.sentence_count_for_page(page);
+        if sentence_count == 0 {
+            return;
+        }
+        let last = sentence_count - 1;
+        let (start, end) = (start.min(end).min(last), start.max(end).min(last));
+        self.tts.set_current_sentence_clamped(start, sentence_count);
+        self.tts.sentence_offset = start;
+        self.tts.resume_after_prepare = true;
+        info!(start, end, "Playing selected sentences");
+        effects.push(Effect::StartTtsSelection { page, start, end });
+        effects.push(Effect::AutoScrollToCurrent);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::test_app;
+
+    fn app_with_sentences() -> App {
+        test_app("First one. Second one. Third one. Fourth one.")
+    }
+
+    #[test]
+    fn marking_sentences_extends_and_clears_the_selection() {
+        let mut app = app_with_sentences();
+        app.handle_select_sentence(2);
+        app.handle_select_sentence(0);
+        assert_eq!(app.sentence_selection.map(|s| s.bounds()), Some((0, 2)));
+
+        app.handle_select_sentence(1);
+        assert_eq!(app.sentence_selection.map(|s| s.bounds()), Some((1, 2)));
+
+        app.handle_select_sentence(2);
+        app.handle_select_sentence(2);
+        assert_eq!(app.sentence_selection, None);
+    }
+
+    #[test]
+    fn play_selection_orders_and_clamps_the_range() {
+        let mut app = app_with_sentences();
+        let last = app.sentence_count_for_page(0) - 1;
+        let mut effects = Vec::new();
+        app.handle_play_selection(99, 1, &mut effects);
+
+        assert_eq!(app.tts.current_sentence_idx, Some(1));
+        assert!(effects.iter().any(|effect| matches!(
+            effect,
+            Effect::StartTtsSelection { page: 0, start: 1, end }
+                if *end == last
+        )));
+    }
+}
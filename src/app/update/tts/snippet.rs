@@ -0,0 +1,174 @@
+use super::super::super::state::{App, SentencePauses, SnippetPlayback};
+use super::super::Effect;
+use std::path::PathBuf;
+use tracing::{debug, info, warn};
+
+impl App {
+    /// Speak `text` through the normalizer and engine on its own sink, pausing book
+    /// playback meanwhile so its position is left exactly where it was.
+    pub(in crate::app::update) fn handle_speak_snippet(
+        &mut self,
+        text: String,
+        effects: &mut Vec<Effect>,
+    ) {
+        if self.tts.engine.is_none() {
+            warn!("TTS engine unavailable; cannot speak snippet");
+            return;
+        }
+        let sentences = self.normalizer.plan_page(&[text]).audio_sentences;
+        if sentences.is_empty() {
+            debug!("Snippet has nothing to speak");
+            return;
+        }
+        // A replaced snippet may already be holding book playback paused.
+        let mut resume_book = self
+            .tts
+            .snippet
+            .as_ref()
+            .is_some_and(|snippet| snippet.resume_book);
+        self.stop_snippet();
+        if self.tts_is_active() {
+            self.handle_pause(effects);
+            resume_book = true;
+        }
+        self.tts.snippet_request_id = self.tts.snippet_request_id.wrapping_add(1);
+        let request_id = self.tts.snippet_request_id;
+        info!(
+            request_id,
+            sentences = sentences.len(),
+            resume_book,
+            "Speaking snippet"
+        );
+        self.tts.snippet = Some(SnippetPlayback {
+            request_id,
+            playback: None,
+            files: Vec::new(),
+            resume_book,
+        });
+        effects.push(Effect::SynthesizeSnippet {
+            request_id,
+            sentences,
+        });
+    }
+
+    pub(in crate::app::update) fn handle_snippet_synthesized(
+        &mut self,
+        request_id: u64,
+        result: Result<Vec<PathBuf>, String>,
+        effects: &mut Vec<Effect>,
+    ) {
+        let current =
+            self.tts.snippet.as_ref().is_some_and(|snippet| {
+                snippet.playback.is_none() && snippet.request_id == request_id
+            });
+        if !current {
+            debug!(request_id, "Discarding stale snippet audio");
+            if let Ok(files) = result {
+                crate::tts::remove_files(&files);
+            }
+            return;
+        }
+        let files = match result {
+            Ok(files) => files,
+            Err(err) => {
+                warn!("Failed to synthesize snippet: {err}");
+                self.finish_snippet(effects);
+                return;
+            }
+        };
+        let pauses = vec![SentencePauses::from_config(&self.config).sentence; files.len()];
+        let speeds = vec![self.current_tts_speed(); files.len()];
+        let played = self.tts.engine.as_ref().map(|engine| {
+            engine.play_files(&files, &pauses, &speeds, self.config.tts_volume, false)
+        });
+        let Some(snippet) = self.tts.snippet.as_mut() else {
+            return;
+        };
+        snippet.files = files;
+        match played {
+            Some(Ok(playback)) => snippet.playback = Some(playback),
+            Some(Err(err)) => {
+                warn!("Failed to play snippet: {err}");
+                self.finish_snippet(effects);
+            }
+            None => self.finish_snippet(effects),
+        }
+    }
+
+    /// Called on every tick; wraps up the snippet once its sink runs dry.
+    pub(in crate::app::update) fn poll_snippet(&mut self, effects: &mut Vec<Effect>) {
+        let finished = self
+            .tts
+            .snippet
+            .as_ref()
+            .and_then(|snippet| snippet.playback.as_ref())
+            .is_some_and(|playback| playback.queued_sources() == 0);
+        if finished {
+            debug!("Snippet playback finished");
+            self.finish_snippet(effects);
+        }
+    }
+
+    /// Clean up the snippet and hand the speakers back to the book.
+    fn finish_snippet(&mut self, effects: &mut Vec<Effect>) {
+        let resume_book = self
+            .tts
+            .snippet
+            .as_ref()
+            .is_some_and(|snippet| snippet.resume_book);
+        self.stop_snippet();
+        if resume_book {
+            self.handle_play(effects);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::{TtsLifecycle, test_app};
+
+    fn app_with_snippet(resume_book: bool) -> App {
+        let mut app = test_app("One sentence here. Another sentence there.");
+        app.tts.snippet_request_id = 3;
+        app.tts.snippet = Some(SnippetPlayback {
+            request_id: 3,
+            playback: None,
+            files: Vec::new(),
+            resume_book,
+        });
+        app.tts.current_sentence_idx = Some(1);
+        app
+    }
+
+    #[test]
+    fn failed_snippet_resumes_the_book_from_its_sentence() {
+        let mut app = app_with_snippet(true);
+        let mut effects = Vec::new();
+
+        app.handle_snippet_synthesized(3, Err("no voice".to_string()), &mut effects);
+        assert!(app.tts.snippet.is_none());
+        assert_eq!(app.tts.lifecycle, TtsLifecycle::Idle);
+        assert!(effects.iter().any(|effect| matches!(
+            effect,
+            Effect::StartTts {
+                page: 0,
+                sentence_idx: 1
+            }
+        )));
+    }
+
+    #[test]
+    fn stale_snippet_audio_is_deleted() {
+        let mut app = app_with_snippet(false);
+        let stale =
+            std::env::temp_dir().join(format!("ebup-snippet-stale-{}.wav", std::process::id()));
+        std::fs::write(&stale, b"").unwrap();
+        let mut effects = Vec::new();
+
+        app.handle_snippet_synthesized(2, Ok(vec![stale.clone()]), &mut effects);
+        assert!(!stale.exists());
+        assert!(app.tts.snippet.is_some());
+        assert!(effects.is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use super::super::super::state::{App, TtsLifecycle};
+use super::super::super::state::{App, SentencePauses, TtsLifecycle};
 use crate::normalizer::PageNormalization;
 use tracing::{debug, info, warn};
 
@@ -54,7 +54,7 @@ fn on_start_requested(app: &mut App, page: usize, sentence_idx: usize) -> Vec<Tt
     app.stop_playback();
     app.tts.clear_transient_playback_state();
 
-    let display_sentences = app.raw_sentences_for_page(page);
+    let display_sentences = app.speech_sentences_for_page(page);
     if display_sentences.is_empty() {
         app.tts.lifecycle = TtsLifecycle::Idle;
         app.tts.pending_append = false;
@@ -63,6 +63,7 @@ fn on_start_requested(app: &mut App, page: usize, sentence_idx: usize) -> Vec<Tt
         app.tts.sentence_offset = 0;
         app.tts.display_to_audio.clear();
         app.tts.audio_to_display.clear();
+        app.tts.audio_pauses.clear();
         return Vec::new();
     }
 
@@ -154,6 +155,15 @@ fn on_plan_ready(
         plan.audio_to_display,
         full_audio_sentences.len(),
     );
+    app.tts.audio_pauses = SentencePauses::from_config(&app.config).plan(
+        app.reader.pages.get(page).map_or("", String::as_str),
+        app.reader
+            .page_sentences
+            .get(page)
+            .map(Vec::as_slice)
+            .unwrap_or(&[]),
+        &app.tts.audio_to_display,
+    );
 
     let Some(mut audio_start_idx) =
         app.find_audio_start_for_display_sentence(requested_display_idx)
@@ -178,10 +188,132 @@ fn on_plan_ready(
     app.tts.sentence_offset = audio_start_idx;
     app.tts.current_sentence_idx = Some(display_start_idx);
 
+    let mut audio_sentences = full_audio_sentences;
+    if let Some(end) = app.tts.selection_end {
+        // Only synthesize through the selection's last sentence.
+        let selection_len = app
+            .tts
+            .audio_to_display
+            .iter()
+            .rposition(|&display| display <= end)
+            .map_or(0, |idx| idx + 1);
+        audio_sentences.truncate(selection_len.max(audio_start_idx + 1));
+    }
+
     vec![TtsAction::DispatchPrepareBatches {
         page,
         request_id,
         audio_start_idx,
-        audio_sentences: full_audio_sentences,
+        audio_sentences,
     }]
 }
+
+/// Whether a prepared batch still lines up with `page`'s audio mapping.
+///
+/// Repeated restarts can leave a batch planned against an older normalization
+/// of the page: its start past the page's audio, more clips than sentences
+/// remain, or clips mapped past the page's last sentence. Such a batch cannot
+/// be trimmed into place because its first clip is already the wrong one.
+pub(super) fn prepared_batch_fits_page(
+    app: &mut App,
+    page: usize,
+    start_idx: usize,
+    clip_count: usize,
+) -> bool {
+    let display_sentences = app.raw_sentences_for_page(page);
+    if app.tts.last_sentences != display_sentences {
+        debug!(
+            page = page + 1,
+            stale = app.tts.last_sentences.len(),
+            current = display_sentences.len(),
+            "Re-syncing TTS sentence list with the page"
+        );
+        app.tts.last_sentences = display_sentences;
+    }
+
+    let display_count = app.tts.last_sentences.len();
+    let audio_to_display = &app.tts.audio_to_display;
+    let fits = start_idx
+        .checked_add(clip_count)
+        .and_then(|end| audio_to_display.get(start_idx..end))
+        .is_some_and(|mapped| mapped.iter().all(|&display| display < display_count));
+    if !fits {
+        warn!(
+            page = page + 1,
+            start_idx,
+            clip_count,
+            audio_len = audio_to_display.len(),
+            display_count,
+            "Prepared TTS batch no longer matches the page"
+        );
+    }
+    fits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::Effect;
+    use super::*;
+    use crate::app::state::test_app;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    #[test]
+    fn batches_planned_before_a_restart_are_requested_again() {
+        let text = (1..=8)
+            .map(|idx| format!("Sentence number {idx}."))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut app = test_app(text);
+        let sentences = app.speech_sentences_for_page(0);
+        assert_eq!(sentences.len(), 8);
+        let clip = |idx: usize| (PathBuf::from(format!("clip-{idx}.wav")), Duration::ZERO);
+        let plan_ready = |app: &mut App| {
+            let plan = app.normalizer.plan_page(&sentences);
+            transition(
+                app,
+                TtsEvent::PlanReady {
+                    page: 0,
+                    requested_display_idx: 6,
+                    request_id: app.tts.request_id,
+                    plan,
+                },
+            )
+        };
+
+        // The batch for the last two sentences goes out for synthesis...
+        app.tts.request_id = 1;
+        let dispatched = plan_ready(&mut app);
+        assert!(matches!(
+            dispatched.as_slice(),
+            [TtsAction::DispatchPrepareBatches {
+                audio_start_idx: 6,
+                ..
+            }]
+        ));
+
+        // ...while speed changes restart playback and the page is re-planned
+        // into paired sentences under it.
+        for speed in [1.2, 1.4, 1.6] {
+            app.handle_set_tts_speed(speed, &mut Vec::new());
+        }
+        app.normalizer.set_sentence_grouping(2);
+        plan_ready(&mut app);
+        assert_eq!(app.tts.audio_to_display.len(), 4);
+        assert!(!prepared_batch_fits_page(&mut app, 0, 6, 2));
+
+        let mut effects = Vec::new();
+        app.handle_tts_prepared(0, 6, 1, vec![clip(6), clip(7)], None, &mut effects);
+        assert!(app.tts.track.is_empty());
+        assert!(effects.iter().any(|effect| matches!(
+            effect,
+            Effect::StartTts {
+                page: 0,
+                sentence_idx: 6
+            }
+        )));
+
+        // The re-requested batch lines up with the new plan.
+        assert!(prepared_batch_fits_page(&mut app, 0, 3, 1));
+    }
+}
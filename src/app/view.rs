@@ -1,23 +1,35 @@
-use super::messages::{Component, Message, NumericSetting};
+use super::messages::{CacheClearScope, Component, FontChoice, Message, NumericSetting};
 use super::state::{
-    App, IMAGE_BLOCK_SPACING_PX, IMAGE_FOOTER_FONT_SIZE_PX, IMAGE_FOOTER_LINE_HEIGHT,
+    App, CHAPTER_PICKER_TITLE_CHARS, CHAPTER_PICKER_WIDTH_PX, ChapterProgress,
+    IMAGE_BLOCK_SPACING_PX, IMAGE_FOOTER_FONT_SIZE_PX, IMAGE_FOOTER_LINE_HEIGHT,
     IMAGE_LABEL_FONT_SIZE_PX, IMAGE_LABEL_LINE_HEIGHT, IMAGE_PREVIEW_HEIGHT_PX,
-    MAX_HORIZONTAL_MARGIN, MAX_LETTER_SPACING, MAX_TTS_VOLUME, MAX_VERTICAL_MARGIN,
-    MAX_WORD_SPACING, MIN_TTS_SPEED, MIN_TTS_VOLUME, PAGE_FLOW_SPACING_PX,
+    MAX_HORIZONTAL_MARGIN, MAX_LETTER_SPACING, MAX_PARAGRAPH_SPACING, MAX_TTS_VOLUME,
+    MAX_VERTICAL_MARGIN, MAX_WORD_SPACING, MIN_TTS_SPEED, MIN_TTS_VOLUME, PAGE_FLOW_SPACING_PX,
+    PAGE_JUMP_INPUT_WIDTH_PX, SentenceEmphasis, SentenceFootnote, SentenceHeading, SentencePauses,
+    TimedOperation, VOICE_PREVIEW_TEXT, WordDefinition, offset_after_collapse,
+};
+use super::topbar_layout::{
+    ACCESSIBLE_BUTTON_PADDING, ACCESSIBLE_BUTTON_TEXT_SIZE, OVERFLOW_LABEL, OverflowItem,
+    TopBarAction, TopBarLabels, estimate_button_width_px, overflow_items, topbar_plan,
 };
-use super::topbar_layout::{TopBarLabels, estimate_button_width_px, topbar_plan};
 use crate::calibre::CalibreColumn;
-use crate::config::HighlightColor;
+use crate::config::{HighlightColor, HighlightStyle, ThemeMode};
+use crate::crossref::find_crossrefs;
+use crate::dictionary::word_segments;
+use crate::epub_loader::EmphasisStyle;
+use crate::hyphenate::insert_soft_hyphens;
 use crate::pagination::{MAX_FONT_SIZE, MAX_LINES_PER_PAGE, MIN_FONT_SIZE, MIN_LINES_PER_PAGE};
 use iced::alignment::Horizontal;
 use iced::alignment::Vertical;
 use iced::widget::text::{LineHeight, Wrapping};
 use iced::widget::{
-    Column, Row, button, checkbox, column, container, horizontal_space, image, pick_list, row,
-    scrollable, slider, text, text_input,
+    Column, Row, button, checkbox, column, container, horizontal_space, image, mouse_area,
+    pick_list, progress_bar, row, scrollable, slider, stack, text, text_input, tooltip,
+    vertical_space,
 };
-use iced::{Border, Color, ContentFit, Element, Length};
-use std::time::Duration;
+use iced::{Border, Color, ContentFit, Element, Font, Length, mouse};
+use std::ops::Range;
+use std::time::{Duration, Instant};
 
 impl App {
     pub fn view(&self) -> Element<'_, Message> {
@@ -27,172 +39,258 @@ impl App {
 
         let total_pages = self.reader.pages.len().max(1);
 
-        let theme_label = if matches!(self.config.theme, crate::config::ThemeMode::Night) {
-            "Day Mode"
-        } else {
-            "Night Mode"
+        let theme_label = match self.config.theme.next(self.config.custom_colors) {
+            ThemeMode::Day => "Day Mode",
+            ThemeMode::Night => "Night Mode",
+            ThemeMode::Sepia => "Sepia Mode",
+            ThemeMode::Custom { .. } => "Custom Mode",
         };
-        let close_session_button =
-            Self::control_button("Close Book").on_press(Message::CloseReadingSession);
-        let theme_toggle = Self::control_button(theme_label).on_press(Message::ToggleTheme);
-        let settings_toggle = Self::control_button(if self.config.show_settings {
-            "Hide Settings"
-        } else {
-            "Show Settings"
-        })
-        .on_press(Message::ToggleSettings);
-        let stats_toggle = Self::control_button(if self.show_stats {
-            "Hide Stats"
-        } else {
-            "Show Stats"
-        })
-        .on_press(Message::ToggleStats);
-        let search_toggle = Self::control_button(if self.search.visible {
-            "Hide Search"
-        } else {
-            "Search"
-        })
-        .on_press(Message::ToggleSearch);
-        let tts_toggle = Self::control_button(if self.config.show_tts {
-            "Hide TTS"
+        let close_session_button = self
+            .control_button("Close Book")
+            .on_press(Message::CloseReadingSession);
+        let theme_toggle = self
+            .control_button(theme_label)
+            .on_press(Message::ToggleTheme);
+        let settings_toggle = self
+            .control_button(if self.config.show_settings {
+                "Hide Settings"
+            } else {
+                "Show Settings"
+            })
+            .on_press(Message::ToggleSettings);
+        let stats_toggle = self
+            .control_button(if self.show_stats {
+                "Hide Stats"
+            } else {
+                "Show Stats"
+            })
+            .on_press(Message::ToggleStats);
+        let search_toggle = self
+            .control_button(if self.search.visible {
+                "Hide Search"
+            } else {
+                "Search"
+            })
+            .on_press(Message::ToggleSearch);
+        let tts_toggle = self
+            .control_button(if self.config.show_tts {
+                "Hide TTS"
+            } else {
+                "Show TTS"
+            })
+            .on_press(Message::ToggleTtsControls);
+        let text_only_toggle = self
+            .control_button(if self.text_only_mode {
+                "Pretty Text"
+            } else {
+                "Text Only"
+            })
+            .on_press(Message::ToggleTextOnly);
+        let contents_label = if self.show_contents {
+            "Hide Contents"
         } else {
-            "Show TTS"
-        })
-        .on_press(Message::ToggleTtsControls);
-        let text_only_toggle = Self::control_button(if self.text_only_mode {
-            "Pretty Text"
+            "Contents"
+        };
+        let contents_toggle = self
+            .control_button(contents_label)
+            .on_press(Message::ToggleContents);
+        let notes_label = if self.show_notes {
+            "Hide Notes"
         } else {
-            "Text Only"
-        })
-        .on_press(Message::ToggleTextOnly);
+            "Notes"
+        };
+        let notes_toggle = self
+            .control_button(notes_label)
+            .on_press(Message::ToggleNotes);
+        let auto_page_label = self.config.auto_page_seconds.map(|_| {
+            if self.auto_page_paused {
+                "Auto Turn"
+            } else {
+                "Stop Auto Turn"
+            }
+        });
 
         let prev_button = if self.reader.current_page > 0 {
-            Self::control_button("Previous").on_press(Message::PreviousPage)
+            self.control_button("Previous")
+                .on_press(Message::PreviousPage)
         } else {
-            Self::control_button("Previous")
+            self.control_button("Previous")
         };
 
-        let next_button = if self.reader.current_page + 1 < total_pages {
-            Self::control_button("Next").on_press(Message::NextPage)
+        let shown_pages = self.displayed_pages();
+        let next_button = if shown_pages.end < total_pages {
+            self.control_button("Next").on_press(Message::NextPage)
         } else {
-            Self::control_button("Next")
+            self.control_button("Next")
         };
 
-        let visibility = topbar_plan(
-            self.controls_layout_width(),
-            TopBarLabels {
-                theme: theme_label,
-                settings: if self.config.show_settings {
-                    "Hide Settings"
-                } else {
-                    "Show Settings"
-                },
-                stats: if self.show_stats {
-                    "Hide Stats"
-                } else {
-                    "Show Stats"
-                },
-                text_mode: if self.text_only_mode {
-                    "Pretty Text"
-                } else {
-                    "Text Only"
-                },
-                tts: if self.config.show_tts {
-                    "Hide TTS"
-                } else {
-                    "Show TTS"
-                },
-                search: if self.search.visible {
-                    "Hide Search"
-                } else {
-                    "Search"
-                },
+        let mut page_jump = text_input(
+            &format!("{}", self.reader.current_page + 1),
+            &self.page_input,
+        )
+        .on_input(Message::PageInputChanged)
+        .width(Length::Fixed(PAGE_JUMP_INPUT_WIDTH_PX));
+        if let Some(page) = self.page_input_target() {
+            page_jump = page_jump.on_submit(Message::GoToPage(page));
+        }
+
+        let chapter_picker = self.chapter_picker();
+        let chapter_picker_width = if chapter_picker.is_some() {
+            CHAPTER_PICKER_WIDTH_PX + 10.0
+        } else {
+            0.0
+        };
+        let labels = TopBarLabels {
+            theme: theme_label,
+            settings: if self.config.show_settings {
+                "Hide Settings"
+            } else {
+                "Show Settings"
+            },
+            stats: if self.show_stats {
+                "Hide Stats"
+            } else {
+                "Show Stats"
+            },
+            text_mode: if self.text_only_mode {
+                "Pretty Text"
+            } else {
+                "Text Only"
+            },
+            tts: if self.config.show_tts {
+                "Hide TTS"
+            } else {
+                "Show TTS"
+            },
+            search: if self.search.visible {
+                "Hide Search"
+            } else {
+                "Search"
             },
+            contents: contents_label,
+            notes: notes_label,
+            auto_page: auto_page_label,
+        };
+        let visibility = topbar_plan(
+            // The page box and chapter picker sit between the fixed buttons, so
+            // reserve their width up front.
+            self.controls_layout_width() - PAGE_JUMP_INPUT_WIDTH_PX - 10.0 - chapter_picker_width,
+            labels,
+            self.config.compact_controls,
+            self.config.accessibility_mode,
         );
 
+        // Right-to-left books turn pages towards the left, so Next goes first.
+        let (left_page_button, right_page_button) = if self.is_rtl() {
+            (next_button, prev_button)
+        } else {
+            (prev_button, next_button)
+        };
+        let (left_description, right_description) = if self.is_rtl() {
+            ("Go to the next page", "Go to the previous page")
+        } else {
+            ("Go to the previous page", "Go to the next page")
+        };
         let mut controls_row = row![
-            prev_button,
-            next_button,
-            theme_toggle,
-            close_session_button,
-            settings_toggle,
-            stats_toggle
+            self.described(left_page_button, left_description),
+            self.described(right_page_button, right_description),
+            page_jump
         ]
         .spacing(10)
         .align_y(Vertical::Center)
         .width(Length::Fill);
+        if let Some(picker) = chapter_picker {
+            controls_row = controls_row.push(picker);
+        }
+        if visibility.show_essentials {
+            controls_row = controls_row
+                .push(self.described(theme_toggle, "Switch to the next color theme"))
+                .push(self.described(
+                    close_session_button,
+                    "Close this book and return to the library",
+                ))
+                .push(self.described(settings_toggle, "Show or hide the settings panel"))
+                .push(self.described(stats_toggle, "Show or hide reading statistics"));
+        }
         if visibility.show_text_mode {
-            controls_row = controls_row.push(text_only_toggle);
+            controls_row = controls_row.push(self.described(
+                text_only_toggle,
+                "Switch between formatted text and the text that is read aloud",
+            ));
         }
         if visibility.show_tts {
-            controls_row = controls_row.push(tts_toggle);
+            controls_row =
+                controls_row.push(self.described(tts_toggle, "Show or hide the speech controls"));
         }
         if visibility.show_search {
-            controls_row = controls_row.push(search_toggle);
+            controls_row =
+                controls_row.push(self.described(search_toggle, "Search the text of this book"));
+        }
+        if visibility.show_contents {
+            controls_row = controls_row.push(self.described(
+                contents_toggle,
+                "Show or hide the table of contents and bookmarks",
+            ));
+        }
+        if visibility.show_notes {
+            controls_row = controls_row
+                .push(self.described(notes_toggle, "Show or hide your notes for this book"));
+        }
+        if let (true, Some(label)) = (visibility.show_auto_page, auto_page_label) {
+            controls_row = controls_row.push(self.described(
+                self.control_button(label).on_press(Message::ToggleAutoPage),
+                "Start or stop turning pages automatically",
+            ));
+        }
+        if visibility.show_overflow {
+            controls_row = controls_row.push(
+                pick_list(
+                    overflow_items(visibility, labels),
+                    None::<OverflowItem<'_>>,
+                    |item| Self::overflow_message(item.action),
+                )
+                .placeholder(OVERFLOW_LABEL)
+                .width(Length::Fixed(estimate_button_width_px(
+                    OVERFLOW_LABEL,
+                    self.config.accessibility_mode,
+                ))),
+            );
         }
         controls_row = controls_row.push(horizontal_space());
         let controls = container(controls_row)
-            .height(Length::Fixed(42.0))
+            .height(Length::Fixed(self.controls_row_height()))
             .align_y(Vertical::Center)
             .width(Length::Fill);
 
-        let font_controls = row![
-            column![
-                text(format!("Font: {}", self.config.font_size)),
-                slider(
-                    MIN_FONT_SIZE as f32..=MAX_FONT_SIZE as f32,
-                    self.config.font_size as f32,
-                    |value| Message::FontSizeChanged(value.round() as u32),
-                )
-            ]
-            .spacing(4)
-            .width(Length::FillPortion(1)),
-            column![
-                text(format!("Speed: {:.2}x", self.config.tts_speed)),
-                slider(
-                    MIN_TTS_SPEED..=super::state::MAX_TTS_SPEED,
-                    self.config.tts_speed,
-                    Message::SetTtsSpeed,
-                )
-                .step(0.05)
-            ]
-            .spacing(4)
-            .width(Length::FillPortion(1)),
-            column![
-                text(format!("Volume: {:.0}%", self.config.tts_volume * 100.0)),
-                slider(
-                    MIN_TTS_VOLUME..=MAX_TTS_VOLUME,
-                    self.config.tts_volume,
-                    Message::SetTtsVolume,
-                )
-                .step(0.01)
-            ]
-            .spacing(4)
-            .width(Length::FillPortion(1)),
-        ]
-        .spacing(12)
-        .align_y(Vertical::Center)
-        .width(Length::Fill);
-
-        let raw_sentences = self.raw_sentences_for_page(self.reader.current_page);
+        // Mixed-direction runs are still shaped by the text engine; this only
+        // decides which edge lines hug.
+        let text_align = if self.is_rtl() {
+            Horizontal::Right
+        } else {
+            Horizontal::Left
+        };
         let text_view_content: Element<'_, Message> = if self.text_only_mode {
             if let Some(preview) = self.text_only_preview_for_current_page() {
                 let highlight_idx = self.text_only_highlight_audio_idx_for_current_page();
                 let highlight = self.highlight_color();
+                let highlight_style = self.config.highlight_style;
                 let mut spans: Vec<iced::widget::text::Span<'_, Message>> =
                     Vec::with_capacity(preview.audio_sentences.len().saturating_mul(2));
 
                 for (idx, sentence) in preview.audio_sentences.iter().enumerate() {
                     let display_idx = preview.audio_to_display.get(idx).copied().unwrap_or(idx);
                     let mut span: iced::widget::text::Span<'_, Message> =
-                        iced::widget::text::Span::new(sentence.as_str())
-                            .font(self.current_font())
-                            .size(self.config.font_size as f32)
-                            .line_height(LineHeight::Relative(self.config.line_spacing))
-                            .link(Message::SentenceClicked(display_idx));
+                        iced::widget::text::Span::new(insert_soft_hyphens(
+                            sentence,
+                            self.sentence_hyphen_breaks(idx),
+                        ))
+                        .font(self.current_font())
+                        .size(self.config.font_size as f32)
+                        .line_height(LineHeight::Relative(self.config.line_spacing))
+                        .link(Message::SentenceClicked(display_idx));
 
                     if Some(idx) == highlight_idx {
-                        span = span.background(iced::Background::Color(highlight));
+                        span = highlight_span(span, highlight_style, highlight);
                     }
                     spans.push(span);
 
@@ -210,7 +308,7 @@ impl App {
                     iced::widget::text::Rich::with_spans(spans);
                 rich.width(Length::Fill)
                     .wrapping(Wrapping::WordOrGlyph)
-                    .align_x(Horizontal::Left)
+                    .align_x(text_align)
                     .into()
             } else {
                 text("Preparing normalized text preview...")
@@ -218,18 +316,13 @@ impl App {
                     .line_height(LineHeight::Relative(self.config.line_spacing))
                     .width(Length::Fill)
                     .wrapping(Wrapping::WordOrGlyph)
-                    .align_x(Horizontal::Left)
+                    .align_x(text_align)
                     .font(self.current_font())
                     .into()
             }
         } else {
             let fallback_page_content = self.formatted_page_content();
-            let display_sentences =
-                if self.config.word_spacing == 0 && self.config.letter_spacing == 0 {
-                    raw_sentences.clone()
-                } else {
-                    self.display_sentences_for_current_page()
-                };
+            let display_sentences = self.display_sentences_for_current_page();
 
             if display_sentences.is_empty() {
                 text(fallback_page_content)
@@ -237,53 +330,218 @@ impl App {
                     .line_height(LineHeight::Relative(self.config.line_spacing))
                     .width(Length::Fill)
                     .wrapping(Wrapping::WordOrGlyph)
-                    .align_x(Horizontal::Left)
+                    .align_x(text_align)
                     .font(self.current_font())
                     .into()
             } else {
                 let highlight_idx = self
-                    .tts
-                    .current_sentence_idx
+                    .highlighted_sentence_idx()
                     .filter(|idx| *idx < display_sentences.len());
                 let highlight = self.highlight_color();
+                let highlight_style = self.config.highlight_style;
+                let word_highlight = Color {
+                    a: (highlight.a * 2.5).min(1.0),
+                    ..highlight
+                };
+                let active_word_idx = self
+                    .tts
+                    .current_word_idx
+                    .filter(|_| self.config.word_level_highlight);
+                // Emphasis and footnote offsets refer to the raw sentences, so spacing
+                // tweaks disable them except in sentences holding code, shown raw.
+                let raw_sentences =
+                    self.config.word_spacing == 0 && self.config.letter_spacing == 0;
+                let page = self.reader.current_page;
+                let page_emphasis = self.reader.sentence_emphasis.get(page);
+                let page_footnotes = self.reader.sentence_footnotes.get(page);
+                let page_headings = self.reader.sentence_headings.get(page);
+                let page_search = self.search_marks_for_page(page);
+                let search_color = self.search_highlight_color();
 
                 let spans: Vec<iced::widget::text::Span<'_, Message>> = display_sentences
                     .into_iter()
                     .enumerate()
-                    .map(|(idx, sentence)| {
-                        let mut span: iced::widget::text::Span<'_, Message> =
-                            iced::widget::text::Span::new(sentence)
+                    .flat_map(|(idx, sentence)| {
+                        let code = self.shown_preformatted(page, idx);
+                        let markup = if raw_sentences || !code.is_empty() {
+                            SentenceMarkup {
+                                emphasis: page_emphasis
+                                    .and_then(|page| page.get(idx))
+                                    .map(Vec::as_slice)
+                                    .unwrap_or(&[]),
+                                footnotes: page_footnotes
+                                    .and_then(|page| page.get(idx))
+                                    .map(Vec::as_slice)
+                                    .unwrap_or(&[]),
+                                headings: page_headings
+                                    .and_then(|page| page.get(idx))
+                                    .map(Vec::as_slice)
+                                    .unwrap_or(&[]),
+                                search: page_search.get(idx).map(Vec::as_slice).unwrap_or(&[]),
+                                code,
+                            }
+                        } else {
+                            SentenceMarkup::default()
+                        };
+                        let collapsed_runs = self.collapsed_blank_lines(page, idx);
+                        let collapsed;
+                        let markup = if collapsed_runs.is_empty() {
+                            markup
+                        } else {
+                            collapsed = CollapsedMarkup::new(&markup, &collapsed_runs);
+                            collapsed.markup()
+                        };
+                        let active_word = if Some(idx) == highlight_idx {
+                            active_word_idx.and_then(|word| nth_word_range(&sentence, word))
+                        } else {
+                            None
+                        };
+                        let hyphen_breaks = self.sentence_hyphen_breaks(idx);
+                        // A box goes around the whole sentence rather than each
+                        // emphasis run or word, so the sentence is one plain span
+                        // while it is spoken.
+                        if Some(idx) == highlight_idx && highlight_style == HighlightStyle::Box {
+                            let text = if markup.code.is_empty() {
+                                insert_soft_hyphens(&sentence, hyphen_breaks)
+                            } else {
+                                sentence
+                            };
+                            let span = iced::widget::text::Span::new(text)
                                 .font(self.current_font())
                                 .size(self.config.font_size as f32)
                                 .line_height(LineHeight::Relative(self.config.line_spacing))
                                 .link(Message::SentenceClicked(idx));
-
-                        if Some(idx) == highlight_idx {
-                            span = span.background(iced::Background::Color(highlight));
+                            return vec![highlight_span(span, highlight_style, highlight)];
                         }
-
-                        span
+                        self.sentence_pieces(sentence, markup, active_word)
+                            .into_iter()
+                            .flat_map(move |piece| {
+                                let highlighted = Some(idx) == highlight_idx;
+                                let active_word = piece.active_word;
+                                let search_match = piece.search_match;
+                                let code = piece.code;
+                                let mut font = self.emphasis_font(piece.style);
+                                if code {
+                                    font.family = iced::font::Family::Monospace;
+                                }
+                                let size = match piece.heading {
+                                    Some(level) => {
+                                        font.weight = iced::font::Weight::Bold;
+                                        self.config.font_size as f32 * heading_scale(level)
+                                    }
+                                    None => self.config.font_size as f32,
+                                };
+                                let underline =
+                                    piece.crossref_page.is_some() || piece.footnote.is_some();
+                                let start = piece.start;
+                                let runs: Vec<(String, usize, Message)> =
+                                    match (piece.crossref_page, piece.footnote) {
+                                        (Some(page), _) => {
+                                            vec![(piece.text, start, Message::FollowCrossRef(page))]
+                                        }
+                                        (None, Some(note)) => {
+                                            vec![(piece.text, start, Message::ShowFootnote(note))]
+                                        }
+                                        (None, None) if self.config.lookup_words_on_click => {
+                                            word_segments(&piece.text)
+                                                .into_iter()
+                                                .map(|(range, is_word)| {
+                                                    let run_start = start + range.start;
+                                                    let run = piece.text[range].to_string();
+                                                    let link = if is_word {
+                                                        Message::LookupWord(run.clone())
+                                                    } else {
+                                                        Message::SentenceClicked(idx)
+                                                    };
+                                                    (run, run_start, link)
+                                                })
+                                                .collect()
+                                        }
+                                        (None, None) => {
+                                            vec![(piece.text, start, Message::SentenceClicked(idx))]
+                                        }
+                                    };
+                                runs.into_iter().map(move |(run, run_start, link)| {
+                                    // Soft hyphens would break identifiers when copied.
+                                    let run = if code {
+                                        run
+                                    } else {
+                                        hyphenate_run(run, run_start, hyphen_breaks)
+                                    };
+                                    let mut span: iced::widget::text::Span<'_, Message> =
+                                        iced::widget::text::Span::new(run)
+                                            .font(font)
+                                            .size(size)
+                                            .line_height(LineHeight::Relative(
+                                                self.config.line_spacing,
+                                            ))
+                                            .underline(underline)
+                                            .link(link);
+                                    if highlighted {
+                                        span = highlight_span(span, highlight_style, highlight);
+                                    }
+                                    if active_word {
+                                        span = span
+                                            .background(iced::Background::Color(word_highlight));
+                                    }
+                                    if let Some(selected) = search_match {
+                                        span = search_match_span(
+                                            span,
+                                            highlight_style,
+                                            search_color,
+                                            selected,
+                                        );
+                                    }
+                                    span
+                                })
+                            })
+                            .collect::<Vec<_>>()
                     })
                     .collect();
 
-                let rich: iced::widget::text::Rich<'_, Message> =
-                    iced::widget::text::Rich::with_spans(spans);
-
-                rich.width(Length::Fill)
-                    .wrapping(Wrapping::WordOrGlyph)
-                    .align_x(Horizontal::Left)
-                    .into()
+                // Paragraphs become separate blocks so the gap between them can
+                // grow past the blank line `line_spacing` alone would give.
+                let paragraph_gap = self.config.font_size as f32 * self.config.line_spacing
+                    + self.config.paragraph_spacing;
+                Column::with_children(paragraph_blocks(spans).into_iter().map(|block| {
+                    let rich: iced::widget::text::Rich<'_, Message> =
+                        iced::widget::text::Rich::with_spans(block);
+                    rich.width(Length::Fill)
+                        .wrapping(Wrapping::WordOrGlyph)
+                        .align_x(text_align)
+                        .into()
+                }))
+                .spacing(paragraph_gap)
+                .width(Length::Fill)
+                .into()
             }
         };
 
-        let mut pane_content: Column<'_, Message> = column![text_view_content]
-            .spacing(PAGE_FLOW_SPACING_PX)
-            .width(Length::Fill);
+        // Continuous reading surrounds the live page with the rest of its chapter;
+        // only the live page carries highlights and sentence links.
+        let section_page = |page: usize| {
+            text(self.formatted_page_text(page))
+                .size(self.config.font_size as f32)
+                .line_height(LineHeight::Relative(self.config.line_spacing))
+                .width(Length::Fill)
+                .wrapping(Wrapping::WordOrGlyph)
+                .align_x(text_align)
+                .font(self.current_font())
+        };
+        let mut pane_content: Column<'_, Message> =
+            column![].spacing(PAGE_FLOW_SPACING_PX).width(Length::Fill);
+        for page in shown_pages.start..self.reader.current_page {
+            pane_content = pane_content.push(section_page(page));
+        }
+        pane_content = pane_content.push(text_view_content);
+        for page in self.reader.current_page + 1..shown_pages.end {
+            pane_content = pane_content.push(section_page(page));
+        }
 
         if !self.text_only_mode {
             let mut image_count = 0usize;
             for (idx, img) in self.reader.images.iter().enumerate() {
-                if self.image_assigned_page(idx) != self.reader.current_page {
+                if !shown_pages.contains(&self.image_assigned_page(idx)) {
                     continue;
                 }
                 image_count += 1;
@@ -309,34 +567,96 @@ impl App {
             }
         }
 
-        let text_view = scrollable(
-            container(pane_content)
-                .width(Length::Fill)
-                .padding([self.config.margin_vertical, self.config.margin_horizontal]),
-        )
-        .on_scroll(|viewport| Message::Scrolled {
-            offset: viewport.relative_offset(),
-            viewport_width: viewport.bounds().width,
-            viewport_height: viewport.bounds().height,
-            content_width: viewport.content_bounds().width,
-            content_height: viewport.content_bounds().height,
-        })
-        .id(super::state::TEXT_SCROLL_ID.clone())
-        .height(Length::FillPortion(1));
+        let mut text_column = container(pane_content)
+            .width(Length::Fill)
+            .padding([self.config.margin_vertical, self.config.margin_horizontal]);
+        if let Some(cap) = self.config.max_text_width {
+            // The cap is on the text itself, so the margins come on top of it.
+            text_column =
+                text_column.max_width(cap as f32 + 2.0 * f32::from(self.config.margin_horizontal));
+        }
+        let text_view = scrollable(container(text_column).center_x(Length::Fill))
+            .on_scroll(|viewport| Message::Scrolled {
+                offset: viewport.relative_offset(),
+                viewport_width: viewport.bounds().width,
+                viewport_height: viewport.bounds().height,
+                content_width: viewport.content_bounds().width,
+                content_height: viewport.content_bounds().height,
+            })
+            .id(super::state::TEXT_SCROLL_ID.clone())
+            .height(Length::FillPortion(1));
+        let text_view: Element<'_, Message> = match self.reading_ruler_overlay() {
+            Some(ruler) => stack![text_view, ruler]
+                .height(Length::FillPortion(1))
+                .into(),
+            None => text_view.into(),
+        };
+        let popup = match (&self.definition, self.open_footnote) {
+            (Some(lookup), _) => Some(self.definition_popup(lookup)),
+            (None, Some(note)) => self.footnote_popup(note),
+            (None, None) => None,
+        };
+        let text_view: Element<'_, Message> = match popup {
+            Some(popup) => stack![
+                text_view,
+                container(popup)
+                    .width(Length::Fill)
+                    .align_x(Horizontal::Right)
+                    .padding(8),
+            ]
+            .into(),
+            None => text_view,
+        };
+        let text_view: Element<'_, Message> = match self.chapter_ruler() {
+            Some(ruler) => row![text_view, ruler]
+                .spacing(4)
+                .height(Length::FillPortion(1))
+                .into(),
+            None => text_view,
+        };
 
-        let mut content: Column<'_, Message> = column![controls, font_controls].spacing(12);
+        let mut content: Column<'_, Message> = column![controls].spacing(12);
+        // Compact controls move the sliders into the settings panel.
+        if !self.config.compact_controls {
+            content = content.push(self.quick_sliders());
+        }
 
         if self.search.visible {
             content = content.push(self.search_bar());
         }
+        if self.book_loading {
+            content = content.push(text("Loading selected book...").size(13.0));
+        } else if let Some(err) = &self.book_loading_error {
+            content = content.push(text(err).size(13.0));
+        }
+        if self.source_missing {
+            content = content.push(self.missing_source_banner());
+        }
 
-        content = content.push(text_view).padding(16).height(Length::Fill);
+        content = content
+            .push(progress_bar(0.0..=1.0, self.reading_progress()).height(Length::Fixed(4.0)))
+            .push(text_view)
+            .padding(16)
+            .height(Length::Fill);
 
         if self.config.show_tts {
             content = content.push(self.tts_controls());
         }
 
-        let mut layout: Row<'_, Message> = row![container(content).width(Length::Fill)].spacing(16);
+        let mut layout: Row<'_, Message> = row![].spacing(16);
+        if self.show_contents {
+            layout = layout.push(self.contents_panel());
+        }
+        layout = layout.push(container(content).width(Length::Fill));
+        if self.show_sentence_list {
+            layout = layout.push(self.sentence_list_panel());
+        }
+        if self.show_notes {
+            layout = layout.push(self.notes_panel());
+        }
+        if let Some(panel) = self.book_diagnostics_panel() {
+            layout = layout.push(panel);
+        }
 
         if self.config.show_settings {
             layout = layout.push(self.settings_panel());
@@ -349,6 +669,107 @@ impl App {
 }
 
 impl App {
+    /// Font size, TTS speed and volume sliders.
+    fn quick_sliders(&self) -> Element<'_, Message> {
+        // Accessibility mode spells the labels out and gives the sliders a
+        // taller hit area.
+        let accessible = self.config.accessibility_mode;
+        let slider_height = if accessible { 28.0 } else { 16.0 };
+        let (font_label, speed_label) = if accessible {
+            ("Font size", "Reading speed")
+        } else {
+            ("Font", "Speed")
+        };
+        // While a speed ramp runs the slider follows it; dragging it hands the
+        // speed back to the slider.
+        let (speed_label, speed) = if self.config.speed_ramp.is_some() {
+            (format!("{speed_label} (ramp)"), self.current_tts_speed())
+        } else {
+            (speed_label.to_string(), self.config.tts_speed)
+        };
+        row![
+            column![
+                text(format!("{font_label}: {}", self.config.font_size)),
+                slider(
+                    MIN_FONT_SIZE as f32..=MAX_FONT_SIZE as f32,
+                    self.config.font_size as f32,
+                    |value| Message::FontSizeChanged(value.round() as u32),
+                )
+                .height(slider_height)
+            ]
+            .spacing(4)
+            .width(Length::FillPortion(1)),
+            column![
+                text(format!("{speed_label}: {speed:.2}x")),
+                slider(
+                    MIN_TTS_SPEED..=super::state::MAX_TTS_SPEED,
+                    speed,
+                    Message::SetTtsSpeed,
+                )
+                .step(0.05)
+                .height(slider_height)
+            ]
+            .spacing(4)
+            .width(Length::FillPortion(1)),
+            column![
+                text(format!("Volume: {:.0}%", self.config.tts_volume * 100.0)),
+                slider(
+                    MIN_TTS_VOLUME..=MAX_TTS_VOLUME,
+                    self.config.tts_volume,
+                    Message::SetTtsVolume,
+                )
+                .step(0.01)
+                .height(slider_height)
+            ]
+            .spacing(4)
+            .width(Length::FillPortion(1)),
+        ]
+        .spacing(12)
+        .align_y(Vertical::Center)
+        .width(Length::Fill)
+        .into()
+    }
+
+    fn overflow_message(action: TopBarAction) -> Message {
+        match action {
+            TopBarAction::Theme => Message::ToggleTheme,
+            TopBarAction::CloseBook => Message::CloseReadingSession,
+            TopBarAction::Settings => Message::ToggleSettings,
+            TopBarAction::Stats => Message::ToggleStats,
+            TopBarAction::TextMode => Message::ToggleTextOnly,
+            TopBarAction::Tts => Message::ToggleTtsControls,
+            TopBarAction::Search => Message::ToggleSearch,
+            TopBarAction::Contents => Message::ToggleContents,
+            TopBarAction::Notes => Message::ToggleNotes,
+            TopBarAction::AutoPage => Message::ToggleAutoPage,
+        }
+    }
+
+    fn missing_source_banner(&self) -> Element<'_, Message> {
+        let mut banner = column![
+            text(format!(
+                "{} was moved or deleted. Progress is still saved; point to its new location to keep it with the file.",
+                self.epub_path.display()
+            ))
+            .size(13.0),
+            row![
+                text_input("New path to the book", &self.relocate_path_input)
+                    .on_input(Message::RelocatePathChanged)
+                    .on_submit(Message::RelocateSource)
+                    .padding(6)
+                    .width(Length::Fill),
+                button("Relocate").on_press(Message::RelocateSource),
+            ]
+            .spacing(8)
+            .align_y(Vertical::Center),
+        ]
+        .spacing(6);
+        if let Some(status) = &self.relocate_status {
+            banner = banner.push(text(status).size(12.0));
+        }
+        banner.into()
+    }
+
     fn starter_view(&self) -> Element<'_, Message> {
         let starter_width = self.config.window_width.max(320.0);
         let show_calibre_panel = self.calibre.visible && starter_width >= 980.0;
@@ -464,8 +885,50 @@ impl App {
         .align_y(Vertical::Center)
     }
 
+    fn rgb_row<'a>(
+        &self,
+        label: &'a str,
+        rgb: [f32; 3],
+        msg: impl Fn(Component, f32) -> Message + Copy + 'a,
+    ) -> Row<'a, Message> {
+        row![
+            text(label),
+            slider(0.0..=1.0, rgb[0], move |v| msg(Component::R, v)).step(0.01),
+            slider(0.0..=1.0, rgb[1], move |v| msg(Component::G, v)).step(0.01),
+            slider(0.0..=1.0, rgb[2], move |v| msg(Component::B, v)).step(0.01),
+        ]
+        .spacing(6)
+        .align_y(Vertical::Center)
+    }
+
+    fn definition_popup(&self, lookup: &'_ WordDefinition) -> Element<'_, Message> {
+        let hint = self
+            .dictionary
+            .is_none()
+            .then_some("Set dictionary_path in the config to enable lookups.");
+        popup_card(
+            lookup.word.clone(),
+            lookup
+                .definition
+                .clone()
+                .unwrap_or_else(|| "No definition found".to_string()),
+            hint,
+            Message::CloseDefinition,
+        )
+    }
+
+    fn footnote_popup(&self, note: usize) -> Option<Element<'_, Message>> {
+        let footnote = self.reader.footnotes.get(note)?;
+        Some(popup_card(
+            format!("Note {}", footnote.marker),
+            footnote.text.clone(),
+            None,
+            Message::CloseFootnote,
+        ))
+    }
+
     fn search_bar(&self) -> Element<'_, Message> {
-        let query_input = text_input("Regex search (current page)", &self.search.query)
+        let query_input = text_input("Regex search (whole book)", &self.search.query)
             .on_input(Message::SearchQueryChanged)
             .on_submit(Message::SearchSubmit)
             .padding(8)
@@ -484,10 +947,15 @@ impl App {
             button("Next")
         };
         let status = if has_matches {
+            let hit_page = self
+                .selected_search_hit()
+                .map(|hit| hit.page + 1)
+                .unwrap_or_default();
             format!(
-                "Match {} of {}",
+                "{} of {}{} (page {hit_page})",
                 self.search.selected_match.saturating_add(1),
-                self.search.matches.len()
+                self.search.matches.len(),
+                if self.search.truncated { "+" } else { "" }
             )
         } else {
             "No matches".to_string()
@@ -880,16 +1348,54 @@ impl App {
     }
 
     pub(super) fn settings_panel(&self) -> Element<'_, Message> {
-        let family_picker = pick_list(
-            super::state::FONT_FAMILIES,
-            Some(self.config.font_family),
-            Message::FontFamilyChanged,
+        // The custom entry carries the colors in use so the current selection matches it.
+        let custom_theme = match self.config.theme {
+            custom @ ThemeMode::Custom { .. } => custom,
+            _ => ThemeMode::custom(self.config.custom_colors),
+        };
+        let theme_picker = pick_list(
+            vec![
+                ThemeMode::Day,
+                ThemeMode::Night,
+                ThemeMode::Sepia,
+                custom_theme,
+            ],
+            Some(self.config.theme),
+            Message::ThemeChanged,
+        );
+        let font_choices: Vec<FontChoice> = super::state::FONT_FAMILIES
+            .into_iter()
+            .map(FontChoice::Builtin)
+            .chain(self.custom_fonts.iter().copied().map(FontChoice::Custom))
+            .collect();
+        let selected_font = self.active_custom_font().map_or(
+            FontChoice::Builtin(self.config.font_family),
+            FontChoice::Custom,
         );
+        let family_picker = pick_list(font_choices, Some(selected_font), |choice| match choice {
+            FontChoice::Builtin(family) => Message::FontFamilyChanged(family),
+            FontChoice::Custom(name) => Message::CustomFontChanged(name.to_string()),
+        });
         let weight_picker = pick_list(
             super::state::FONT_WEIGHTS,
             Some(self.config.font_weight),
             Message::FontWeightChanged,
         );
+        let direction_picker = pick_list(
+            super::state::TEXT_DIRECTIONS,
+            Some(self.config.text_direction),
+            Message::TextDirectionChanged,
+        );
+        let reading_mode_picker = pick_list(
+            super::state::READING_MODES,
+            Some(self.config.reading_mode),
+            Message::ReadingModeChanged,
+        );
+        let highlight_style_picker = pick_list(
+            super::state::HIGHLIGHT_STYLES,
+            Some(self.config.highlight_style),
+            Message::HighlightStyleChanged,
+        );
 
         let line_spacing_slider = slider(
             0.8..=2.5,
@@ -928,20 +1434,65 @@ impl App {
             |value| Message::LetterSpacingChanged(value.round() as u32),
         );
 
-        let panel = column![
+        let paragraph_spacing_slider = slider(
+            0.0..=MAX_PARAGRAPH_SPACING,
+            self.config.paragraph_spacing,
+            Message::ParagraphSpacingChanged,
+        )
+        .step(1.0);
+
+        let history_controls = row![
             text("Reader Settings").size(20.0),
+            horizontal_space(),
+            button(text("Undo").size(13.0))
+                .padding([2, 6])
+                .on_press_maybe(
+                    self.settings_history
+                        .can_undo()
+                        .then_some(Message::UndoSettings)
+                ),
+            button(text("Redo").size(13.0))
+                .padding([2, 6])
+                .on_press_maybe(
+                    self.settings_history
+                        .can_redo()
+                        .then_some(Message::RedoSettings)
+                ),
+        ]
+        .spacing(6)
+        .align_y(Vertical::Center);
+
+        let mut panel = column![
+            column![history_controls]
+                .push_maybe(self.config.compact_controls.then(|| self.quick_sliders()))
+                .spacing(12),
+            row![text("Theme"), theme_picker]
+                .spacing(8)
+                .align_y(Vertical::Center),
             row![text("Font family"), family_picker]
                 .spacing(8)
                 .align_y(Vertical::Center),
             row![text("Font weight"), weight_picker]
                 .spacing(8)
                 .align_y(Vertical::Center),
+            row![text("Text direction"), direction_picker]
+                .spacing(8)
+                .align_y(Vertical::Center),
+            row![text("Reading mode"), reading_mode_picker]
+                .spacing(8)
+                .align_y(Vertical::Center),
             row![
                 self.numeric_setting_editor(NumericSetting::LineSpacing),
                 line_spacing_slider
             ]
             .spacing(8)
             .align_y(Vertical::Center),
+            row![
+                self.numeric_setting_editor(NumericSetting::ParagraphSpacing),
+                paragraph_spacing_slider
+            ]
+            .spacing(8)
+            .align_y(Vertical::Center),
             row![
                 self.numeric_setting_editor(NumericSetting::PauseAfterSentence),
                 slider(
@@ -953,6 +1504,17 @@ impl App {
             ]
             .spacing(8)
             .align_y(Vertical::Center),
+            row![
+                self.numeric_setting_editor(NumericSetting::PauseAfterParagraph),
+                slider(
+                    0.0..=2.0,
+                    self.config.pause_after_paragraph,
+                    Message::PauseAfterParagraphChanged
+                )
+                .step(0.01)
+            ]
+            .spacing(8)
+            .align_y(Vertical::Center),
             checkbox(
                 "Auto-scroll to spoken sentence",
                 self.config.auto_scroll_tts
@@ -963,6 +1525,21 @@ impl App {
                 self.config.center_spoken_sentence
             )
             .on_toggle(Message::CenterSpokenSentenceChanged),
+            checkbox("Smooth auto-scroll", self.config.smooth_scroll)
+                .on_toggle(Message::SmoothScrollChanged),
+            checkbox("Reading ruler", self.config.reading_ruler)
+                .on_toggle(Message::ReadingRulerChanged),
+            checkbox("Hyphenate long words", self.config.hyphenate)
+                .on_toggle(Message::HyphenateChanged),
+            checkbox("Compact controls", self.config.compact_controls)
+                .on_toggle(Message::CompactControlsChanged),
+            checkbox("Accessibility mode", self.config.accessibility_mode)
+                .on_toggle(Message::AccessibilityModeChanged),
+            checkbox(
+                "Click a word to look it up",
+                self.config.lookup_words_on_click
+            )
+            .on_toggle(Message::LookupWordsOnClickChanged),
             row![
                 self.numeric_setting_editor(NumericSetting::LinesPerPage),
                 lines_per_page_slider
@@ -993,28 +1570,472 @@ impl App {
             ]
             .spacing(8)
             .align_y(Vertical::Center),
-            text("Highlight Colors").size(18.0),
+            text("Highlight").size(18.0),
+            row![text("Style"), highlight_style_picker]
+                .spacing(8)
+                .align_y(Vertical::Center),
             self.color_row("Day highlight", self.config.day_highlight, |c, v| {
                 Message::DayHighlightChanged(c, v)
             }),
             self.color_row("Night highlight", self.config.night_highlight, |c, v| {
                 Message::NightHighlightChanged(c, v)
             }),
+            self.color_row(
+                "Day search match",
+                self.config.day_search_highlight,
+                Message::DaySearchHighlightChanged
+            ),
+            self.color_row(
+                "Night search match",
+                self.config.night_search_highlight,
+                Message::NightSearchHighlightChanged
+            ),
         ]
         .spacing(12)
         .width(Length::Fixed(280.0));
+        if let ThemeMode::Custom { bg, fg } = self.config.theme {
+            panel = panel
+                .push(text("Custom Theme").size(18.0))
+                .push(self.rgb_row("Background", bg, |c, v| {
+                    Message::CustomThemeBackgroundChanged(c, v)
+                }))
+                .push(self.rgb_row("Text", fg, |c, v| {
+                    Message::CustomThemeForegroundChanged(c, v)
+                }));
+        }
+
+        panel = panel
+            .push(text("Profile").size(18.0))
+            .push(
+                text_input("Path to profile .toml", &self.profile_path_input)
+                    .on_input(Message::ProfilePathChanged),
+            )
+            .push(
+                row![
+                    button("Export").on_press(Message::ExportProfile),
+                    button("Import").on_press(Message::ImportProfile),
+                ]
+                .spacing(8),
+            );
+        if let Some(status) = &self.profile_status {
+            panel = panel.push(text(status.as_str()).size(13.0));
+        }
+        panel = panel
+            .push(text("Diagnostics").size(18.0))
+            .push(
+                row![
+                    text("Log level"),
+                    pick_list(
+                        super::state::LOG_LEVELS,
+                        Some(self.config.log_level),
+                        Message::LogLevelChanged,
+                    ),
+                ]
+                .spacing(8)
+                .align_y(Vertical::Center),
+            )
+            .push(button("Copy diagnostics").on_press(Message::CopyDiagnostics));
 
         container(panel).padding(12).into()
     }
 
-    fn stats_panel(&self) -> Element<'_, Message> {
-        let total_pages = self.reader.pages.len().max(1);
-        let current_page = self.reader.current_page.min(total_pages.saturating_sub(1));
-        let page_words = self.word_count_for_page(current_page);
-        let page_sentences = self.sentence_count_for_page(current_page);
-        let words_before = self.word_count_before_page(current_page);
-        let total_words = self.total_word_count();
-        let words_through = words_before + page_words;
+    /// Tinted band laid over the text to mark the reading line.
+    ///
+    /// Only the handle at its edge takes pointer input, so clicks and wheel
+    /// scrolling reach the text underneath; while dragging, a full-size layer
+    /// tracks the pointer instead.
+    fn reading_ruler_overlay(&self) -> Option<Element<'_, Message>> {
+        if !self.config.reading_ruler {
+            return None;
+        }
+        let position = self.config.reading_ruler_position.clamp(0.0, 1.0);
+        let above = ((position * 1000.0).round() as u16).max(1);
+        let below = (1000 - above).max(1);
+        let tint = Color {
+            a: super::state::READING_RULER_ALPHA,
+            ..self.highlight_color()
+        };
+        let handle = mouse_area(container(text("\u{2195}").size(12.0)).padding([0, 6]))
+            .on_press(Message::ReadingRulerDragStarted)
+            .interaction(mouse::Interaction::ResizingVertically);
+        let band = container(row![horizontal_space(), handle].align_y(Vertical::Center))
+            .width(Length::Fill)
+            .height(Length::Fixed(self.reading_ruler_height()))
+            .align_y(Vertical::Center)
+            .style(move |_theme: &iced::Theme| container::Style {
+                background: Some(tint.into()),
+                ..container::Style::default()
+            });
+        let ruler = column![
+            vertical_space().height(Length::FillPortion(above)),
+            band,
+            vertical_space().height(Length::FillPortion(below)),
+        ];
+
+        let mut layers = stack![ruler];
+        if self.reading_ruler_dragging {
+            layers = layers.push(
+                mouse_area(vertical_space().height(Length::Fill))
+                    .on_move(|point| Message::ReadingRulerDragged(point.y))
+                    .on_release(Message::ReadingRulerDragEnded)
+                    .on_exit(Message::ReadingRulerDragEnded)
+                    .interaction(mouse::Interaction::ResizingVertically),
+            );
+        }
+        Some(layers.width(Length::Fill).height(Length::Fill).into())
+    }
+
+    /// Vertical ruler beside the text with one segment per chapter, sized by its
+    /// page count. Each segment starts with a tick and jumps to its chapter when
+    /// clicked; the current page shows as a marker. Segments are derived from
+    /// `chapter_starts`, so they follow repagination.
+    fn chapter_ruler(&self) -> Option<Element<'_, Message>> {
+        let starts = &self.reader.chapter_starts;
+        let total_pages = self.reader.pages.len();
+        if !self.config.show_chapter_ruler || starts.len() < 2 {
+            return None;
+        }
+        let current_page = self.reader.current_page;
+        let marker_color = Color {
+            a: 1.0,
+            ..self.highlight_color()
+        };
+
+        let mut ruler = column![]
+            .width(Length::Fixed(super::state::CHAPTER_RULER_WIDTH_PX))
+            .height(Length::Fill);
+        if starts[0] > 0 {
+            ruler = ruler.push(vertical_space().height(ruler_portion(starts[0], total_pages)));
+        }
+        for (idx, &start) in starts.iter().enumerate() {
+            let end = starts.get(idx + 1).copied().unwrap_or(total_pages);
+            if end <= start {
+                continue;
+            }
+            let tick = container(vertical_space().height(Length::Fixed(2.0)))
+                .width(Length::Fill)
+                .style(|theme: &iced::Theme| container::Style {
+                    background: Some(
+                        Color {
+                            a: 0.6,
+                            ..theme.palette().text
+                        }
+                        .into(),
+                    ),
+                    ..Default::default()
+                });
+            let mut segment = column![tick].height(Length::Fill);
+            if (start..end).contains(&current_page) {
+                let before = current_page - start;
+                let after = end - current_page - 1;
+                if before > 0 {
+                    segment =
+                        segment.push(vertical_space().height(ruler_portion(before, end - start)));
+                }
+                segment = segment.push(
+                    container(vertical_space().height(Length::Fixed(4.0)))
+                        .width(Length::Fill)
+                        .style(move |_theme: &iced::Theme| container::Style {
+                            background: Some(marker_color.into()),
+                            ..Default::default()
+                        }),
+                );
+                if after > 0 {
+                    segment =
+                        segment.push(vertical_space().height(ruler_portion(after, end - start)));
+                }
+            }
+            ruler = ruler.push(
+                button(segment)
+                    .padding(0)
+                    .width(Length::Fill)
+                    .height(ruler_portion(end - start, total_pages))
+                    .style(iced::widget::button::text)
+                    .on_press(Message::GoToPage(start)),
+            );
+        }
+        Some(ruler.into())
+    }
+
+    /// Top-bar dropdown of TOC entries; the hover tooltip shows the full title.
+    fn chapter_picker(&self) -> Option<Element<'_, Message>> {
+        if self.reader.toc.is_empty() {
+            return None;
+        }
+        let choices: Vec<ChapterChoice> = self
+            .reader
+            .toc
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| ChapterChoice {
+                idx,
+                label: format!(
+                    "{}{}",
+                    "  ".repeat(entry.depth.min(4)),
+                    Self::truncate_text(&entry.title, CHAPTER_PICKER_TITLE_CHARS)
+                ),
+            })
+            .collect();
+        let current = self.current_toc_entry();
+        let selected = current.and_then(|idx| choices.get(idx).cloned());
+        let full_title = current
+            .and_then(|idx| self.reader.toc.get(idx))
+            .map(|entry| entry.title.clone())
+            .unwrap_or_else(|| "Go to chapter".to_string());
+        let picker = pick_list(choices, selected, |choice: ChapterChoice| {
+            Message::OpenTocEntry(choice.idx)
+        })
+        .placeholder("Go to chapter")
+        .text_size(13.0)
+        .width(Length::Fixed(CHAPTER_PICKER_WIDTH_PX));
+        Some(
+            tooltip(
+                picker,
+                container(text(full_title).size(13.0))
+                    .padding(6)
+                    .style(container::rounded_box),
+                tooltip::Position::Bottom,
+            )
+            .into(),
+        )
+    }
+
+    fn contents_panel(&self) -> Element<'_, Message> {
+        let current_entry = self.current_toc_entry();
+        let progress = self
+            .reader
+            .chapter_progress(self.reading_stats.stats.furthest_offset);
+
+        let mut entries = column![].spacing(2);
+        if self.reader.toc.is_empty() {
+            entries = entries.push(text("This book has no table of contents.").size(13.0));
+        } else {
+            entries = entries.push(
+                button(text("Skip to first chapter").size(13.0))
+                    .padding([2, 6])
+                    .on_press_maybe(
+                        self.reader
+                            .first_chapter_page()
+                            .map(|_| Message::SkipToFirstChapter),
+                    ),
+            );
+        }
+        for (idx, entry) in self.reader.toc.iter().enumerate() {
+            let mut font = self.current_font();
+            if Some(idx) == current_entry {
+                font.weight = iced::font::Weight::Bold;
+            }
+            let read = progress.get(idx) == Some(&ChapterProgress::Read);
+            let current = progress.get(idx) == Some(&ChapterProgress::Current);
+            let label = text(Self::truncate_text(&entry.title, 48))
+                .font(font)
+                .size(14.0)
+                .style(move |theme: &iced::Theme| {
+                    let palette = theme.extended_palette();
+                    iced::widget::text::Style {
+                        color: if current {
+                            Some(palette.primary.strong.color)
+                        } else if read {
+                            Some(Color {
+                                a: 0.5,
+                                ..palette.background.base.text
+                            })
+                        } else {
+                            None
+                        },
+                    }
+                });
+            let indent = (entry.depth.min(4) * 14) as f32;
+            entries = entries.push(
+                container(
+                    button(label)
+                        .style(iced::widget::button::text)
+                        .padding([2, 6])
+                        .on_press(Message::OpenTocEntry(idx)),
+                )
+                .padding(iced::Padding::ZERO.left(indent)),
+            );
+        }
+
+        entries = entries.push(text("Bookmarks").size(18.0)).push(
+            row![
+                text_input("Bookmark name", &self.bookmark.name_input)
+                    .on_input(Message::BookmarkNameChanged)
+                    .on_submit(Message::AddBookmark(self.bookmark.name_input.clone()))
+                    .padding(4)
+                    .size(13.0),
+                button("Add").on_press(Message::AddBookmark(self.bookmark.name_input.clone())),
+            ]
+            .spacing(6)
+            .align_y(Vertical::Center),
+        );
+        if self.bookmark.named.is_empty() {
+            entries = entries.push(text("No bookmarks yet.").size(13.0));
+        }
+        for (name, bookmark) in &self.bookmark.named {
+            let label = format!(
+                "{} (p. {})",
+                Self::truncate_text(name, 36),
+                bookmark.page + 1
+            );
+            entries = entries.push(
+                row![
+                    button(text(label).size(14.0))
+                        .style(iced::widget::button::text)
+                        .padding([2, 6])
+                        .width(Length::Fill)
+                        .on_press(Message::JumpToBookmark(name.clone())),
+                    button(text("x").size(12.0))
+                        .style(iced::widget::button::text)
+                        .on_press(Message::DeleteBookmark(name.clone())),
+                ]
+                .align_y(Vertical::Center),
+            );
+        }
+        entries = entries.push(
+            button(text("Export to Markdown").size(13.0))
+                .padding([2, 6])
+                .on_press(Message::ExportBookmarks),
+        );
+        if let Some(status) = &self.bookmark.export_status {
+            entries = entries.push(text(status.as_str()).size(12.0));
+        }
+
+        let panel = column![
+            text("Contents").size(20.0),
+            scrollable(entries).height(Length::Fill),
+        ]
+        .spacing(8)
+        .width(Length::Fixed(260.0));
+
+        container(panel).padding(12).height(Length::Fill).into()
+    }
+
+    fn sentence_list_panel(&self) -> Element<'_, Message> {
+        let sentences = self.current_sentences();
+        let selection = self
+            .sentence_selection
+            .filter(|selection| selection.page == self.reader.current_page)
+            .map(|selection| selection.bounds());
+        let mut entries = column![].spacing(2);
+        if sentences.is_empty() {
+            entries = entries.push(text("No sentences on this page.").size(13.0));
+        }
+        for (idx, sentence) in sentences.iter().enumerate() {
+            let current = self.tts.current_sentence_idx == Some(idx);
+            let selected = selection.is_some_and(|(start, end)| (start..=end).contains(&idx));
+            let mut font = self.current_font();
+            if current {
+                font.weight = iced::font::Weight::Bold;
+            }
+            let label = text(format!(
+                "{}. {}",
+                idx + 1,
+                Self::truncate_text(sentence.trim(), 90)
+            ))
+            .font(font)
+            .size(13.0);
+            let entry = button(label)
+                .padding([3, 6])
+                .width(Length::Fill)
+                .on_press(Message::PlayFromCursor(idx));
+            let entry = if current {
+                entry.style(iced::widget::button::secondary)
+            } else {
+                entry.style(iced::widget::button::text)
+            };
+            let mark = button(text(if selected { "[x]" } else { "[ ]" }).size(13.0))
+                .padding([3, 4])
+                .style(iced::widget::button::text)
+                .on_press(Message::SelectSentence(idx));
+            entries = entries.push(row![mark, entry].align_y(Vertical::Center));
+        }
+
+        let selection_controls: Element<'_, Message> = match selection {
+            Some((start, end)) => row![
+                button("Play selection").on_press(Message::PlaySelection { start, end }),
+                button("Clear").on_press(Message::ClearSentenceSelection),
+            ]
+            .spacing(8)
+            .into(),
+            None => text("Mark sentences with [ ] to play just that passage.")
+                .size(12.0)
+                .into(),
+        };
+        let panel = column![
+            text("Sentences").size(20.0),
+            selection_controls,
+            scrollable(entries).height(Length::Fill),
+        ]
+        .spacing(8)
+        .width(Length::Fixed(280.0));
+
+        container(panel).padding(12).height(Length::Fill).into()
+    }
+
+    fn notes_panel(&self) -> Element<'_, Message> {
+        let input = text_input("Note on this sentence", &self.bookmark.note_input)
+            .on_input(Message::NoteInputChanged)
+            .on_submit(Message::AddNote)
+            .padding(4)
+            .size(13.0);
+        let add = button("Add").on_press_maybe(
+            (!self.bookmark.note_input.trim().is_empty()).then_some(Message::AddNote),
+        );
+
+        let mut entries = column![].spacing(6);
+        if self.bookmark.notes.is_empty() {
+            entries = entries.push(text("No notes yet.").size(13.0));
+        }
+        for (idx, note) in self.bookmark.notes.iter().enumerate() {
+            let location = match note.sentence_idx {
+                Some(sentence) => format!("Page {}, sentence {}", note.page + 1, sentence + 1),
+                None => format!("Page {}", note.page + 1),
+            };
+            let mut font = self.current_font();
+            if note.page == self.reader.current_page {
+                font.weight = iced::font::Weight::Bold;
+            }
+            entries = entries.push(
+                button(
+                    column![
+                        text(location).font(font).size(12.0),
+                        text(note.text.as_str()).size(14.0),
+                    ]
+                    .spacing(2),
+                )
+                .style(iced::widget::button::text)
+                .padding([3, 6])
+                .width(Length::Fill)
+                .on_press(Message::JumpToNote(idx)),
+            );
+        }
+
+        let panel = column![
+            text("Notes").size(20.0),
+            row![input, add].spacing(6).align_y(Vertical::Center),
+            scrollable(entries).height(Length::Fill),
+        ]
+        .spacing(8)
+        .width(Length::Fixed(280.0));
+
+        container(panel).padding(12).height(Length::Fill).into()
+    }
+
+    fn stats_panel(&self) -> Element<'_, Message> {
+        let total_pages = self.reader.pages.len().max(1);
+        let current_page = self.reader.current_page.min(total_pages.saturating_sub(1));
+        let page_counts = self.page_text_counts(current_page);
+        let page_sentences = self.sentence_count_for_page(current_page);
+        let words_before: usize = self
+            .reader
+            .page_text_counts
+            .iter()
+            .take(current_page)
+            .map(|counts| counts.words)
+            .sum();
+        let total_words = self.book_word_count();
+        let words_through = words_before + page_counts.words;
 
         let sentences_before: usize = self
             .reader
@@ -1036,8 +2057,9 @@ impl App {
             sentences_through as f32 / total_sentences as f32 * 100.0
         };
 
-        let panel = column![
+        let mut panel = column![
             text("Reading Stats").size(20.0),
+            self.book_info(),
             text(format!(
                 "Page index: {} / {}",
                 current_page + 1,
@@ -1046,7 +2068,16 @@ impl App {
             text(self.audio_progress_label()),
             text(format!("Page time remaining: {}", self.page_eta_label())),
             text(format!("Book time remaining: {}", self.book_eta_label())),
-            text(format!("Words on page: {}", page_words)),
+            text(format!(
+                "Book progress: {:.1}%",
+                self.reading_progress() * 100.0
+            )),
+            text(format!("Words on page: {}", self.page_word_count())),
+            text(format!("Characters on page: {}", page_counts.chars)),
+            text(format!(
+                "Book length: {} words, {} characters",
+                total_words, self.reader.book_text_counts.chars
+            )),
             text(format!("Sentences on page: {}", page_sentences)),
             text(format!("Percent at page start: {:.3}%", percent_start)),
             text(format!("Percent at page end: {:.3}%", percent_end)),
@@ -1058,13 +2089,210 @@ impl App {
                 "Sentences read through this page: {} / {}",
                 sentences_through, total_sentences
             )),
+            text("Timings").size(16.0),
         ]
         .spacing(8)
         .width(Length::Fixed(280.0));
 
+        for operation in [
+            TimedOperation::Pagination,
+            TimedOperation::NormalizerPlan,
+            TimedOperation::TtsBatchPrep,
+        ] {
+            let label = match self.metrics.summary(operation) {
+                Some(summary) => format!(
+                    "{}: {}x, min {} / avg {} / max {}",
+                    operation.label(),
+                    summary.count,
+                    Self::format_timing_ms(summary.min),
+                    Self::format_timing_ms(summary.avg),
+                    Self::format_timing_ms(summary.max)
+                ),
+                None => format!("{}: no samples", operation.label()),
+            };
+            panel = panel.push(text(label).size(13.0));
+        }
+
+        let reading = &self.reading_stats.stats;
+        panel = panel
+            .push(text("This Book").size(16.0))
+            .push(text(format!(
+                "Time reading: {}",
+                Self::format_duration_dhms(Duration::from_secs(reading.total_seconds))
+            )))
+            .push(text(format!("Sessions: {}", reading.sessions)))
+            .push(text(format!("Pages turned: {}", reading.pages_turned)))
+            .push(text(match self.reading_stats.rolling_wpm() {
+                Some(wpm) => {
+                    let pages = self.reading_stats.rolling_pages();
+                    let noun = if pages == 1 { "page" } else { "pages" };
+                    format!("Reading speed: {wpm:.0} wpm (last {pages} {noun})")
+                }
+                None => "Reading speed: turn a few pages without TTS to measure".to_string(),
+            }))
+            .push(text(match self.reading_stats.average_wpm() {
+                Some(wpm) => format!("Average reading speed: {wpm:.0} wpm"),
+                None => "Average reading speed: not measured yet".to_string(),
+            }));
+
+        panel = panel.push(text("Progress").size(16.0)).push(
+            row![
+                button("Export").on_press(Message::ExportProgress),
+                button("Import").on_press(Message::ImportProgress),
+            ]
+            .spacing(8),
+        );
+        if let Some(status) = &self.progress_status {
+            panel = panel.push(text(status.as_str()).size(13.0));
+        }
+
+        panel = panel.push(text("Cache").size(16.0));
+        panel = match self.pending_cache_clear {
+            Some(scope) => {
+                let prompt = match scope {
+                    CacheClearScope::Audio => "Delete this book's cached audio?",
+                    CacheClearScope::Everything => {
+                        "Delete all cached data for this book, including bookmarks and settings?"
+                    }
+                };
+                panel.push(text(prompt).size(13.0)).push(
+                    row![
+                        button("Delete").on_press(Message::ConfirmClearBookCache),
+                        button("Cancel").on_press(Message::CancelClearBookCache),
+                    ]
+                    .spacing(8),
+                )
+            }
+            None => panel.push(
+                row![
+                    button("Clear Audio").on_press(Message::ClearBookCache(CacheClearScope::Audio)),
+                    button("Clear All")
+                        .on_press(Message::ClearBookCache(CacheClearScope::Everything)),
+                ]
+                .spacing(8),
+            ),
+        };
+        if let Some(status) = &self.cache_status {
+            panel = panel.push(text(status.as_str()).size(13.0));
+        }
+
+        panel = panel.push(text("Book Check").size(16.0)).push(
+            button(if self.book_diagnostics_running {
+                "Checking..."
+            } else {
+                "Run Diagnostics"
+            })
+            .on_press_maybe((!self.book_diagnostics_running).then_some(Message::ShowDiagnostics)),
+        );
+
         container(panel).padding(12).into()
     }
 
+    /// Report from the last book check: chapter count and sizes, empty and
+    /// mis-encoded chapters, TOC and language.
+    fn book_diagnostics_panel(&self) -> Option<Element<'_, Message>> {
+        if self.book_diagnostics.is_none() && !self.book_diagnostics_running {
+            return None;
+        }
+        let mut entries = column![].spacing(4);
+        match &self.book_diagnostics {
+            None => entries = entries.push(text("Checking the book...").size(13.0)),
+            Some(Err(err)) => entries = entries.push(text(err.as_str()).size(13.0)),
+            Some(Ok(report)) => {
+                let toc = if report.toc_entries == 0 {
+                    "Table of contents: missing".to_string()
+                } else {
+                    format!(
+                        "Table of contents: {} entries, {} located in the text",
+                        report.toc_entries,
+                        self.reader.toc.len()
+                    )
+                };
+                entries = entries
+                    .push(text(format!(
+                        "Chapters: {} ({} empty)",
+                        report.chapters.len(),
+                        report.empty_chapters()
+                    )))
+                    .push(text(match report.lossy_chapters() {
+                        0 => "Encoding: no issues".to_string(),
+                        lossy => format!("Encoding: {lossy} chapters decoded lossily"),
+                    }))
+                    .push(text(toc))
+                    .push(text(format!(
+                        "Language: {}",
+                        report.language.as_deref().unwrap_or("not declared")
+                    )));
+                if report.encrypted {
+                    entries = entries.push(text("Content is encrypted (DRM)"));
+                }
+                entries = entries.push(text("Chapter sizes").size(16.0));
+                for (idx, chapter) in report.chapters.iter().enumerate() {
+                    let mut label = format!(
+                        "{}. {} - {}, {} chars",
+                        idx + 1,
+                        chapter.resource,
+                        Self::format_bytes(chapter.bytes as u64),
+                        chapter.text_chars
+                    );
+                    if chapter.text_chars == 0 {
+                        label.push_str(" (empty)");
+                    }
+                    if chapter.lossy {
+                        label.push_str(" (lossy)");
+                    }
+                    entries = entries.push(text(label).size(12.0));
+                }
+            }
+        }
+
+        let panel = column![
+            row![
+                text("Book Diagnostics").size(20.0).width(Length::Fill),
+                button(text("x").size(12.0))
+                    .style(iced::widget::button::text)
+                    .on_press_maybe(
+                        self.book_diagnostics
+                            .is_some()
+                            .then_some(Message::CloseDiagnostics)
+                    ),
+            ]
+            .align_y(Vertical::Center),
+            scrollable(entries).height(Length::Fill),
+        ]
+        .spacing(8)
+        .width(Length::Fixed(280.0));
+
+        Some(container(panel).padding(12).height(Length::Fill).into())
+    }
+
+    /// Cover and package metadata; lines the EPUB does not declare are left out.
+    fn book_info(&self) -> Element<'_, Message> {
+        let metadata = &self.reader.metadata;
+        let mut info = column![].spacing(4);
+        if let Some(cover) = &self.reader.cover {
+            info = info.push(
+                image(cover.clone())
+                    .width(Length::Fill)
+                    .height(Length::Fixed(IMAGE_PREVIEW_HEIGHT_PX))
+                    .content_fit(ContentFit::Contain),
+            );
+        }
+        if let Some(title) = &metadata.title {
+            info = info.push(text(title.as_str()).size(16.0));
+        }
+        for (label, value) in [
+            ("Author", &metadata.creator),
+            ("Publisher", &metadata.publisher),
+            ("Language", &metadata.language),
+        ] {
+            if let Some(value) = value {
+                info = info.push(text(format!("{label}: {value}")).size(13.0));
+            }
+        }
+        info.into()
+    }
+
     fn numeric_setting_editor(&self, setting: NumericSetting) -> Element<'_, Message> {
         if self.active_numeric_setting == Some(setting) {
             let input = text_input("", &self.numeric_setting_input)
@@ -1143,6 +2371,12 @@ impl App {
                     self.config.pause_after_sentence
                 )
             }
+            NumericSetting::PauseAfterParagraph => {
+                format!(
+                    "Extra pause after paragraph: {:.2} s",
+                    self.config.pause_after_paragraph
+                )
+            }
             NumericSetting::LinesPerPage => {
                 format!("Lines per page: {}", self.config.lines_per_page)
             }
@@ -1156,18 +2390,22 @@ impl App {
             NumericSetting::LetterSpacing => {
                 format!("Letter spacing: {}", self.config.letter_spacing)
             }
+            NumericSetting::ParagraphSpacing => {
+                format!("Paragraph spacing: {:.0} px", self.config.paragraph_spacing)
+            }
         }
     }
 
     fn numeric_setting_bounds(setting: NumericSetting) -> (f32, f32) {
         match setting {
             NumericSetting::LineSpacing => (0.8, 2.5),
-            NumericSetting::PauseAfterSentence => (0.0, 2.0),
+            NumericSetting::PauseAfterSentence | NumericSetting::PauseAfterParagraph => (0.0, 2.0),
             NumericSetting::LinesPerPage => (MIN_LINES_PER_PAGE as f32, MAX_LINES_PER_PAGE as f32),
             NumericSetting::MarginHorizontal => (0.0, MAX_HORIZONTAL_MARGIN as f32),
             NumericSetting::MarginVertical => (0.0, MAX_VERTICAL_MARGIN as f32),
             NumericSetting::WordSpacing => (0.0, MAX_WORD_SPACING as f32),
             NumericSetting::LetterSpacing => (0.0, MAX_LETTER_SPACING as f32),
+            NumericSetting::ParagraphSpacing => (0.0, MAX_PARAGRAPH_SPACING),
         }
     }
 
@@ -1197,32 +2435,39 @@ impl App {
             "Pause"
         };
 
+        let tts_available = self.tts_unavailable.is_none();
         let play_button = if self.tts.is_preparing() {
-            Self::control_button(play_label).on_press(Message::Pause)
+            self.control_button(play_label).on_press(Message::Pause)
         } else if play_label == "Play" {
-            Self::control_button(play_label).on_press(Message::Play)
+            self.control_button(play_label)
+                .on_press_maybe(tts_available.then_some(Message::Play))
         } else {
-            Self::control_button(play_label).on_press(Message::Pause)
+            self.control_button(play_label).on_press(Message::Pause)
         };
-        let play_from_start =
-            Self::control_button("Play Page").on_press(Message::PlayFromPageStart);
+        let play_from_start = self
+            .control_button("Play Page")
+            .on_press_maybe(tts_available.then_some(Message::PlayFromPageStart));
         let jump_disabled = self.tts.current_sentence_idx.is_none();
         let jump_button = if jump_disabled {
-            Self::control_button("Jump to Audio")
-        } else {
-            Self::control_button("Jump to Audio").on_press(Message::JumpToCurrentAudio)
-        };
-        let play_from_cursor = if let Some(idx) = self.tts.current_sentence_idx {
-            Self::control_button("Play From Highlight").on_press(Message::PlayFromCursor(idx))
+            self.control_button("Jump to Audio")
         } else {
-            Self::control_button("Play From Highlight")
+            self.control_button("Jump to Audio")
+                .on_press(Message::JumpToCurrentAudio)
         };
+        let play_from_cursor =
+            if let Some(idx) = self.tts.current_sentence_idx.filter(|_| tts_available) {
+                self.control_button("Play From Highlight")
+                    .on_press(Message::PlayFromCursor(idx))
+            } else {
+                self.control_button("Play From Highlight")
+            };
         let available_width = self.controls_layout_width();
         let controls_spacing = 10.0;
         let controls_budget = (available_width - 12.0).max(0.0);
-        let mut used_controls_width = estimate_button_width_px(play_label);
+        let accessible = self.config.accessibility_mode;
+        let mut used_controls_width = estimate_button_width_px(play_label, accessible);
         let mut add_optional = |label: &str| -> bool {
-            let extra = controls_spacing + estimate_button_width_px(label);
+            let extra = controls_spacing + estimate_button_width_px(label, accessible);
             if used_controls_width + extra <= controls_budget {
                 used_controls_width += extra;
                 true
@@ -1235,67 +2480,196 @@ impl App {
         let show_play_page = add_optional("Play Page");
         let show_play_from_highlight = add_optional("Play From Highlight");
         let show_jump = add_optional("Jump to Audio");
+        let show_cache_chapter = add_optional("Cache Chapter");
+        let cache_book_label = if self.tts.chapter_cache.is_some() {
+            "Cancel Caching"
+        } else {
+            "Cache Book"
+        };
+        let show_cache_book = add_optional(cache_book_label);
+        let export_label = if self.tts.audio_export.is_some() {
+            "Cancel Export"
+        } else {
+            "Export Audio"
+        };
+        let show_export_audio = add_optional(export_label);
 
         let mut controls_row = row![]
             .spacing(10)
             .align_y(Vertical::Center)
             .width(Length::Fill);
         if show_prev_sentence {
-            controls_row = controls_row
-                .push(Self::control_button("Prev Sent").on_press(Message::SeekBackward));
+            controls_row = controls_row.push(
+                self.described(
+                    self.control_button("Prev Sent")
+                        .on_press(Message::SeekBackward),
+                    "Read the previous sentence",
+                ),
+            );
         }
-        controls_row = controls_row.push(play_button);
+        controls_row =
+            controls_row.push(self.described(play_button, "Start or pause reading aloud"));
         if show_next_sentence {
-            controls_row =
-                controls_row.push(Self::control_button("Next Sent").on_press(Message::SeekForward));
+            controls_row = controls_row.push(
+                self.described(
+                    self.control_button("Next Sent")
+                        .on_press(Message::SeekForward),
+                    "Skip to the next sentence",
+                ),
+            );
         }
         if show_play_page {
-            controls_row = controls_row.push(play_from_start);
+            controls_row = controls_row
+                .push(self.described(play_from_start, "Read this page aloud from the start"));
         }
         if show_play_from_highlight {
-            controls_row = controls_row.push(play_from_cursor);
+            controls_row = controls_row
+                .push(self.described(play_from_cursor, "Read aloud from the highlighted sentence"));
         }
         if show_jump {
-            controls_row = controls_row.push(jump_button);
+            controls_row =
+                controls_row.push(self.described(jump_button, "Scroll to the sentence being read"));
+        }
+        if show_cache_chapter {
+            let cache_button = self.control_button("Cache Chapter");
+            let cache_button = if self.tts.chapter_cache.is_none() {
+                cache_button.on_press(Message::CacheChapterAudio(
+                    self.chapter_index_for_page(self.reader.current_page),
+                ))
+            } else {
+                cache_button
+            };
+            controls_row = controls_row
+                .push(self.described(cache_button, "Prepare this chapter's audio ahead of time"));
+        }
+        if show_cache_book {
+            let cache_message = if self.tts.chapter_cache.is_some() {
+                Message::CancelAudioCache
+            } else {
+                Message::CacheBookAudio
+            };
+            controls_row = controls_row.push(
+                self.described(
+                    self.control_button(cache_book_label)
+                        .on_press(cache_message),
+                    "Prepare the whole book's audio for offline listening, or stop caching",
+                ),
+            );
+        }
+        if show_export_audio {
+            let export_message = if self.tts.audio_export.is_some() {
+                Message::CancelAudioExport
+            } else {
+                Message::ExportAudio
+            };
+            controls_row = controls_row.push(self.described(
+                self.control_button(export_label).on_press(export_message),
+                "Save the book's audio to a file, or stop a running export",
+            ));
         }
         controls_row = controls_row.push(horizontal_space());
         let controls = container(controls_row)
-            .height(Length::Fixed(42.0))
+            .height(Length::Fixed(self.controls_row_height()))
             .align_y(Vertical::Center)
             .width(Length::Fill);
 
-        container(
-            column![text("TTS Controls"), controls]
-                .spacing(8)
-                .padding(8),
+        let sentence_list_toggle = button(
+            text(if self.show_sentence_list {
+                "Hide Sentences"
+            } else {
+                "Sentences"
+            })
+            .size(13.0),
         )
-        .height(Length::Fixed(86.0))
-        .into()
-    }
-
-    fn word_count_for_page(&self, page: usize) -> usize {
-        self.reader
-            .pages
-            .get(page)
-            .map(|content| content.split_whitespace().count())
-            .unwrap_or(0)
-    }
+        .style(iced::widget::button::text)
+        .padding([2, 6])
+        .on_press(Message::ToggleSentenceList);
+        let mut header = row![text("TTS Controls"), sentence_list_toggle]
+            .spacing(12)
+            .align_y(Vertical::Center);
+        if self.tts.voices.len() > 1 {
+            let voice_ids: Vec<String> = self.tts.voices.iter().map(|v| v.id.clone()).collect();
+            let current = self
+                .tts
+                .engine
+                .as_ref()
+                .map(|engine| engine.voice_id().to_string());
+            header = header.push(
+                pick_list(voice_ids, current, Message::SetTtsVoice)
+                    .text_size(13.0)
+                    .padding([2, 6]),
+            );
+        }
+        if self.tts.engine.is_some() {
+            header = header.push(
+                button(text("Preview voice").size(13.0))
+                    .style(iced::widget::button::text)
+                    .padding([2, 6])
+                    .on_press(Message::SpeakSnippet(VOICE_PREVIEW_TEXT.to_string())),
+            );
+        }
+        if let Some(remaining) = self.estimated_time_remaining() {
+            header = header.push(
+                text(format!(
+                    "Book remaining: ~{}",
+                    Self::format_duration_dhms(remaining)
+                ))
+                .size(13.0),
+            );
+        }
+        if let Some(reason) = &self.tts_unavailable {
+            header = header.push(text(format!("TTS unavailable: {reason}")).size(13.0));
+        }
+        if let Some(status) = &self.tts.chapter_cache_status {
+            header = header.push(text(status.as_str()).size(13.0));
+        }
+        if let Some(status) = &self.tts.audio_export_status {
+            header = header.push(text(status.as_str()).size(13.0));
+        }
 
-    fn word_count_before_page(&self, page: usize) -> usize {
-        self.reader
-            .pages
-            .iter()
-            .take(page)
-            .map(|content| content.split_whitespace().count())
-            .sum()
+        let mut panel = column![header, controls].spacing(8).padding(8);
+        let mut height = 86.0;
+        if let Some(scrubber) = self.audio_scrubber() {
+            panel = panel.push(scrubber);
+            height += 28.0;
+        }
+        container(panel).height(Length::Fixed(height)).into()
     }
 
-    fn total_word_count(&self) -> usize {
-        self.reader
-            .pages
-            .iter()
-            .map(|content| content.split_whitespace().count())
-            .sum()
+    /// Slider over the page audio queued in `tts.track`, filling as it plays.
+    fn audio_scrubber(&self) -> Option<Element<'_, Message>> {
+        let marks = self
+            .tts
+            .track_marks(SentencePauses::from_config(&self.config).sentence);
+        let total = marks.last().copied().unwrap_or_default();
+        if total.is_zero() {
+            return None;
+        }
+        let position = self
+            .tts
+            .scrub_position
+            .unwrap_or_else(|| self.tts.track_position(Instant::now()))
+            .min(total);
+        let clock = |at: Duration| {
+            let secs = at.as_secs();
+            format!("{}:{:02}", secs / 60, secs % 60)
+        };
+        Some(
+            row![
+                text(clock(position)).size(12.0),
+                slider(
+                    0.0..=total.as_secs_f32(),
+                    position.as_secs_f32(),
+                    Message::AudioScrubbed
+                )
+                .step(0.1)
+                .on_release(Message::AudioScrubReleased),
+                text(clock(total)).size(12.0),
+            ]
+            .spacing(8)
+            .align_y(Vertical::Center)
+            .into(),
+        )
     }
 
     fn page_eta_label(&self) -> String {
@@ -1303,6 +2677,9 @@ impl App {
     }
 
     fn book_eta_label(&self) -> String {
+        if let Some(remaining) = self.estimated_time_remaining() {
+            return Self::format_duration_dhms(remaining);
+        }
         let page_remaining = self.estimate_remaining_page_duration();
         let average_sentence = self.estimated_avg_sentence_duration();
         let mut remaining_after_page = 0usize;
@@ -1334,10 +2711,15 @@ impl App {
                 let speech_remaining = self.tts.track[start..]
                     .iter()
                     .fold(Duration::ZERO, |acc, (_, d)| acc + *d);
-                let pause = Duration::from_secs_f32(self.config.pause_after_sentence.max(0.0));
-                let pause_remaining = Duration::from_secs_f64(
-                    pause.as_secs_f64() * (self.tts.track.len() - start) as f64,
-                );
+                let pause_remaining: Duration = self
+                    .tts
+                    .pauses_from(
+                        current_audio_idx,
+                        self.tts.track.len() - start,
+                        SentencePauses::from_config(&self.config).sentence,
+                    )
+                    .into_iter()
+                    .sum();
                 return speech_remaining + pause_remaining;
             }
         }
@@ -1375,11 +2757,13 @@ impl App {
         if !sentences.is_empty() {
             let total_chars: usize = sentences.iter().map(|s| s.chars().count()).sum();
             let avg_chars = total_chars as f64 / sentences.len() as f64;
-            let speech_secs = (avg_chars / 14.0) / self.config.tts_speed.max(0.1) as f64;
+            let speech_secs = (avg_chars / 14.0) / self.current_tts_speed().max(0.1) as f64;
             return Duration::from_secs_f64((speech_secs + pause.as_secs_f64()).max(0.1));
         }
 
-        Duration::from_secs_f64((2.5 / self.config.tts_speed.max(0.1)) as f64 + pause.as_secs_f64())
+        Duration::from_secs_f64(
+            (2.5 / self.current_tts_speed().max(0.1)) as f64 + pause.as_secs_f64(),
+        )
     }
 
     fn estimated_controls_width(&self) -> f32 {
@@ -1388,6 +2772,13 @@ impl App {
             // Settings panel is fixed width (280) plus row spacing (16).
             width = (width - 296.0).max(0.0);
         }
+        if self.show_sentence_list {
+            // Sentence list is the same fixed width as the settings panel.
+            width = (width - 296.0).max(0.0);
+        }
+        if self.show_notes {
+            width = (width - 296.0).max(0.0);
+        }
         // Reader content applies 16px horizontal padding on each side.
         (width - 32.0).max(0.0)
     }
@@ -1396,9 +2787,43 @@ impl App {
         self.estimated_controls_width().max(320.0)
     }
 
-    fn control_button<'a>(label: &'a str) -> iced::widget::Button<'a, Message> {
-        button(text(label).wrapping(Wrapping::None))
-            .width(Length::Fixed(estimate_button_width_px(label)))
+    fn control_button<'a>(&self, label: &'a str) -> iced::widget::Button<'a, Message> {
+        let accessible = self.config.accessibility_mode;
+        let label_text = text(label).wrapping(Wrapping::None);
+        let control = if accessible {
+            button(label_text.size(ACCESSIBLE_BUTTON_TEXT_SIZE)).padding(ACCESSIBLE_BUTTON_PADDING)
+        } else {
+            button(label_text)
+        };
+        control.width(Length::Fixed(estimate_button_width_px(label, accessible)))
+    }
+
+    /// `control` with a hover description of what it does in accessibility mode.
+    fn described<'a>(
+        &self,
+        control: impl Into<Element<'a, Message>>,
+        description: &'a str,
+    ) -> Element<'a, Message> {
+        if !self.config.accessibility_mode {
+            return control.into();
+        }
+        tooltip(
+            control,
+            container(text(description).size(ACCESSIBLE_BUTTON_TEXT_SIZE))
+                .padding(8)
+                .style(container::rounded_box),
+            tooltip::Position::Bottom,
+        )
+        .into()
+    }
+
+    /// Height of the top bar and TTS control rows.
+    fn controls_row_height(&self) -> f32 {
+        if self.config.accessibility_mode {
+            60.0
+        } else {
+            42.0
+        }
     }
 
     fn format_duration_dhms(duration: Duration) -> String {
@@ -1409,4 +2834,441 @@ impl App {
         let seconds = total_secs % 60;
         format!("{days}d {hours:02}h {minutes:02}m {seconds:02}s")
     }
+
+    /// Split a sentence into pieces that share one emphasis style and link target.
+    fn sentence_pieces(
+        &self,
+        sentence: String,
+        markup: SentenceMarkup<'_>,
+        active_word: Option<Range<usize>>,
+    ) -> Vec<SentencePiece> {
+        let SentenceMarkup {
+            emphasis,
+            footnotes,
+            headings,
+            search,
+            code,
+        } = markup;
+        let links: Vec<(Range<usize>, usize)> = if self.config.linkify_crossrefs {
+            find_crossrefs(&sentence)
+                .into_iter()
+                .filter_map(|found| Some((found.range, self.crossref_page(found.target)?)))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        if emphasis.is_empty()
+            && links.is_empty()
+            && footnotes.is_empty()
+            && headings.is_empty()
+            && search.is_empty()
+            && code.is_empty()
+            && active_word.is_none()
+        {
+            return vec![SentencePiece {
+                text: sentence,
+                start: 0,
+                style: None,
+                crossref_page: None,
+                footnote: None,
+                heading: None,
+                search_match: None,
+                active_word: false,
+                code: false,
+            }];
+        }
+
+        let mut bounds = vec![0, sentence.len()];
+        for run in emphasis {
+            bounds.extend([run.range.start, run.range.end]);
+        }
+        for (range, _) in &links {
+            bounds.extend([range.start, range.end]);
+        }
+        for marker in footnotes {
+            bounds.extend([marker.range.start, marker.range.end]);
+        }
+        for heading in headings {
+            bounds.extend([heading.range.start, heading.range.end]);
+        }
+        for (range, _) in search {
+            bounds.extend([range.start, range.end]);
+        }
+        for range in code {
+            bounds.extend([range.start, range.end]);
+        }
+        if let Some(range) = &active_word {
+            bounds.extend([range.start, range.end]);
+        }
+        bounds.retain(|bound| sentence.is_char_boundary(*bound));
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        bounds
+            .windows(2)
+            .map(|window| {
+                let (start, end) = (window[0], window[1]);
+                SentencePiece {
+                    text: sentence[start..end].to_string(),
+                    start,
+                    style: emphasis
+                        .iter()
+                        .find(|run| run.range.contains(&start))
+                        .map(|run| run.style),
+                    crossref_page: links
+                        .iter()
+                        .find(|(range, _)| range.contains(&start))
+                        .map(|(_, page)| *page),
+                    footnote: footnotes
+                        .iter()
+                        .find(|marker| marker.range.contains(&start))
+                        .map(|marker| marker.note),
+                    heading: headings
+                        .iter()
+                        .find(|heading| heading.range.contains(&start))
+                        .map(|heading| heading.level),
+                    search_match: search
+                        .iter()
+                        .find(|(range, _)| range.contains(&start))
+                        .map(|(_, selected)| *selected),
+                    active_word: active_word
+                        .as_ref()
+                        .is_some_and(|range| range.contains(&start)),
+                    code: code.iter().any(|range| range.contains(&start)),
+                }
+            })
+            .collect()
+    }
+
+    fn emphasis_font(&self, style: Option<EmphasisStyle>) -> Font {
+        let mut font = self.current_font();
+        if matches!(
+            style,
+            Some(EmphasisStyle::Italic | EmphasisStyle::BoldItalic)
+        ) {
+            font.style = iced::font::Style::Italic;
+        }
+        if matches!(style, Some(EmphasisStyle::Bold | EmphasisStyle::BoldItalic)) {
+            font.weight = iced::font::Weight::Bold;
+        }
+        font
+    }
+
+    fn format_timing_ms(duration: Duration) -> String {
+        format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+    }
+}
+
+/// Small closable card overlaid on the reading pane.
+fn popup_card<'a>(
+    title: String,
+    body: String,
+    hint: Option<&'a str>,
+    close: Message,
+) -> Element<'a, Message> {
+    let mut card = column![
+        row![
+            text(title).size(18.0),
+            horizontal_space(),
+            button("Close")
+                .style(iced::widget::button::text)
+                .on_press(close),
+        ]
+        .align_y(Vertical::Center),
+        text(body).size(14.0),
+    ]
+    .spacing(6);
+    if let Some(hint) = hint {
+        card = card.push(text(hint).size(12.0));
+    }
+    container(card)
+        .padding(12)
+        .width(Length::Fixed(320.0))
+        .style(container::rounded_box)
+        .into()
+}
+
+/// Entry in the top-bar chapter picker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ChapterChoice {
+    idx: usize,
+    label: String,
+}
+
+impl std::fmt::Display for ChapterChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
+/// Markup projected onto one page sentence, as byte ranges within it.
+#[derive(Default)]
+struct SentenceMarkup<'a> {
+    emphasis: &'a [SentenceEmphasis],
+    footnotes: &'a [SentenceFootnote],
+    headings: &'a [SentenceHeading],
+    /// Search matches; `true` for the selected match.
+    search: &'a [(Range<usize>, bool)],
+    /// Code block text, shown preformatted.
+    code: &'a [Range<usize>],
+}
+
+/// Markup moved onto a sentence whose blank-line runs were collapsed.
+struct CollapsedMarkup {
+    emphasis: Vec<SentenceEmphasis>,
+    footnotes: Vec<SentenceFootnote>,
+    headings: Vec<SentenceHeading>,
+    search: Vec<(Range<usize>, bool)>,
+}
+
+impl CollapsedMarkup {
+    fn new(markup: &SentenceMarkup<'_>, runs: &[Range<usize>]) -> Self {
+        let shift = |range: &Range<usize>| {
+            offset_after_collapse(range.start, runs)..offset_after_collapse(range.end, runs)
+        };
+        Self {
+            emphasis: markup
+                .emphasis
+                .iter()
+                .map(|run| SentenceEmphasis {
+                    range: shift(&run.range),
+                    ..run.clone()
+                })
+                .collect(),
+            footnotes: markup
+                .footnotes
+                .iter()
+                .map(|marker| SentenceFootnote {
+                    range: shift(&marker.range),
+                    ..marker.clone()
+                })
+                .collect(),
+            headings: markup
+                .headings
+                .iter()
+                .map(|heading| SentenceHeading {
+                    range: shift(&heading.range),
+                    ..heading.clone()
+                })
+                .collect(),
+            search: markup
+                .search
+                .iter()
+                .map(|(range, selected)| (shift(range), *selected))
+                .collect(),
+        }
+    }
+
+    /// Code sentences are never collapsed, so there is no code to carry over.
+    fn markup(&self) -> SentenceMarkup<'_> {
+        SentenceMarkup {
+            emphasis: &self.emphasis,
+            footnotes: &self.footnotes,
+            headings: &self.headings,
+            search: &self.search,
+            code: &[],
+        }
+    }
+}
+
+/// A run of sentence text sharing one emphasis style and link target.
+struct SentencePiece {
+    text: String,
+    /// Byte offset of `text` in its sentence.
+    start: usize,
+    style: Option<EmphasisStyle>,
+    crossref_page: Option<usize>,
+    footnote: Option<usize>,
+    /// Heading level when the piece is part of a chapter or section title.
+    heading: Option<u8>,
+    /// Part of a search match; `true` for the selected match.
+    search_match: Option<bool>,
+    active_word: bool,
+    /// Part of a code block.
+    code: bool,
+}
+
+/// `run`, found at byte `start` of its sentence, with the sentence's soft
+/// hyphens that fall inside it.
+fn hyphenate_run(run: String, start: usize, breaks: &[usize]) -> String {
+    let end = start + run.len();
+    let local: Vec<usize> = breaks
+        .iter()
+        .filter(|split| (start + 1..end).contains(*split))
+        .map(|split| split - start)
+        .collect();
+    if local.is_empty() {
+        run
+    } else {
+        insert_soft_hyphens(&run, &local)
+    }
+}
+
+/// Share of the ruler height for `pages` out of `total` pages.
+fn ruler_portion(pages: usize, total: usize) -> Length {
+    Length::FillPortion((pages * 1000 / total.max(1)).clamp(1, 1000) as u16)
+}
+
+/// Mark a span of the spoken sentence in the configured style.
+fn highlight_span<'a>(
+    span: iced::widget::text::Span<'a, Message>,
+    style: HighlightStyle,
+    color: Color,
+) -> iced::widget::text::Span<'a, Message> {
+    match style {
+        HighlightStyle::Background => span.background(iced::Background::Color(color)),
+        // The underline takes the text color, so the sentence is drawn in the
+        // highlight color too.
+        HighlightStyle::Underline => span.underline(true).color(Color { a: 1.0, ..color }),
+        HighlightStyle::Box => span.border(Border {
+            color: Color { a: 1.0, ..color },
+            width: 1.0,
+            radius: 3.0.into(),
+        }),
+    }
+}
+
+/// Mark a span of a search match so it stays distinct from the spoken-sentence
+/// highlight: an outline, or a fill when that highlight is itself a box.
+fn search_match_span<'a>(
+    span: iced::widget::text::Span<'a, Message>,
+    tts_style: HighlightStyle,
+    color: Color,
+    selected: bool,
+) -> iced::widget::text::Span<'a, Message> {
+    if tts_style == HighlightStyle::Box {
+        let alpha = if selected { 0.45 } else { 0.25 };
+        return span.background(iced::Background::Color(Color { a: alpha, ..color }));
+    }
+    span.border(Border {
+        color,
+        width: if selected { 2.0 } else { 1.0 },
+        radius: 3.0.into(),
+    })
+}
+
+/// Regroup page spans into paragraphs, splitting spans that straddle a break.
+///
+/// Span boundaries, and so sentence links and highlights, are left as they were.
+fn paragraph_blocks(
+    spans: Vec<iced::widget::text::Span<'_, Message>>,
+) -> Vec<Vec<iced::widget::text::Span<'_, Message>>> {
+    let mut blocks = vec![Vec::new()];
+    for span in spans {
+        let pieces = split_paragraph_breaks(&span.text);
+        if pieces.len() == 1 {
+            blocks.last_mut().expect("at least one block").push(span);
+            continue;
+        }
+        for (n, piece) in pieces.into_iter().enumerate() {
+            if n > 0 {
+                blocks.push(Vec::new());
+            }
+            if !piece.is_empty() {
+                let mut part = span.clone();
+                part.text = piece.to_string().into();
+                blocks.last_mut().expect("at least one block").push(part);
+            }
+        }
+    }
+    blocks.retain(|block| block.iter().any(|span| !span.text.trim().is_empty()));
+    blocks
+}
+
+/// Split `text` at paragraph breaks: whitespace runs holding two or more newlines.
+fn split_paragraph_breaks(text: &str) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((idx, ch)) = chars.next() {
+        if !ch.is_whitespace() {
+            continue;
+        }
+        let mut end = idx + ch.len_utf8();
+        let mut newlines = usize::from(ch == '\n');
+        while let Some(&(next_idx, next)) = chars.peek() {
+            if !next.is_whitespace() {
+                break;
+            }
+            newlines += usize::from(next == '\n');
+            end = next_idx + next.len_utf8();
+            chars.next();
+        }
+        if newlines >= 2 {
+            pieces.push(&text[start..idx]);
+            start = end;
+        }
+    }
+    pieces.push(&text[start..]);
+    pieces
+}
+
+/// Font size multiplier for a heading of `level` (1 for `<h1>`).
+fn heading_scale(level: u8) -> f32 {
+    match level {
+        1 => 1.6,
+        2 => 1.4,
+        3 => 1.25,
+        _ => 1.1,
+    }
+}
+
+/// Byte range of the `n`th whitespace-separated word in `sentence`.
+fn nth_word_range(sentence: &str, n: usize) -> Option<Range<usize>> {
+    sentence.split_whitespace().nth(n).map(|word| {
+        let start = word.as_ptr() as usize - sentence.as_ptr() as usize;
+        start..start + word.len()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::widget::text::Span;
+
+    #[test]
+    fn paragraph_breaks_need_two_newlines() {
+        assert_eq!(
+            split_paragraph_breaks("One.\nStill one.\n\nTwo. \n \t\n Three."),
+            vec!["One.\nStill one.", "Two.", "Three."]
+        );
+        assert_eq!(
+            split_paragraph_breaks("One. \n Still one."),
+            vec!["One. \n Still one."]
+        );
+        assert_eq!(split_paragraph_breaks("\n\nLead."), vec!["", "Lead."]);
+        assert_eq!(split_paragraph_breaks("Tail.\n\n"), vec!["Tail.", ""]);
+        assert_eq!(
+            split_paragraph_breaks("Ünïcödé\n\n日本"),
+            vec!["Ünïcödé", "日本"]
+        );
+    }
+
+    #[test]
+    fn paragraph_blocks_split_spans_and_keep_their_links() {
+        let spans = vec![
+            Span::new("First. ").link(Message::SentenceClicked(0)),
+            Span::new("Also first.\n\nSecond ").link(Message::SentenceClicked(1)),
+            Span::new("part.\n\n\n\n"),
+            Span::new("  ").link(Message::SentenceClicked(2)),
+        ];
+        let blocks = paragraph_blocks(spans);
+        let texts: Vec<Vec<&str>> = blocks
+            .iter()
+            .map(|block| block.iter().map(|span| span.text.as_ref()).collect())
+            .collect();
+        assert_eq!(
+            texts,
+            vec![vec!["First. ", "Also first."], vec!["Second ", "part."]]
+        );
+        assert!(matches!(
+            blocks[0][1].link,
+            Some(Message::SentenceClicked(1))
+        ));
+        assert!(matches!(
+            blocks[1][0].link,
+            Some(Message::SentenceClicked(1))
+        ));
+        assert!(blocks[1][1].link.is_none());
+    }
 }
@@ -4,26 +4,49 @@
 //! Files are stored under `.cache/` using a hash of the source file contents
 //! as the directory name so path aliases do not fragment the cache. The format
 //! is a tiny TOML file with a `page` field plus optional `sentence_idx`,
-//! `sentence_text`, and `scroll_y` for resuming inside the page.
+//! `sentence_text`, and `scroll_y` for resuming inside the page. Named
+//! bookmarks use the same fields plus a `name`, one file each under
+//! `bookmarks/`, so they never overwrite the resume position. Cumulative
+//! reading statistics live next to the bookmark in `stats.toml`, and the
+//! reader's own notes, each anchored to a page and sentence, in `notes.toml`.
+//!
+//! The one global file is `recents.toml` at the cache root, listing recently
+//! opened books newest first. Synthesized sentence audio is shared by every
+//! book under `tts/<voice>/`; a clip's modification time is refreshed when it
+//! is played so `tts_cache_max_mb` can evict the least recently used ones.
+//! Each book lists the clips it has played in `audio-clips.txt`, so clearing
+//! one book's audio leaves clips another book still uses.
 
 use crate::config::{AppConfig, parse_config, serialize_config};
 use epub::doc::EpubDoc;
 use image::codecs::jpeg::JpegEncoder;
 use image::imageops::FilterType;
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::Cursor;
 use std::io::Read;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::{Mutex, OnceLock};
-use std::time::UNIX_EPOCH;
-use tracing::{debug, warn};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
 
 pub const CACHE_DIR: &str = ".cache";
 const SOURCE_PATH_FILE: &str = "source-path.txt";
+/// Synthesized sentence clips, shared by every book.
+const SENTENCE_AUDIO_DIR: &str = "tts";
+const RECENTS_FILE: &str = "recents.toml";
+/// Per-book list of the shared clips the book uses, relative to `tts/`.
+const BOOK_CLIPS_FILE: &str = "audio-clips.txt";
+/// Most books kept in `recents.toml`.
+const MAX_RECENTS: usize = 64;
+/// Shortest gap between two walks of the shared audio cache for eviction.
+const EVICTION_INTERVAL: Duration = Duration::from_secs(30);
+const PROGRESS_FORMAT: &str = "ebup-viewer-progress";
+const PROGRESS_VERSION: u32 = 1;
 static CONTENT_DIGEST_CACHE: OnceLock<Mutex<HashMap<PathBuf, SourceDigestEntry>>> = OnceLock::new();
+static LAST_EVICTION: Mutex<Option<Instant>> = Mutex::new(None);
 
 #[derive(Clone)]
 struct SourceDigestEntry {
@@ -32,7 +55,7 @@ struct SourceDigestEntry {
     digest: String,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Bookmark {
     pub page: usize,
     #[serde(default)]
@@ -43,6 +66,48 @@ pub struct Bookmark {
     pub scroll_y: f32,
 }
 
+/// Cumulative reading activity for one book.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ReadingStats {
+    /// Seconds spent with the reader window focused.
+    #[serde(default)]
+    pub total_seconds: u64,
+    #[serde(default)]
+    pub sessions: u64,
+    #[serde(default)]
+    pub pages_turned: u64,
+    /// Text offset of the furthest page read through in order, so chapters
+    /// stay read after paging back or changing the layout.
+    #[serde(default)]
+    pub furthest_offset: usize,
+    /// Words on pages read silently and timed, for the long-term reading speed.
+    #[serde(default)]
+    pub silent_words: u64,
+    /// Seconds spent on those pages.
+    #[serde(default)]
+    pub silent_seconds: u64,
+}
+
+/// A reader-written note anchored to a page and, where known, a sentence.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Note {
+    pub page: usize,
+    #[serde(default)]
+    pub sentence_idx: Option<usize>,
+    /// The anchored sentence, so jumps survive re-pagination like bookmarks do.
+    #[serde(default)]
+    pub sentence_text: Option<String>,
+    pub text: String,
+    #[serde(default)]
+    pub created_at: u64,
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct NotesFile {
+    #[serde(default)]
+    notes: Vec<Note>,
+}
+
 #[derive(Debug, Clone)]
 pub struct RecentBook {
     pub source_path: PathBuf,
@@ -51,6 +116,20 @@ pub struct RecentBook {
     pub last_opened_unix_secs: u64,
 }
 
+/// A book in the global recents store.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RecentEntry {
+    pub path: PathBuf,
+    pub title: String,
+    pub opened_unix_secs: u64,
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct RecentsFile {
+    #[serde(default)]
+    books: Vec<RecentEntry>,
+}
+
 fn default_scroll() -> f32 {
     0.0
 }
@@ -102,6 +181,336 @@ pub fn save_bookmark(epub_path: &Path, bookmark: &Bookmark) {
     }
 }
 
+/// Load the reading statistics for a book; missing or unreadable files start from zero.
+pub fn load_stats(epub_path: &Path) -> ReadingStats {
+    let path = stats_path(epub_path);
+    let Ok(data) = fs::read_to_string(&path) else {
+        return ReadingStats::default();
+    };
+    toml::from_str(&data).unwrap_or_else(|err| {
+        warn!(path = %path.display(), "Ignoring unreadable reading stats: {err}");
+        ReadingStats::default()
+    })
+}
+
+/// Persist the reading statistics for a book. Errors are logged and otherwise ignored.
+pub fn save_stats(epub_path: &Path, stats: &ReadingStats) {
+    let path = stats_path(epub_path);
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    match toml::to_string(stats) {
+        Ok(contents) => {
+            if let Err(err) = fs::write(&path, contents) {
+                warn!(path = %path.display(), "Failed to save reading stats: {err}");
+            } else {
+                debug!(seconds = stats.total_seconds, "Saved reading stats");
+            }
+        }
+        Err(err) => warn!("Failed to serialize reading stats: {err}"),
+    }
+}
+
+fn stats_path(epub_path: &Path) -> PathBuf {
+    hash_dir(epub_path).join("stats.toml")
+}
+
+/// Load the notes for a book in the order they were written; a missing or
+/// unreadable file yields none.
+pub fn load_notes(epub_path: &Path) -> Vec<Note> {
+    let path = notes_path(epub_path);
+    match read_notes_file(&path) {
+        Ok(file) => file.notes,
+        Err(err) => {
+            warn!(path = %path.display(), "Ignoring unreadable notes: {err}");
+            Vec::new()
+        }
+    }
+}
+
+/// The notes file at `path`; a missing file is an empty list.
+fn read_notes_file(path: &Path) -> Result<NotesFile, String> {
+    match fs::read_to_string(path) {
+        Ok(data) => toml::from_str(&data).map_err(|err| err.to_string()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(NotesFile::default()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Append `note` to the book's notes file.
+pub fn save_note(epub_path: &Path, note: &Note) -> Result<(), String> {
+    if note.text.trim().is_empty() {
+        return Err("note is empty".to_string());
+    }
+    let path = notes_path(epub_path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    // Writing over a file we couldn't read would destroy every note in it.
+    let mut file = read_notes_file(&path)
+        .map_err(|err| format!("existing notes at {} are unreadable: {err}", path.display()))?;
+    file.notes.push(note.clone());
+    let contents = toml::to_string(&file).map_err(|err| err.to_string())?;
+    fs::write(&path, contents).map_err(|err| err.to_string())?;
+    debug!(page = note.page, count = file.notes.len(), "Saved note");
+    Ok(())
+}
+
+fn notes_path(epub_path: &Path) -> PathBuf {
+    hash_dir(epub_path).join("notes.toml")
+}
+
+/// Portable reading progress, keyed by the source content hash so it can be
+/// matched against the same book stored under a different path.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+struct ProgressExport {
+    format: String,
+    version: u32,
+    book_id: String,
+    location: String,
+    #[serde(default)]
+    sentence_text: Option<String>,
+    #[serde(default)]
+    scroll_y: f32,
+    #[serde(default)]
+    exported_at_unix_secs: u64,
+    #[serde(default)]
+    bookmarks: Vec<ExportedBookmark>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+struct ExportedBookmark {
+    name: String,
+    location: String,
+    #[serde(default)]
+    sentence_text: Option<String>,
+}
+
+/// Serialize the cached bookmark for `epub_path` as portable progress JSON.
+pub fn export_progress(epub_path: &Path) -> Result<String, String> {
+    let book_id = source_content_hash(epub_path)
+        .ok_or_else(|| format!("cannot read {} to identify the book", epub_path.display()))?;
+    let bookmark = load_bookmark(epub_path).unwrap_or(Bookmark {
+        page: 0,
+        sentence_idx: None,
+        sentence_text: None,
+        scroll_y: 0.0,
+    });
+    let export = ProgressExport {
+        format: PROGRESS_FORMAT.to_string(),
+        version: PROGRESS_VERSION,
+        book_id,
+        location: format_location(&bookmark),
+        sentence_text: bookmark.sentence_text,
+        scroll_y: bookmark.scroll_y,
+        exported_at_unix_secs: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        bookmarks: load_named_bookmarks(epub_path)
+            .into_iter()
+            .map(|(name, named)| ExportedBookmark {
+                name,
+                location: format_location(&named),
+                sentence_text: named.sentence_text,
+            })
+            .collect(),
+    };
+    serde_json::to_string_pretty(&export).map_err(|err| err.to_string())
+}
+
+/// Validate exported progress against `epub_path` and persist it as the
+/// book's resume bookmark, merging in any named bookmarks. Mismatched books
+/// and unknown formats are rejected.
+pub fn import_progress(epub_path: &Path, json: &str) -> Result<Bookmark, String> {
+    let book_id = source_content_hash(epub_path)
+        .ok_or_else(|| format!("cannot read {} to identify the book", epub_path.display()))?;
+    let (bookmark, named) = parse_progress(json, &book_id)?;
+    save_bookmark(epub_path, &bookmark);
+    for (name, named_bookmark) in &named {
+        save_named_bookmark(epub_path, name, named_bookmark)?;
+    }
+    Ok(bookmark)
+}
+
+fn parse_progress(
+    json: &str,
+    book_id: &str,
+) -> Result<(Bookmark, Vec<(String, Bookmark)>), String> {
+    let export: ProgressExport =
+        serde_json::from_str(json.trim()).map_err(|err| format!("not progress JSON: {err}"))?;
+    if export.format != PROGRESS_FORMAT {
+        return Err(format!("unknown progress format '{}'", export.format));
+    }
+    if export.version > PROGRESS_VERSION {
+        return Err(format!(
+            "progress version {} is newer than supported version {PROGRESS_VERSION}",
+            export.version
+        ));
+    }
+    if export.book_id != book_id {
+        return Err(format!(
+            "progress belongs to a different book ({} vs {})",
+            short_id(&export.book_id),
+            short_id(book_id)
+        ));
+    }
+    let (page, sentence_idx) = parse_location(&export.location)
+        .ok_or_else(|| format!("invalid location '{}'", export.location))?;
+    let resume = Bookmark {
+        page,
+        sentence_idx,
+        sentence_text: export.sentence_text,
+        scroll_y: if export.scroll_y.is_finite() {
+            export.scroll_y.clamp(0.0, 1.0)
+        } else {
+            0.0
+        },
+    };
+    let named = export
+        .bookmarks
+        .into_iter()
+        .map(|exported| {
+            let (page, sentence_idx) = parse_location(&exported.location).ok_or_else(|| {
+                format!(
+                    "invalid location '{}' for bookmark '{}'",
+                    exported.location, exported.name
+                )
+            })?;
+            Ok((
+                exported.name,
+                Bookmark {
+                    page,
+                    sentence_idx,
+                    sentence_text: exported.sentence_text,
+                    scroll_y: 0.0,
+                },
+            ))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok((resume, named))
+}
+
+fn short_id(id: &str) -> &str {
+    id.get(..12).unwrap_or(id)
+}
+
+/// Render a bookmark as a location string such as `page=12;sentence=4`
+/// (both 1-based).
+pub fn format_location(bookmark: &Bookmark) -> String {
+    match bookmark.sentence_idx {
+        Some(idx) => format!("page={};sentence={}", bookmark.page + 1, idx + 1),
+        None => format!("page={}", bookmark.page + 1),
+    }
+}
+
+/// Parse a location string into a zero-based page and optional sentence index.
+pub fn parse_location(location: &str) -> Option<(usize, Option<usize>)> {
+    let mut page = None;
+    let mut sentence = None;
+    for part in location.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+        let (key, value) = part.split_once('=')?;
+        let value: usize = value.trim().parse().ok()?;
+        let index = value.checked_sub(1)?;
+        match key.trim() {
+            "page" => page = Some(index),
+            "sentence" => sentence = Some(index),
+            _ => return None,
+        }
+    }
+    Some((page?, sentence))
+}
+
+/// Save `bookmark` under `name`, replacing any bookmark with the same name.
+pub fn save_named_bookmark(
+    epub_path: &Path,
+    name: &str,
+    bookmark: &Bookmark,
+) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("bookmark name is empty".to_string());
+    }
+    let path = named_bookmark_path(epub_path, name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let entry = NamedCacheEntry {
+        name: name.to_string(),
+        entry: CacheEntry {
+            page: bookmark.page,
+            sentence_idx: bookmark.sentence_idx,
+            sentence_text: bookmark.sentence_text.clone(),
+            scroll_y: Some(bookmark.scroll_y),
+        },
+    };
+    let contents = toml::to_string(&entry).map_err(|err| err.to_string())?;
+    fs::write(&path, contents).map_err(|err| err.to_string())?;
+    debug!(name, page = bookmark.page, "Saved named bookmark");
+    Ok(())
+}
+
+/// Load every named bookmark for a book, ordered by position in the book.
+pub fn load_named_bookmarks(epub_path: &Path) -> Vec<(String, Bookmark)> {
+    let Ok(entries) = fs::read_dir(named_bookmarks_dir(epub_path)) else {
+        return Vec::new();
+    };
+    let mut bookmarks: Vec<(String, Bookmark)> = entries
+        .flatten()
+        .filter(|entry| entry.path().extension().and_then(|ext| ext.to_str()) == Some("toml"))
+        .filter_map(|entry| {
+            let data = fs::read_to_string(entry.path()).ok()?;
+            let named: NamedCacheEntry = match toml::from_str(&data) {
+                Ok(named) => named,
+                Err(err) => {
+                    warn!(path = %entry.path().display(), "Skipping invalid named bookmark: {err}");
+                    return None;
+                }
+            };
+            let bookmark = Bookmark {
+                page: named.entry.page,
+                sentence_idx: named.entry.sentence_idx,
+                sentence_text: named.entry.sentence_text,
+                scroll_y: named.entry.scroll_y.unwrap_or_else(default_scroll),
+            };
+            Some((named.name, bookmark))
+        })
+        .collect();
+    bookmarks.sort_by(|(a_name, a), (b_name, b)| {
+        (a.page, a.sentence_idx, a_name).cmp(&(b.page, b.sentence_idx, b_name))
+    });
+    bookmarks
+}
+
+/// Remove the named bookmark, if it exists.
+pub fn delete_named_bookmark(epub_path: &Path, name: &str) -> Result<(), String> {
+    let path = named_bookmark_path(epub_path, name.trim());
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn named_bookmarks_dir(epub_path: &Path) -> PathBuf {
+    hash_dir(epub_path).join("bookmarks")
+}
+
+fn named_bookmark_path(epub_path: &Path, name: &str) -> PathBuf {
+    // Names are free text, so the file name is derived from a hash of it.
+    let mut hasher = Sha256::new();
+    hasher.update(name.as_bytes());
+    let digest = format!("{:x}", hasher.finalize());
+    named_bookmarks_dir(epub_path).join(format!("{}.toml", &digest[..16]))
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct NamedCacheEntry {
+    name: String,
+    #[serde(flatten)]
+    entry: CacheEntry,
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 struct CacheEntry {
     page: usize,
@@ -124,8 +533,18 @@ pub fn hash_dir(epub_path: &Path) -> PathBuf {
 }
 
 fn source_content_hash(path: &Path) -> Option<String> {
-    let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
-    let metadata = fs::metadata(&canonical).ok()?;
+    let canonical = fs::canonicalize(path)
+        .or_else(|_| std::path::absolute(path))
+        .unwrap_or_else(|_| path.to_path_buf());
+    let cache = CONTENT_DIGEST_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let Ok(metadata) = fs::metadata(&canonical) else {
+        // A source moved or deleted while open keeps its last digest, so the
+        // open book keeps saving into its own cache directory.
+        return cache
+            .lock()
+            .ok()
+            .and_then(|guard| guard.get(&canonical).map(|entry| entry.digest.clone()));
+    };
     let len = metadata.len();
     let modified_unix_secs = metadata
         .modified()
@@ -134,7 +553,6 @@ fn source_content_hash(path: &Path) -> Option<String> {
         .map(|d| d.as_secs())
         .unwrap_or(0);
 
-    let cache = CONTENT_DIGEST_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
     if let Ok(guard) = cache.lock() {
         if let Some(entry) = guard.get(&canonical) {
             if entry.len == len && entry.modified_unix_secs == modified_unix_secs {
@@ -185,6 +603,130 @@ pub fn remember_source_path(source_path: &Path) {
     }
 }
 
+/// Record `source_path` as the most recently opened book. Without a title the
+/// EPUB title or file name is used.
+pub fn push_recent(source_path: &Path, title: Option<&str>) {
+    let path = fs::canonicalize(source_path).unwrap_or_else(|_| source_path.to_path_buf());
+    let title = title
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| infer_recent_title(&path));
+    let opened_unix_secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut books = read_recents_file();
+    record_recent(
+        &mut books,
+        RecentEntry {
+            path,
+            title,
+            opened_unix_secs,
+        },
+    );
+    write_recents_file(&books);
+}
+
+/// Recently opened books, newest first. Entries whose file is gone are
+/// dropped from the store.
+pub fn load_recents() -> Vec<RecentEntry> {
+    let mut books = read_recents_file();
+    let before = books.len();
+    books.retain(|entry| entry.path.exists());
+    if books.len() != before {
+        debug!(pruned = before - books.len(), "Pruned missing recent books");
+        write_recents_file(&books);
+    }
+    books
+}
+
+/// Point the cache and recents entry of a book at its new location after the
+/// file was moved or renamed.
+///
+/// Caches are keyed by content, so a plain move keeps the same directory; an
+/// edited file gets its old cache moved over unless the new one already exists.
+pub fn migrate_book_cache(old_path: &Path, new_path: &Path) -> Result<(), String> {
+    if !new_path.is_file() {
+        return Err(format!("{} is not a file", new_path.display()));
+    }
+    let old_dir = hash_dir(old_path);
+    let new_dir = hash_dir(new_path);
+    if old_dir != new_dir && old_dir.exists() {
+        if new_dir.exists() {
+            return Err(format!(
+                "{} already has its own cached progress",
+                new_path.display()
+            ));
+        }
+        fs::rename(&old_dir, &new_dir).map_err(|err| err.to_string())?;
+        info!(
+            from = %old_dir.display(),
+            to = %new_dir.display(),
+            "Moved book cache"
+        );
+    }
+    remember_source_path(new_path);
+
+    // The old file is gone, so it can only be made absolute, not canonical.
+    let old_path = std::path::absolute(old_path).unwrap_or_else(|_| old_path.to_path_buf());
+    let new_path = fs::canonicalize(new_path).unwrap_or_else(|_| new_path.to_path_buf());
+    let mut books = read_recents_file();
+    if rename_recent(&mut books, &old_path, &new_path) {
+        write_recents_file(&books);
+    }
+    Ok(())
+}
+
+/// Re-point the recents entry for `old_path`, returning whether one was found.
+fn rename_recent(books: &mut Vec<RecentEntry>, old_path: &Path, new_path: &Path) -> bool {
+    if old_path == new_path || !books.iter().any(|entry| entry.path == old_path) {
+        return false;
+    }
+    books.retain(|entry| entry.path != new_path);
+    for entry in books.iter_mut().filter(|entry| entry.path == old_path) {
+        entry.path = new_path.to_path_buf();
+    }
+    true
+}
+
+/// Move `entry` to the front of `books`, replacing any older entry for the same path.
+fn record_recent(books: &mut Vec<RecentEntry>, entry: RecentEntry) {
+    books.retain(|existing| existing.path != entry.path);
+    books.insert(0, entry);
+    books.truncate(MAX_RECENTS);
+}
+
+fn read_recents_file() -> Vec<RecentEntry> {
+    let path = Path::new(CACHE_DIR).join(RECENTS_FILE);
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    match toml::from_str::<RecentsFile>(&contents) {
+        Ok(file) => file.books,
+        Err(err) => {
+            warn!(path = %path.display(), "Ignoring unreadable recents file: {err}");
+            Vec::new()
+        }
+    }
+}
+
+fn write_recents_file(books: &[RecentEntry]) {
+    let path = Path::new(CACHE_DIR).join(RECENTS_FILE);
+    let file = RecentsFile {
+        books: books.to_vec(),
+    };
+    let result = toml::to_string(&file)
+        .map_err(|err| err.to_string())
+        .and_then(|contents| {
+            fs::create_dir_all(CACHE_DIR).map_err(|err| err.to_string())?;
+            fs::write(&path, contents).map_err(|err| err.to_string())
+        });
+    if let Err(err) = result {
+        warn!(path = %path.display(), "Failed to save recents: {err}");
+    }
+}
+
 pub fn persist_clipboard_text_source(text: &str) -> Result<PathBuf, String> {
     let normalized = text.replace("\r\n", "\n").replace('\r', "\n");
     let trimmed = normalized.trim();
@@ -226,12 +768,197 @@ pub fn delete_recent_source_and_cache(source_path: &Path) -> Result<(), String>
     Ok(())
 }
 
+/// Remove one book's cache directory: settings, bookmarks, reading stats and
+/// normalization plans. Shared sentence audio lives elsewhere.
+pub fn clear_book_cache(epub_path: &Path) -> Result<(), String> {
+    let cache_path = hash_dir(epub_path);
+    if cache_path.exists() {
+        fs::remove_dir_all(&cache_path).map_err(|err| err.to_string())?;
+    }
+    Ok(())
+}
+
+/// Voices with cached sentence audio on disk.
+pub fn cached_audio_voices() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(Path::new(CACHE_DIR).join(SENTENCE_AUDIO_DIR)) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect()
+}
+
+/// Remember that the book at `epub_path` uses `clips`, so clearing another
+/// book's audio keeps them.
+pub fn record_book_clips<'a>(epub_path: &Path, clips: impl IntoIterator<Item = &'a PathBuf>) {
+    let audio_root = Path::new(CACHE_DIR).join(SENTENCE_AUDIO_DIR);
+    let path = hash_dir(epub_path).join(BOOK_CLIPS_FILE);
+    let known: HashSet<String> = fs::read_to_string(&path)
+        .map(|contents| contents.lines().map(str::to_string).collect())
+        .unwrap_or_default();
+    let mut added = String::new();
+    let mut seen = HashSet::new();
+    for clip in clips {
+        let Some(name) = clip
+            .strip_prefix(&audio_root)
+            .ok()
+            .and_then(|name| name.to_str())
+        else {
+            continue;
+        };
+        if !known.contains(name) && seen.insert(name) {
+            added.push_str(name);
+            added.push('\n');
+        }
+    }
+    if added.is_empty() {
+        return;
+    }
+    let appended = fs::create_dir_all(hash_dir(epub_path)).and_then(|()| {
+        fs::File::options()
+            .create(true)
+            .append(true)
+            .open(&path)?
+            .write_all(added.as_bytes())
+    });
+    if let Err(err) = appended {
+        warn!(path = %path.display(), "Failed to record the book's audio clips: {err}");
+    }
+}
+
+/// Clips recorded by any book other than the one at `epub_path`.
+pub fn clips_used_by_other_books(epub_path: &Path) -> HashSet<PathBuf> {
+    book_clip_references(Path::new(CACHE_DIR), &hash_dir(epub_path))
+}
+
+fn book_clip_references(cache_root: &Path, except: &Path) -> HashSet<PathBuf> {
+    let Ok(entries) = fs::read_dir(cache_root) else {
+        return HashSet::new();
+    };
+    let audio_root = cache_root.join(SENTENCE_AUDIO_DIR);
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|dir| dir.file_name() != except.file_name())
+        .filter_map(|dir| fs::read_to_string(dir.join(BOOK_CLIPS_FILE)).ok())
+        .flat_map(|contents| {
+            contents
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(|line| audio_root.join(line))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Mark a cached clip as just used so LRU eviction keeps it longest.
+pub fn touch_sentence_clip(path: &Path) {
+    let touched = fs::File::options()
+        .write(true)
+        .open(path)
+        .and_then(|file| file.set_modified(SystemTime::now()));
+    if let Err(err) = touched {
+        debug!(path = %path.display(), "Failed to touch cached clip: {err}");
+    }
+}
+
+/// Delete least recently used sentence clips until the shared audio cache
+/// fits in `max_bytes`, never touching a clip in `keep` or one written at or
+/// after `in_flight_since` (work still running may be about to queue it).
+/// The cache is walked at most once per [`EVICTION_INTERVAL`], so the limit
+/// can be overshot briefly. Returns how many clips were removed.
+pub fn enforce_tts_cache_limit(
+    max_bytes: u64,
+    keep: &HashSet<PathBuf>,
+    in_flight_since: SystemTime,
+) -> usize {
+    {
+        let mut last = LAST_EVICTION
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if last.is_some_and(|at| at.elapsed() < EVICTION_INTERVAL) {
+            return 0;
+        }
+        *last = Some(Instant::now());
+    }
+    evict_clips(
+        &Path::new(CACHE_DIR).join(SENTENCE_AUDIO_DIR),
+        max_bytes,
+        keep,
+        in_flight_since,
+    )
+}
+
+fn evict_clips(
+    audio_root: &Path,
+    max_bytes: u64,
+    keep: &HashSet<PathBuf>,
+    in_flight_since: SystemTime,
+) -> usize {
+    let Ok(voices) = fs::read_dir(audio_root) else {
+        return 0;
+    };
+    // Only finished clips count; `.wav.part` files are still being written.
+    let mut clips: Vec<(SystemTime, u64, PathBuf)> = voices
+        .flatten()
+        .filter_map(|voice| fs::read_dir(voice.path()).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "wav"))
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok().filter(|meta| meta.is_file())?;
+            let used = meta.modified().unwrap_or(UNIX_EPOCH);
+            Some((used, meta.len(), entry.path()))
+        })
+        .collect();
+    let mut total: u64 = clips.iter().map(|(_, len, _)| len).sum();
+    if total <= max_bytes {
+        return 0;
+    }
+    clips.sort_by_key(|(used, _, _)| *used);
+    let mut removed = 0;
+    for (used, len, path) in clips {
+        if total <= max_bytes {
+            break;
+        }
+        if used >= in_flight_since || keep.contains(&path) {
+            continue;
+        }
+        match fs::remove_file(&path) {
+            Ok(()) => {
+                total = total.saturating_sub(len);
+                removed += 1;
+            }
+            Err(err) => warn!(path = %path.display(), "Failed to evict cached clip: {err}"),
+        }
+    }
+    info!(
+        removed,
+        remaining_bytes = total,
+        max_bytes,
+        "Evicted cached sentence audio"
+    );
+    removed
+}
+
+/// Books for the starter's recents panel: the recents store, plus books that
+/// only have a cache directory (opened before the store existed).
 pub fn list_recent_books(limit: usize) -> Vec<RecentBook> {
+    let mut books: Vec<RecentBook> = load_recents()
+        .into_iter()
+        .map(|entry| RecentBook {
+            thumbnail_path: infer_recent_thumbnail(&entry.path),
+            source_path: entry.path,
+            display_title: entry.title,
+            last_opened_unix_secs: entry.opened_unix_secs,
+        })
+        .collect();
     let Ok(entries) = fs::read_dir(CACHE_DIR) else {
-        return Vec::new();
+        return books;
     };
 
-    let mut books: Vec<RecentBook> = entries
+    let scanned: Vec<RecentBook> = entries
         .flatten()
         .filter_map(|entry| {
             let Ok(file_type) = entry.file_type() else {
@@ -243,7 +970,10 @@ pub fn list_recent_books(limit: usize) -> Vec<RecentBook> {
             let source_hint_path = entry.path().join(SOURCE_PATH_FILE);
             let source_path_raw = fs::read_to_string(&source_hint_path).ok()?;
             let source_path = PathBuf::from(source_path_raw.trim());
-            if source_path.as_os_str().is_empty() || !source_path.exists() {
+            if source_path.as_os_str().is_empty()
+                || !source_path.exists()
+                || books.iter().any(|book| book.source_path == source_path)
+            {
                 return None;
             }
             let last_opened_unix_secs = fs::metadata(&source_hint_path)
@@ -262,6 +992,7 @@ pub fn list_recent_books(limit: usize) -> Vec<RecentBook> {
             })
         })
         .collect();
+    books.extend(scanned);
 
     books.sort_by(|a, b| b.last_opened_unix_secs.cmp(&a.last_opened_unix_secs));
     books.dedup_by(|a, b| a.source_path == b.source_path);
@@ -271,14 +1002,105 @@ pub fn list_recent_books(limit: usize) -> Vec<RecentBook> {
     books
 }
 
-pub fn tts_dir(epub_path: &Path) -> PathBuf {
-    hash_dir(epub_path).join("tts")
+/// Cached clip for one normalized sentence spoken by `voice`.
+///
+/// The key is the sentence text rather than its position, so repagination and
+/// repeated lines across books reuse the same audio. Speed is applied during
+/// playback, so one clip serves every speed.
+pub fn sentence_cache_path(text: &str, voice: &str) -> PathBuf {
+    let mut hasher = Sha256::new();
+    hasher.update(voice.as_bytes());
+    hasher.update([0]);
+    hasher.update(text.as_bytes());
+    Path::new(CACHE_DIR)
+        .join(SENTENCE_AUDIO_DIR)
+        .join(voice)
+        .join(format!("tts-{:x}.wav", hasher.finalize()))
 }
 
 pub fn normalized_dir(epub_path: &Path) -> PathBuf {
     hash_dir(epub_path).join("normalized")
 }
 
+/// Destination for a whole-book audio export, named after the source file.
+pub fn audio_export_path(epub_path: &Path) -> PathBuf {
+    let stem = epub_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("book");
+    hash_dir(epub_path)
+        .join("exports")
+        .join(format!("{stem}.wav"))
+}
+
+/// Destination for a bookmark export, next to the audio exports.
+pub fn bookmarks_export_path(epub_path: &Path) -> PathBuf {
+    let stem = epub_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("book");
+    hash_dir(epub_path)
+        .join("exports")
+        .join(format!("{stem}-bookmarks.md"))
+}
+
+/// Write the resume position and every named bookmark to `output` as a markdown
+/// list headed by the book title, returning how many bookmarks were written.
+pub fn export_bookmarks(
+    epub_path: &Path,
+    title: Option<&str>,
+    output: &Path,
+) -> Result<usize, String> {
+    let resume = load_bookmark(epub_path);
+    let named = load_named_bookmarks(epub_path);
+    let title = title
+        .map(str::to_string)
+        .unwrap_or_else(|| infer_recent_title(epub_path));
+    let markdown = bookmarks_markdown(&title, resume.as_ref(), &named);
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    fs::write(output, markdown).map_err(|err| err.to_string())?;
+    Ok(named.len() + usize::from(resume.is_some()))
+}
+
+fn bookmarks_markdown(
+    title: &str,
+    resume: Option<&Bookmark>,
+    named: &[(String, Bookmark)],
+) -> String {
+    let mut out = format!("# {}\n\n", title.trim());
+    if resume.is_none() && named.is_empty() {
+        out.push_str("No bookmarks yet.\n");
+        return out;
+    }
+    if let Some(bookmark) = resume {
+        out.push_str(&format!("- **Last read**: {}\n", bookmark_line(bookmark)));
+    }
+    for (name, bookmark) in named {
+        out.push_str(&format!(
+            "- **{}**: {}\n",
+            name.trim(),
+            bookmark_line(bookmark)
+        ));
+    }
+    out
+}
+
+/// "Page N — sentence text", or just the page when no sentence was captured.
+fn bookmark_line(bookmark: &Bookmark) -> String {
+    let page = bookmark.page + 1;
+    let sentence = bookmark
+        .sentence_text
+        .as_deref()
+        .map(|text| text.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|text| !text.is_empty());
+    match sentence {
+        Some(sentence) => format!("Page {page} — {sentence}"),
+        None => format!("Page {page}"),
+    }
+}
+
 fn infer_recent_title(source_path: &Path) -> String {
     if source_path
         .parent()
@@ -377,6 +1199,50 @@ pub fn load_epub_config(epub_path: &Path) -> Option<AppConfig> {
     }
 }
 
+/// Config a book opens with, given the base config from `config.toml`.
+///
+/// With `per_book_overrides` on in the base config, the book's cached config
+/// wins, except for machine-wide settings (logging, TTS workers, keybindings)
+/// that always come from the base. With it off the cache is not read and the
+/// base config applies as is.
+pub fn load_book_config(base_config: &AppConfig, epub_path: &Path) -> AppConfig {
+    let cached = base_config
+        .per_book_overrides
+        .then(|| load_epub_config(epub_path))
+        .flatten();
+    merge_book_config(base_config, cached)
+}
+
+fn merge_book_config(base_config: &AppConfig, cached: Option<AppConfig>) -> AppConfig {
+    let Some(mut overrides) = cached else {
+        return base_config.clone();
+    };
+    info!("Loaded per-epub overrides from cache");
+    overrides.per_book_overrides = base_config.per_book_overrides;
+    // Always honor the base config's log level so user changes take effect.
+    overrides.log_level = base_config.log_level;
+    overrides.log_timing_metrics = base_config.log_timing_metrics;
+    // Always honor base TTS worker count and lookahead to avoid stale cached values.
+    overrides.tts_threads = base_config.tts_threads;
+    overrides.tts_lookahead = base_config.tts_lookahead;
+    // Always honor base progress logging cadence for batch generation.
+    overrides.tts_progress_log_interval_secs = base_config.tts_progress_log_interval_secs;
+    // Always honor base keybinding configuration.
+    overrides.key_toggle_play_pause = base_config.key_toggle_play_pause.clone();
+    overrides.key_safe_quit = base_config.key_safe_quit.clone();
+    overrides.key_next_sentence = base_config.key_next_sentence.clone();
+    overrides.key_prev_sentence = base_config.key_prev_sentence.clone();
+    overrides.key_next_page = base_config.key_next_page.clone();
+    overrides.key_prev_page = base_config.key_prev_page.clone();
+    overrides.key_repeat_sentence = base_config.key_repeat_sentence.clone();
+    overrides.key_toggle_search = base_config.key_toggle_search.clone();
+    overrides.key_toggle_settings = base_config.key_toggle_settings.clone();
+    overrides.key_toggle_stats = base_config.key_toggle_stats.clone();
+    overrides.key_toggle_tts = base_config.key_toggle_tts.clone();
+    overrides.key_map = base_config.key_map.clone();
+    overrides
+}
+
 pub fn save_epub_config(epub_path: &Path, config: &AppConfig) {
     let dir = hash_dir(epub_path);
     let path = dir.join("config.toml");
@@ -391,3 +1257,313 @@ pub fn save_epub_config(epub_path: &Path, config: &AppConfig) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bookmarks_export_as_a_markdown_list() {
+        let at = |page, sentence_text: Option<&str>| Bookmark {
+            page,
+            sentence_idx: Some(0),
+            sentence_text: sentence_text.map(str::to_string),
+            scroll_y: 0.0,
+        };
+        let resume = at(41, Some("It was a bright\ncold day in April."));
+        let named = vec![
+            ("Opening".to_string(), at(0, Some("Call me Ishmael."))),
+            ("Map".to_string(), at(6, None)),
+        ];
+        assert_eq!(
+            bookmarks_markdown("Some Book", Some(&resume), &named),
+            "# Some Book\n\n\
+             - **Last read**: Page 42 — It was a bright cold day in April.\n\
+             - **Opening**: Page 1 — Call me Ishmael.\n\
+             - **Map**: Page 7\n"
+        );
+        assert_eq!(
+            bookmarks_markdown("Empty", None, &[]),
+            "# Empty\n\nNo bookmarks yet.\n"
+        );
+    }
+
+    #[test]
+    fn notes_append_and_reload_in_order() {
+        let epub_path =
+            std::env::temp_dir().join(format!("ebup-notes-test-{}.epub", std::process::id()));
+        let note = |page, text: &str| Note {
+            page,
+            sentence_idx: Some(2),
+            sentence_text: Some("Call me Ishmael.".to_string()),
+            text: text.to_string(),
+            created_at: 1_700_000_000,
+        };
+        assert!(load_notes(&epub_path).is_empty());
+
+        save_note(&epub_path, &note(4, "Narrator introduced")).unwrap();
+        save_note(&epub_path, &note(1, "Look up \"hypos\"")).unwrap();
+        assert!(save_note(&epub_path, &note(0, "  ")).is_err());
+        assert_eq!(
+            load_notes(&epub_path),
+            vec![note(4, "Narrator introduced"), note(1, "Look up \"hypos\"")]
+        );
+
+        // A damaged file is left alone rather than replaced by the new note.
+        fs::write(notes_path(&epub_path), "notes = [ broken").unwrap();
+        assert!(save_note(&epub_path, &note(2, "Lost?")).is_err());
+        assert_eq!(
+            fs::read_to_string(notes_path(&epub_path)).unwrap(),
+            "notes = [ broken"
+        );
+
+        let _ = fs::remove_dir_all(hash_dir(&epub_path));
+    }
+
+    #[test]
+    fn named_bookmarks_round_trip_replace_and_delete() {
+        let epub_path = std::env::temp_dir().join(format!(
+            "ebup-named-bookmarks-test-{}.epub",
+            std::process::id()
+        ));
+        let at = |page, sentence_idx, text: &str| Bookmark {
+            page,
+            sentence_idx: Some(sentence_idx),
+            sentence_text: Some(text.to_string()),
+            scroll_y: 0.25,
+        };
+        let names = |bookmarks: Vec<(String, Bookmark)>| -> Vec<String> {
+            bookmarks.into_iter().map(|(name, _)| name).collect()
+        };
+        assert!(load_named_bookmarks(&epub_path).is_empty());
+
+        save_named_bookmark(&epub_path, "  Chase  ", &at(9, 3, "Thar she blows!")).unwrap();
+        save_named_bookmark(&epub_path, "Opening", &at(0, 0, "Call me Ishmael.")).unwrap();
+        assert!(save_named_bookmark(&epub_path, "   ", &at(1, 0, "Blank")).is_err());
+        assert_eq!(
+            load_named_bookmarks(&epub_path),
+            vec![
+                ("Opening".to_string(), at(0, 0, "Call me Ishmael.")),
+                ("Chase".to_string(), at(9, 3, "Thar she blows!")),
+            ]
+        );
+
+        // Saving under an existing name moves that bookmark instead of adding one.
+        save_named_bookmark(&epub_path, "Chase", &at(12, 1, "The whale rose.")).unwrap();
+        assert_eq!(
+            load_named_bookmarks(&epub_path)[1],
+            ("Chase".to_string(), at(12, 1, "The whale rose."))
+        );
+
+        // A rename is a save under the new name and a delete of the old one.
+        save_named_bookmark(&epub_path, "Final chase", &at(12, 1, "The whale rose.")).unwrap();
+        delete_named_bookmark(&epub_path, " Chase ").unwrap();
+        assert_eq!(
+            names(load_named_bookmarks(&epub_path)),
+            vec!["Opening", "Final chase"]
+        );
+
+        delete_named_bookmark(&epub_path, "Opening").unwrap();
+        assert!(delete_named_bookmark(&epub_path, "Opening").is_ok());
+        assert_eq!(names(load_named_bookmarks(&epub_path)), vec!["Final chase"]);
+
+        let _ = fs::remove_dir_all(hash_dir(&epub_path));
+    }
+
+    #[test]
+    fn sentence_clips_are_keyed_by_text_and_voice() {
+        let clip = sentence_cache_path("Call me Ishmael.", "en_US-amy-medium");
+        assert_eq!(
+            clip,
+            sentence_cache_path("Call me Ishmael.", "en_US-amy-medium")
+        );
+        assert!(clip.starts_with(Path::new(CACHE_DIR).join("tts").join("en_US-amy-medium")));
+        assert_ne!(
+            clip,
+            sentence_cache_path("Call me Ishmael.", "en_GB-alan-low")
+        );
+        assert_ne!(
+            clip,
+            sentence_cache_path("Call me Ahab.", "en_US-amy-medium")
+        );
+    }
+
+    #[test]
+    fn eviction_drops_least_recently_used_clips_outside_the_track() {
+        let root = std::env::temp_dir().join(format!("ebup-evict-test-{}", std::process::id()));
+        let voice = root.join("en_US-amy-medium");
+        fs::create_dir_all(&voice).unwrap();
+        let clips: Vec<PathBuf> = (0..4)
+            .map(|idx| voice.join(format!("tts-{idx}.wav")))
+            .collect();
+        for (idx, clip) in clips.iter().enumerate() {
+            fs::write(clip, [0u8; 100]).unwrap();
+            let used = UNIX_EPOCH + std::time::Duration::from_secs(1_000 + idx as u64);
+            fs::File::options()
+                .write(true)
+                .open(clip)
+                .unwrap()
+                .set_modified(used)
+                .unwrap();
+        }
+
+        // A clip still being synthesized neither counts nor gets removed.
+        let partial = voice.join("tts-4.wav.part");
+        fs::write(&partial, [0u8; 500]).unwrap();
+        let now = SystemTime::now();
+
+        assert_eq!(evict_clips(&root, 400, &HashSet::new(), now), 0);
+        // The oldest clip is playing and the newest was written by work
+        // still in flight, so only the two in between can go.
+        let playing = HashSet::from([clips[0].clone()]);
+        let in_flight_since = UNIX_EPOCH + std::time::Duration::from_secs(1_003);
+        assert_eq!(evict_clips(&root, 0, &playing, in_flight_since), 2);
+        assert!(clips[0].exists() && !clips[1].exists() && !clips[2].exists());
+        assert!(clips[3].exists() && partial.exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn clips_of_other_books_are_found_by_their_manifests() {
+        let root = std::env::temp_dir().join(format!("ebup-clip-refs-test-{}", std::process::id()));
+        for (book, clips) in [
+            ("book-a", "voice/tts-1.wav\nvoice/tts-2.wav\n"),
+            ("book-b", "voice/tts-2.wav\nvoice/tts-3.wav\n"),
+        ] {
+            fs::create_dir_all(root.join(book)).unwrap();
+            fs::write(root.join(book).join(BOOK_CLIPS_FILE), clips).unwrap();
+        }
+        let clip = |name: &str| root.join(SENTENCE_AUDIO_DIR).join("voice").join(name);
+
+        let others = book_clip_references(&root, &root.join("book-a"));
+        assert_eq!(
+            others,
+            HashSet::from([clip("tts-2.wav"), clip("tts-3.wav")])
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn location_strings_round_trip() {
+        let bookmark = Bookmark {
+            page: 11,
+            sentence_idx: Some(3),
+            sentence_text: None,
+            scroll_y: 0.0,
+        };
+        let location = format_location(&bookmark);
+        assert_eq!(location, "page=12;sentence=4");
+        assert_eq!(parse_location(&location), Some((11, Some(3))));
+        assert_eq!(parse_location("page=2"), Some((1, None)));
+        assert_eq!(parse_location("page=0"), None);
+        assert_eq!(parse_location("chapter=3"), None);
+    }
+
+    #[test]
+    fn progress_import_rejects_other_books_and_formats() {
+        let export = ProgressExport {
+            format: PROGRESS_FORMAT.to_string(),
+            version: PROGRESS_VERSION,
+            book_id: "a".repeat(64),
+            location: "page=3;sentence=2".to_string(),
+            sentence_text: Some("Hello there.".to_string()),
+            scroll_y: 0.25,
+            exported_at_unix_secs: 0,
+            bookmarks: vec![ExportedBookmark {
+                name: "Quote".to_string(),
+                location: "page=1;sentence=3".to_string(),
+                sentence_text: None,
+            }],
+        };
+        let json = serde_json::to_string(&export).expect("serialize");
+
+        let (bookmark, named) = parse_progress(&json, &"a".repeat(64)).expect("matching book");
+        assert_eq!(bookmark.page, 2);
+        assert_eq!(bookmark.sentence_idx, Some(1));
+        assert_eq!(bookmark.sentence_text.as_deref(), Some("Hello there."));
+        assert_eq!(named.len(), 1);
+        assert_eq!(named[0].0, "Quote");
+        assert_eq!((named[0].1.page, named[0].1.sentence_idx), (0, Some(2)));
+
+        let err = parse_progress(&json, &"b".repeat(64)).expect_err("different book");
+        assert!(err.contains("different book"), "{err}");
+
+        let foreign = json.replace(PROGRESS_FORMAT, "other-app");
+        assert!(parse_progress(&foreign, &"a".repeat(64)).is_err());
+        assert!(parse_progress("{not json", &"a".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn cached_book_config_wins_except_machine_settings() {
+        let mut base = AppConfig::default();
+        base.font_size = 18;
+        base.tts_threads = 6;
+        let mut cached = AppConfig::default();
+        cached.font_size = 30;
+        cached.tts_threads = 1;
+        cached.per_book_overrides = false;
+
+        let merged = merge_book_config(&base, Some(cached));
+        assert_eq!(merged.font_size, 30);
+        assert_eq!(merged.tts_threads, 6);
+        assert!(merged.per_book_overrides);
+        assert_eq!(merge_book_config(&base, None).font_size, 18);
+    }
+
+    #[test]
+    fn moved_sources_keep_their_cache_and_recents_entry() {
+        let dir = std::env::temp_dir().join(format!("ebup-moved-source-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("book.txt");
+        fs::write(&source, "Call me Ishmael.").unwrap();
+        let before = hash_dir(&source);
+        fs::remove_file(&source).unwrap();
+        assert_eq!(hash_dir(&source), before);
+        let _ = fs::remove_dir_all(&dir);
+
+        let entry = |path: &str| RecentEntry {
+            path: PathBuf::from(path),
+            title: "Book".to_string(),
+            opened_unix_secs: 1,
+        };
+        let mut books = vec![entry("/old/book.epub"), entry("/new/book.epub")];
+        assert!(rename_recent(
+            &mut books,
+            Path::new("/old/book.epub"),
+            Path::new("/new/book.epub")
+        ));
+        assert_eq!(books, vec![entry("/new/book.epub")]);
+        assert!(!rename_recent(
+            &mut books,
+            Path::new("/missing.epub"),
+            Path::new("/new/book.epub")
+        ));
+    }
+
+    #[test]
+    fn recents_move_reopened_books_to_the_front() {
+        let entry = |name: &str, opened_unix_secs| RecentEntry {
+            path: PathBuf::from(format!("/books/{name}.epub")),
+            title: name.to_string(),
+            opened_unix_secs,
+        };
+        let mut books = Vec::new();
+        record_recent(&mut books, entry("a", 1));
+        record_recent(&mut books, entry("b", 2));
+        record_recent(&mut books, entry("a", 3));
+        assert_eq!(books, vec![entry("a", 3), entry("b", 2)]);
+
+        for n in 0..MAX_RECENTS + 5 {
+            record_recent(&mut books, entry(&n.to_string(), n as u64));
+        }
+        assert_eq!(books.len(), MAX_RECENTS);
+        assert_eq!(books[0].title, (MAX_RECENTS + 4).to_string());
+
+        let file = RecentsFile { books };
+        let round_trip: RecentsFile =
+            toml::from_str(&toml::to_string(&file).expect("serialize")).expect("parse");
+        assert_eq!(round_trip.books, file.books);
+    }
+}
@@ -101,11 +101,11 @@ pub(crate) fn default_key_safe_quit() -> String {
 }
 
 pub(crate) fn default_key_next_sentence() -> String {
-    "f".to_string()
+    "f,j".to_string()
 }
 
 pub(crate) fn default_key_prev_sentence() -> String {
-    "s".to_string()
+    "s,k".to_string()
 }
 
 pub(crate) fn default_key_repeat_sentence() -> String {
@@ -127,3 +127,225 @@ pub(crate) fn default_key_toggle_stats() -> String {
 pub(crate) fn default_key_toggle_tts() -> String {
     "ctrl+y".to_string()
 }
+
+pub(crate) fn default_collapse_duplicate_pages() -> bool {
+    false
+}
+
+pub(crate) fn default_snap_resume_to_sentence() -> bool {
+    true
+}
+
+pub(crate) fn default_log_timing_metrics() -> bool {
+    false
+}
+
+pub(crate) fn default_preserve_emphasis() -> bool {
+    false
+}
+
+pub(crate) fn default_linkify_crossrefs() -> bool {
+    false
+}
+
+pub(crate) fn default_word_level_highlight() -> bool {
+    false
+}
+
+pub(crate) fn default_key_next_page() -> String {
+    "right".to_string()
+}
+
+pub(crate) fn default_key_prev_page() -> String {
+    "left".to_string()
+}
+
+pub(crate) fn default_fold_orphan_pages() -> bool {
+    false
+}
+
+pub(crate) fn default_dictionary_path() -> String {
+    String::new()
+}
+
+pub(crate) fn default_lookup_words_on_click() -> bool {
+    false
+}
+
+pub(crate) fn default_footnote_mode() -> crate::config::FootnoteMode {
+    crate::config::FootnoteMode::EndOfChapter
+}
+
+pub(crate) fn default_skip_leading_pages() -> usize {
+    0
+}
+
+pub(crate) fn default_tts_voice() -> String {
+    String::new()
+}
+
+pub(crate) fn default_text_direction() -> crate::config::TextDirection {
+    crate::config::TextDirection::Auto
+}
+
+pub(crate) fn default_auto_page_seconds() -> Option<f32> {
+    None
+}
+
+pub(crate) fn default_show_chapter_ruler() -> bool {
+    false
+}
+
+pub(crate) fn default_image_alt_text() -> crate::config::ImageAltText {
+    crate::config::ImageAltText::Omit
+}
+
+pub(crate) fn default_tts_lookahead() -> usize {
+    1
+}
+
+pub(crate) fn default_pause_on_focus_loss() -> bool {
+    false
+}
+
+pub(crate) fn default_resume_on_focus() -> bool {
+    false
+}
+
+pub(crate) fn default_reading_mode() -> crate::config::ReadingMode {
+    crate::config::ReadingMode::Paged
+}
+
+pub(crate) fn default_continuous_above_pages() -> usize {
+    0
+}
+
+pub(crate) fn default_highlight_style() -> crate::config::HighlightStyle {
+    crate::config::HighlightStyle::Background
+}
+
+pub(crate) fn default_paragraph_spacing() -> f32 {
+    0.0
+}
+
+pub(crate) fn default_reading_ruler() -> bool {
+    false
+}
+
+pub(crate) fn default_reading_ruler_position() -> f32 {
+    0.4
+}
+
+pub(crate) fn default_pause_after_clause() -> Option<f32> {
+    None
+}
+
+pub(crate) fn default_pause_after_paragraph() -> f32 {
+    0.0
+}
+
+pub(crate) fn default_auto_theme_schedule() -> Option<(u8, u8)> {
+    None
+}
+
+pub(crate) fn default_max_text_width() -> Option<u32> {
+    None
+}
+
+pub(crate) fn default_per_book_overrides() -> bool {
+    true
+}
+
+pub(crate) fn default_day_search_highlight() -> crate::config::HighlightColor {
+    crate::config::HighlightColor {
+        r: 0.85,
+        g: 0.45,
+        b: 0.0,
+        a: 0.9,
+    }
+}
+
+pub(crate) fn default_night_search_highlight() -> crate::config::HighlightColor {
+    crate::config::HighlightColor {
+        r: 1.0,
+        g: 0.65,
+        b: 0.2,
+        a: 0.9,
+    }
+}
+
+pub(crate) fn default_on_book_end() -> crate::config::BookEndAction {
+    crate::config::BookEndAction::Stop
+}
+
+pub(crate) fn default_custom_font() -> Option<String> {
+    None
+}
+
+pub(crate) fn default_custom_colors() -> Option<crate::config::CustomColors> {
+    None
+}
+
+pub(crate) fn default_compact_controls() -> bool {
+    false
+}
+
+pub(crate) fn default_speed_ramp() -> Option<crate::config::SpeedRamp> {
+    None
+}
+
+pub(crate) fn default_smooth_scroll() -> bool {
+    false
+}
+
+pub(crate) fn default_collapse_blank_lines() -> bool {
+    false
+}
+
+pub(crate) fn default_auto_resume_tts() -> bool {
+    false
+}
+
+pub(crate) fn default_accessibility_mode() -> bool {
+    false
+}
+
+pub(crate) fn default_hyphenate() -> bool {
+    false
+}
+
+pub(crate) fn default_tts_cache_max_mb() -> u64 {
+    0
+}
+
+pub(crate) fn default_announce_chapters() -> bool {
+    false
+}
+
+pub(crate) fn default_tts_group_sentences() -> usize {
+    1
+}
+
+pub(crate) fn default_strip_running_headers() -> bool {
+    false
+}
+
+pub(crate) fn default_running_header_min_repeats() -> usize {
+    10
+}
+
+pub(crate) fn default_play_starts_at_scroll() -> bool {
+    false
+}
+
+pub(crate) fn default_preformatted_blocks() -> bool {
+    false
+}
+
+pub(crate) fn default_speak_code_blocks() -> bool {
+    true
+}
+
+pub(crate) fn default_reading_speed_idle_secs() -> u64 {
+    300
+}
@@ -1,5 +1,7 @@
 use super::models::AppConfig;
+use super::paths::{base_config_path, saved_settings_path};
 use super::tables::ConfigTables;
+use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::fs;
 use std::path::Path;
@@ -40,6 +42,28 @@ pub fn load_config(path: &Path) -> AppConfig {
     }
 }
 
+/// Config every book starts from: the settings saved from the reader when
+/// there are any, else the base config. `per_book_overrides` always comes from
+/// the base config.
+pub fn load_app_config() -> AppConfig {
+    let base = load_config(base_config_path());
+    let path = saved_settings_path();
+    let Ok(contents) = fs::read_to_string(path) else {
+        return base;
+    };
+    match parse_config(&contents) {
+        Ok(mut saved) => {
+            info!(path = %path.display(), "Loaded saved settings");
+            saved.per_book_overrides = base.per_book_overrides;
+            saved
+        }
+        Err(err) => {
+            warn!(path = %path.display(), "Ignoring invalid saved settings: {err}");
+            base
+        }
+    }
+}
+
 pub fn parse_config(contents: &str) -> Result<AppConfig, toml::de::Error> {
     let cfg = toml::from_str::<ConfigInput>(contents)?;
     Ok(match cfg {
@@ -51,3 +75,115 @@ pub fn parse_config(contents: &str) -> Result<AppConfig, toml::de::Error> {
 pub fn serialize_config(config: &AppConfig) -> Result<String, toml::ser::Error> {
     toml::to_string(&ConfigTables::from(config))
 }
+
+/// Write `config` as TOML to `path`, creating its directory if needed.
+pub fn save_config(config: &AppConfig, path: &Path) -> Result<()> {
+    let contents = serialize_config(config).context("Serializing config")?;
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Creating config directory {}", parent.display()))?;
+    }
+    fs::write(path, contents).with_context(|| format!("Writing config {}", path.display()))?;
+    debug!(path = %path.display(), "Persisted config");
+    Ok(())
+}
+
+/// Write `config` as a standalone profile other users can import.
+pub fn export_profile(config: &AppConfig, path: &Path) -> Result<()> {
+    save_config(config, path)?;
+    info!(path = %path.display(), "Exported config profile");
+    Ok(())
+}
+
+/// Read a profile written by [`export_profile`] or any config TOML.
+///
+/// Unlike [`load_config`], errors are returned instead of falling back to
+/// defaults, and values are not range-checked here.
+pub fn import_profile(path: &Path) -> Result<AppConfig> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Reading profile {}", path.display()))?;
+    let config =
+        parse_config(&contents).with_context(|| format!("Parsing profile {}", path.display()))?;
+    info!(path = %path.display(), "Imported config profile");
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CustomColors, KeyAction, ThemeMode};
+
+    #[test]
+    fn profiles_round_trip_and_report_bad_files() {
+        let dir = std::env::temp_dir().join(format!("ebup-profile-test-{}", std::process::id()));
+        let path = dir.join("nested").join("profile.toml");
+        let mut config = AppConfig::default();
+        config.font_size = 31;
+        config.theme = ThemeMode::Sepia;
+        config.tts_voice = "en_GB-alba-medium".to_string();
+
+        export_profile(&config, &path).expect("export");
+        let imported = import_profile(&path).expect("import");
+        assert_eq!(imported.font_size, 31);
+        assert_eq!(imported.theme, ThemeMode::Sepia);
+        assert_eq!(imported.tts_voice, "en_GB-alba-medium");
+
+        fs::write(&path, "[appearance\nfont_size = ").expect("write bad profile");
+        assert!(import_profile(&path).is_err());
+        assert!(import_profile(&dir.join("missing.toml")).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn key_map_keeps_known_actions_and_survives_saving() {
+        let config = parse_config(
+            "[keybindings]\n\
+             next_page = \"n\"\n\
+             \"Ctrl+D\" = \"toggle_theme\"\n\
+             x = \"launch_rockets\"\n\
+             y = 5\n",
+        )
+        .expect("parse");
+        assert_eq!(config.key_next_page, "n");
+        assert_eq!(
+            config.key_map.into_iter().collect::<Vec<_>>(),
+            vec![("ctrl+d".to_string(), KeyAction::ToggleTheme)]
+        );
+
+        let mut config = AppConfig::default();
+        config
+            .key_map
+            .insert("ctrl+d".to_string(), KeyAction::ToggleTheme);
+        let reparsed = parse_config(&serialize_config(&config).expect("serialize")).expect("parse");
+        assert_eq!(reparsed.key_map, config.key_map);
+    }
+
+    #[test]
+    fn legacy_theme_names_and_custom_colors_load() {
+        let day = parse_config("[appearance]\ntheme = \"day\"\n").expect("parse");
+        assert_eq!(day.theme, ThemeMode::Day);
+        let night = parse_config("[appearance]\ntheme = \"night\"\n").expect("parse");
+        assert_eq!(night.theme, ThemeMode::Night);
+        assert_eq!(night.custom_colors, None);
+
+        let mut config = AppConfig::default();
+        config.theme = ThemeMode::Custom {
+            bg: [0.25, 0.5, 0.75],
+            fg: [1.0, 0.875, 0.0],
+        };
+        config.custom_colors = config.theme.custom_colors();
+        let serialized = serialize_config(&config).expect("serialize");
+        let reparsed = parse_config(&serialized).expect("parse");
+        assert_eq!(reparsed.theme, config.theme);
+        assert_eq!(
+            reparsed.custom_colors,
+            Some(CustomColors {
+                bg: [0.25, 0.5, 0.75],
+                fg: [1.0, 0.875, 0.0],
+            })
+        );
+    }
+}
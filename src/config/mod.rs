@@ -1,13 +1,22 @@
 //! Configuration loading for the EPUB viewer.
 //!
-//! All user-tunable settings are centralized here and loaded from
-//! `conf/config.toml` if present. Any missing or invalid entries fall back to
-//! sensible defaults so the UI can still launch.
+//! All user-tunable settings are centralized here and loaded from the first
+//! `config.toml` found by the lookup in `paths.rs`. Any missing or invalid
+//! entries fall back to sensible defaults so the UI can still launch.
 
 mod defaults;
 mod io;
 mod models;
+mod paths;
 mod tables;
 
-pub use io::{load_config, parse_config, serialize_config};
-pub use models::{AppConfig, FontFamily, FontWeight, HighlightColor, LogLevel, ThemeMode};
+pub use io::{
+    export_profile, import_profile, load_app_config, load_config, parse_config, save_config,
+    serialize_config,
+};
+pub use models::{
+    AppConfig, BookEndAction, CustomColors, FontFamily, FontWeight, FootnoteMode, HighlightColor,
+    HighlightStyle, ImageAltText, KeyAction, LogLevel, ReadingMode, SpeedRamp, TextDirection,
+    ThemeMode,
+};
+pub(crate) use paths::{fonts_dir, init_config_paths, normalizer_config_path, saved_settings_path};
@@ -1,7 +1,8 @@
 use serde::Deserialize;
+use std::collections::BTreeMap;
 
 /// High-level app configuration; deserializable from TOML.
-#[derive(Debug, Clone, Deserialize, serde::Serialize)]
+#[derive(Debug, Clone, Deserialize, serde::Serialize, PartialEq)]
 pub struct AppConfig {
     #[serde(default)]
     pub theme: ThemeMode,
@@ -77,6 +78,117 @@ pub struct AppConfig {
     pub key_toggle_stats: String,
     #[serde(default = "crate::config::defaults::default_key_toggle_tts")]
     pub key_toggle_tts: String,
+    #[serde(default = "crate::config::defaults::default_collapse_duplicate_pages")]
+    pub collapse_duplicate_pages: bool,
+    #[serde(default = "crate::config::defaults::default_snap_resume_to_sentence")]
+    pub snap_resume_to_sentence: bool,
+    #[serde(default = "crate::config::defaults::default_log_timing_metrics")]
+    pub log_timing_metrics: bool,
+    #[serde(default = "crate::config::defaults::default_preserve_emphasis")]
+    pub preserve_emphasis: bool,
+    #[serde(default = "crate::config::defaults::default_linkify_crossrefs")]
+    pub linkify_crossrefs: bool,
+    #[serde(default = "crate::config::defaults::default_word_level_highlight")]
+    pub word_level_highlight: bool,
+    #[serde(default = "crate::config::defaults::default_key_next_page")]
+    pub key_next_page: String,
+    #[serde(default = "crate::config::defaults::default_key_prev_page")]
+    pub key_prev_page: String,
+    /// Extra bindings from `[keybindings]`, key to action; checked before the
+    /// per-action keys above.
+    #[serde(default)]
+    pub key_map: BTreeMap<String, KeyAction>,
+    #[serde(default = "crate::config::defaults::default_fold_orphan_pages")]
+    pub fold_orphan_pages: bool,
+    #[serde(default = "crate::config::defaults::default_dictionary_path")]
+    pub dictionary_path: String,
+    #[serde(default = "crate::config::defaults::default_lookup_words_on_click")]
+    pub lookup_words_on_click: bool,
+    #[serde(default = "crate::config::defaults::default_footnote_mode")]
+    pub footnote_mode: FootnoteMode,
+    #[serde(default = "crate::config::defaults::default_skip_leading_pages")]
+    pub skip_leading_pages: usize,
+    #[serde(default = "crate::config::defaults::default_tts_voice")]
+    pub tts_voice: String,
+    #[serde(default = "crate::config::defaults::default_text_direction")]
+    pub text_direction: TextDirection,
+    #[serde(default = "crate::config::defaults::default_auto_page_seconds")]
+    pub auto_page_seconds: Option<f32>,
+    #[serde(default = "crate::config::defaults::default_show_chapter_ruler")]
+    pub show_chapter_ruler: bool,
+    #[serde(default = "crate::config::defaults::default_image_alt_text")]
+    pub image_alt_text: ImageAltText,
+    #[serde(default = "crate::config::defaults::default_tts_lookahead")]
+    pub tts_lookahead: usize,
+    #[serde(default = "crate::config::defaults::default_pause_on_focus_loss")]
+    pub pause_on_focus_loss: bool,
+    #[serde(default = "crate::config::defaults::default_resume_on_focus")]
+    pub resume_on_focus: bool,
+    #[serde(default = "crate::config::defaults::default_reading_mode")]
+    pub reading_mode: ReadingMode,
+    #[serde(default = "crate::config::defaults::default_continuous_above_pages")]
+    pub continuous_above_pages: usize,
+    #[serde(default = "crate::config::defaults::default_highlight_style")]
+    pub highlight_style: HighlightStyle,
+    #[serde(default = "crate::config::defaults::default_paragraph_spacing")]
+    pub paragraph_spacing: f32,
+    #[serde(default = "crate::config::defaults::default_reading_ruler")]
+    pub reading_ruler: bool,
+    #[serde(default = "crate::config::defaults::default_reading_ruler_position")]
+    pub reading_ruler_position: f32,
+    #[serde(default = "crate::config::defaults::default_pause_after_clause")]
+    pub pause_after_clause: Option<f32>,
+    #[serde(default = "crate::config::defaults::default_pause_after_paragraph")]
+    pub pause_after_paragraph: f32,
+    #[serde(default = "crate::config::defaults::default_auto_theme_schedule")]
+    pub auto_theme_schedule: Option<(u8, u8)>,
+    #[serde(default = "crate::config::defaults::default_max_text_width")]
+    pub max_text_width: Option<u32>,
+    #[serde(default = "crate::config::defaults::default_per_book_overrides")]
+    pub per_book_overrides: bool,
+    #[serde(default = "crate::config::defaults::default_day_search_highlight")]
+    pub day_search_highlight: HighlightColor,
+    #[serde(default = "crate::config::defaults::default_night_search_highlight")]
+    pub night_search_highlight: HighlightColor,
+    #[serde(default = "crate::config::defaults::default_on_book_end")]
+    pub on_book_end: BookEndAction,
+    #[serde(default = "crate::config::defaults::default_custom_font")]
+    pub custom_font: Option<String>,
+    /// Last colors picked for the `Custom` theme, kept while another theme is on.
+    #[serde(default = "crate::config::defaults::default_custom_colors")]
+    pub custom_colors: Option<CustomColors>,
+    #[serde(default = "crate::config::defaults::default_compact_controls")]
+    pub compact_controls: bool,
+    #[serde(default = "crate::config::defaults::default_speed_ramp")]
+    pub speed_ramp: Option<SpeedRamp>,
+    #[serde(default = "crate::config::defaults::default_smooth_scroll")]
+    pub smooth_scroll: bool,
+    #[serde(default = "crate::config::defaults::default_collapse_blank_lines")]
+    pub collapse_blank_lines: bool,
+    #[serde(default = "crate::config::defaults::default_auto_resume_tts")]
+    pub auto_resume_tts: bool,
+    #[serde(default = "crate::config::defaults::default_accessibility_mode")]
+    pub accessibility_mode: bool,
+    #[serde(default = "crate::config::defaults::default_hyphenate")]
+    pub hyphenate: bool,
+    #[serde(default = "crate::config::defaults::default_tts_cache_max_mb")]
+    pub tts_cache_max_mb: u64,
+    #[serde(default = "crate::config::defaults::default_announce_chapters")]
+    pub announce_chapters: bool,
+    #[serde(default = "crate::config::defaults::default_tts_group_sentences")]
+    pub tts_group_sentences: usize,
+    #[serde(default = "crate::config::defaults::default_strip_running_headers")]
+    pub strip_running_headers: bool,
+    #[serde(default = "crate::config::defaults::default_running_header_min_repeats")]
+    pub running_header_min_repeats: usize,
+    #[serde(default = "crate::config::defaults::default_play_starts_at_scroll")]
+    pub play_starts_at_scroll: bool,
+    #[serde(default = "crate::config::defaults::default_preformatted_blocks")]
+    pub preformatted_blocks: bool,
+    #[serde(default = "crate::config::defaults::default_speak_code_blocks")]
+    pub speak_code_blocks: bool,
+    #[serde(default = "crate::config::defaults::default_reading_speed_idle_secs")]
+    pub reading_speed_idle_secs: u64,
 }
 
 impl Default for AppConfig {
@@ -120,16 +232,85 @@ impl Default for AppConfig {
             key_toggle_settings: crate::config::defaults::default_key_toggle_settings(),
             key_toggle_stats: crate::config::defaults::default_key_toggle_stats(),
             key_toggle_tts: crate::config::defaults::default_key_toggle_tts(),
+            collapse_duplicate_pages: crate::config::defaults::default_collapse_duplicate_pages(),
+            snap_resume_to_sentence: crate::config::defaults::default_snap_resume_to_sentence(),
+            log_timing_metrics: crate::config::defaults::default_log_timing_metrics(),
+            preserve_emphasis: crate::config::defaults::default_preserve_emphasis(),
+            linkify_crossrefs: crate::config::defaults::default_linkify_crossrefs(),
+            word_level_highlight: crate::config::defaults::default_word_level_highlight(),
+            key_next_page: crate::config::defaults::default_key_next_page(),
+            key_prev_page: crate::config::defaults::default_key_prev_page(),
+            key_map: BTreeMap::new(),
+            fold_orphan_pages: crate::config::defaults::default_fold_orphan_pages(),
+            dictionary_path: crate::config::defaults::default_dictionary_path(),
+            lookup_words_on_click: crate::config::defaults::default_lookup_words_on_click(),
+            footnote_mode: crate::config::defaults::default_footnote_mode(),
+            skip_leading_pages: crate::config::defaults::default_skip_leading_pages(),
+            tts_voice: crate::config::defaults::default_tts_voice(),
+            text_direction: crate::config::defaults::default_text_direction(),
+            auto_page_seconds: crate::config::defaults::default_auto_page_seconds(),
+            show_chapter_ruler: crate::config::defaults::default_show_chapter_ruler(),
+            image_alt_text: crate::config::defaults::default_image_alt_text(),
+            tts_lookahead: crate::config::defaults::default_tts_lookahead(),
+            pause_on_focus_loss: crate::config::defaults::default_pause_on_focus_loss(),
+            resume_on_focus: crate::config::defaults::default_resume_on_focus(),
+            reading_mode: crate::config::defaults::default_reading_mode(),
+            continuous_above_pages: crate::config::defaults::default_continuous_above_pages(),
+            highlight_style: crate::config::defaults::default_highlight_style(),
+            paragraph_spacing: crate::config::defaults::default_paragraph_spacing(),
+            reading_ruler: crate::config::defaults::default_reading_ruler(),
+            reading_ruler_position: crate::config::defaults::default_reading_ruler_position(),
+            pause_after_clause: crate::config::defaults::default_pause_after_clause(),
+            pause_after_paragraph: crate::config::defaults::default_pause_after_paragraph(),
+            auto_theme_schedule: crate::config::defaults::default_auto_theme_schedule(),
+            max_text_width: crate::config::defaults::default_max_text_width(),
+            per_book_overrides: crate::config::defaults::default_per_book_overrides(),
+            day_search_highlight: crate::config::defaults::default_day_search_highlight(),
+            night_search_highlight: crate::config::defaults::default_night_search_highlight(),
+            on_book_end: crate::config::defaults::default_on_book_end(),
+            custom_font: crate::config::defaults::default_custom_font(),
+            custom_colors: crate::config::defaults::default_custom_colors(),
+            compact_controls: crate::config::defaults::default_compact_controls(),
+            speed_ramp: crate::config::defaults::default_speed_ramp(),
+            smooth_scroll: crate::config::defaults::default_smooth_scroll(),
+            collapse_blank_lines: crate::config::defaults::default_collapse_blank_lines(),
+            auto_resume_tts: crate::config::defaults::default_auto_resume_tts(),
+            accessibility_mode: crate::config::defaults::default_accessibility_mode(),
+            hyphenate: crate::config::defaults::default_hyphenate(),
+            tts_cache_max_mb: crate::config::defaults::default_tts_cache_max_mb(),
+            announce_chapters: crate::config::defaults::default_announce_chapters(),
+            tts_group_sentences: crate::config::defaults::default_tts_group_sentences(),
+            strip_running_headers: crate::config::defaults::default_strip_running_headers(),
+            running_header_min_repeats: crate::config::defaults::default_running_header_min_repeats(
+            ),
+            play_starts_at_scroll: crate::config::defaults::default_play_starts_at_scroll(),
+            preformatted_blocks: crate::config::defaults::default_preformatted_blocks(),
+            speak_code_blocks: crate::config::defaults::default_speak_code_blocks(),
+            reading_speed_idle_secs: crate::config::defaults::default_reading_speed_idle_secs(),
         }
     }
 }
 
+impl AppConfig {
+    /// Playback speed for the `spoken`-th sentence of the session: along
+    /// `speed_ramp` while it is set, `tts_speed` otherwise.
+    pub fn tts_speed_at(&self, spoken: usize) -> f32 {
+        self.speed_ramp
+            .map_or(self.tts_speed, |ramp| ramp.speed_at(spoken))
+    }
+}
+
 /// Theme mode.
-#[derive(Debug, Clone, Copy, Deserialize, serde::Serialize, PartialEq, Eq)]
+///
+/// Unit variants serialize as plain strings (`"day"`), so older configs keep loading;
+/// `Custom` is written as a `custom = { bg = [...], fg = [...] }` table.
+#[derive(Debug, Clone, Copy, Deserialize, serde::Serialize, PartialEq)]
 #[serde(rename_all = "kebab-case")]
 pub enum ThemeMode {
     Day,
     Night,
+    Sepia,
+    Custom { bg: [f32; 3], fg: [f32; 3] },
 }
 
 impl Default for ThemeMode {
@@ -138,16 +319,69 @@ impl Default for ThemeMode {
     }
 }
 
+impl ThemeMode {
+    /// Colors used when switching to `Custom` without any saved colors.
+    pub const DEFAULT_CUSTOM: ThemeMode = ThemeMode::Custom {
+        bg: [0.16, 0.18, 0.20],
+        fg: [0.88, 0.86, 0.80],
+    };
+
+    /// `Custom` with the saved `colors`, or [`Self::DEFAULT_CUSTOM`] without any.
+    pub fn custom(colors: Option<CustomColors>) -> Self {
+        match colors {
+            Some(CustomColors { bg, fg }) => ThemeMode::Custom { bg, fg },
+            None => ThemeMode::DEFAULT_CUSTOM,
+        }
+    }
+
+    /// Next mode for the theme toggle: Day, Night, Sepia, Custom with the saved
+    /// `custom` colors, then back to Day.
+    pub fn next(self, custom: Option<CustomColors>) -> Self {
+        match self {
+            ThemeMode::Day => ThemeMode::Night,
+            ThemeMode::Night => ThemeMode::Sepia,
+            ThemeMode::Sepia => ThemeMode::custom(custom),
+            ThemeMode::Custom { .. } => ThemeMode::Day,
+        }
+    }
+
+    /// Colors of a `Custom` theme.
+    pub fn custom_colors(self) -> Option<CustomColors> {
+        match self {
+            ThemeMode::Custom { bg, fg } => Some(CustomColors { bg, fg }),
+            _ => None,
+        }
+    }
+
+    /// Whether text is light on a dark background, which picks the highlight color set.
+    pub fn is_dark(self) -> bool {
+        match self {
+            ThemeMode::Day | ThemeMode::Sepia => false,
+            ThemeMode::Night => true,
+            ThemeMode::Custom { bg, .. } => 0.2126 * bg[0] + 0.7152 * bg[1] + 0.0722 * bg[2] < 0.5,
+        }
+    }
+}
+
 impl std::fmt::Display for ThemeMode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let label = match self {
             ThemeMode::Day => "Day",
             ThemeMode::Night => "Night",
+            ThemeMode::Sepia => "Sepia",
+            ThemeMode::Custom { .. } => "Custom",
         };
         write!(f, "{}", label)
     }
 }
 
+/// Background and text colors of the `Custom` theme, components `0.0..1.0`.
+#[derive(Debug, Clone, Copy, Deserialize, serde::Serialize, PartialEq)]
+pub struct CustomColors {
+    pub bg: [f32; 3],
+    pub fg: [f32; 3],
+}
+
 /// Font family options.
 #[derive(Debug, Clone, Copy, Deserialize, serde::Serialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
@@ -220,7 +454,173 @@ impl std::fmt::Display for FontWeight {
     }
 }
 
-#[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize)]
+/// Where EPUB footnote text is placed in the reading text.
+#[derive(Debug, Clone, Copy, Default, Deserialize, serde::Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FootnoteMode {
+    /// Right after the footnote marker, in brackets.
+    Inline,
+    /// Collected after the chapter that references them.
+    #[default]
+    EndOfChapter,
+}
+
+/// Whether EPUB image alt text is kept in the reading text.
+#[derive(Debug, Clone, Copy, Default, Deserialize, serde::Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImageAltText {
+    /// Shown as an `[Image: ...]` marker where the image was.
+    Include,
+    /// Dropped along with the image.
+    #[default]
+    Omit,
+}
+
+/// How the reading view lays out pages.
+#[derive(Debug, Clone, Copy, Default, Deserialize, serde::Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReadingMode {
+    /// One page at a time.
+    #[default]
+    Paged,
+    /// The whole chapter in one scrolling view; page turns jump by chapter.
+    Continuous,
+}
+
+impl std::fmt::Display for ReadingMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            ReadingMode::Paged => "Paged",
+            ReadingMode::Continuous => "Continuous",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// What TTS does after finishing the last page of the book.
+#[derive(Debug, Clone, Copy, Default, Deserialize, serde::Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BookEndAction {
+    /// Stop playback on the last page.
+    #[default]
+    Stop,
+    /// Go back to the first page and keep reading, e.g. for ambient looping.
+    RestartFromStart,
+    /// Save the reading position and quit.
+    CloseApp,
+}
+
+/// Playback speed that moves from `start` to `end` over the first
+/// `over_sentences` sentences spoken in a session.
+#[derive(Debug, Clone, Copy, Deserialize, serde::Serialize, PartialEq)]
+pub struct SpeedRamp {
+    pub start: f32,
+    pub end: f32,
+    pub over_sentences: usize,
+}
+
+impl SpeedRamp {
+    /// Speed for the `sentence`-th sentence of the session, counted from 0;
+    /// linear along the ramp and `end` once it is over.
+    pub fn speed_at(&self, sentence: usize) -> f32 {
+        if sentence >= self.over_sentences {
+            return self.end;
+        }
+        let progress = sentence as f32 / self.over_sentences as f32;
+        self.start + (self.end - self.start) * progress
+    }
+}
+
+/// Something a key can be bound to in `[keybindings]`.
+#[derive(Debug, Clone, Copy, Deserialize, serde::Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyAction {
+    PlayPause,
+    SafeQuit,
+    NextSentence,
+    PrevSentence,
+    RepeatSentence,
+    NextPage,
+    PrevPage,
+    ToggleSearch,
+    ToggleSettings,
+    ToggleStats,
+    ToggleTts,
+    ToggleTheme,
+    ToggleContents,
+    ToggleTextOnly,
+}
+
+/// Reading direction of the book's text.
+#[derive(Debug, Clone, Copy, Default, Deserialize, serde::Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TextDirection {
+    /// Right-to-left when the EPUB language is Arabic, Hebrew, Persian, Urdu, etc.
+    #[default]
+    Auto,
+    Ltr,
+    Rtl,
+}
+
+impl TextDirection {
+    /// Resolve `Auto` against the book's language tag (e.g. `ar`, `he-IL`).
+    pub fn is_rtl(self, language: Option<&str>) -> bool {
+        const RTL_LANGUAGES: &[&str] = &[
+            "ar", "arc", "ckb", "dv", "fa", "he", "iw", "ks", "ku-arab", "ps", "sd", "ug", "ur",
+            "yi",
+        ];
+        match self {
+            TextDirection::Ltr => false,
+            TextDirection::Rtl => true,
+            TextDirection::Auto => language.is_some_and(|tag| {
+                let tag = tag.trim().to_ascii_lowercase().replace('_', "-");
+                RTL_LANGUAGES.iter().any(|rtl| {
+                    tag == *rtl
+                        || tag
+                            .strip_prefix(rtl)
+                            .is_some_and(|rest| rest.starts_with('-'))
+                })
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for TextDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            TextDirection::Auto => "Auto",
+            TextDirection::Ltr => "Left to right",
+            TextDirection::Rtl => "Right to left",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// How the spoken sentence is marked in the text.
+#[derive(Debug, Clone, Copy, Default, Deserialize, serde::Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum HighlightStyle {
+    /// Filled with the highlight color.
+    #[default]
+    Background,
+    /// Underlined, leaving the page colors untouched.
+    Underline,
+    /// Outlined with the highlight color.
+    Box,
+}
+
+impl std::fmt::Display for HighlightStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            HighlightStyle::Background => "Background",
+            HighlightStyle::Underline => "Underline",
+            HighlightStyle::Box => "Box",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+#[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize, PartialEq)]
 pub struct HighlightColor {
     pub r: f32,
     pub g: f32,
@@ -269,3 +669,74 @@ impl LogLevel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_direction_follows_language_unless_forced() {
+        assert!(TextDirection::Auto.is_rtl(Some("ar")));
+        assert!(TextDirection::Auto.is_rtl(Some("he-IL")));
+        assert!(TextDirection::Auto.is_rtl(Some("fa_IR")));
+        assert!(!TextDirection::Auto.is_rtl(Some("en-US")));
+        assert!(!TextDirection::Auto.is_rtl(Some("arn")));
+        assert!(!TextDirection::Auto.is_rtl(None));
+        assert!(TextDirection::Rtl.is_rtl(Some("en")));
+        assert!(!TextDirection::Ltr.is_rtl(Some("ar")));
+    }
+    #[test]
+    fn speed_ramp_interpolates_then_holds_the_end() {
+        let ramp = SpeedRamp {
+            start: 0.8,
+            end: 1.6,
+            over_sentences: 4,
+        };
+        let speeds: Vec<f32> = (0..6).map(|sentence| ramp.speed_at(sentence)).collect();
+        let expected = [0.8, 1.0, 1.2, 1.4, 1.6, 1.6];
+        for (speed, expected) in speeds.iter().zip(expected) {
+            assert!((speed - expected).abs() < 1e-6, "{speeds:?}");
+        }
+
+        let slowing = SpeedRamp {
+            start: 2.0,
+            end: 1.0,
+            over_sentences: 2,
+        };
+        assert_eq!(slowing.speed_at(1), 1.5);
+
+        let instant = SpeedRamp {
+            over_sentences: 0,
+            ..ramp
+        };
+        assert_eq!(instant.speed_at(0), 1.6);
+
+        let mut config = AppConfig::default();
+        config.tts_speed = 1.25;
+        assert_eq!(config.tts_speed_at(0), 1.25);
+        config.speed_ramp = Some(ramp);
+        assert_eq!(config.tts_speed_at(0), 0.8);
+    }
+
+    #[test]
+    fn theme_toggle_returns_to_the_saved_custom_colors() {
+        let saved = CustomColors {
+            bg: [0.1, 0.2, 0.3],
+            fg: [0.9, 0.8, 0.7],
+        };
+        assert_eq!(
+            ThemeMode::Sepia.next(Some(saved)),
+            ThemeMode::Custom {
+                bg: saved.bg,
+                fg: saved.fg
+            }
+        );
+        assert_eq!(ThemeMode::Sepia.next(None), ThemeMode::DEFAULT_CUSTOM);
+        assert_eq!(
+            ThemeMode::custom(Some(saved)).next(Some(saved)),
+            ThemeMode::Day
+        );
+        assert_eq!(ThemeMode::custom(Some(saved)).custom_colors(), Some(saved));
+        assert_eq!(ThemeMode::Night.custom_colors(), None);
+    }
+}
@@ -0,0 +1,177 @@
+//! Where the base config and normalizer files are read from.
+//!
+//! Lookup order: the directory of an explicit `--config` file, then the
+//! per-user config directory (`$XDG_CONFIG_HOME/ebup-viewer/` or the platform
+//! equivalent), then the bundled `conf/` files next to the working directory.
+//!
+//! Settings changed in the reader are saved to a separate `settings.toml`, so
+//! the hand-edited base config keeps its comments.
+
+use crate::normalizer::DEFAULT_NORMALIZER_PATH;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tracing::info;
+
+/// Bundled base configuration, checked last for backward compatibility.
+const DEFAULT_CONFIG_PATH: &str = "conf/config.toml";
+const APP_CONFIG_DIR: &str = "ebup-viewer";
+const CONFIG_FILE: &str = "config.toml";
+const NORMALIZER_FILE: &str = "normalizer.toml";
+const SETTINGS_FILE: &str = "settings.toml";
+const FONTS_DIR: &str = "fonts";
+
+static CONFIG_PATHS: OnceLock<ConfigPaths> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ConfigPaths {
+    config: PathBuf,
+    normalizer: PathBuf,
+    settings: PathBuf,
+}
+
+impl ConfigPaths {
+    fn resolve(explicit: Option<&Path>, user_dir: Option<&Path>) -> Self {
+        let explicit_dir = explicit.map(|path| path.parent().unwrap_or(Path::new("")));
+        let config = explicit
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| locate(None, user_dir, CONFIG_FILE, Path::new(DEFAULT_CONFIG_PATH)));
+        let settings = explicit_dir
+            .or(user_dir)
+            .or_else(|| config.parent())
+            .unwrap_or(Path::new(""))
+            .join(SETTINGS_FILE);
+        Self {
+            normalizer: locate(
+                explicit_dir,
+                user_dir,
+                NORMALIZER_FILE,
+                Path::new(DEFAULT_NORMALIZER_PATH),
+            ),
+            config,
+            settings,
+        }
+    }
+}
+
+/// Resolve the config locations once at startup; `explicit` is the `--config` path.
+pub(crate) fn init_config_paths(explicit: Option<&Path>) {
+    let paths = ConfigPaths::resolve(explicit, user_config_dir().as_deref());
+    info!(
+        config = %paths.config.display(),
+        normalizer = %paths.normalizer.display(),
+        "Resolved config paths"
+    );
+    let _ = CONFIG_PATHS.set(paths);
+}
+
+/// Base configuration every book starts from.
+pub(crate) fn base_config_path() -> &'static Path {
+    &config_paths().config
+}
+
+/// Settings saved from the reader without `per_book_overrides`, and imported
+/// profiles; read in place of the base config while present.
+pub(crate) fn saved_settings_path() -> &'static Path {
+    &config_paths().settings
+}
+
+/// Text normalizer configuration used for TTS.
+pub(crate) fn normalizer_config_path() -> &'static Path {
+    &config_paths().normalizer
+}
+
+/// Directory of user `.ttf`/`.otf` fonts, beside the base config.
+pub(crate) fn fonts_dir() -> PathBuf {
+    base_config_path()
+        .parent()
+        .unwrap_or(Path::new(""))
+        .join(FONTS_DIR)
+}
+
+fn config_paths() -> &'static ConfigPaths {
+    CONFIG_PATHS.get_or_init(|| ConfigPaths::resolve(None, user_config_dir().as_deref()))
+}
+
+/// First existing `file_name` among the candidate directories, then `bundled`.
+///
+/// When none exists yet the user directory is preferred, so settings saved
+/// later land where the next launch looks first.
+fn locate(
+    explicit_dir: Option<&Path>,
+    user_dir: Option<&Path>,
+    file_name: &str,
+    bundled: &Path,
+) -> PathBuf {
+    explicit_dir
+        .into_iter()
+        .chain(user_dir)
+        .map(|dir| dir.join(file_name))
+        .chain([bundled.to_path_buf()])
+        .find(|path| path.is_file())
+        .or_else(|| user_dir.map(|dir| dir.join(file_name)))
+        .unwrap_or_else(|| bundled.to_path_buf())
+}
+
+/// Per-user config directory for the app, following platform conventions.
+fn user_config_dir() -> Option<PathBuf> {
+    let home = || {
+        env::var_os("HOME")
+            .filter(|home| !home.is_empty())
+            .map(PathBuf::from)
+    };
+    let base = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home().map(|home| home.join("Library").join("Application Support"))
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| home().map(|home| home.join(".config")))
+    };
+    base.map(|dir| dir.join(APP_CONFIG_DIR))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn lookup_prefers_explicit_then_user_then_bundled() {
+        let root = env::temp_dir().join(format!("ebup-config-paths-{}", std::process::id()));
+        let explicit_dir = root.join("explicit");
+        let user_dir = root.join("user");
+        let bundled = root.join("conf").join(NORMALIZER_FILE);
+        for dir in [&explicit_dir, &user_dir, &root.join("conf")] {
+            fs::create_dir_all(dir).unwrap();
+        }
+
+        let locate_normalizer = || {
+            locate(
+                Some(&explicit_dir),
+                Some(&user_dir),
+                NORMALIZER_FILE,
+                &bundled,
+            )
+        };
+        assert_eq!(locate_normalizer(), user_dir.join(NORMALIZER_FILE));
+        fs::write(&bundled, "").unwrap();
+        assert_eq!(locate_normalizer(), bundled);
+        fs::write(user_dir.join(NORMALIZER_FILE), "").unwrap();
+        assert_eq!(locate_normalizer(), user_dir.join(NORMALIZER_FILE));
+        fs::write(explicit_dir.join(NORMALIZER_FILE), "").unwrap();
+        assert_eq!(locate_normalizer(), explicit_dir.join(NORMALIZER_FILE));
+
+        let explicit = explicit_dir.join("custom.toml");
+        let paths = ConfigPaths::resolve(Some(&explicit), Some(&user_dir));
+        assert_eq!(paths.config, explicit);
+        assert_eq!(paths.normalizer, explicit_dir.join(NORMALIZER_FILE));
+        assert_eq!(paths.settings, explicit_dir.join(SETTINGS_FILE));
+        let paths = ConfigPaths::resolve(None, Some(&user_dir));
+        assert_eq!(paths.settings, user_dir.join(SETTINGS_FILE));
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
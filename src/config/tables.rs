@@ -1,6 +1,12 @@
 use super::defaults;
-use super::models::{AppConfig, FontFamily, FontWeight, HighlightColor, LogLevel, ThemeMode};
+use super::models::{
+    AppConfig, BookEndAction, CustomColors, FontFamily, FontWeight, FootnoteMode, HighlightColor,
+    HighlightStyle, ImageAltText, KeyAction, LogLevel, ReadingMode, SpeedRamp, TextDirection,
+    ThemeMode,
+};
 use serde::Deserialize;
+use std::collections::BTreeMap;
+use tracing::warn;
 
 #[derive(Debug, Clone, Deserialize, serde::Serialize)]
 pub(super) struct ConfigTables {
@@ -60,6 +66,60 @@ impl From<ConfigTables> for AppConfig {
             tts_volume: tables.tts.tts_volume,
             tts_threads: tables.tts.tts_threads,
             tts_progress_log_interval_secs: tables.tts.tts_progress_log_interval_secs,
+            collapse_duplicate_pages: tables.reading_behavior.collapse_duplicate_pages,
+            snap_resume_to_sentence: tables.reading_behavior.snap_resume_to_sentence,
+            log_timing_metrics: tables.logging.log_timing_metrics,
+            preserve_emphasis: tables.appearance.preserve_emphasis,
+            linkify_crossrefs: tables.reading_behavior.linkify_crossrefs,
+            word_level_highlight: tables.tts.word_level_highlight,
+            key_next_page: tables.keybindings.next_page,
+            key_prev_page: tables.keybindings.prev_page,
+            key_map: key_map_from_bindings(tables.keybindings.bindings),
+            fold_orphan_pages: tables.reading_behavior.fold_orphan_pages,
+            dictionary_path: tables.reading_behavior.dictionary_path,
+            lookup_words_on_click: tables.reading_behavior.lookup_words_on_click,
+            footnote_mode: tables.reading_behavior.footnote_mode,
+            skip_leading_pages: tables.reading_behavior.skip_leading_pages,
+            tts_voice: tables.tts.tts_voice,
+            text_direction: tables.appearance.text_direction,
+            auto_page_seconds: tables.reading_behavior.auto_page_seconds,
+            show_chapter_ruler: tables.ui.show_chapter_ruler,
+            image_alt_text: tables.reading_behavior.image_alt_text,
+            tts_lookahead: tables.tts.tts_lookahead,
+            pause_on_focus_loss: tables.tts.pause_on_focus_loss,
+            resume_on_focus: tables.tts.resume_on_focus,
+            reading_mode: tables.reading_behavior.reading_mode,
+            continuous_above_pages: tables.reading_behavior.continuous_above_pages,
+            highlight_style: tables.appearance.highlight_style,
+            paragraph_spacing: tables.appearance.paragraph_spacing,
+            reading_ruler: tables.appearance.reading_ruler,
+            reading_ruler_position: tables.appearance.reading_ruler_position,
+            pause_after_clause: tables.reading_behavior.pause_after_clause,
+            pause_after_paragraph: tables.reading_behavior.pause_after_paragraph,
+            auto_theme_schedule: tables.appearance.auto_theme_schedule,
+            max_text_width: tables.appearance.max_text_width,
+            per_book_overrides: tables.ui.per_book_overrides,
+            day_search_highlight: tables.appearance.day_search_highlight,
+            night_search_highlight: tables.appearance.night_search_highlight,
+            on_book_end: tables.tts.on_book_end,
+            custom_font: tables.appearance.custom_font,
+            custom_colors: tables.appearance.custom_colors,
+            compact_controls: tables.ui.compact_controls,
+            speed_ramp: tables.tts.speed_ramp,
+            smooth_scroll: tables.reading_behavior.smooth_scroll,
+            collapse_blank_lines: tables.appearance.collapse_blank_lines,
+            auto_resume_tts: tables.tts.auto_resume_tts,
+            accessibility_mode: tables.ui.accessibility_mode,
+            hyphenate: tables.appearance.hyphenate,
+            tts_cache_max_mb: tables.tts.tts_cache_max_mb,
+            announce_chapters: tables.tts.announce_chapters,
+            tts_group_sentences: tables.tts.tts_group_sentences,
+            strip_running_headers: tables.reading_behavior.strip_running_headers,
+            running_header_min_repeats: tables.reading_behavior.running_header_min_repeats,
+            play_starts_at_scroll: tables.tts.play_starts_at_scroll,
+            preformatted_blocks: tables.appearance.preformatted_blocks,
+            speak_code_blocks: tables.tts.speak_code_blocks,
+            reading_speed_idle_secs: tables.reading_behavior.reading_speed_idle_secs,
         }
     }
 }
@@ -80,6 +140,21 @@ impl From<&AppConfig> for ConfigTables {
                 margin_vertical: config.margin_vertical,
                 day_highlight: config.day_highlight,
                 night_highlight: config.night_highlight,
+                preserve_emphasis: config.preserve_emphasis,
+                text_direction: config.text_direction,
+                highlight_style: config.highlight_style,
+                paragraph_spacing: config.paragraph_spacing,
+                reading_ruler: config.reading_ruler,
+                reading_ruler_position: config.reading_ruler_position,
+                auto_theme_schedule: config.auto_theme_schedule,
+                max_text_width: config.max_text_width,
+                day_search_highlight: config.day_search_highlight,
+                night_search_highlight: config.night_search_highlight,
+                custom_font: config.custom_font.clone(),
+                custom_colors: config.custom_colors,
+                collapse_blank_lines: config.collapse_blank_lines,
+                hyphenate: config.hyphenate,
+                preformatted_blocks: config.preformatted_blocks,
             },
             window: WindowConfig {
                 width: config.window_width,
@@ -91,13 +166,36 @@ impl From<&AppConfig> for ConfigTables {
                 pause_after_sentence: config.pause_after_sentence,
                 auto_scroll_tts: config.auto_scroll_tts,
                 center_spoken_sentence: config.center_spoken_sentence,
+                collapse_duplicate_pages: config.collapse_duplicate_pages,
+                snap_resume_to_sentence: config.snap_resume_to_sentence,
+                linkify_crossrefs: config.linkify_crossrefs,
+                fold_orphan_pages: config.fold_orphan_pages,
+                dictionary_path: config.dictionary_path.clone(),
+                lookup_words_on_click: config.lookup_words_on_click,
+                footnote_mode: config.footnote_mode,
+                skip_leading_pages: config.skip_leading_pages,
+                auto_page_seconds: config.auto_page_seconds,
+                image_alt_text: config.image_alt_text,
+                reading_mode: config.reading_mode,
+                continuous_above_pages: config.continuous_above_pages,
+                pause_after_clause: config.pause_after_clause,
+                pause_after_paragraph: config.pause_after_paragraph,
+                smooth_scroll: config.smooth_scroll,
+                strip_running_headers: config.strip_running_headers,
+                running_header_min_repeats: config.running_header_min_repeats,
+                reading_speed_idle_secs: config.reading_speed_idle_secs,
             },
             ui: UiConfig {
                 show_tts: config.show_tts,
                 show_settings: config.show_settings,
+                show_chapter_ruler: config.show_chapter_ruler,
+                per_book_overrides: config.per_book_overrides,
+                compact_controls: config.compact_controls,
+                accessibility_mode: config.accessibility_mode,
             },
             logging: LoggingConfig {
                 log_level: config.log_level,
+                log_timing_metrics: config.log_timing_metrics,
             },
             tts: TtsConfig {
                 tts_model_path: config.tts_model_path.clone(),
@@ -106,6 +204,19 @@ impl From<&AppConfig> for ConfigTables {
                 tts_volume: config.tts_volume,
                 tts_threads: config.tts_threads,
                 tts_progress_log_interval_secs: config.tts_progress_log_interval_secs,
+                word_level_highlight: config.word_level_highlight,
+                tts_voice: config.tts_voice.clone(),
+                tts_lookahead: config.tts_lookahead,
+                pause_on_focus_loss: config.pause_on_focus_loss,
+                resume_on_focus: config.resume_on_focus,
+                on_book_end: config.on_book_end,
+                speed_ramp: config.speed_ramp,
+                auto_resume_tts: config.auto_resume_tts,
+                tts_cache_max_mb: config.tts_cache_max_mb,
+                announce_chapters: config.announce_chapters,
+                tts_group_sentences: config.tts_group_sentences,
+                play_starts_at_scroll: config.play_starts_at_scroll,
+                speak_code_blocks: config.speak_code_blocks,
             },
             keybindings: KeybindingsConfig {
                 toggle_play_pause: config.key_toggle_play_pause.clone(),
@@ -117,11 +228,38 @@ impl From<&AppConfig> for ConfigTables {
                 toggle_settings: config.key_toggle_settings.clone(),
                 toggle_stats: config.key_toggle_stats.clone(),
                 toggle_tts: config.key_toggle_tts.clone(),
+                next_page: config.key_next_page.clone(),
+                prev_page: config.key_prev_page.clone(),
+                bindings: config
+                    .key_map
+                    .iter()
+                    .filter_map(|(key, action)| {
+                        Some((key.clone(), toml::Value::try_from(action).ok()?))
+                    })
+                    .collect(),
             },
         }
     }
 }
 
+/// Keep the `[keybindings]` entries that name a known action, warning about the rest.
+fn key_map_from_bindings(bindings: BTreeMap<String, toml::Value>) -> BTreeMap<String, KeyAction> {
+    bindings
+        .into_iter()
+        .filter_map(|(key, value)| {
+            // Action names are the enum's serde names.
+            let action = value
+                .as_str()
+                .map(|name| toml::Value::String(name.trim().to_string()))
+                .and_then(|name| name.try_into::<KeyAction>().ok());
+            if action.is_none() {
+                warn!(key, %value, "Ignoring keybinding to an unknown action");
+            }
+            Some((key.trim().to_ascii_lowercase(), action?))
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone, Deserialize, serde::Serialize)]
 struct AppearanceConfig {
     #[serde(default)]
@@ -148,6 +286,36 @@ struct AppearanceConfig {
     day_highlight: HighlightColor,
     #[serde(default = "defaults::default_night_highlight")]
     night_highlight: HighlightColor,
+    #[serde(default = "defaults::default_preserve_emphasis")]
+    preserve_emphasis: bool,
+    #[serde(default = "defaults::default_text_direction")]
+    text_direction: TextDirection,
+    #[serde(default = "defaults::default_highlight_style")]
+    highlight_style: HighlightStyle,
+    #[serde(default = "defaults::default_paragraph_spacing")]
+    paragraph_spacing: f32,
+    #[serde(default = "defaults::default_reading_ruler")]
+    reading_ruler: bool,
+    #[serde(default = "defaults::default_reading_ruler_position")]
+    reading_ruler_position: f32,
+    #[serde(default = "defaults::default_auto_theme_schedule")]
+    auto_theme_schedule: Option<(u8, u8)>,
+    #[serde(default = "defaults::default_max_text_width")]
+    max_text_width: Option<u32>,
+    #[serde(default = "defaults::default_day_search_highlight")]
+    day_search_highlight: HighlightColor,
+    #[serde(default = "defaults::default_night_search_highlight")]
+    night_search_highlight: HighlightColor,
+    #[serde(default = "defaults::default_custom_font")]
+    custom_font: Option<String>,
+    #[serde(default = "defaults::default_custom_colors")]
+    custom_colors: Option<CustomColors>,
+    #[serde(default = "defaults::default_collapse_blank_lines")]
+    collapse_blank_lines: bool,
+    #[serde(default = "defaults::default_hyphenate")]
+    hyphenate: bool,
+    #[serde(default = "defaults::default_preformatted_blocks")]
+    preformatted_blocks: bool,
 }
 
 impl Default for AppearanceConfig {
@@ -165,6 +333,21 @@ impl Default for AppearanceConfig {
             margin_vertical: defaults::default_margin_vertical(),
             day_highlight: defaults::default_day_highlight(),
             night_highlight: defaults::default_night_highlight(),
+            preserve_emphasis: defaults::default_preserve_emphasis(),
+            text_direction: defaults::default_text_direction(),
+            highlight_style: defaults::default_highlight_style(),
+            paragraph_spacing: defaults::default_paragraph_spacing(),
+            reading_ruler: defaults::default_reading_ruler(),
+            reading_ruler_position: defaults::default_reading_ruler_position(),
+            auto_theme_schedule: defaults::default_auto_theme_schedule(),
+            max_text_width: defaults::default_max_text_width(),
+            day_search_highlight: defaults::default_day_search_highlight(),
+            night_search_highlight: defaults::default_night_search_highlight(),
+            custom_font: defaults::default_custom_font(),
+            custom_colors: defaults::default_custom_colors(),
+            collapse_blank_lines: defaults::default_collapse_blank_lines(),
+            hyphenate: defaults::default_hyphenate(),
+            preformatted_blocks: defaults::default_preformatted_blocks(),
         }
     }
 }
@@ -200,6 +383,42 @@ struct ReadingBehaviorConfig {
     auto_scroll_tts: bool,
     #[serde(default = "defaults::default_center_spoken_sentence")]
     center_spoken_sentence: bool,
+    #[serde(default = "defaults::default_collapse_duplicate_pages")]
+    collapse_duplicate_pages: bool,
+    #[serde(default = "defaults::default_snap_resume_to_sentence")]
+    snap_resume_to_sentence: bool,
+    #[serde(default = "defaults::default_linkify_crossrefs")]
+    linkify_crossrefs: bool,
+    #[serde(default = "defaults::default_fold_orphan_pages")]
+    fold_orphan_pages: bool,
+    #[serde(default = "defaults::default_dictionary_path")]
+    dictionary_path: String,
+    #[serde(default = "defaults::default_lookup_words_on_click")]
+    lookup_words_on_click: bool,
+    #[serde(default = "defaults::default_footnote_mode")]
+    footnote_mode: FootnoteMode,
+    #[serde(default = "defaults::default_skip_leading_pages")]
+    skip_leading_pages: usize,
+    #[serde(default = "defaults::default_auto_page_seconds")]
+    auto_page_seconds: Option<f32>,
+    #[serde(default = "defaults::default_image_alt_text")]
+    image_alt_text: ImageAltText,
+    #[serde(default = "defaults::default_reading_mode")]
+    reading_mode: ReadingMode,
+    #[serde(default = "defaults::default_continuous_above_pages")]
+    continuous_above_pages: usize,
+    #[serde(default = "defaults::default_pause_after_clause")]
+    pause_after_clause: Option<f32>,
+    #[serde(default = "defaults::default_pause_after_paragraph")]
+    pause_after_paragraph: f32,
+    #[serde(default = "defaults::default_smooth_scroll")]
+    smooth_scroll: bool,
+    #[serde(default = "defaults::default_strip_running_headers")]
+    strip_running_headers: bool,
+    #[serde(default = "defaults::default_running_header_min_repeats")]
+    running_header_min_repeats: usize,
+    #[serde(default = "defaults::default_reading_speed_idle_secs")]
+    reading_speed_idle_secs: u64,
 }
 
 impl Default for ReadingBehaviorConfig {
@@ -208,6 +427,24 @@ impl Default for ReadingBehaviorConfig {
             pause_after_sentence: defaults::default_pause_after_sentence(),
             auto_scroll_tts: defaults::default_auto_scroll_tts(),
             center_spoken_sentence: defaults::default_center_spoken_sentence(),
+            collapse_duplicate_pages: defaults::default_collapse_duplicate_pages(),
+            snap_resume_to_sentence: defaults::default_snap_resume_to_sentence(),
+            linkify_crossrefs: defaults::default_linkify_crossrefs(),
+            fold_orphan_pages: defaults::default_fold_orphan_pages(),
+            dictionary_path: defaults::default_dictionary_path(),
+            lookup_words_on_click: defaults::default_lookup_words_on_click(),
+            footnote_mode: defaults::default_footnote_mode(),
+            skip_leading_pages: defaults::default_skip_leading_pages(),
+            auto_page_seconds: defaults::default_auto_page_seconds(),
+            image_alt_text: defaults::default_image_alt_text(),
+            reading_mode: defaults::default_reading_mode(),
+            continuous_above_pages: defaults::default_continuous_above_pages(),
+            pause_after_clause: defaults::default_pause_after_clause(),
+            pause_after_paragraph: defaults::default_pause_after_paragraph(),
+            smooth_scroll: defaults::default_smooth_scroll(),
+            strip_running_headers: defaults::default_strip_running_headers(),
+            running_header_min_repeats: defaults::default_running_header_min_repeats(),
+            reading_speed_idle_secs: defaults::default_reading_speed_idle_secs(),
         }
     }
 }
@@ -218,6 +455,14 @@ struct UiConfig {
     show_tts: bool,
     #[serde(default = "defaults::default_show_settings")]
     show_settings: bool,
+    #[serde(default = "defaults::default_show_chapter_ruler")]
+    show_chapter_ruler: bool,
+    #[serde(default = "defaults::default_per_book_overrides")]
+    per_book_overrides: bool,
+    #[serde(default = "defaults::default_compact_controls")]
+    compact_controls: bool,
+    #[serde(default = "defaults::default_accessibility_mode")]
+    accessibility_mode: bool,
 }
 
 impl Default for UiConfig {
@@ -225,6 +470,10 @@ impl Default for UiConfig {
         UiConfig {
             show_tts: defaults::default_show_tts(),
             show_settings: defaults::default_show_settings(),
+            show_chapter_ruler: defaults::default_show_chapter_ruler(),
+            per_book_overrides: defaults::default_per_book_overrides(),
+            compact_controls: defaults::default_compact_controls(),
+            accessibility_mode: defaults::default_accessibility_mode(),
         }
     }
 }
@@ -233,12 +482,15 @@ impl Default for UiConfig {
 struct LoggingConfig {
     #[serde(default = "defaults::default_log_level")]
     log_level: LogLevel,
+    #[serde(default = "defaults::default_log_timing_metrics")]
+    log_timing_metrics: bool,
 }
 
 impl Default for LoggingConfig {
     fn default() -> Self {
         LoggingConfig {
             log_level: defaults::default_log_level(),
+            log_timing_metrics: defaults::default_log_timing_metrics(),
         }
     }
 }
@@ -257,6 +509,32 @@ struct TtsConfig {
     tts_threads: usize,
     #[serde(default = "defaults::default_tts_progress_log_interval_secs")]
     tts_progress_log_interval_secs: f32,
+    #[serde(default = "defaults::default_word_level_highlight")]
+    word_level_highlight: bool,
+    #[serde(default = "defaults::default_tts_voice")]
+    tts_voice: String,
+    #[serde(default = "defaults::default_tts_lookahead")]
+    tts_lookahead: usize,
+    #[serde(default = "defaults::default_pause_on_focus_loss")]
+    pause_on_focus_loss: bool,
+    #[serde(default = "defaults::default_resume_on_focus")]
+    resume_on_focus: bool,
+    #[serde(default = "defaults::default_on_book_end")]
+    on_book_end: BookEndAction,
+    #[serde(default = "defaults::default_speed_ramp")]
+    speed_ramp: Option<SpeedRamp>,
+    #[serde(default = "defaults::default_auto_resume_tts")]
+    auto_resume_tts: bool,
+    #[serde(default = "defaults::default_tts_cache_max_mb")]
+    tts_cache_max_mb: u64,
+    #[serde(default = "defaults::default_announce_chapters")]
+    announce_chapters: bool,
+    #[serde(default = "defaults::default_tts_group_sentences")]
+    tts_group_sentences: usize,
+    #[serde(default = "defaults::default_play_starts_at_scroll")]
+    play_starts_at_scroll: bool,
+    #[serde(default = "defaults::default_speak_code_blocks")]
+    speak_code_blocks: bool,
 }
 
 impl Default for TtsConfig {
@@ -268,6 +546,19 @@ impl Default for TtsConfig {
             tts_volume: defaults::default_tts_volume(),
             tts_threads: defaults::default_tts_threads(),
             tts_progress_log_interval_secs: defaults::default_tts_progress_log_interval_secs(),
+            word_level_highlight: defaults::default_word_level_highlight(),
+            tts_voice: defaults::default_tts_voice(),
+            tts_lookahead: defaults::default_tts_lookahead(),
+            pause_on_focus_loss: defaults::default_pause_on_focus_loss(),
+            resume_on_focus: defaults::default_resume_on_focus(),
+            on_book_end: defaults::default_on_book_end(),
+            speed_ramp: defaults::default_speed_ramp(),
+            auto_resume_tts: defaults::default_auto_resume_tts(),
+            tts_cache_max_mb: defaults::default_tts_cache_max_mb(),
+            announce_chapters: defaults::default_announce_chapters(),
+            tts_group_sentences: defaults::default_tts_group_sentences(),
+            play_starts_at_scroll: defaults::default_play_starts_at_scroll(),
+            speak_code_blocks: defaults::default_speak_code_blocks(),
         }
     }
 }
@@ -292,6 +583,13 @@ struct KeybindingsConfig {
     toggle_stats: String,
     #[serde(default = "defaults::default_key_toggle_tts")]
     toggle_tts: String,
+    #[serde(default = "defaults::default_key_next_page")]
+    next_page: String,
+    #[serde(default = "defaults::default_key_prev_page")]
+    prev_page: String,
+    /// Any other entry binds a key to an action, e.g. `"ctrl+d" = "toggle_theme"`.
+    #[serde(flatten)]
+    bindings: BTreeMap<String, toml::Value>,
 }
 
 impl Default for KeybindingsConfig {
@@ -306,6 +604,9 @@ impl Default for KeybindingsConfig {
             toggle_settings: defaults::default_key_toggle_settings(),
             toggle_stats: defaults::default_key_toggle_stats(),
             toggle_tts: defaults::default_key_toggle_tts(),
+            next_page: defaults::default_key_next_page(),
+            prev_page: defaults::default_key_prev_page(),
+            bindings: BTreeMap::new(),
         }
     }
 }
@@ -0,0 +1,94 @@
+//! Detection of in-text cross-references such as "see Chapter 5".
+//!
+//! Matching is deliberately conservative: a reference must be introduced by
+//! "see" or "cf." so ordinary prose like "the next chapter 3 times" or a
+//! dateline "page 1 of the Times" is left alone.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::ops::Range;
+
+static RE_CROSSREF: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(?:see|cf\.)\s+(?:also\s+)?(?P<target>(?P<kind>chapter|ch\.|page|p\.)\s*(?P<num>\d{1,4}))\b",
+    )
+    .expect("valid cross-reference regex")
+});
+
+/// What a cross-reference points at, as written in the text (1-based).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossRefTarget {
+    Chapter(usize),
+    Page(usize),
+}
+
+/// A detected reference; `range` covers the linkable "Chapter 5" part in bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrossRef {
+    pub range: Range<usize>,
+    pub target: CrossRefTarget,
+}
+
+/// Find cross-references in `text`.
+pub fn find_crossrefs(text: &str) -> Vec<CrossRef> {
+    RE_CROSSREF
+        .captures_iter(text)
+        .filter_map(|caps| {
+            let target = caps.name("target")?;
+            let number: usize = caps.name("num")?.as_str().parse().ok()?;
+            if number == 0 {
+                return None;
+            }
+            let kind = caps.name("kind")?.as_str().to_ascii_lowercase();
+            let target_kind = if kind.starts_with('c') {
+                CrossRefTarget::Chapter(number)
+            } else {
+                CrossRefTarget::Page(number)
+            };
+            Some(CrossRef {
+                range: target.range(),
+                target: target_kind,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets(text: &str) -> Vec<(&str, CrossRefTarget)> {
+        find_crossrefs(text)
+            .into_iter()
+            .map(|found| (&text[found.range], found.target))
+            .collect()
+    }
+
+    #[test]
+    fn recognizes_see_chapter_and_page_forms() {
+        assert_eq!(
+            targets("For details, see Chapter 5."),
+            vec![("Chapter 5", CrossRefTarget::Chapter(5))]
+        );
+        assert_eq!(
+            targets("(See also page 212 and cf. ch. 3)"),
+            vec![
+                ("page 212", CrossRefTarget::Page(212)),
+                ("ch. 3", CrossRefTarget::Chapter(3)),
+            ]
+        );
+        assert_eq!(
+            targets("as discussed earlier (see p. 14)"),
+            vec![("p. 14", CrossRefTarget::Page(14))]
+        );
+    }
+
+    #[test]
+    fn ignores_unintroduced_or_invalid_references() {
+        assert!(targets("Chapter 5 begins at dawn.").is_empty());
+        assert!(targets("He read page 3 twice.").is_empty());
+        assert!(targets("see chapter zero").is_empty());
+        assert!(targets("see page 0").is_empty());
+        assert!(targets("oversee chapter 4").is_empty());
+    }
+}
@@ -0,0 +1,147 @@
+//! Word definitions for the reader's lookup popup.
+//!
+//! Lookups go through [`DictionarySource`] so other backends (for example an
+//! online API) can sit behind the same interface as the bundled offline file.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+/// Something that can define a word.
+pub trait DictionarySource: Send + Sync {
+    /// Definition for `word`, which is already normalized with [`normalize_word`].
+    fn lookup(&self, word: &str) -> Option<String>;
+}
+
+/// Offline dictionary loaded from a plain-text file.
+///
+/// Each non-empty line is `word<TAB>definition`; lines starting with `#` are
+/// comments. Repeated words collect every definition, one per line.
+#[derive(Debug, Default)]
+pub struct LocalDictionary {
+    entries: HashMap<String, String>,
+}
+
+impl LocalDictionary {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Reading dictionary {}", path.display()))?;
+        Ok(Self::parse(&contents))
+    }
+
+    pub fn parse(contents: &str) -> Self {
+        let mut entries: HashMap<String, String> = HashMap::new();
+        for line in contents.lines() {
+            if line.trim_start().starts_with('#') {
+                continue;
+            }
+            let Some((word, definition)) = line.split_once('\t') else {
+                continue;
+            };
+            let (Some(word), definition) = (normalize_word(word), definition.trim()) else {
+                continue;
+            };
+            if definition.is_empty() {
+                continue;
+            }
+            entries
+                .entry(word)
+                .and_modify(|existing| {
+                    existing.push('\n');
+                    existing.push_str(definition);
+                })
+                .or_insert_with(|| definition.to_string());
+        }
+        Self { entries }
+    }
+}
+
+impl DictionarySource for LocalDictionary {
+    fn lookup(&self, word: &str) -> Option<String> {
+        self.entries.get(word).cloned().or_else(|| {
+            // Possessives are rarely listed separately.
+            let base = word
+                .strip_suffix("'s")
+                .or_else(|| word.strip_suffix("\u{2019}s"))?;
+            self.entries.get(base).cloned()
+        })
+    }
+}
+
+/// Lowercase `raw` and drop surrounding punctuation; `None` if no letters remain.
+pub fn normalize_word(raw: &str) -> Option<String> {
+    let trimmed = raw.trim_matches(|ch: char| !ch.is_alphanumeric());
+    if !trimmed.chars().any(char::is_alphabetic) {
+        return None;
+    }
+    Some(trimmed.to_lowercase())
+}
+
+/// Split `text` into alternating word and non-word runs, as `(range, is_word)`.
+///
+/// Apostrophes and hyphens between letters stay inside the word.
+pub fn word_segments(text: &str) -> Vec<(Range<usize>, bool)> {
+    let mut segments: Vec<(Range<usize>, bool)> = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((idx, ch)) = chars.next() {
+        let joins_word = matches!(ch, '\'' | '\u{2019}' | '-')
+            && segments
+                .last()
+                .is_some_and(|(range, is_word)| *is_word && range.end == idx)
+            && chars.peek().is_some_and(|(_, next)| next.is_alphanumeric());
+        let is_word = ch.is_alphanumeric() || joins_word;
+        let end = idx + ch.len_utf8();
+        match segments.last_mut() {
+            Some((range, last_is_word)) if *last_is_word == is_word => range.end = end,
+            _ => segments.push((idx..end, is_word)),
+        }
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_entries_and_merges_repeats() {
+        let dict = LocalDictionary::parse(
+            "# sample\nHarbor\ta sheltered port\nharbor\tto shelter\nbroken line\n\tno word\n",
+        );
+        assert_eq!(dict.entries.len(), 1);
+        assert_eq!(
+            dict.lookup("harbor").as_deref(),
+            Some("a sheltered port\nto shelter")
+        );
+        assert_eq!(
+            dict.lookup("harbor's").as_deref(),
+            Some("a sheltered port\nto shelter")
+        );
+        assert_eq!(dict.lookup("quay"), None);
+    }
+
+    #[test]
+    fn normalizes_and_segments_words() {
+        assert_eq!(normalize_word("\"Harbor,\"").as_deref(), Some("harbor"));
+        assert_eq!(normalize_word("--"), None);
+        assert_eq!(normalize_word("1984"), None);
+
+        let text = "It's a well-known port, isn't it?";
+        let words: Vec<&str> = word_segments(text)
+            .into_iter()
+            .filter(|(_, is_word)| *is_word)
+            .map(|(range, _)| &text[range])
+            .collect();
+        assert_eq!(
+            words,
+            vec!["It's", "a", "well-known", "port", "isn't", "it"]
+        );
+        let rebuilt: String = word_segments(text)
+            .into_iter()
+            .map(|(range, _)| &text[range])
+            .collect();
+        assert_eq!(rebuilt, text);
+    }
+}
@@ -4,14 +4,17 @@
 //! image assets for rendering in the reading pane.
 
 use crate::cache::hash_dir;
+use crate::config::{FootnoteMode, ImageAltText};
 use anyhow::{Context, Result};
 use epub::doc::EpubDoc;
+use html2text::render::RichAnnotation;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::UNIX_EPOCH;
@@ -19,6 +22,104 @@ use tracing::{debug, info, warn};
 
 static RE_MARKDOWN_IMAGE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"!\[([^\]]*)\]\(([^)]+)\)").expect("valid markdown image regex"));
+static RE_MARKDOWN_LINK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\[([^\]]*)\](?:\([^)]*\)|\[[^\]]*\])").expect("valid markdown link regex")
+});
+static RE_MARKDOWN_LINK_DEFINITION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^ {0,3}\[[^\]]+\]:\s+\S").expect("valid markdown link definition regex")
+});
+// Ordered list numbers are capped at three digits so a line opening with a
+// year, like "1984. It began", keeps it.
+static RE_MARKDOWN_BLOCK_MARKER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:>\s?)*(?:(?P<heading>#{1,6})\s+|[-*+]\s+|\d{1,3}[.)]\s+)?")
+        .expect("valid markdown block marker regex")
+});
+static RE_MARKDOWN_CLOSING_HASHES: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:^|[ \t]+)#+[ \t]*$").expect("valid markdown closing hashes regex")
+});
+static RE_MARKDOWN_RULE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:(?:-\s*){3,}|(?:\*\s*){3,}|(?:_\s*){3,}|=+)$")
+        .expect("valid markdown rule regex")
+});
+static RE_MARKDOWN_EMPHASIS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\*\*(\S(?:.*?\S)?)\*\*|~~(\S(?:.*?\S)?)~~|\*(\S(?:.*?\S)?)\*|`([^`]+)`")
+        .expect("valid markdown emphasis regex")
+});
+static RE_MARKDOWN_UNDERSCORE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(^|\W)(?:__|_)(\S(?:.*?\S)?)(?:__|_)(\W|$)")
+        .expect("valid markdown underscore regex")
+});
+static RE_HTML_LINK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?is)<a\b([^>]*)>(.*?)</a\s*>").expect("valid link regex"));
+static RE_HTML_IMG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?is)<img\b[^>]*>").expect("valid img regex"));
+static RE_HTML_ALT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)\balt\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("valid alt regex")
+});
+static RE_HTML_HREF: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?i)\bhref\s*=\s*["']([^"']*)["']"#).expect("valid href regex"));
+static RE_HTML_ID: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)<([a-z][a-z0-9]*)\b[^>]*?\bid\s*=\s*["']([^"']+)["']"#)
+        .expect("valid id regex")
+});
+static RE_XML_ENCODING: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)<\?xml\b[^>]*?\bencoding\s*=\s*["']([a-z0-9._:-]+)["']"#)
+        .expect("valid xml encoding regex")
+});
+static RE_META_CHARSET: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)<meta\b[^>]*?\bcharset\s*=\s*["']?([a-z0-9._:-]+)"#)
+        .expect("valid meta charset regex")
+});
+static RE_HTML_TAG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<(/?)([a-zA-Z][a-zA-Z0-9]*)\b[^>]*?(/?)>").expect("valid tag regex")
+});
+static RE_HTML_HEADING: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?is)<h([1-6])\b[^>]*>(.*?)</h[1-6]\s*>").expect("valid heading regex")
+});
+static RE_HTML_PRE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?is)<pre\b[^>]*>.*?</pre\s*>").expect("valid pre regex"));
+static RE_ENCRYPTION_METHOD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)<(?:\w+:)?EncryptionMethod\b[^>]*?\bAlgorithm\s*=\s*["']([^"']+)["']"#)
+        .expect("valid encryption method regex")
+});
+static RE_NOTE_MARKER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\[?(?:\d{1,3}|[*†‡§¶]{1,3})\]?$").expect("valid note marker regex"));
+/// A note's own marker at the start of its text, e.g. `1.` or `[*]`.
+static RE_LEADING_NOTE_MARKER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\[?(\d{1,3}|[*†‡§¶]{1,3})\]?[.):]?\s*").expect("valid leading marker regex")
+});
+/// Link attributes declaring a note reference (EPUB 3 or DPUB-ARIA).
+static RE_NOTEREF_ATTR: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)\b(?:epub:type|role)\s*=\s*["'][^"']*\b(?:doc-)?noteref\b"#)
+        .expect("valid noteref regex")
+});
+/// Tag attributes marking an element as a footnote or endnote, or a list of them.
+static RE_NOTE_CONTAINER_ATTR: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)\b(?:epub:type|role)\s*=\s*["'][^"']*\b(?:doc-)?(?:foot|end|rear)notes?\b"#)
+        .expect("valid note container regex")
+});
+/// The EPUB 3 navigation list of printed pages.
+static RE_NAV_PAGE_LIST: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?is)<nav\b[^>]*\b(?:epub:type|role)\s*=\s*["'][^"']*\b(?:doc-)?page-?list\b[^>]*>(.*?)</nav>"#,
+    )
+    .expect("valid page-list regex")
+});
+/// One printed page in an NCX `pageList`.
+static RE_NCX_PAGE_TARGET: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?is)<pageTarget\b[^>]*>.*?<text>(.*?)</text>.*?<content\b[^>]*\bsrc\s*=\s*["']([^"']+)["']"#,
+    )
+    .expect("valid page target regex")
+});
+/// Font obfuscation algorithms (IDPF and Adobe). They scramble embedded fonts
+/// only, so books that use nothing else in `encryption.xml` still read fine.
+const FONT_OBFUSCATION_ALGORITHMS: &[&str] = &[
+    "http://www.idpf.org/2008/embedding",
+    "http://ns.adobe.com/pdf/enc#RC",
+];
+/// Lines longer than this read as prose rather than a running header.
+const RUNNING_HEADER_MAX_CHARS: usize = 60;
 const PANDOC_FILTER_REL_PATH: &str = "conf/pandoc/strip-nontext.lua";
 const PANDOC_PIPELINE_REV: &str = "pandoc-clean-v1";
 const QUACK_CHECK_CONFIG_REL_PATH: &str = "conf/quack-check.toml";
@@ -31,15 +132,217 @@ pub struct BookImage {
     pub label: String,
 }
 
-#[derive(Debug, Clone)]
+/// Inline emphasis carried over from the source markup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmphasisStyle {
+    Italic,
+    Bold,
+    BoldItalic,
+}
+
+/// Emphasized byte range within [`LoadedBook::text`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmphasisRun {
+    pub start: usize,
+    pub end: usize,
+    pub style: EmphasisStyle,
+}
+
+/// Table-of-contents entry resolved against [`LoadedBook::text`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TocEntry {
+    pub title: String,
+    /// Nesting level; top-level chapters are `0`.
+    pub depth: usize,
+    /// Byte offset into the combined text where the entry's chapter begins.
+    pub char_offset: usize,
+}
+
+/// Printed page from the EPUB page-list, resolved against [`LoadedBook::text`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrintedPage {
+    /// Page number as printed, such as `12` or `xiv`.
+    pub label: String,
+    /// Byte offset into the combined text where the printed page begins.
+    pub char_offset: usize,
+}
+
+/// EPUB footnote resolved against [`LoadedBook::text`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Footnote {
+    /// Marker as printed at the reference point, such as `3` or `*`.
+    pub marker: String,
+    pub text: String,
+    /// Byte range of the marker within [`LoadedBook::text`].
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Chapter or section heading within [`LoadedBook::text`].
+///
+/// Kept beside the text rather than marked up inside it, so the normalizer and
+/// TTS see the heading as an ordinary line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    pub start: usize,
+    pub end: usize,
+    /// `1` for `<h1>` through `6` for `<h6>`.
+    pub level: u8,
+}
+
+/// Descriptive metadata from the EPUB package document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EpubMetadata {
+    pub title: Option<String>,
+    pub creator: Option<String>,
+    pub language: Option<String>,
+    pub publisher: Option<String>,
+    pub identifier: Option<String>,
+}
+
+/// Findings for one spine item, from [`diagnose_book`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChapterDiagnostics {
+    /// Resource path inside the package.
+    pub resource: String,
+    /// Size of the raw XHTML.
+    pub bytes: usize,
+    /// Characters left once markup is removed; `0` marks an empty chapter.
+    pub text_chars: usize,
+    /// The chapter had undecodable bytes that were replaced.
+    pub lossy: bool,
+}
+
+/// Structural checks on an EPUB, for when a book renders oddly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BookDiagnostics {
+    pub chapters: Vec<ChapterDiagnostics>,
+    /// Entries in the package's navigation document, nested ones included.
+    pub toc_entries: usize,
+    pub language: Option<String>,
+    pub encrypted: bool,
+}
+
+impl BookDiagnostics {
+    pub fn empty_chapters(&self) -> usize {
+        self.chapters
+            .iter()
+            .filter(|chapter| chapter.text_chars == 0)
+            .count()
+    }
+
+    pub fn lossy_chapters(&self) -> usize {
+        self.chapters.iter().filter(|chapter| chapter.lossy).count()
+    }
+}
+
+/// Why a book could not be loaded.
+///
+/// Most failures stay as [`anyhow::Error`] context chains; variants exist for
+/// cases the UI explains in its own words.
+#[derive(Debug)]
+pub enum EpubError {
+    /// The EPUB's content documents are encrypted, usually by DRM.
+    Encrypted,
+    Other(anyhow::Error),
+}
+
+impl std::fmt::Display for EpubError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EpubError::Encrypted => {
+                write!(f, "This book is DRM-protected and cannot be opened.")
+            }
+            EpubError::Other(err) => write!(f, "{err:#}"),
+        }
+    }
+}
+
+impl std::error::Error for EpubError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EpubError::Encrypted => None,
+            EpubError::Other(err) => Some(err.as_ref()),
+        }
+    }
+}
+
+impl From<anyhow::Error> for EpubError {
+    fn from(err: anyhow::Error) -> Self {
+        EpubError::Other(err)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct LoadedBook {
     pub text: String,
     pub images: Vec<BookImage>,
+    pub emphasis: Vec<EmphasisRun>,
+    pub toc: Vec<TocEntry>,
+    /// The publisher's printed page numbers; empty when the EPUB has no page-list.
+    pub page_list: Vec<PrintedPage>,
+    pub metadata: EpubMetadata,
+    pub footnotes: Vec<Footnote>,
+    pub headings: Vec<Heading>,
+    /// Byte ranges of code blocks (`<pre>` or fenced), sorted and disjoint.
+    pub preformatted: Vec<Range<usize>>,
+    /// Raw cover image bytes, when the EPUB declares a cover.
+    pub cover: Option<Vec<u8>>,
+}
+
+/// Optional extraction passes requested by the reader configuration.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoadOptions {
+    pub preserve_emphasis: bool,
+    pub footnote_mode: FootnoteMode,
+    pub image_alt_text: ImageAltText,
+    /// Repeats a line needs before it is stripped as a running header; `None` keeps every line.
+    pub running_header_min_repeats: Option<usize>,
+}
+
+impl LoadOptions {
+    pub fn from_config(config: &crate::config::AppConfig) -> Self {
+        LoadOptions {
+            preserve_emphasis: config.preserve_emphasis,
+            footnote_mode: config.footnote_mode,
+            image_alt_text: config.image_alt_text,
+            running_header_min_repeats: config
+                .strip_running_headers
+                .then_some(config.running_header_min_repeats),
+        }
+    }
 }
 
 /// Load a supported source file and return plain text plus extracted image paths.
-pub fn load_book_content(path: &Path) -> Result<LoadedBook> {
-    let text = load_source_text(path)?;
+pub fn load_book_content(
+    path: &Path,
+    options: &LoadOptions,
+) -> std::result::Result<LoadedBook, EpubError> {
+    // Checked before conversion, which would otherwise turn ciphertext into garbage.
+    if is_epub(path) && epub_is_encrypted(path) {
+        warn!(path = %path.display(), "Refusing to load encrypted EPUB");
+        return Err(EpubError::Encrypted);
+    }
+    let mut text = load_source_text(path, options.image_alt_text)?;
+    if let Some(min_repeats) = options.running_header_min_repeats {
+        let (stripped, removed) = strip_running_headers(&text, min_repeats);
+        if removed > 0 {
+            info!(path = %path.display(), removed, "Stripped running headers");
+            text = stripped;
+        }
+    }
+    // Footnotes go first: placing them rewrites the text that later passes locate against.
+    let footnotes = if is_epub(path) {
+        match load_epub_footnotes(path, &mut text, options.footnote_mode) {
+            Ok(footnotes) => footnotes,
+            Err(err) => {
+                warn!(path = %path.display(), "Footnote extraction failed: {err}");
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
     let images = match collect_images(path) {
         Ok(images) => images,
         Err(err) => {
@@ -47,15 +350,244 @@ pub fn load_book_content(path: &Path) -> Result<LoadedBook> {
             Vec::new()
         }
     };
+    let emphasis = if options.preserve_emphasis && is_epub(path) {
+        match load_epub_emphasis(path, &text) {
+            Ok(runs) => runs,
+            Err(err) => {
+                warn!(path = %path.display(), "Emphasis extraction failed: {err}");
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+    let toc = if is_epub(path) {
+        match load_epub_toc(path, &text) {
+            Ok(toc) => toc,
+            Err(err) => {
+                warn!(path = %path.display(), "TOC extraction failed: {err}");
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+    let page_list = if is_epub(path) {
+        match load_epub_page_list(path, &text) {
+            Ok(pages) => pages,
+            Err(err) => {
+                warn!(path = %path.display(), "Page-list extraction failed: {err}");
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+    let headings = if is_epub(path) {
+        match load_epub_headings(path, &text) {
+            Ok(headings) => headings,
+            Err(err) => {
+                warn!(path = %path.display(), "Heading extraction failed: {err}");
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+    let preformatted = if is_epub(path) {
+        match load_epub_preformatted(path, &text) {
+            Ok(blocks) => blocks,
+            Err(err) => {
+                warn!(path = %path.display(), "Code block extraction failed: {err}");
+                Vec::new()
+            }
+        }
+    } else if is_markdown(path) {
+        match fs::read_to_string(path) {
+            Ok(markdown) => locate_blocks(&markdown_code_blocks(&markdown), &text),
+            Err(err) => {
+                warn!(path = %path.display(), "Code block extraction failed: {err}");
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+    let metadata = if is_epub(path) {
+        match load_epub_metadata(path) {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                warn!(path = %path.display(), "Metadata extraction failed: {err}");
+                EpubMetadata::default()
+            }
+        }
+    } else {
+        EpubMetadata::default()
+    };
+    let cover = if is_epub(path) {
+        load_epub_cover(path)
+    } else {
+        None
+    };
     info!(
         path = %path.display(),
         image_count = images.len(),
+        emphasis_runs = emphasis.len(),
+        toc_entries = toc.len(),
+        printed_pages = page_list.len(),
+        footnotes = footnotes.len(),
+        headings = headings.len(),
+        code_blocks = preformatted.len(),
+        has_cover = cover.is_some(),
+        title = metadata.title.as_deref().unwrap_or(""),
         "Source load complete"
     );
-    Ok(LoadedBook { text, images })
+    Ok(LoadedBook {
+        text,
+        images,
+        emphasis,
+        toc,
+        page_list,
+        metadata,
+        footnotes,
+        headings,
+        preformatted,
+        cover,
+    })
+}
+
+/// Drop repeats of short lines that recur at least `min_repeats` times, such as
+/// a book title or chapter name a converter copied onto every source page.
+///
+/// The first occurrence stays so headings and the TOC still find it. Lines
+/// with quotes or closing punctuation read as dialogue or prose and are never
+/// stripped, however often they repeat. Returns the text and the lines removed.
+fn strip_running_headers(text: &str, min_repeats: usize) -> (String, usize) {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for line in &lines {
+        let line = line.trim();
+        if is_running_header_candidate(line) {
+            *counts.entry(line).or_default() += 1;
+        }
+    }
+    let repeated: HashSet<&str> = counts
+        .into_iter()
+        .filter(|(_, count)| *count >= min_repeats.max(2))
+        .map(|(line, _)| line)
+        .collect();
+    if repeated.is_empty() {
+        return (text.to_string(), 0);
+    }
+    debug!(?repeated, "Detected running headers");
+
+    let mut seen = HashSet::new();
+    let mut kept: Vec<&str> = Vec::with_capacity(lines.len());
+    let mut removed = 0;
+    let mut skip_blank = false;
+    for line in lines {
+        let trimmed = line.trim();
+        if std::mem::take(&mut skip_blank) && trimmed.is_empty() {
+            continue;
+        }
+        if repeated.contains(trimmed) && !seen.insert(trimmed) {
+            removed += 1;
+            // Keep one blank line between the paragraphs the header sat between.
+            skip_blank = kept.last().is_none_or(|prev| prev.trim().is_empty());
+            continue;
+        }
+        kept.push(line);
+    }
+    (kept.join("\n"), removed)
 }
 
-fn load_source_text(path: &Path) -> Result<String> {
+fn is_running_header_candidate(line: &str) -> bool {
+    !line.is_empty()
+        && line.chars().count() <= RUNNING_HEADER_MAX_CHARS
+        && line.chars().any(char::is_alphabetic)
+        && !line.starts_with('[')
+        && !line.contains(['"', '\u{201c}', '\u{201d}', '\u{ab}', '\u{bb}'])
+        && !line.ends_with(['.', '!', '?', ',', ';', ':', '\u{2026}', '\u{2019}', '\''])
+}
+
+/// Walk the spine of the EPUB at `path` the way loading does, recording what
+/// each chapter contributes.
+pub fn diagnose_book(path: &Path) -> Result<BookDiagnostics> {
+    if !is_epub(path) {
+        anyhow::bail!("Diagnostics are only available for EPUB books");
+    }
+    let mut doc =
+        EpubDoc::new(path).with_context(|| format!("Failed to open EPUB at {}", path.display()))?;
+    let mut report = BookDiagnostics {
+        toc_entries: count_nav_points(&doc.toc),
+        language: doc
+            .mdata("language")
+            .and_then(|value| clean_metadata_value(&value)),
+        encrypted: epub_is_encrypted(path),
+        ..BookDiagnostics::default()
+    };
+    loop {
+        let resource = doc
+            .get_current_path()
+            .map(|resource| resource.display().to_string())
+            .unwrap_or_default();
+        if let Some((bytes, _mime)) = doc.get_current() {
+            report.chapters.push(diagnose_chapter(resource, &bytes));
+        }
+        if !doc.go_next() {
+            break;
+        }
+    }
+    info!(
+        path = %path.display(),
+        chapters = report.chapters.len(),
+        empty = report.empty_chapters(),
+        lossy = report.lossy_chapters(),
+        toc_entries = report.toc_entries,
+        "Diagnosed book"
+    );
+    Ok(report)
+}
+
+fn diagnose_chapter(resource: String, bytes: &[u8]) -> ChapterDiagnostics {
+    let decoded = decode_chapter(bytes);
+    let text_chars = html2text::from_read(decoded.text.as_bytes(), 10_000)
+        .map(|plain| plain.trim().chars().count())
+        .unwrap_or(0);
+    ChapterDiagnostics {
+        resource,
+        bytes: bytes.len(),
+        text_chars,
+        lossy: decoded.lossy,
+    }
+}
+
+fn count_nav_points(points: &[epub::doc::NavPoint]) -> usize {
+    points
+        .iter()
+        .map(|point| 1 + count_nav_points(&point.children))
+        .sum()
+}
+
+/// Whether `META-INF/encryption.xml` encrypts more than embedded fonts.
+///
+/// A package the EPUB reader cannot open is left for the normal load path to
+/// report, since pandoc may still manage it.
+fn epub_is_encrypted(path: &Path) -> bool {
+    let Ok(mut doc) = EpubDoc::new(path) else {
+        return false;
+    };
+    doc.get_resource_str_by_path("META-INF/encryption.xml")
+        .is_some_and(|xml| encryption_beyond_fonts(&xml))
+}
+
+fn encryption_beyond_fonts(encryption_xml: &str) -> bool {
+    RE_ENCRYPTION_METHOD
+        .captures_iter(encryption_xml)
+        .any(|caps| !FONT_OBFUSCATION_ALGORITHMS.contains(&caps[1].trim()))
+}
+
+fn load_source_text(path: &Path, image_alt_text: ImageAltText) -> Result<String> {
     if is_text_file(path) {
         info!(path = %path.display(), "Loading plain text content");
         let data = fs::read_to_string(path)
@@ -76,7 +608,7 @@ fn load_source_text(path: &Path) -> Result<String> {
         return load_pdf_with_quack_check(path);
     }
 
-    match load_with_pandoc(path) {
+    match load_with_pandoc(path, image_alt_text) {
         Ok(text) => return Ok(text),
         Err(err) => {
             warn!(
@@ -89,7 +621,7 @@ fn load_source_text(path: &Path) -> Result<String> {
     if is_markdown(path) {
         let data = fs::read_to_string(path)
             .with_context(|| format!("Failed to read markdown file at {}", path.display()))?;
-        return Ok(data);
+        return Ok(markdown_to_plain_text(&data, image_alt_text));
     }
 
     if !is_epub(path) {
@@ -107,12 +639,13 @@ fn load_source_text(path: &Path) -> Result<String> {
     let mut chapters = 0usize;
 
     loop {
-        match doc.get_current_str() {
-            Some((chapter, _mime)) => {
+        match current_chapter(&mut doc) {
+            Some(chapter) => {
                 chapters += 1;
                 if !combined.is_empty() {
                     combined.push_str("\n\n");
                 }
+                let chapter = replace_html_images(&chapter, image_alt_text);
                 // Use a lightweight HTML-to-text pass to remove most markup; fall back to raw chapter on errors.
                 // Use a very large width so we do not bake in hard line breaks; let the UI handle wrapping.
                 let plain = match html2text::from_read(chapter.as_bytes(), 10_000) {
@@ -149,6 +682,1042 @@ fn load_source_text(path: &Path) -> Result<String> {
     Ok(combined)
 }
 
+/// Text of the spine item the document is positioned on, decoded per
+/// [`decode_chapter`].
+fn current_chapter<R: std::io::Read + std::io::Seek>(doc: &mut EpubDoc<R>) -> Option<String> {
+    let (bytes, _mime) = doc.get_current()?;
+    let chapter = decode_chapter(&bytes);
+    if chapter.lossy {
+        warn!(
+            resource = ?doc.get_current_path(),
+            "Chapter is not valid UTF-8 and declares no known encoding; decoding lossily"
+        );
+    }
+    Some(chapter.text)
+}
+
+/// A decoded chapter, flagging when undecodable bytes were replaced.
+#[derive(Debug, PartialEq, Eq)]
+struct DecodedChapter {
+    text: String,
+    lossy: bool,
+}
+
+/// Decode raw XHTML bytes.
+///
+/// A byte-order mark wins, then valid UTF-8 (mislabelled declarations are common),
+/// then the charset from the XML declaration or a `<meta>` tag. Anything else
+/// falls back to lossy UTF-8.
+fn decode_chapter(bytes: &[u8]) -> DecodedChapter {
+    if let Some((encoding, bom_len)) = encoding_rs::Encoding::for_bom(bytes) {
+        let (text, _) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+        return DecodedChapter {
+            text: text.into_owned(),
+            lossy: false,
+        };
+    }
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return DecodedChapter {
+            text: text.to_string(),
+            lossy: false,
+        };
+    }
+    // Declarations sit at the top and are ASCII in every encoding we can sniff.
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(1024)]);
+    let declared = RE_XML_ENCODING
+        .captures(&head)
+        .or_else(|| RE_META_CHARSET.captures(&head))
+        .and_then(|caps| encoding_rs::Encoding::for_label(caps[1].as_bytes()))
+        .filter(|encoding| *encoding != encoding_rs::UTF_8);
+    match declared {
+        Some(encoding) => {
+            let (text, had_errors) = encoding.decode_without_bom_handling(bytes);
+            DecodedChapter {
+                text: text.into_owned(),
+                lossy: had_errors,
+            }
+        }
+        None => DecodedChapter {
+            text: String::from_utf8_lossy(bytes).into_owned(),
+            lossy: true,
+        },
+    }
+}
+
+/// Walk the EPUB spine and locate emphasized fragments in the already-loaded text.
+///
+/// The text may come from pandoc or html2text, so fragments are matched by content
+/// rather than by offsets from a second rendering pass.
+fn load_epub_emphasis(path: &Path, text: &str) -> Result<Vec<EmphasisRun>> {
+    let mut doc =
+        EpubDoc::new(path).with_context(|| format!("Failed to open EPUB at {}", path.display()))?;
+    let mut runs = Vec::new();
+    let mut cursor = 0usize;
+    loop {
+        if let Some(chapter) = current_chapter(&mut doc) {
+            cursor = locate_emphasis_runs(chapter.as_bytes(), text, cursor, &mut runs);
+        }
+        if !doc.go_next() {
+            break;
+        }
+    }
+    debug!(runs = runs.len(), "Extracted emphasis runs");
+    Ok(runs)
+}
+
+/// Render `html` with rich annotations and find each fragment in `text` starting at
+/// `cursor`. Plain fragments only advance the cursor so emphasized words anchor to the
+/// right occurrence. Returns the cursor after the last located fragment.
+fn locate_emphasis_runs(
+    html: &[u8],
+    text: &str,
+    mut cursor: usize,
+    runs: &mut Vec<EmphasisRun>,
+) -> usize {
+    // Bound each search so fragments that never made it into `text` stay cheap.
+    const SEARCH_WINDOW_BYTES: usize = 4096;
+
+    let Ok(lines) = html2text::from_read_rich(html, 10_000) else {
+        return cursor;
+    };
+    for line in &lines {
+        for piece in line.tagged_strings() {
+            let fragment = piece.s.trim();
+            if fragment.is_empty() {
+                continue;
+            }
+            let mut window_end = (cursor + SEARCH_WINDOW_BYTES).min(text.len());
+            while !text.is_char_boundary(window_end) {
+                window_end -= 1;
+            }
+            let Some(found) = text
+                .get(cursor..window_end)
+                .and_then(|window| window.find(fragment))
+            else {
+                continue;
+            };
+            let start = cursor + found;
+            let end = start + fragment.len();
+            cursor = end;
+
+            let italic = piece
+                .tag
+                .iter()
+                .any(|tag| matches!(tag, RichAnnotation::Emphasis));
+            let bold = piece
+                .tag
+                .iter()
+                .any(|tag| matches!(tag, RichAnnotation::Strong));
+            let style = match (italic, bold) {
+                (true, true) => EmphasisStyle::BoldItalic,
+                (true, false) => EmphasisStyle::Italic,
+                (false, true) => EmphasisStyle::Bold,
+                (false, false) => continue,
+            };
+            runs.push(EmphasisRun { start, end, style });
+        }
+    }
+    cursor
+}
+
+/// Walk the EPUB spine and locate `<h1>`–`<h6>` headings in the already-loaded text.
+fn load_epub_headings(path: &Path, text: &str) -> Result<Vec<Heading>> {
+    let mut doc =
+        EpubDoc::new(path).with_context(|| format!("Failed to open EPUB at {}", path.display()))?;
+    let mut headings = Vec::new();
+    let mut cursor = 0usize;
+    loop {
+        if let Some(chapter) = current_chapter(&mut doc) {
+            cursor = locate_headings(&chapter, text, cursor, &mut headings);
+        }
+        if !doc.go_next() {
+            break;
+        }
+    }
+    debug!(headings = headings.len(), "Extracted headings");
+    Ok(headings)
+}
+
+/// Find each heading of `html` in `text` starting at `cursor`. A heading only
+/// matches where it fills a whole line, so a short title like "II" doesn't
+/// anchor inside body text. Returns the cursor after the last located heading.
+fn locate_headings(
+    html: &str,
+    text: &str,
+    mut cursor: usize,
+    headings: &mut Vec<Heading>,
+) -> usize {
+    // Headings open their chapter, but front matter before them can be long.
+    const SEARCH_WINDOW_BYTES: usize = 16 * 1024;
+
+    for caps in RE_HTML_HEADING.captures_iter(html) {
+        let level = caps[1].parse().unwrap_or(1);
+        let Some(title) = plain_inline_text(&caps[2]) else {
+            continue;
+        };
+        let mut window_end = (cursor + SEARCH_WINDOW_BYTES).min(text.len());
+        while !text.is_char_boundary(window_end) {
+            window_end -= 1;
+        }
+        let Some(found) = text
+            .get(cursor..window_end)
+            .and_then(|window| find_whole_line(window, &title))
+        else {
+            continue;
+        };
+        let start = cursor + found;
+        let end = start + title.len();
+        cursor = end;
+        headings.push(Heading { start, end, level });
+    }
+    cursor
+}
+
+/// First occurrence of `needle` in `haystack` that is alone on its line, ignoring
+/// the `#` prefix html2text puts before headings.
+fn find_whole_line(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .match_indices(needle)
+        .map(|(idx, _)| idx)
+        .find(|&idx| {
+            let before = haystack[..idx].trim_end_matches([' ', '\t', '#']);
+            let after = haystack[idx + needle.len()..].trim_start_matches([' ', '\t']);
+            (before.is_empty() || before.ends_with('\n'))
+                && (after.is_empty() || after.starts_with(['\n', '\r']))
+        })
+}
+
+/// Walk the EPUB spine and locate `<pre>` blocks in the already-loaded text.
+fn load_epub_preformatted(path: &Path, text: &str) -> Result<Vec<Range<usize>>> {
+    let mut doc =
+        EpubDoc::new(path).with_context(|| format!("Failed to open EPUB at {}", path.display()))?;
+    let mut blocks = Vec::new();
+    loop {
+        if let Some(chapter) = current_chapter(&mut doc) {
+            blocks.extend(
+                RE_HTML_PRE
+                    .find_iter(&chapter)
+                    .filter_map(|pre| html2text::from_read(pre.as_str().as_bytes(), 10_000).ok()),
+            );
+        }
+        if !doc.go_next() {
+            break;
+        }
+    }
+    let located = locate_blocks(&blocks, text);
+    debug!(
+        blocks = blocks.len(),
+        located = located.len(),
+        "Extracted code blocks"
+    );
+    Ok(located)
+}
+
+/// Contents of each fenced (```` ``` ```` or `~~~`) block in `markdown`.
+fn markdown_code_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current: Option<String> = None;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            if let Some(block) = current.take() {
+                blocks.push(block);
+            } else {
+                current = Some(String::new());
+            }
+            continue;
+        }
+        if let Some(block) = current.as_mut() {
+            block.push_str(line);
+            block.push('\n');
+        }
+    }
+    blocks
+}
+
+/// Byte ranges in `text` covering each of `blocks`, in order.
+///
+/// Each non-blank line of a block must fill a whole line of `text`, close
+/// after the previous one, so indentation changes from the converter don't
+/// matter. A block's first line is searched for anywhere after the previous
+/// block, trying each occurrence until the rest of the block follows it.
+/// Blocks that cannot be found are skipped.
+fn locate_blocks(blocks: &[String], text: &str) -> Vec<Range<usize>> {
+    // Blank lines and wrapped output are all that should separate block lines.
+    const NEXT_LINE_WINDOW_BYTES: usize = 1024;
+
+    let window = |start: usize, len: usize| {
+        let mut end = (start + len).min(text.len());
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.get(start..end)
+    };
+    let follows = |mut end: usize, lines: &[&str]| {
+        for line in lines {
+            let found = window(end, NEXT_LINE_WINDOW_BYTES)
+                .and_then(|window| find_whole_line(window, line))?;
+            end += found + line.len();
+        }
+        Some(end)
+    };
+    let mut located = Vec::new();
+    let mut cursor = 0usize;
+    for block in blocks {
+        let lines: Vec<&str> = block
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        let Some((first, rest)) = lines.split_first() else {
+            continue;
+        };
+        let mut from = cursor;
+        while let Some(start) = text
+            .get(from..)
+            .and_then(|tail| find_whole_line(tail, first))
+            .map(|found| from + found)
+        {
+            if let Some(end) = follows(start + first.len(), rest) {
+                located.push(start..end);
+                cursor = end;
+                break;
+            }
+            from = start + first.len();
+        }
+    }
+    located
+}
+
+/// Resolve footnote references in the EPUB spine and move their note text to
+/// where `mode` asks for it. `text` is left untouched when no footnotes are found.
+fn load_epub_footnotes(
+    path: &Path,
+    text: &mut String,
+    mode: FootnoteMode,
+) -> Result<Vec<Footnote>> {
+    let mut doc =
+        EpubDoc::new(path).with_context(|| format!("Failed to open EPUB at {}", path.display()))?;
+    let mut chapters = Vec::new();
+    loop {
+        if let Some(chapter) = current_chapter(&mut doc) {
+            chapters.push(chapter);
+        }
+        if !doc.go_next() {
+            break;
+        }
+    }
+    let footnotes = place_footnotes(&chapters, text, mode);
+    debug!(footnotes = footnotes.len(), "Extracted footnotes");
+    Ok(footnotes)
+}
+
+/// A footnote reference found in the spine, before it is anchored in the text.
+#[derive(Debug)]
+struct NoteRef {
+    chapter: usize,
+    href: String,
+    marker: String,
+    text: String,
+    /// Chapter holding the note body.
+    body_chapter: usize,
+}
+
+/// Pending rewrite of `range` in the book text.
+#[derive(Debug)]
+struct TextEdit {
+    range: Range<usize>,
+    replacement: String,
+}
+
+/// Find footnote references in `chapters`, anchor their markers in `text` and
+/// rewrite `text` so each note appears once, placed according to `mode`.
+///
+/// A reference is a link with a short marker (digits or `*†‡§¶`) pointing at a
+/// later element; links pointing backwards are the notes' own back-links.
+fn place_footnotes(chapters: &[String], text: &mut String, mode: FootnoteMode) -> Vec<Footnote> {
+    let refs = collect_note_refs(chapters);
+    if refs.is_empty() {
+        return Vec::new();
+    }
+    let markers = locate_note_markers(chapters, text, &refs);
+    if markers.is_empty() {
+        return Vec::new();
+    }
+
+    let chapter_starts = locate_chapter_starts(chapters, text);
+    let chapter_end = |chapter: usize| {
+        chapter_starts[chapter + 1..]
+            .iter()
+            .flatten()
+            .next()
+            .copied()
+            .unwrap_or(text.len())
+    };
+
+    let mut edits = Vec::new();
+    let mut removed_lines: Vec<Range<usize>> = Vec::new();
+    for &(ref_idx, ref range) in &markers {
+        let note = &refs[ref_idx];
+        let search_start = chapter_starts[note.body_chapter]
+            .unwrap_or(range.end)
+            .max(range.end);
+        let search_end = chapter_end(note.body_chapter).max(search_start);
+        if let Some(line) = find_note_line(text, search_start..search_end, &note.text)
+            && !removed_lines.contains(&line)
+        {
+            removed_lines.push(line.clone());
+            edits.push(TextEdit {
+                range: line,
+                replacement: String::new(),
+            });
+        }
+    }
+
+    let mut chapter_notes: Vec<(usize, String)> = Vec::new();
+    for &(ref_idx, ref range) in &markers {
+        let note = &refs[ref_idx];
+        match mode {
+            FootnoteMode::Inline => edits.push(TextEdit {
+                range: range.end..range.end,
+                replacement: format!(" [{}]", note.text),
+            }),
+            FootnoteMode::EndOfChapter => {
+                let mut at = chapter_end(note.chapter).max(range.end);
+                // Back up over trailing whitespace and any note lines removed there.
+                loop {
+                    at = text[..at].trim_end().len();
+                    match removed_lines
+                        .iter()
+                        .find(|line| line.start < at && at <= line.end)
+                    {
+                        Some(line) => at = line.start,
+                        None => break,
+                    }
+                }
+                let block = format!("\n\n{} {}", note.marker, note.text);
+                match chapter_notes.iter_mut().find(|(pos, _)| *pos == at) {
+                    Some((_, notes)) => notes.push_str(&block),
+                    None => chapter_notes.push((at, block)),
+                }
+            }
+        }
+    }
+    edits.extend(chapter_notes.into_iter().map(|(at, replacement)| TextEdit {
+        range: at..at,
+        replacement,
+    }));
+    edits.sort_by_key(|edit| (edit.range.start, edit.range.end));
+
+    let mut rebuilt = String::with_capacity(text.len());
+    let mut applied: Vec<(Range<usize>, isize)> = Vec::new();
+    let mut copied = 0usize;
+    for edit in edits {
+        if edit.range.start < copied {
+            continue;
+        }
+        rebuilt.push_str(&text[copied..edit.range.start]);
+        rebuilt.push_str(&edit.replacement);
+        copied = edit.range.end;
+        let delta = edit.replacement.len() as isize - edit.range.len() as isize;
+        applied.push((edit.range, delta));
+    }
+    rebuilt.push_str(&text[copied..]);
+
+    let footnotes = markers
+        .into_iter()
+        .filter(|(_, range)| {
+            !applied
+                .iter()
+                .any(|(edit, _)| edit.start < range.end && range.start < edit.end)
+        })
+        .map(|(ref_idx, range)| {
+            let shift: isize = applied
+                .iter()
+                .filter(|(edit, _)| edit.end <= range.start)
+                .map(|(_, delta)| delta)
+                .sum();
+            let start = range.start.saturating_add_signed(shift);
+            Footnote {
+                marker: refs[ref_idx].marker.clone(),
+                text: refs[ref_idx].text.clone(),
+                start,
+                end: start + range.len(),
+            }
+        })
+        .collect();
+    *text = rebuilt;
+    footnotes
+}
+
+/// Footnote references in spine order, with their note text resolved.
+fn collect_note_refs(chapters: &[String]) -> Vec<NoteRef> {
+    let mut ids: HashMap<&str, (usize, usize, &str)> = HashMap::new();
+    for (chapter, html) in chapters.iter().enumerate() {
+        for caps in RE_HTML_ID.captures_iter(html) {
+            let (Some(tag), Some(id)) = (caps.get(1), caps.get(2)) else {
+                continue;
+            };
+            ids.entry(id.as_str())
+                .or_insert((chapter, tag.start() - 1, tag.as_str()));
+        }
+    }
+
+    let containers: Vec<Vec<Range<usize>>> = chapters
+        .iter()
+        .map(|html| note_container_ranges(html))
+        .collect();
+    let mut refs = Vec::new();
+    for (chapter, html) in chapters.iter().enumerate() {
+        for caps in RE_HTML_LINK.captures_iter(html) {
+            let (Some(link), Some(attrs), Some(inner)) = (caps.get(0), caps.get(1), caps.get(2))
+            else {
+                continue;
+            };
+            let Some(href) = RE_HTML_HREF
+                .captures(attrs.as_str())
+                .and_then(|href| href.get(1))
+            else {
+                continue;
+            };
+            let marker = strip_tags(inner.as_str());
+            if !RE_NOTE_MARKER.is_match(&marker) {
+                continue;
+            }
+            let Some(&(body_chapter, body_start, tag)) = href
+                .as_str()
+                .split_once('#')
+                .and_then(|(_, id)| ids.get(id))
+            else {
+                continue;
+            };
+            if (body_chapter, body_start) <= (chapter, link.start()) {
+                continue;
+            }
+            // A small number linking forward is as often a cross-reference, so
+            // the link or its target must say it is a note.
+            let declared = RE_NOTEREF_ATTR.is_match(attrs.as_str());
+            if !declared
+                && !containers[body_chapter]
+                    .iter()
+                    .any(|container| container.contains(&body_start))
+            {
+                continue;
+            }
+            let body = note_element(&chapters[body_chapter], body_start, tag);
+            let marker = marker.trim_matches(['[', ']']).to_string();
+            let Some(text) = note_text(body, &marker) else {
+                continue;
+            };
+            refs.push(NoteRef {
+                chapter,
+                href: href.as_str().to_string(),
+                marker,
+                text,
+                body_chapter,
+            });
+        }
+    }
+    refs
+}
+
+/// Walk each chapter's rendering alongside `text` and record where every
+/// reference marker landed, as `(index into refs, marker range)`.
+fn locate_note_markers(
+    chapters: &[String],
+    text: &str,
+    refs: &[NoteRef],
+) -> Vec<(usize, Range<usize>)> {
+    const SEARCH_WINDOW_BYTES: usize = 4096;
+
+    let mut markers = Vec::new();
+    let mut cursor = 0usize;
+    let mut next_ref = 0usize;
+    for (chapter, html) in chapters.iter().enumerate() {
+        while next_ref < refs.len() && refs[next_ref].chapter < chapter {
+            next_ref += 1;
+        }
+        let Ok(lines) = html2text::from_read_rich(html.as_bytes(), 10_000) else {
+            continue;
+        };
+        for piece in lines.iter().flat_map(|line| line.tagged_strings()) {
+            let fragment = piece.s.trim();
+            if fragment.is_empty() {
+                continue;
+            }
+            let mut window_end = (cursor + SEARCH_WINDOW_BYTES).min(text.len());
+            while !text.is_char_boundary(window_end) {
+                window_end -= 1;
+            }
+            let window = text.get(cursor..window_end).unwrap_or_default();
+            let is_ref = refs.get(next_ref).is_some_and(|note| {
+                note.chapter == chapter
+                    && fragment.trim_matches(['[', ']']) == note.marker
+                    && piece
+                        .tag
+                        .iter()
+                        .any(|tag| matches!(tag, RichAnnotation::Link(href) if *href == note.href))
+            });
+            if is_ref {
+                let marker = &refs[next_ref].marker;
+                let found = [Some(marker.clone()), superscript_marker(marker)]
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|form| window.find(&form).map(|at| at..at + form.len()))
+                    .min_by_key(|range| range.start);
+                if let Some(found) = found {
+                    let range = cursor + found.start..cursor + found.end;
+                    cursor = range.end;
+                    markers.push((next_ref, range));
+                }
+                next_ref += 1;
+                continue;
+            }
+            if let Some(found) = window.find(fragment) {
+                cursor += found + fragment.len();
+            }
+        }
+    }
+    markers
+}
+
+/// Where each chapter's rendering begins in `text`, if it could be found.
+fn locate_chapter_starts(chapters: &[String], text: &str) -> Vec<Option<usize>> {
+    let mut cursor = 0usize;
+    chapters
+        .iter()
+        .map(|html| {
+            let plain = html2text::from_read(html.as_bytes(), 10_000).ok()?;
+            let found = locate_chapter_start(&plain, text, cursor)?;
+            cursor = found.end;
+            Some(found.start)
+        })
+        .collect()
+}
+
+/// The line within `within` holding the note body, including the newlines
+/// after it. Lines are compared by letters and digits only, so converter
+/// markup and the leading marker do not matter, but a line much longer than
+/// the note (ordinary prose quoting it) is never picked.
+fn find_note_line(text: &str, within: Range<usize>, note: &str) -> Option<Range<usize>> {
+    const KEY_CHARS: usize = 40;
+    const SLACK_CHARS: usize = 8;
+
+    let alnum = |s: &str| -> String {
+        s.chars()
+            .filter(|ch| ch.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    let note_key = alnum(note);
+    let probe: String = note_key.chars().take(KEY_CHARS).collect();
+    if probe.is_empty() {
+        return None;
+    }
+    let mut line_start = within.start;
+    if let Some(newline) = text[..line_start].rfind('\n') {
+        line_start = newline + 1;
+    }
+    for line in text.get(line_start..within.end)?.split_inclusive('\n') {
+        let range = line_start..line_start + line.len();
+        line_start = range.end;
+        let key = alnum(line);
+        if key.contains(&probe) && key.chars().count() <= note_key.chars().count() + SLACK_CHARS {
+            let blank_after =
+                text[range.end..].len() - text[range.end..].trim_start_matches('\n').len();
+            return Some(range.start..range.end + blank_after);
+        }
+    }
+    None
+}
+
+/// The element opened at `start`, widened to the enclosing block when the id
+/// sits on an inline tag such as `<a id="fn1">`.
+fn note_element<'a>(html: &'a str, start: usize, tag: &str) -> &'a str {
+    const INLINE_TAGS: &[&str] = &["a", "span", "sup", "sub", "em", "strong", "b", "i", "small"];
+    const BLOCK_TAGS: &[&str] = &["p", "li", "aside", "div", "section", "dd", "td"];
+
+    let (start, tag) = if INLINE_TAGS
+        .iter()
+        .any(|inline| tag.eq_ignore_ascii_case(inline))
+    {
+        match RE_HTML_TAG
+            .captures_iter(&html[..start])
+            .filter(|caps| {
+                caps.get(1).is_some_and(|close| close.is_empty())
+                    && caps.get(2).is_some_and(|name| {
+                        BLOCK_TAGS
+                            .iter()
+                            .any(|b| name.as_str().eq_ignore_ascii_case(b))
+                    })
+            })
+            .last()
+            .and_then(|caps| Some((caps.get(0)?.start(), caps.get(2)?.as_str())))
+        {
+            Some(block) => block,
+            None => (start, tag),
+        }
+    } else {
+        (start, tag)
+    };
+
+    let mut depth = 0usize;
+    for caps in RE_HTML_TAG.captures_iter(&html[start..]) {
+        let (Some(whole), Some(close), Some(name), Some(self_closing)) =
+            (caps.get(0), caps.get(1), caps.get(2), caps.get(3))
+        else {
+            continue;
+        };
+        if !name.as_str().eq_ignore_ascii_case(tag) || !self_closing.is_empty() {
+            continue;
+        }
+        if close.is_empty() {
+            depth += 1;
+        } else {
+            depth = depth.saturating_sub(1);
+            if depth == 0 {
+                return &html[start..start + whole.end()];
+            }
+        }
+    }
+    &html[start..]
+}
+
+/// Plain note text from its element, without the leading marker or trailing back-link arrow.
+fn note_text(element: &str, marker: &str) -> Option<String> {
+    let plain = plain_inline_text(element)?;
+    let body = match RE_LEADING_NOTE_MARKER.captures(&plain) {
+        Some(caps) if &caps[1] == marker => &plain[caps[0].len()..],
+        _ => plain.as_str(),
+    };
+    let text = body.trim_end_matches(['↩', '\u{fe0e}', ' ']).to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+/// Byte ranges of `<aside>` elements and of elements marked as footnotes or
+/// endnotes through `epub:type` or `role`.
+fn note_container_ranges(html: &str) -> Vec<Range<usize>> {
+    let mut open: Vec<(&str, usize, bool)> = Vec::new();
+    let mut ranges = Vec::new();
+    for caps in RE_HTML_TAG.captures_iter(html) {
+        let (Some(whole), Some(close), Some(name), Some(self_closing)) =
+            (caps.get(0), caps.get(1), caps.get(2), caps.get(3))
+        else {
+            continue;
+        };
+        if !self_closing.is_empty() {
+            continue;
+        }
+        let name = name.as_str();
+        if close.is_empty() {
+            let container = name.eq_ignore_ascii_case("aside")
+                || RE_NOTE_CONTAINER_ATTR.is_match(whole.as_str());
+            open.push((name, whole.start(), container));
+        } else if let Some(depth) = open
+            .iter()
+            .rposition(|(tag, _, _)| tag.eq_ignore_ascii_case(name))
+        {
+            // Unclosed tags inside end here too.
+            ranges.extend(
+                open.drain(depth..)
+                    .filter(|(_, _, container)| *container)
+                    .map(|(_, start, _)| start..whole.end()),
+            );
+        }
+    }
+    ranges
+}
+
+/// Text content of an HTML fragment on one line, with whitespace collapsed.
+fn plain_inline_text(html: &str) -> Option<String> {
+    let lines = html2text::from_read_rich(html.as_bytes(), 10_000).ok()?;
+    let plain = lines
+        .iter()
+        .map(|line| {
+            line.tagged_strings()
+                .map(|piece| piece.s.as_str())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let plain = plain.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!plain.is_empty()).then_some(plain)
+}
+
+fn strip_tags(html: &str) -> String {
+    RE_HTML_TAG.replace_all(html, "").trim().to_string()
+}
+
+/// Unicode superscript form of a numeric marker, as pandoc renders `<sup>`.
+fn superscript_marker(marker: &str) -> Option<String> {
+    const SUPERSCRIPT_DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+    marker
+        .chars()
+        .map(|ch| Some(SUPERSCRIPT_DIGITS[ch.to_digit(10)? as usize]))
+        .collect()
+}
+
+/// Read title, creator, language, publisher and identifier from the EPUB
+/// metadata. Missing or blank fields are `None`.
+pub fn load_epub_metadata(path: &Path) -> Result<EpubMetadata> {
+    let doc =
+        EpubDoc::new(path).with_context(|| format!("Failed to open EPUB at {}", path.display()))?;
+    let field = |name: &str| {
+        doc.mdata(name)
+            .and_then(|value| clean_metadata_value(&value))
+    };
+    Ok(EpubMetadata {
+        title: field("title"),
+        creator: field("creator"),
+        language: field("language"),
+        publisher: field("publisher"),
+        identifier: field("identifier"),
+    })
+}
+
+/// Read the cover image declared in the EPUB package, if any.
+pub fn load_epub_cover(path: &Path) -> Option<Vec<u8>> {
+    let mut doc = EpubDoc::new(path).ok()?;
+    let (cover, mime) = doc.get_cover()?;
+    debug!(path = %path.display(), mime = %mime, bytes = cover.len(), "Found EPUB cover");
+    Some(cover)
+}
+
+fn clean_metadata_value(raw: &str) -> Option<String> {
+    let cleaned = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!cleaned.is_empty()).then_some(cleaned)
+}
+
+/// Read the EPUB navigation (nav/ncx) and resolve each entry to the offset in
+/// `text` where its chapter starts.
+///
+/// `text` may come from pandoc rather than the native spine walk, so chapter
+/// starts are found by matching each spine document's opening line in order.
+/// Entries pointing at a fragment inside a chapter resolve to that chapter's
+/// start; entries whose chapter cannot be found are dropped.
+pub fn load_epub_toc(path: &Path, text: &str) -> Result<Vec<TocEntry>> {
+    let mut doc =
+        EpubDoc::new(path).with_context(|| format!("Failed to open EPUB at {}", path.display()))?;
+    let chapter_offsets = locate_spine_chapters(&mut doc, text);
+
+    let mut entries = Vec::new();
+    flatten_nav_points(&doc.toc, 0, &chapter_offsets, &mut entries);
+    debug!(
+        entries = entries.len(),
+        located_chapters = chapter_offsets.len(),
+        "Extracted table of contents"
+    );
+    Ok(entries)
+}
+
+/// Where each spine document's rendering starts in `text`, keyed by its path
+/// in the package. Documents that cannot be found are left out.
+fn locate_spine_chapters<R: std::io::Read + std::io::Seek>(
+    doc: &mut EpubDoc<R>,
+    text: &str,
+) -> HashMap<PathBuf, usize> {
+    let mut chapter_offsets = HashMap::new();
+    let mut cursor = 0usize;
+    loop {
+        if let (Some(resource), Some(chapter)) = (doc.get_current_path(), current_chapter(doc))
+            && let Ok(plain) = html2text::from_read(chapter.as_bytes(), 10_000)
+            && let Some(found) = locate_chapter_start(&plain, text, cursor)
+        {
+            chapter_offsets.insert(resource, found.start);
+            cursor = found.end;
+        }
+        if !doc.go_next() {
+            break;
+        }
+    }
+    chapter_offsets
+}
+
+/// Read the EPUB page-list (the nav document's, or the NCX `pageList`) and
+/// resolve each printed page to the offset in `text` where it begins.
+///
+/// Page breaks are found by rendering a little of their chapter from the
+/// target element on and matching its first line, like chapter starts. Pages
+/// whose chapter cannot be found are dropped; a missing fragment falls back
+/// to the chapter start.
+pub fn load_epub_page_list(path: &Path, text: &str) -> Result<Vec<PrintedPage>> {
+    let mut doc =
+        EpubDoc::new(path).with_context(|| format!("Failed to open EPUB at {}", path.display()))?;
+    let mut candidates: Vec<PathBuf> = doc
+        .resources
+        .values()
+        .filter(|item| {
+            item.mime == "application/x-dtbncx+xml" || item.mime == "application/xhtml+xml"
+        })
+        .map(|item| item.path.clone())
+        .collect();
+    // The EPUB 3 nav document wins over the NCX when both list pages.
+    candidates.sort_by_key(|candidate| candidate.extension().is_some_and(|ext| ext == "ncx"));
+    let Some((nav_path, targets)) = candidates.into_iter().find_map(|candidate| {
+        let source = doc.get_resource_str_by_path(&candidate)?;
+        let targets = parse_page_list(&source);
+        (!targets.is_empty()).then_some((candidate, targets))
+    }) else {
+        return Ok(Vec::new());
+    };
+
+    let chapter_offsets = locate_spine_chapters(&mut doc, text);
+    let mut chapters: HashMap<PathBuf, Option<String>> = HashMap::new();
+    let mut pages = Vec::new();
+    for (label, href) in targets {
+        let Some((resource, fragment)) = resolve_href(&nav_path, &href) else {
+            continue;
+        };
+        let Some(&chapter_start) = chapter_offsets.get(&resource) else {
+            debug!(href = %href, "Skipping printed page outside the located chapters");
+            continue;
+        };
+        let html = chapters
+            .entry(resource.clone())
+            .or_insert_with(|| doc.get_resource_str_by_path(&resource));
+        let char_offset = html
+            .as_deref()
+            .zip(fragment)
+            .and_then(|(html, fragment)| page_break_offset(html, fragment, text, chapter_start))
+            .unwrap_or(chapter_start);
+        pages.push(PrintedPage { label, char_offset });
+    }
+    debug!(
+        printed_pages = pages.len(),
+        nav = %nav_path.display(),
+        "Extracted page-list"
+    );
+    Ok(pages)
+}
+
+/// `(label, href)` for every printed page listed in a nav document or NCX.
+fn parse_page_list(source: &str) -> Vec<(String, String)> {
+    if let Some(list) = RE_NAV_PAGE_LIST
+        .captures(source)
+        .and_then(|caps| caps.get(1))
+    {
+        return RE_HTML_LINK
+            .captures_iter(list.as_str())
+            .filter_map(|caps| {
+                let href = RE_HTML_HREF.captures(caps.get(1)?.as_str())?.get(1)?;
+                let label = strip_tags(caps.get(2)?.as_str());
+                (!label.is_empty()).then(|| (label, href.as_str().to_string()))
+            })
+            .collect();
+    }
+    RE_NCX_PAGE_TARGET
+        .captures_iter(source)
+        .filter_map(|caps| {
+            let label = strip_tags(caps.get(1)?.as_str());
+            let src = caps.get(2)?.as_str().to_string();
+            (!label.is_empty()).then_some((label, src))
+        })
+        .collect()
+}
+
+/// Package path and fragment an `href` in the document at `base` points to.
+fn resolve_href<'a>(base: &Path, href: &'a str) -> Option<(PathBuf, Option<&'a str>)> {
+    let (target, fragment) = match href.split_once('#') {
+        Some((target, fragment)) => (target, Some(fragment).filter(|f| !f.is_empty())),
+        None => (href, None),
+    };
+    if target.is_empty() || target.contains(':') {
+        return None;
+    }
+    let mut resolved = base.parent().map(Path::to_path_buf).unwrap_or_default();
+    for part in target.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                resolved.pop();
+            }
+            part => resolved.push(part),
+        }
+    }
+    Some((resolved, fragment))
+}
+
+/// Where the element with id `fragment` lands in `text`, found by matching
+/// the first line rendered from it onward, searching from `chapter_start`.
+fn page_break_offset(
+    html: &str,
+    fragment: &str,
+    text: &str,
+    chapter_start: usize,
+) -> Option<usize> {
+    /// Enough of the chapter after the break to render its first line.
+    const RENDER_BYTES: usize = 4096;
+
+    let tag = RE_HTML_ID
+        .captures_iter(html)
+        .find(|caps| caps.get(2).is_some_and(|id| id.as_str() == fragment))?
+        .get(1)?;
+    let start = tag.start() - 1;
+    let mut end = (start + RENDER_BYTES).min(html.len());
+    while !html.is_char_boundary(end) {
+        end -= 1;
+    }
+    // Rich rendering leaves out emphasis markers, which a break mid-paragraph
+    // would otherwise carry into the line being matched.
+    let lines = html2text::from_read_rich(html[start..end].as_bytes(), 10_000).ok()?;
+    let plain = lines
+        .iter()
+        .map(|line| {
+            line.tagged_strings()
+                .map(|piece| piece.s.as_str())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    locate_chapter_start(&plain, text, chapter_start).map(|found| found.start)
+}
+
+fn flatten_nav_points(
+    points: &[epub::doc::NavPoint],
+    depth: usize,
+    chapter_offsets: &HashMap<PathBuf, usize>,
+    entries: &mut Vec<TocEntry>,
+) {
+    for point in points {
+        let target = point.content.to_string_lossy();
+        let resource = PathBuf::from(target.split('#').next().unwrap_or_default());
+        let title = point.label.split_whitespace().collect::<Vec<_>>().join(" ");
+        match chapter_offsets.get(&resource) {
+            Some(&char_offset) if !title.is_empty() => entries.push(TocEntry {
+                title,
+                depth,
+                char_offset,
+            }),
+            _ => debug!(target = %target, "Skipping unresolved TOC entry"),
+        }
+        flatten_nav_points(&point.children, depth + 1, chapter_offsets, entries);
+    }
+}
+
+/// Find where a rendered chapter begins in `text`, searching from `cursor`.
+///
+/// Only the opening line is matched, with markdown-style emphasis markers from
+/// html2text stripped, so minor converter differences later in the chapter
+/// do not matter. Returns the matched span so callers can continue after it:
+/// a following chapter that opens with the same line must not resolve here.
+fn locate_chapter_start(plain_chapter: &str, text: &str, cursor: usize) -> Option<Range<usize>> {
+    const SNIPPET_CHARS: usize = 60;
+
+    let line = plain_chapter
+        .lines()
+        .map(|line| line.trim_matches(|c: char| c.is_whitespace() || matches!(c, '*' | '#' | '_')))
+        .find(|line| !line.is_empty())?;
+    let snippet_end = line
+        .char_indices()
+        .nth(SNIPPET_CHARS)
+        .map(|(idx, _)| idx)
+        .unwrap_or(line.len());
+    let snippet = &line[..snippet_end];
+    text.get(cursor..)?
+        .find(snippet)
+        .map(|found| cursor + found..cursor + found + snippet.len())
+}
+
 fn is_text_file(path: &Path) -> bool {
     matches!(
         path.extension()
@@ -281,13 +1850,107 @@ fn flush_pdf_paragraph(out: &mut String, paragraph: &mut String) {
     paragraph.clear();
 }
 
-fn load_with_pandoc(path: &Path) -> Result<String> {
+/// Swap `<img>` tags for an `[Image: alt]` marker, or drop them, before conversion.
+fn replace_html_images(html: &str, image_alt_text: ImageAltText) -> String {
+    RE_HTML_IMG
+        .replace_all(html, |caps: &regex::Captures| {
+            if image_alt_text == ImageAltText::Omit {
+                return String::new();
+            }
+            let alt = RE_HTML_ALT
+                .captures(&caps[0])
+                .and_then(|alt| alt.get(1).or_else(|| alt.get(2)))
+                .map(|alt| {
+                    alt.as_str()
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .unwrap_or_default();
+            if alt.is_empty() {
+                String::new()
+            } else {
+                format!("[Image: {alt}]")
+            }
+        })
+        .into_owned()
+}
+
+/// Strip markdown syntax line by line, for when pandoc is unavailable.
+///
+/// Headings, quotes, list markers, rules, link targets and inline emphasis are
+/// dropped; fenced code keeps its lines. Images become an `[Image: alt]` marker,
+/// as in the pandoc path.
+fn markdown_to_plain_text(markdown: &str, image_alt_text: ImageAltText) -> String {
+    let mut lines = Vec::new();
+    let mut in_fence = false;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            lines.push(line.to_string());
+            continue;
+        }
+        if RE_MARKDOWN_RULE.is_match(line) || RE_MARKDOWN_LINK_DEFINITION.is_match(line) {
+            lines.push(String::new());
+            continue;
+        }
+        let heading = RE_MARKDOWN_BLOCK_MARKER
+            .captures(line)
+            .is_some_and(|caps| caps.name("heading").is_some());
+        let mut line = RE_MARKDOWN_BLOCK_MARKER.replace(line, "").into_owned();
+        // A heading's closing `#` run must follow whitespace, so `C#` keeps its hash.
+        if heading {
+            line = RE_MARKDOWN_CLOSING_HASHES.replace(&line, "").into_owned();
+        }
+        let line = line.trim_end();
+        let line = RE_MARKDOWN_IMAGE.replace_all(line, |caps: &regex::Captures| {
+            let alt = caps[1].trim();
+            if image_alt_text == ImageAltText::Omit || alt.is_empty() {
+                String::new()
+            } else {
+                format!("[Image: {alt}]")
+            }
+        });
+        let line = RE_MARKDOWN_LINK.replace_all(&line, "$1");
+        let line = RE_HTML_TAG.replace_all(&line, "");
+        // Nested emphasis such as `***bold italic***` takes more than one pass.
+        let mut line = line.into_owned();
+        loop {
+            let stripped = RE_MARKDOWN_EMPHASIS.replace_all(&line, |caps: &regex::Captures| {
+                caps.iter()
+                    .skip(1)
+                    .flatten()
+                    .next()
+                    .map_or("", |inner| inner.as_str())
+                    .to_string()
+            });
+            let stripped = RE_MARKDOWN_UNDERSCORE.replace_all(&stripped, "$1$2$3");
+            if stripped == line {
+                break;
+            }
+            line = stripped.into_owned();
+        }
+        lines.push(line);
+    }
+    let text = lines.join("\n");
+    if text.trim().is_empty() {
+        "No textual content found in this file.".to_string()
+    } else {
+        text
+    }
+}
+
+fn load_with_pandoc(path: &Path, image_alt_text: ImageAltText) -> Result<String> {
     info!(
         path = %path.display(),
         "Converting source to plain text with pandoc"
     );
 
-    let signature = source_signature(path)?;
+    let signature = source_signature(path, image_alt_text)?;
     if let Some(cached) = try_read_pandoc_cache(path, &signature)? {
         info!(path = %path.display(), "Using cached pandoc plain-text conversion");
         return Ok(cached);
@@ -304,6 +1967,13 @@ fn load_with_pandoc(path: &Path) -> Result<String> {
         .arg("--eol=lf")
         .arg("--lua-filter")
         .arg(&filter_path)
+        .env(
+            "EBUP_IMAGE_ALT_TEXT",
+            match image_alt_text {
+                ImageAltText::Include => "include",
+                ImageAltText::Omit => "omit",
+            },
+        )
         .output()
         .with_context(|| format!("Failed to start pandoc for {}", path.display()))?;
 
@@ -516,6 +2186,8 @@ struct PandocCacheMeta {
     pipeline_rev: String,
     #[serde(default)]
     filter_sha256: String,
+    #[serde(default)]
+    image_alt_text: ImageAltText,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -540,7 +2212,7 @@ struct QuackCheckOutputToml {
     text_filename: Option<String>,
 }
 
-fn source_signature(path: &Path) -> Result<PandocCacheMeta> {
+fn source_signature(path: &Path, image_alt_text: ImageAltText) -> Result<PandocCacheMeta> {
     let meta = fs::metadata(path)
         .with_context(|| format!("Failed to read source metadata for {}", path.display()))?;
 
@@ -559,6 +2231,7 @@ fn source_signature(path: &Path) -> Result<PandocCacheMeta> {
         source_modified_unix_secs: modified,
         pipeline_rev: PANDOC_PIPELINE_REV.to_string(),
         filter_sha256,
+        image_alt_text,
     })
 }
 
@@ -614,6 +2287,7 @@ fn try_read_pandoc_cache(path: &Path, signature: &PandocCacheMeta) -> Result<Opt
         || cached_meta.source_modified_unix_secs != signature.source_modified_unix_secs
         || cached_meta.pipeline_rev != signature.pipeline_rev
         || cached_meta.filter_sha256 != signature.filter_sha256
+        || cached_meta.image_alt_text != signature.image_alt_text
     {
         return Ok(None);
     }
@@ -792,3 +2466,383 @@ fn quack_check_text_filename(config_path: &Path) -> Result<String> {
         Ok(trimmed.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn running_headers_keep_their_first_occurrence_and_spare_dialogue() {
+        let mut text = String::from("THE LONG ROAD\n\nChapter One\n\n");
+        for idx in 0..4 {
+            text.push_str(&format!(
+                "Paragraph {idx} goes on.\n\n\u{201c}No.\u{201d}\n\nTHE LONG ROAD\n\n* * *\n\n"
+            ));
+        }
+        let (stripped, removed) = strip_running_headers(&text, 3);
+        assert_eq!(removed, 4);
+        assert_eq!(stripped.matches("THE LONG ROAD").count(), 1);
+        assert!(stripped.starts_with("THE LONG ROAD\n\nChapter One\n\nParagraph 0"));
+        assert_eq!(stripped.matches("\u{201c}No.\u{201d}").count(), 4);
+        assert_eq!(stripped.matches("* * *").count(), 4);
+        assert!(!stripped.contains("\n\n\n"));
+
+        let (unchanged, removed) = strip_running_headers(&text, 6);
+        assert_eq!((unchanged.as_str(), removed), (text.as_str(), 0));
+    }
+
+    #[test]
+    fn markdown_fallback_strips_syntax() {
+        let markdown = "# Chapter *One* #\n\
+            \n\
+            It was **very** dark, said _Ann_; see [the map](map.html) and snake_case.\n\
+            \n\
+            > - A ***quoted*** item with `code`\n\
+            1. First\n\
+            ---\n\
+            ![A lighthouse](img/light.png)\n\
+            ```\n\
+            let *x* = 1;\n\
+            ```\n\
+            [map]: https://example.com\n\
+            ## Learning C#\n\
+            1984. The year it began.\n";
+        assert_eq!(
+            markdown_to_plain_text(markdown, ImageAltText::Include),
+            "Chapter One\n\
+             \n\
+             It was very dark, said Ann; see the map and snake_case.\n\
+             \n\
+             A quoted item with code\n\
+             First\n\
+             \n\
+             [Image: A lighthouse]\n\
+             let *x* = 1;\n\
+             \n\
+             Learning C#\n\
+             1984. The year it began."
+        );
+        assert_eq!(
+            markdown_to_plain_text("![Alt](a.png)", ImageAltText::Omit),
+            "No textual content found in this file."
+        );
+    }
+
+    #[test]
+    fn chapters_decode_from_their_declared_encoding() {
+        let windows_1252: &[u8] = b"<?xml version=\"1.0\" encoding=\"windows-1252\"?>\n\
+            <html><body><p>Caf\xe9 \x93quoted\x94 \x96 na\xefve</p></body></html>";
+        let decoded = decode_chapter(windows_1252);
+        assert!(!decoded.lossy);
+        assert!(
+            decoded
+                .text
+                .contains("<p>Caf\u{e9} \u{201c}quoted\u{201d} \u{2013} na\u{ef}ve</p>")
+        );
+
+        let meta = b"<html><head><meta charset=\"ISO-8859-1\"></head><p>\xc0 bient\xf4t</p></html>";
+        assert!(decode_chapter(meta).text.contains("\u{c0} bient\u{f4}t"));
+
+        let utf8 = "<?xml version=\"1.0\" encoding=\"iso-8859-1\"?><p>Caf\u{e9}</p>";
+        assert_eq!(
+            decode_chapter(utf8.as_bytes()),
+            DecodedChapter {
+                text: utf8.to_string(),
+                lossy: false,
+            }
+        );
+
+        let undeclared = decode_chapter(b"<p>Caf\xe9</p>");
+        assert!(undeclared.lossy);
+        assert_eq!(undeclared.text, "<p>Caf\u{fffd}</p>");
+    }
+
+    const EMPHASIS_FIXTURE: &str = "<html><body>\
+        <p>It was <em>not</em> the storm that frightened her.</p>\
+        <p>The note read <strong>do not open</strong> in red ink, and \
+        <em><strong>never</strong></em> was underlined.</p>\
+        <p>Plain text says not much at all.</p>\
+        </body></html>";
+
+    #[test]
+    fn extracts_emphasis_runs_for_em_and_strong() {
+        let text = html2text::from_read(EMPHASIS_FIXTURE.as_bytes(), 10_000).expect("plain text");
+        let mut runs = Vec::new();
+        locate_emphasis_runs(EMPHASIS_FIXTURE.as_bytes(), &text, 0, &mut runs);
+
+        let styled: Vec<(&str, EmphasisStyle)> = runs
+            .iter()
+            .map(|run| (&text[run.start..run.end], run.style))
+            .collect();
+        assert_eq!(
+            styled,
+            vec![
+                ("not", EmphasisStyle::Italic),
+                ("do not open", EmphasisStyle::Bold),
+                ("never", EmphasisStyle::BoldItalic),
+            ]
+        );
+        // The emphasized "not" must anchor to its own occurrence, not a later plain one.
+        let first_not = text.find("not").expect("first not");
+        assert_eq!(runs[0].start, first_not);
+    }
+
+    #[test]
+    fn locates_headings_on_their_own_lines() {
+        let html = "<h1>Chapter <em>II</em></h1><p>Body text.</p><h2 class=\"sub\">The Storm</h2>";
+        let text = "Intro mentions II and the storm.\n\n# Chapter II\n\nBody text.\n\nThe Storm\n";
+        let mut headings = Vec::new();
+        let cursor = locate_headings(html, text, 0, &mut headings);
+
+        let found: Vec<(&str, u8)> = headings
+            .iter()
+            .map(|heading| (&text[heading.start..heading.end], heading.level))
+            .collect();
+        assert_eq!(found, vec![("Chapter II", 1), ("The Storm", 2)]);
+        assert_eq!(cursor, headings[1].end);
+    }
+
+    #[test]
+    fn fenced_code_blocks_are_located_in_the_converted_text() {
+        let markdown = "Run it:\n\n```rust\nfn main() {\n    run();\n}\n```\n\nThen main() returns.\n\n~~~\nmissing\n~~~\n";
+        let blocks = markdown_code_blocks(markdown);
+        assert_eq!(blocks, vec!["fn main() {\n    run();\n}\n", "missing\n"]);
+
+        // Converters indent code; prose that merely mentions it must not match.
+        let text = "Run it: fn main() {\n\n    fn main() {\n        run();\n    }\n\nThen main() returns.\n";
+        let located = locate_blocks(&blocks, text);
+        assert_eq!(located.len(), 1);
+        assert_eq!(
+            &text[located[0].clone()],
+            "fn main() {\n        run();\n    }"
+        );
+
+        // Long chapters put code far from the start, sometimes after a
+        // look-alike first line that the rest of the block does not follow.
+        let far = format!(
+            "{}\nfn main() {{\nprose again\n{}\n    fn main() {{\n        run();\n    }}\n",
+            "Filler line.\n".repeat(10_000),
+            "More filler.\n".repeat(200),
+        );
+        let located = locate_blocks(&blocks, &far);
+        assert_eq!(located.len(), 1);
+        assert!(located[0].start > 128 * 1024);
+        assert_eq!(
+            &far[located[0].clone()],
+            "fn main() {\n        run();\n    }"
+        );
+    }
+
+    #[test]
+    fn chapter_diagnostics_flag_empty_and_lossy_chapters() {
+        let report = BookDiagnostics {
+            chapters: vec![
+                diagnose_chapter(
+                    "text/one.xhtml".to_string(),
+                    b"<html><body><p>Hello there.</p></body></html>",
+                ),
+                diagnose_chapter(
+                    "text/blank.xhtml".to_string(),
+                    b"<html><body><div> </div></body></html>",
+                ),
+                diagnose_chapter("text/bad.xhtml".to_string(), b"<p>caf\xe9</p>"),
+            ],
+            ..BookDiagnostics::default()
+        };
+        assert_eq!(report.chapters[0].text_chars, "Hello there.".len());
+        assert_eq!(report.chapters[1].bytes, 38);
+        assert_eq!(report.empty_chapters(), 1);
+        assert_eq!(report.lossy_chapters(), 1);
+    }
+
+    #[test]
+    fn only_non_font_encryption_counts_as_drm() {
+        let fonts_only = r#"<encryption xmlns="urn:oasis:names:tc:opendocument:xmlns:container"
+            xmlns:enc="http://www.w3.org/2001/04/xmlenc#">
+          <enc:EncryptedData>
+            <enc:EncryptionMethod Algorithm="http://www.idpf.org/2008/embedding"/>
+            <enc:CipherData><enc:CipherReference URI="OEBPS/fonts/serif.otf"/></enc:CipherData>
+          </enc:EncryptedData>
+        </encryption>"#;
+        assert!(!encryption_beyond_fonts(fonts_only));
+
+        let drm = r#"<encryption xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+          <EncryptedData xmlns="http://www.w3.org/2001/04/xmlenc#">
+            <EncryptionMethod Algorithm="http://www.w3.org/2001/04/xmlenc#aes128-cbc"/>
+            <CipherData><CipherReference URI="OEBPS/chapter1.xhtml"/></CipherData>
+          </EncryptedData>
+        </encryption>"#;
+        assert!(encryption_beyond_fonts(drm));
+        assert!(!encryption_beyond_fonts(""));
+    }
+
+    #[test]
+    fn metadata_values_are_trimmed_and_blank_ones_dropped() {
+        assert_eq!(
+            clean_metadata_value("  The   Left Hand\n of Darkness "),
+            Some("The Left Hand of Darkness".to_string())
+        );
+        assert_eq!(clean_metadata_value(" \n\t"), None);
+    }
+
+    #[test]
+    fn locates_chapter_starts_in_order() {
+        let chapters = [
+            "<h1>Chapter One</h1><p>It began with <em>rain</em>.</p>",
+            "<h1>Chapter Two</h1><p>Then came Chapter One again, as a memory.</p>",
+            "<h1><em>Coda</em></h1><p>The end.</p>",
+            "<h1>Coda</h1><p>Really.</p>",
+        ];
+        let rendered: Vec<String> = chapters
+            .iter()
+            .map(|html| html2text::from_read(html.as_bytes(), 10_000).expect("plain text"))
+            .collect();
+        // Pandoc-style text without html2text's heading and emphasis markers.
+        let text = "Chapter One\n\nIt began with rain.\n\nChapter Two\n\n\
+                    Then came Chapter One again, as a memory.\n\nCoda\n\nThe end.\n\n\
+                    Coda\n\nReally.";
+
+        let mut cursor = 0;
+        let mut starts = Vec::new();
+        for plain in &rendered {
+            let found = locate_chapter_start(plain, text, cursor).expect("chapter start");
+            starts.push(found.start);
+            cursor = found.end;
+        }
+        assert_eq!(
+            starts,
+            vec![
+                0,
+                text.find("Chapter Two").unwrap(),
+                text.find("Coda").unwrap(),
+                text.rfind("Coda").unwrap()
+            ]
+        );
+        assert_eq!(locate_chapter_start("<missing>", text, 0), None);
+    }
+
+    #[test]
+    fn page_lists_resolve_to_their_page_breaks() {
+        let nav = "<nav epub:type=\"toc\"><ol><li><a href=\"text/one.xhtml\">One</a></li></ol></nav>\
+                   <nav epub:type=\"page-list\" hidden=\"\"><ol>\
+                   <li><a href=\"text/one.xhtml#page1\">1</a></li>\
+                   <li><a href=\"text/one.xhtml#page2\"><span>2</span></a></li></ol></nav>";
+        let targets = parse_page_list(nav);
+        assert_eq!(
+            targets,
+            vec![
+                ("1".to_string(), "text/one.xhtml#page1".to_string()),
+                ("2".to_string(), "text/one.xhtml#page2".to_string())
+            ]
+        );
+        let ncx = "<pageList><pageTarget id=\"p7\" type=\"normal\" value=\"7\">\
+                   <navLabel><text>7</text></navLabel><content src=\"one.xhtml#page7\"/>\
+                   </pageTarget></pageList>";
+        assert_eq!(
+            parse_page_list(ncx),
+            vec![("7".to_string(), "one.xhtml#page7".to_string())]
+        );
+        assert!(
+            parse_page_list(nav.split("<nav epub:type=\"page-list\"").next().unwrap()).is_empty()
+        );
+
+        assert_eq!(
+            resolve_href(Path::new("OEBPS/nav/nav.xhtml"), "../text/one.xhtml#page2"),
+            Some((PathBuf::from("OEBPS/text/one.xhtml"), Some("page2")))
+        );
+        assert_eq!(resolve_href(Path::new("OEBPS/nav.xhtml"), "#page2"), None);
+
+        let chapter = "<h1>One</h1><p>It rained.<span epub:type=\"pagebreak\" id=\"page2\" \
+                       title=\"2\"/> It rained <em>again</em> the next day.</p>";
+        let text = "One\n\nIt rained. It rained again the next day.\n";
+        assert_eq!(
+            page_break_offset(chapter, "page2", text, 0),
+            text.find("It rained again")
+        );
+        assert_eq!(page_break_offset(chapter, "page9", text, 0), None);
+    }
+
+    fn footnote_fixture() -> (Vec<String>, String) {
+        let chapters = [
+            "<h1>One</h1><p>He left<sup><a id=\"r1\" epub:type=\"noteref\" \
+             href=\"notes.xhtml#n1\">1</a></sup> at dawn. \
+             She stayed.<a id=\"r2\" href=\"notes.xhtml#n2\">*</a></p>",
+            "<h1>Two</h1><p>Nothing here.</p>",
+            "<h1>Notes</h1><p id=\"n1\"><a href=\"one.xhtml#r1\">1</a>. Dawn was <em>late</em> \
+             that year.</p><aside epub:type=\"footnote\"><p><a id=\"n2\" href=\"one.xhtml#r2\">*</a> \
+             Or so she said.</p></aside>",
+        ];
+        let text = "One\n\nHe left¹ at dawn. She stayed.*\n\nTwo\n\nNothing here.\n\n\
+                    Notes\n\n1. Dawn was late that year.\n\n* Or so she said.\n";
+        (
+            chapters.iter().map(|html| html.to_string()).collect(),
+            text.to_string(),
+        )
+    }
+
+    #[test]
+    fn footnotes_move_to_the_end_of_the_referencing_chapter() {
+        let (chapters, mut text) = footnote_fixture();
+        let footnotes = place_footnotes(&chapters, &mut text, FootnoteMode::EndOfChapter);
+        assert_eq!(
+            text,
+            "One\n\nHe left¹ at dawn. She stayed.*\n\n1 Dawn was late that year.\n\n\
+             * Or so she said.\n\nTwo\n\nNothing here.\n\nNotes\n\n"
+        );
+        let placed: Vec<(&str, &str, &str)> = footnotes
+            .iter()
+            .map(|note| {
+                (
+                    &text[note.start..note.end],
+                    note.marker.as_str(),
+                    note.text.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            placed,
+            vec![
+                ("¹", "1", "Dawn was late that year."),
+                ("*", "*", "Or so she said."),
+            ]
+        );
+    }
+
+    #[test]
+    fn footnotes_can_be_inlined_and_plain_books_are_untouched() {
+        let (chapters, mut text) = footnote_fixture();
+        let footnotes = place_footnotes(&chapters, &mut text, FootnoteMode::Inline);
+        assert_eq!(
+            text,
+            "One\n\nHe left¹ [Dawn was late that year.] at dawn. \
+             She stayed.* [Or so she said.]\n\nTwo\n\nNothing here.\n\nNotes\n\n"
+        );
+        assert_eq!(&text[footnotes[1].start..footnotes[1].end], "*");
+
+        let chapters = vec!["<p>See <a href=\"#top\">the top</a> of page 2.</p>".to_string()];
+        let mut text = "See the top of page 2.".to_string();
+        assert!(place_footnotes(&chapters, &mut text, FootnoteMode::Inline).is_empty());
+        assert_eq!(text, "See the top of page 2.");
+
+        // A bare number linking ahead to an ordinary element is a cross-reference.
+        let chapters = vec![
+            "<p>As in part <a href=\"two.xhtml#p2\">2</a>, it rained.</p>".to_string(),
+            "<p id=\"p2\">It rained all week.</p>".to_string(),
+        ];
+        let mut text = "As in part 2, it rained.\n\nIt rained all week.\n".to_string();
+        assert!(place_footnotes(&chapters, &mut text, FootnoteMode::Inline).is_empty());
+    }
+
+    #[test]
+    fn image_tags_become_alt_markers_only_when_included() {
+        let html = r#"<p>Before <img src="a.png" alt=" A  map "/> after <img src='b.png'></p>"#;
+        assert_eq!(
+            replace_html_images(html, ImageAltText::Include),
+            "<p>Before [Image: A map] after </p>"
+        );
+        assert_eq!(
+            replace_html_images(html, ImageAltText::Omit),
+            "<p>Before  after </p>"
+        );
+    }
+}
@@ -0,0 +1,56 @@
+//! Headless plain-text export (`--extract-text`) for scripts and pipelines.
+//!
+//! Uses the same loader and normalizer as the reader, but never starts iced.
+
+use crate::normalizer::TextNormalizer;
+use crate::text_utils::split_sentences;
+
+/// Render loaded book text as plain paragraphs separated by blank lines.
+///
+/// With a normalizer, each paragraph is replaced by the sentences the TTS
+/// pipeline would speak; paragraphs that normalize to nothing are dropped.
+pub fn render_plain_text(text: &str, normalizer: Option<&TextNormalizer>) -> String {
+    let mut out = String::with_capacity(text.len());
+    for paragraph in text.split("\n\n") {
+        let paragraph = paragraph.trim();
+        if paragraph.is_empty() {
+            continue;
+        }
+        let rendered = match normalizer {
+            Some(normalizer) => normalizer
+                .plan_page(&split_sentences(paragraph))
+                .audio_sentences
+                .join(" "),
+            None => paragraph.to_string(),
+        };
+        if rendered.trim().is_empty() {
+            continue;
+        }
+        if !out.is_empty() {
+            out.push_str("\n\n");
+        }
+        out.push_str(rendered.trim());
+    }
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_paragraphs_and_optionally_normalizes() {
+        let text = "\n\nChapter One\n\n\n\"Hello,\" she said. It was late.\n\n   \n";
+        assert_eq!(
+            render_plain_text(text, None),
+            "Chapter One\n\n\"Hello,\" she said. It was late.\n"
+        );
+
+        let normalized = render_plain_text(text, Some(&TextNormalizer::default()));
+        let paragraphs: Vec<&str> = normalized.trim_end().split("\n\n").collect();
+        assert_eq!(paragraphs.len(), 2);
+        assert!(!paragraphs[1].contains('"'));
+        assert!(paragraphs[1].contains("It was late."));
+    }
+}
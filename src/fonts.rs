@@ -0,0 +1,142 @@
+//! User fonts dropped into the fonts directory next to the base config.
+//!
+//! Each `.ttf`/`.otf` file is handed to iced at startup and listed in the font
+//! picker under the family name stored in the file itself, which is the name
+//! the text renderer matches against.
+
+use std::fs;
+use std::path::Path;
+use tracing::{info, warn};
+use ttf_parser::name::{self, name_id};
+use ttf_parser::{PlatformId, RawFace, Tag};
+
+/// A font file read from the fonts directory.
+pub struct CustomFont {
+    /// Family name to select the font by; lives for the rest of the process
+    /// because iced font families are `&'static str`.
+    pub family: &'static str,
+    pub bytes: Vec<u8>,
+}
+
+/// Read every font file in `dir`, sorted by family name. A missing directory
+/// simply yields no fonts.
+pub fn load_custom_fonts(dir: &Path) -> Vec<CustomFont> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut fonts: Vec<(String, Vec<u8>)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| {
+                    ext.eq_ignore_ascii_case("ttf") || ext.eq_ignore_ascii_case("otf")
+                })
+        })
+        .filter_map(|path| match fs::read(&path) {
+            Ok(bytes) => {
+                let family = family_name(&bytes).or_else(|| {
+                    path.file_stem()
+                        .map(|stem| stem.to_string_lossy().into_owned())
+                })?;
+                Some((family, bytes))
+            }
+            Err(err) => {
+                warn!(path = %path.display(), "Failed to read font: {err}");
+                None
+            }
+        })
+        .collect();
+    fonts.sort_by(|a, b| a.0.cmp(&b.0));
+    info!(dir = %dir.display(), count = fonts.len(), "Loaded custom fonts");
+    fonts
+        .into_iter()
+        .map(|(family, bytes)| CustomFont {
+            family: family.leak(),
+            bytes,
+        })
+        .collect()
+}
+
+/// Family names in `fonts`, one per family even when several styles were loaded.
+pub fn font_families(fonts: &[CustomFont]) -> Vec<&'static str> {
+    let mut families: Vec<&'static str> = fonts.iter().map(|font| font.family).collect();
+    families.dedup();
+    families
+}
+
+/// Family name from an OpenType font's `name` table, preferring the
+/// typographic family (name ID 16) over the legacy one (name ID 1).
+fn family_name(data: &[u8]) -> Option<String> {
+    let face = RawFace::parse(data, 0).ok()?;
+    let names = name::Table::parse(face.table(Tag::from_bytes(b"name"))?)?;
+    let mut best: Option<(u16, String)> = None;
+    for record in names.names {
+        if record.name_id != name_id::FAMILY && record.name_id != name_id::TYPOGRAPHIC_FAMILY {
+            continue;
+        }
+        // Mac Roman is close enough to Latin-1 for a family name.
+        let Some(name) = record.to_string().or_else(|| {
+            (record.platform_id == PlatformId::Macintosh)
+                .then(|| record.name.iter().map(|&byte| char::from(byte)).collect())
+        }) else {
+            continue;
+        };
+        let name = name.trim().to_string();
+        if !name.is_empty() && best.as_ref().is_none_or(|(id, _)| record.name_id > *id) {
+            best = Some((record.name_id, name));
+        }
+    }
+    best.map(|(_, name)| name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Smallest font-shaped buffer with a `name` table holding `names`.
+    fn font_with_names(names: &[(u16, u16, &str)]) -> Vec<u8> {
+        let name_table = 12 + 16;
+        let mut font = vec![0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+        font.extend_from_slice(b"name");
+        font.extend_from_slice(&[0; 4]);
+        font.extend_from_slice(&(name_table as u32).to_be_bytes());
+        font.extend_from_slice(&[0; 4]);
+
+        let encoded: Vec<Vec<u8>> = names
+            .iter()
+            .map(|(platform, _, name)| match platform {
+                1 => name.bytes().collect(),
+                _ => name.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+            })
+            .collect();
+        font.extend_from_slice(&0u16.to_be_bytes());
+        font.extend_from_slice(&(names.len() as u16).to_be_bytes());
+        font.extend_from_slice(&(6 + names.len() as u16 * 12).to_be_bytes());
+        let mut offset = 0u16;
+        for ((platform, name_id, _), bytes) in names.iter().zip(&encoded) {
+            for field in [*platform, 0, 0, *name_id, bytes.len() as u16, offset] {
+                font.extend_from_slice(&field.to_be_bytes());
+            }
+            offset += bytes.len() as u16;
+        }
+        for bytes in &encoded {
+            font.extend_from_slice(bytes);
+        }
+        let length = (font.len() - name_table) as u32;
+        font[24..28].copy_from_slice(&length.to_be_bytes());
+        font
+    }
+
+    #[test]
+    fn family_name_prefers_the_typographic_family() {
+        let legacy_only = font_with_names(&[(1, 1, "Literata"), (3, 2, "Regular")]);
+        assert_eq!(family_name(&legacy_only).as_deref(), Some("Literata"));
+
+        let with_typographic = font_with_names(&[(3, 1, "Literata Light"), (3, 16, "Literata")]);
+        assert_eq!(family_name(&with_typographic).as_deref(), Some("Literata"));
+
+        assert_eq!(family_name(b"not a font"), None);
+    }
+}
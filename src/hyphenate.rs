@@ -0,0 +1,152 @@
+//! Display-time hyphenation so long words can break across lines.
+//!
+//! Soft hyphens are inserted at the break points the `hyphenation` crate's
+//! embedded patterns allow for the book's language. Only rendered text goes
+//! through here; sentences for TTS, search and bookmarks keep the plain words.
+//! Every language the crate ships is embedded (`embed_all` in `Cargo.toml`);
+//! book languages `language_for_tag` doesn't map are left unhyphenated.
+
+use hyphenation::{Hyphenator, Language, Load, Standard};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use tracing::{debug, warn};
+use unicode_segmentation::UnicodeSegmentation;
+
+const SOFT_HYPHEN: char = '\u{ad}';
+/// Shorter words gain little from a break and only add hyphen clutter.
+const MIN_WORD_CHARS: usize = 6;
+
+/// Patterns for the book's `language` tag, loaded once per language.
+pub fn hyphenator_for(language: Option<&str>) -> Option<&'static Standard> {
+    static LOADED: OnceLock<Mutex<HashMap<Language, Option<&'static Standard>>>> = OnceLock::new();
+    let language = language_for_tag(language?)?;
+    let mut loaded = LOADED
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .ok()?;
+    *loaded
+        .entry(language)
+        .or_insert_with(|| match Standard::from_embedded(language) {
+            Ok(patterns) => {
+                debug!(?language, "Loaded hyphenation patterns");
+                Some(&*Box::leak(Box::new(patterns)))
+            }
+            Err(err) => {
+                warn!(?language, "Hyphenation patterns unavailable: {err}");
+                None
+            }
+        })
+}
+
+/// `text` with soft hyphens at the allowed breaks inside each long word.
+pub fn hyphenate_text(text: &str, patterns: &Standard) -> String {
+    insert_soft_hyphens(text, &soft_hyphen_breaks(text, patterns))
+}
+
+/// Byte offsets in `text`, ascending, where a soft hyphen may go.
+pub fn soft_hyphen_breaks(text: &str, patterns: &Standard) -> Vec<usize> {
+    let mut breaks = Vec::new();
+    for (start, segment) in text.split_word_bound_indices() {
+        let word =
+            segment.chars().all(char::is_alphabetic) && segment.chars().count() >= MIN_WORD_CHARS;
+        // Patterns are lowercase; a word whose case mapping changes its length
+        // would shift the break offsets, so it is left whole.
+        let lower = segment.to_lowercase();
+        if !word || lower.len() != segment.len() {
+            continue;
+        }
+        breaks.extend(
+            patterns
+                .hyphenate(&lower)
+                .breaks
+                .into_iter()
+                .map(|split| start + split),
+        );
+    }
+    breaks
+}
+
+/// `text` with a soft hyphen at each of `breaks`, byte offsets into `text`.
+/// Offsets outside `text` or off a character boundary are ignored.
+pub fn insert_soft_hyphens(text: &str, breaks: &[usize]) -> String {
+    let mut output = String::with_capacity(text.len() + breaks.len() * SOFT_HYPHEN.len_utf8());
+    let mut last = 0;
+    for &split in breaks {
+        if split <= last || split >= text.len() || !text.is_char_boundary(split) {
+            continue;
+        }
+        output.push_str(&text[last..split]);
+        output.push(SOFT_HYPHEN);
+        last = split;
+    }
+    output.push_str(&text[last..]);
+    output
+}
+
+/// Pattern language for a BCP 47 tag such as `en-GB` or `de`.
+fn language_for_tag(tag: &str) -> Option<Language> {
+    let tag = tag.trim().to_ascii_lowercase().replace('_', "-");
+    let mut parts = tag.split('-');
+    let primary = parts.next()?;
+    let region = parts.next();
+    Some(match primary {
+        "en" if matches!(region, Some("gb" | "au" | "nz" | "ie" | "za" | "in")) => {
+            Language::EnglishGB
+        }
+        "en" => Language::EnglishUS,
+        "de" if region == Some("ch") => Language::GermanSwiss,
+        "de" => Language::German1996,
+        "fr" => Language::French,
+        "es" => Language::Spanish,
+        "it" => Language::Italian,
+        "pt" => Language::Portuguese,
+        "nl" => Language::Dutch,
+        "ca" => Language::Catalan,
+        "da" => Language::Danish,
+        "sv" => Language::Swedish,
+        "nb" | "no" => Language::NorwegianBokmal,
+        "nn" => Language::NorwegianNynorsk,
+        "fi" => Language::Finnish,
+        "pl" => Language::Polish,
+        "cs" => Language::Czech,
+        "sk" => Language::Slovak,
+        "hu" => Language::Hungarian,
+        "ru" => Language::Russian,
+        "uk" => Language::Ukrainian,
+        "tr" => Language::Turkish,
+        "la" => Language::Latin,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_words_gain_soft_hyphens_only() {
+        assert_eq!(language_for_tag("en_GB"), Some(Language::EnglishGB));
+        assert_eq!(language_for_tag("de-CH"), Some(Language::GermanSwiss));
+        assert_eq!(language_for_tag("tlh"), None);
+        assert!(hyphenator_for(Some("tlh")).is_none());
+
+        let patterns = hyphenator_for(Some("en-US")).expect("English patterns are embedded");
+        let text = "Extraordinary hyphenation, said the cat.";
+        let hyphenated = hyphenate_text(text, patterns);
+        assert!(hyphenated.contains(SOFT_HYPHEN));
+        assert_eq!(hyphenated.replace(SOFT_HYPHEN, ""), text);
+        assert!(hyphenated.ends_with(" said the cat."));
+
+        let breaks = soft_hyphen_breaks(text, patterns);
+        assert!(
+            breaks
+                .iter()
+                .all(|split| *split < "Extraordinary hyphenation".len())
+        );
+        assert_eq!(insert_soft_hyphens(text, &breaks), hyphenated);
+        assert_eq!(
+            insert_soft_hyphens("hyphenation", &[0, 2, 6, 11, 40]),
+            "hy\u{ad}phen\u{ad}ation"
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! Tracing setup with a log filter that can be changed while the app runs.
+//!
+//! The filter sits behind a reload handle kept for the whole process, so the
+//! settings panel can raise verbosity to capture a bug without a restart. The
+//! most recent formatted lines are also kept in memory for "Copy diagnostics".
+
+use std::collections::VecDeque;
+use std::io;
+use std::sync::{Mutex, OnceLock};
+use tracing::{info, warn};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::{EnvFilter, Registry, fmt, prelude::*, reload};
+
+/// Lines kept for diagnostics; older ones are dropped first.
+const RECENT_LINE_LIMIT: usize = 300;
+
+static FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+static RECENT_LINES: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Install the global subscriber: `RUST_LOG` or `debug` until the config's
+/// `log_level` is applied.
+pub fn init() {
+    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("debug"));
+    let (filter_layer, handle) = reload::Layer::new(env_filter);
+    tracing_subscriber::registry()
+        .with(filter_layer)
+        .with(
+            fmt::layer()
+                .with_target(true)
+                .with_file(true)
+                .with_line_number(true)
+                // Keep stdout clean for `--extract-text`.
+                .with_writer(io::stderr),
+        )
+        .with(
+            fmt::layer()
+                .with_target(true)
+                .with_ansi(false)
+                .with_writer(RecentLines),
+        )
+        .init();
+    let _ = FILTER.set(handle);
+    warn!("Logging initialized; override level with config.log_level or RUST_LOG");
+}
+
+/// Replace the active filter with `level`, any `EnvFilter` directive string.
+pub fn set_level(level: &str) {
+    let Some(handle) = FILTER.get() else {
+        return;
+    };
+    let parsed = EnvFilter::builder()
+        .parse(level)
+        .unwrap_or_else(|_| EnvFilter::new("debug"));
+    if let Err(err) = handle.modify(|filter| *filter = parsed) {
+        warn!(%level, "Failed to update log level: {err}");
+    } else {
+        info!(%level, "Applied log level");
+    }
+}
+
+/// The filter in effect right now.
+pub fn current_filter() -> Option<String> {
+    FILTER.get()?.with_current(ToString::to_string).ok()
+}
+
+/// Most recent log lines, oldest first.
+pub fn recent_lines() -> Vec<String> {
+    RECENT_LINES
+        .lock()
+        .map(|lines| lines.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Writer keeping formatted events in [`RECENT_LINES`].
+struct RecentLines;
+
+impl<'a> MakeWriter<'a> for RecentLines {
+    type Writer = RecentLines;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RecentLines
+    }
+}
+
+impl io::Write for RecentLines {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Ok(mut lines) = RECENT_LINES.lock() {
+            push_lines(&mut lines, &String::from_utf8_lossy(buf));
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn push_lines(lines: &mut VecDeque<String>, text: &str) {
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        if lines.len() == RECENT_LINE_LIMIT {
+            lines.pop_front();
+        }
+        lines.push_back(line.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recent_lines_keep_only_the_newest() {
+        let mut lines = VecDeque::new();
+        push_lines(&mut lines, "first\n\nsecond\n");
+        assert_eq!(lines, ["first", "second"]);
+
+        for idx in 0..RECENT_LINE_LIMIT {
+            push_lines(&mut lines, &format!("line {idx}\n"));
+        }
+        assert_eq!(lines.len(), RECENT_LINE_LIMIT);
+        assert_eq!(lines.front().map(String::as_str), Some("line 0"));
+        assert_eq!(
+            lines.back().cloned(),
+            Some(format!("line {}", RECENT_LINE_LIMIT - 1))
+        );
+    }
+}
@@ -3,14 +3,22 @@
 //! Responsibilities here are intentionally minimal:
 //! - Parse command-line arguments.
 //! - Load the EPUB text via `epub_loader`.
-//! - Load user configuration from `conf/config.toml`.
-//! - Launch the GUI application with the loaded text and config.
+//! - Load user configuration from `--config`, the user config directory, or
+//!   the bundled `conf/config.toml`.
+//! - Launch the GUI application with the loaded text and config, or write the
+//!   book's plain text and exit when `--extract-text` is given.
 
 mod app;
 mod cache;
 mod calibre;
 mod config;
+mod crossref;
+mod dictionary;
 mod epub_loader;
+mod extract;
+mod fonts;
+mod hyphenate;
+mod logging;
 mod normalizer;
 mod pagination;
 mod quack_check;
@@ -18,18 +26,19 @@ mod text_utils;
 mod tts;
 mod tts_worker;
 
-use crate::app::{run_app, run_app_starter};
-use crate::cache::{load_bookmark, load_epub_config, remember_source_path};
-use crate::config::load_config;
-use crate::epub_loader::load_book_content;
+use crate::app::{StartTarget, run_app, run_app_starter};
+use crate::cache::{load_book_config, load_bookmark, push_recent, remember_source_path};
+use crate::config::{AppConfig, init_config_paths, load_app_config};
+use crate::epub_loader::{LoadOptions, load_book_content};
+use crate::normalizer::TextNormalizer;
 use anyhow::{Context, Result, anyhow};
 use std::env;
+use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use tracing::{error, info, warn};
-use tracing_subscriber::{EnvFilter, fmt, prelude::*, reload};
+use tracing::{error, info};
 
-type ReloadHandle = reload::Handle<EnvFilter, tracing_subscriber::Registry>;
 static SIGINT_REQUESTED: AtomicBool = AtomicBool::new(false);
 
 fn main() {
@@ -37,8 +46,8 @@ fn main() {
         return;
     }
     install_signal_handlers();
-    let reload_handle = init_tracing();
-    if let Err(err) = run(&reload_handle) {
+    logging::init();
+    if let Err(err) = run() {
         error!("{err:?}");
         std::process::exit(1);
     }
@@ -56,12 +65,23 @@ fn install_signal_handlers() {
     }
 }
 
-fn run(reload_handle: &ReloadHandle) -> Result<()> {
-    let base_config = load_config(Path::new("conf/config.toml"));
-    let path_arg = parse_args()?;
+fn run() -> Result<()> {
+    let CliArgs {
+        path,
+        start,
+        extract,
+        config,
+    } = parse_args()?;
+    init_config_paths(config.as_deref());
+    let base_config = load_app_config();
 
-    let Some(epub_path) = path_arg else {
-        set_log_level(reload_handle, base_config.log_level.as_filter_str());
+    if let (Some(extract), Some(epub_path)) = (extract, &path) {
+        logging::set_level(base_config.log_level.as_filter_str());
+        return extract_plain_text(epub_path, &base_config, &extract);
+    }
+
+    let Some(epub_path) = path else {
+        logging::set_level(base_config.log_level.as_filter_str());
         info!(
             level = %base_config.log_level,
             "Starting EPUB viewer in starter mode"
@@ -71,28 +91,8 @@ fn run(reload_handle: &ReloadHandle) -> Result<()> {
     };
 
     remember_source_path(&epub_path);
-    let mut config = base_config.clone();
-    if let Some(mut overrides) = load_epub_config(&epub_path) {
-        info!("Loaded per-epub overrides from cache");
-        // Always honor the base config's log level so user changes take effect.
-        overrides.log_level = base_config.log_level;
-        // Always honor base TTS worker count to avoid stale cached values.
-        overrides.tts_threads = base_config.tts_threads;
-        // Always honor base progress logging cadence for batch generation.
-        overrides.tts_progress_log_interval_secs = base_config.tts_progress_log_interval_secs;
-        // Always honor base keybinding configuration.
-        overrides.key_toggle_play_pause = base_config.key_toggle_play_pause.clone();
-        overrides.key_safe_quit = base_config.key_safe_quit.clone();
-        overrides.key_next_sentence = base_config.key_next_sentence.clone();
-        overrides.key_prev_sentence = base_config.key_prev_sentence.clone();
-        overrides.key_repeat_sentence = base_config.key_repeat_sentence.clone();
-        overrides.key_toggle_search = base_config.key_toggle_search.clone();
-        overrides.key_toggle_settings = base_config.key_toggle_settings.clone();
-        overrides.key_toggle_stats = base_config.key_toggle_stats.clone();
-        overrides.key_toggle_tts = base_config.key_toggle_tts.clone();
-        config = overrides;
-    }
-    set_log_level(reload_handle, config.log_level.as_filter_str());
+    let config = load_book_config(&base_config, &epub_path);
+    logging::set_level(config.log_level.as_filter_str());
     info!(
         path = %epub_path.display(),
         level = %config.log_level,
@@ -107,50 +107,206 @@ fn run(reload_handle: &ReloadHandle) -> Result<()> {
         "Active TTS configuration"
     );
     let bookmark = load_bookmark(&epub_path);
-    if let Some(bm) = &bookmark {
+    if let Some(target) = &start {
+        info!(?target, "Opening at start target from command line");
+    } else if let Some(bm) = &bookmark {
         info!(page = bm.page, "Resuming from cached page");
     }
-    let book = load_book_content(&epub_path)?;
-    run_app(book, config, epub_path, bookmark).context("Failed to start the GUI")?;
+    let book = load_book_content(&epub_path, &LoadOptions::from_config(&config))?;
+    push_recent(&epub_path, book.metadata.title.as_deref());
+    run_app(book, config, epub_path, bookmark, start).context("Failed to start the GUI")?;
     Ok(())
 }
 
-fn parse_args() -> Result<Option<PathBuf>> {
-    let mut args = env::args().skip(1);
-    let Some(path) = args.next() else {
-        return Ok(None);
-    };
+/// Load `epub_path` and write its text to stdout or `--output`, without the GUI.
+fn extract_plain_text(epub_path: &Path, config: &AppConfig, extract: &ExtractArgs) -> Result<()> {
+    info!(
+        path = %epub_path.display(),
+        normalize = extract.normalize,
+        "Extracting plain text"
+    );
+    let book = load_book_content(epub_path, &LoadOptions::from_config(config))?;
+    let normalizer = extract.normalize.then(TextNormalizer::load_default);
+    let text = extract::render_plain_text(&book.text, normalizer.as_ref());
+    match &extract.output {
+        Some(output) => fs::write(output, text)
+            .with_context(|| format!("Failed to write {}", output.display()))?,
+        None => std::io::stdout()
+            .lock()
+            .write_all(text.as_bytes())
+            .context("Failed to write extracted text to stdout")?,
+    }
+    Ok(())
+}
+
+/// Parsed command line:
+/// `ebup-viewer [--config FILE] [--page N | --chapter NAME] [book.epub]` or
+/// `ebup-viewer [--config FILE] --extract-text [--normalize] [--output FILE] book.epub`.
+#[derive(Debug, Default, PartialEq)]
+struct CliArgs {
+    path: Option<PathBuf>,
+    start: Option<StartTarget>,
+    extract: Option<ExtractArgs>,
+    config: Option<PathBuf>,
+}
 
-    let path = PathBuf::from(path);
-    if !path.exists() {
-        return Err(anyhow!("File not found: {}", path.as_path().display()));
+/// Options for headless text extraction.
+#[derive(Debug, Default, PartialEq)]
+struct ExtractArgs {
+    normalize: bool,
+    output: Option<PathBuf>,
+}
+
+fn parse_args() -> Result<CliArgs> {
+    let args = parse_arg_list(env::args().skip(1))?;
+    if let Some(path) = &args.path {
+        if !path.exists() {
+            return Err(anyhow!("File not found: {}", path.display()));
+        }
+    } else if args.start.is_some() {
+        return Err(anyhow!("--page and --chapter need a book path"));
+    } else if args.extract.is_some() {
+        return Err(anyhow!("--extract-text needs a book path"));
+    }
+    if let Some(config) = args.config.as_ref().filter(|config| !config.is_file()) {
+        return Err(anyhow!("Config file not found: {}", config.display()));
     }
-    Ok(Some(path))
+    Ok(args)
 }
 
-fn init_tracing() -> ReloadHandle {
-    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("debug"));
-    let (filter_layer, handle) = reload::Layer::new(env_filter);
-    tracing_subscriber::registry()
-        .with(
-            fmt::layer()
-                .with_target(true)
-                .with_file(true)
-                .with_line_number(true)
-                .with_filter(filter_layer),
-        )
-        .init();
-    warn!("Logging initialized; override level with config.log_level or RUST_LOG");
-    handle
+fn parse_arg_list(args: impl IntoIterator<Item = String>) -> Result<CliArgs> {
+    let mut parsed = CliArgs::default();
+    let mut extract_text = false;
+    let mut normalize = false;
+    let mut output = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.into())),
+            _ => (arg.clone(), None),
+        };
+        match flag.as_str() {
+            "--page" | "--chapter" => {
+                let value = inline_value
+                    .or_else(|| args.next())
+                    .ok_or_else(|| anyhow!("{flag} expects a value"))?;
+                let target = if flag == "--page" {
+                    let page = value
+                        .trim()
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|page| *page > 0)
+                        .ok_or_else(|| anyhow!("--page expects a page number, got {value:?}"))?;
+                    StartTarget::Page(page)
+                } else {
+                    StartTarget::Chapter(value)
+                };
+                if parsed.start.replace(target).is_some() {
+                    return Err(anyhow!("Only one of --page or --chapter may be given"));
+                }
+            }
+            "--extract-text" => extract_text = true,
+            "--normalize" => normalize = true,
+            "--config" => {
+                let value = inline_value
+                    .or_else(|| args.next())
+                    .ok_or_else(|| anyhow!("--config expects a file path"))?;
+                parsed.config = Some(PathBuf::from(value));
+            }
+            "--output" => {
+                let value = inline_value
+                    .or_else(|| args.next())
+                    .ok_or_else(|| anyhow!("--output expects a file path"))?;
+                output = Some(PathBuf::from(value));
+            }
+            other if other.starts_with("--") => {
+                return Err(anyhow!("Unknown option: {other}"));
+            }
+            _ => {
+                if parsed.path.replace(PathBuf::from(&arg)).is_some() {
+                    return Err(anyhow!("Unexpected extra argument: {arg}"));
+                }
+            }
+        }
+    }
+    if extract_text {
+        if parsed.start.is_some() {
+            return Err(anyhow!(
+                "--extract-text cannot be combined with --page or --chapter"
+            ));
+        }
+        parsed.extract = Some(ExtractArgs { normalize, output });
+    } else if normalize || output.is_some() {
+        return Err(anyhow!(
+            "--normalize and --output only apply to --extract-text"
+        ));
+    }
+    Ok(parsed)
 }
 
-fn set_log_level(handle: &ReloadHandle, level: &str) {
-    let parsed = EnvFilter::builder()
-        .parse(level)
-        .unwrap_or_else(|_| EnvFilter::new("debug"));
-    if let Err(err) = handle.modify(|filter| *filter = parsed.clone()) {
-        warn!(%level, "Failed to update log level from config: {err}");
-    } else {
-        info!(%level, "Applied log level from config");
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliArgs> {
+        parse_arg_list(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_start_flags_around_the_path() {
+        assert_eq!(
+            parse(&["book.epub", "--page", "42"]).unwrap(),
+            CliArgs {
+                path: Some(PathBuf::from("book.epub")),
+                start: Some(StartTarget::Page(42)),
+                extract: None,
+                config: None,
+            }
+        );
+        assert_eq!(
+            parse(&["--config", "my.toml", "book.epub"]).unwrap().config,
+            Some(PathBuf::from("my.toml"))
+        );
+        assert_eq!(
+            parse(&["--chapter=Chapter 5", "book.epub"]).unwrap().start,
+            Some(StartTarget::Chapter("Chapter 5".to_string()))
+        );
+        assert_eq!(parse(&[]).unwrap(), CliArgs::default());
+    }
+
+    #[test]
+    fn rejects_bad_start_flags() {
+        assert!(parse(&["book.epub", "--page", "0"]).is_err());
+        assert!(parse(&["book.epub", "--page"]).is_err());
+        assert!(parse(&["book.epub", "--page", "2", "--chapter", "x"]).is_err());
+        assert!(parse(&["book.epub", "--bogus"]).is_err());
+        assert!(parse(&["a.epub", "b.epub"]).is_err());
+        assert!(parse(&["book.epub", "--config"]).is_err());
+    }
+
+    #[test]
+    fn parses_extract_text_options() {
+        assert_eq!(
+            parse(&[
+                "--extract-text",
+                "book.epub",
+                "--normalize",
+                "--output=out.txt"
+            ])
+            .unwrap()
+            .extract,
+            Some(ExtractArgs {
+                normalize: true,
+                output: Some(PathBuf::from("out.txt")),
+            })
+        );
+        assert_eq!(
+            parse(&["book.epub", "--extract-text"]).unwrap().extract,
+            Some(ExtractArgs::default())
+        );
+        assert!(parse(&["book.epub", "--normalize"]).is_err());
+        assert!(parse(&["book.epub", "--output", "out.txt"]).is_err());
+        assert!(parse(&["book.epub", "--extract-text", "--page", "2"]).is_err());
+        assert!(parse(&["book.epub", "--extract-text", "--output"]).is_err());
     }
 }
@@ -1,4 +1,5 @@
 use crate::cache::normalized_dir;
+use crate::text_utils::verse_blocks;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -8,10 +9,14 @@ use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-const DEFAULT_NORMALIZER_PATH: &str = "conf/normalizer.toml";
+/// Bundled normalizer config, used when no user copy exists.
+pub(crate) const DEFAULT_NORMALIZER_PATH: &str = "conf/normalizer.toml";
 const SENTENCE_MARKER: &str = "\n<<__EBUP_SENTENCE_BOUNDARY__>>\n";
 
 static RE_INLINE_CODE: Lazy<Regex> = Lazy::new(|| Regex::new(r"`([^`]+)`").unwrap());
+/// Stands in for a backtick inside a spoken code block so `strip_inline_code`
+/// leaves it alone; turned back into a backtick once cleaning is done.
+pub const CODE_BLOCK_BACKTICK: char = '\u{E000}';
 static RE_MARKDOWN_LINK: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[([^\]]+)\]\([^)]*\)").unwrap());
 static RE_NUMERIC_BRACKET_CITE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"\[\s*\d+(?:\s*,\s*\d+)*\s*\]").unwrap());
@@ -20,15 +25,29 @@ static RE_PARENTHETICAL_NUMERIC: Lazy<Regex> =
 static RE_SUPERSCRIPT_CITE: Lazy<Regex> = Lazy::new(|| Regex::new(r"[⁰¹²³⁴⁵⁶⁷⁸⁹]+").unwrap());
 static RE_WORD_SUFFIX_FOOTNOTE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?P<prefix>\p{L})\d{1,3}\b").unwrap());
+static RE_IMAGE_ALT_MARKER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[Image: (?P<alt>[^\]]*)\]").unwrap());
 static RE_SQUARE_BRACKET_BLOCK: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[[^\]]*\]").unwrap());
 static RE_CURLY_BRACKET_BLOCK: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{[^}]*\}").unwrap());
 static RE_HORIZONTAL_WS: Lazy<Regex> = Lazy::new(|| Regex::new(r"[ \t\u{00A0}]+").unwrap());
 static RE_SPACE_BEFORE_PUNCT: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+([,.;:!?])").unwrap());
 static RE_SOFT_BREAK_WS: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
+static RE_LINE_BREAK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?P<last>\S)[ \t\u{00A0}]*\n\s*").unwrap());
+static RE_NUMBER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?P<currency>[$£€])?(?P<int>\d{1,3}(?:,\d{3})+|\d+)(?:\.(?P<frac>\d+))?(?P<suffix>st|nd|rd|th|%)?",
+    )
+    .unwrap()
+});
 
 #[derive(Debug, Clone)]
 pub struct TextNormalizer {
     config: NormalizerConfig,
+    /// Hash of `config`, part of every cache key so edited rules never hit old entries.
+    config_hash: String,
+    /// Display sentences merged into one audio unit (`tts_group_sentences`).
+    group_sentences: usize,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -54,6 +73,7 @@ struct NormalizerConfig {
     remove_space_before_punctuation: bool,
     strip_inline_code: bool,
     strip_markdown_links: bool,
+    citation_mode: CitationMode,
     drop_numeric_bracket_citations: bool,
     drop_parenthetical_numeric_citations: bool,
     drop_superscript_citations: bool,
@@ -65,6 +85,12 @@ struct NormalizerConfig {
     max_audio_words_per_chunk: usize,
     min_sentence_chars: usize,
     require_alphanumeric: bool,
+    /// Spell out numbers, prices, ordinals and years in English words.
+    expand_numbers: bool,
+    /// Read `[Image: ...]` alt text markers aloud instead of dropping them.
+    speak_image_alt_text: bool,
+    /// Keep verse line breaks on the page and read each line end as a short pause.
+    preserve_verse: bool,
     replacements: BTreeMap<String, String>,
     abbreviations: BTreeMap<String, String>,
     drop_tokens: Vec<String>,
@@ -84,6 +110,7 @@ impl Default for NormalizerConfig {
             remove_space_before_punctuation: true,
             strip_inline_code: true,
             strip_markdown_links: true,
+            citation_mode: CitationMode::Drop,
             drop_numeric_bracket_citations: true,
             drop_parenthetical_numeric_citations: true,
             drop_superscript_citations: true,
@@ -95,6 +122,9 @@ impl Default for NormalizerConfig {
             max_audio_words_per_chunk: 32,
             min_sentence_chars: 2,
             require_alphanumeric: true,
+            expand_numbers: false,
+            speak_image_alt_text: false,
+            preserve_verse: false,
             replacements,
             abbreviations: default_abbreviations(),
             drop_tokens: Vec::new(),
@@ -112,6 +142,19 @@ enum NormalizationMode {
     Sentence,
 }
 
+/// How numeric citations (`[12]`, `(3, 4)`, superscripts) reach the audio.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+enum CitationMode {
+    /// Remove them, as selected by the individual `drop_*_citations` flags.
+    #[default]
+    Drop,
+    /// Read the numbers as written, without brackets.
+    Keep,
+    /// Announce them, e.g. `[12]` becomes "reference twelve".
+    Spoken,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct AcronymConfig {
@@ -197,7 +240,7 @@ pub struct PageNormalization {
 
 impl TextNormalizer {
     pub fn load_default() -> Self {
-        Self::load(Path::new(DEFAULT_NORMALIZER_PATH))
+        Self::load(crate::config::normalizer_config_path())
     }
 
     pub fn load(path: &Path) -> Self {
@@ -205,9 +248,7 @@ impl TextNormalizer {
             Ok(contents) => match toml::from_str::<NormalizerFile>(&contents) {
                 Ok(file) => {
                     tracing::info!(path = %path.display(), "Loaded text normalizer config");
-                    Self {
-                        config: file.normalization,
-                    }
+                    Self::new(file.normalization)
                 }
                 Err(err) => {
                     tracing::warn!(path = %path.display(), "Invalid normalizer config TOML: {err}");
@@ -221,45 +262,65 @@ impl TextNormalizer {
         }
     }
 
-    pub fn plan_page_cached(
-        &self,
-        epub_path: &Path,
-        page_idx: usize,
-        display_sentences: &[String],
-    ) -> PageNormalization {
-        if self.config.mode == NormalizationMode::Sentence {
-            return self.plan_page_cached_sentence_mode(epub_path, display_sentences);
+    /// Like [`TextNormalizer::load`], but a missing or malformed file is an
+    /// error rather than a fallback to defaults, so a live reload can keep the
+    /// last good config.
+    pub fn try_load(path: &Path) -> Result<Self, String> {
+        let contents =
+            fs::read_to_string(path).map_err(|err| format!("Reading {}: {err}", path.display()))?;
+        let file = toml::from_str::<NormalizerFile>(&contents)
+            .map_err(|err| format!("Parsing {}: {err}", path.display()))?;
+        Ok(Self::new(file.normalization))
+    }
+
+    fn new(config: NormalizerConfig) -> Self {
+        let serialized = toml::to_string(&config).unwrap_or_default();
+        let mut hasher = Sha256::new();
+        hasher.update(serialized.as_bytes());
+        Self {
+            config,
+            config_hash: format!("{:x}", hasher.finalize()),
+            group_sentences: 1,
         }
-        self.plan_page_cached_page_mode(epub_path, page_idx, display_sentences)
     }
 
-    fn plan_page_cached_page_mode(
+    /// Voice up to `group` consecutive short sentences as one audio unit.
+    pub fn set_sentence_grouping(&mut self, group: usize) {
+        self.group_sentences = group.max(1);
+    }
+
+    /// Plan a page, reusing the on-disk plan from an earlier run when the page
+    /// text, sentence count and normalizer config all match.
+    pub fn plan_page_cached(
         &self,
         epub_path: &Path,
         page_idx: usize,
         display_sentences: &[String],
     ) -> PageNormalization {
-        let source_hash = hash_sentences(display_sentences);
-        let config_hash = self.config_hash();
-        let cache_path =
-            self.normalized_cache_path(epub_path, page_idx, &source_hash, &config_hash);
-
-        if let Ok(contents) = fs::read_to_string(&cache_path) {
-            if let Ok(cached) = toml::from_str::<PageNormalization>(&contents) {
-                tracing::debug!(
-                    path = %cache_path.display(),
-                    page = page_idx + 1,
-                    "Loaded normalized page cache"
-                );
-                return cached;
-            }
+        let cache_path = self.normalized_cache_path(epub_path, page_idx, display_sentences);
+        if let Ok(contents) = fs::read_to_string(&cache_path)
+            && let Ok(cached) = serde_json::from_str::<PageNormalization>(&contents)
+            && cached.display_to_audio.len() == display_sentences.len()
+        {
+            tracing::debug!(
+                path = %cache_path.display(),
+                page = page_idx + 1,
+                "Loaded normalized page cache"
+            );
+            return cached;
         }
 
-        let plan = self.plan_page(display_sentences);
+        let plan = match self.config.mode {
+            NormalizationMode::Sentence => self.group_audio_units(
+                self.plan_page_cached_sentence_mode(epub_path, display_sentences),
+            ),
+            NormalizationMode::Page => self.plan_page(display_sentences),
+        };
         if let Some(parent) = cache_path.parent() {
             let _ = fs::create_dir_all(parent);
         }
-        match toml::to_string(&plan) {
+        // JSON rather than TOML: TOML cannot hold the `None` entries of `display_to_audio`.
+        match serde_json::to_string(&plan) {
             Ok(serialized) => {
                 if let Err(err) = fs::write(&cache_path, serialized) {
                     tracing::warn!(
@@ -294,14 +355,12 @@ impl TextNormalizer {
             };
         }
 
-        let config_hash = self.config_hash();
         let mut audio_sentences = Vec::with_capacity(display_sentences.len());
         let mut display_to_audio = vec![None; display_sentences.len()];
         let mut audio_to_display = Vec::new();
 
         for (display_idx, sentence) in display_sentences.iter().enumerate() {
-            if let Some(cleaned) = self.normalize_sentence_cached(epub_path, &config_hash, sentence)
-            {
+            if let Some(cleaned) = self.normalize_sentence_cached(epub_path, sentence) {
                 let chunks = self.chunk_sentence_for_tts(&cleaned);
                 if chunks.is_empty() {
                     continue;
@@ -322,7 +381,13 @@ impl TextNormalizer {
         }
     }
 
+    /// Audio units for a page's display sentences, grouped under
+    /// `tts_group_sentences`.
     pub fn plan_page(&self, display_sentences: &[String]) -> PageNormalization {
+        self.group_audio_units(self.plan_page_ungrouped(display_sentences))
+    }
+
+    fn plan_page_ungrouped(&self, display_sentences: &[String]) -> PageNormalization {
         if display_sentences.is_empty() {
             return PageNormalization {
                 audio_sentences: Vec::new(),
@@ -332,9 +397,18 @@ impl TextNormalizer {
         }
 
         if !self.config.enabled {
-            let audio_sentences = display_sentences.to_vec();
-            let display_to_audio = (0..display_sentences.len()).map(Some).collect();
-            let audio_to_display = (0..display_sentences.len()).collect();
+            // Blank sentences, such as code blocks the app chose not to speak, stay silent.
+            let mut audio_sentences = Vec::with_capacity(display_sentences.len());
+            let mut display_to_audio = vec![None; display_sentences.len()];
+            let mut audio_to_display = Vec::with_capacity(display_sentences.len());
+            for (display_idx, sentence) in display_sentences.iter().enumerate() {
+                if sentence.trim().is_empty() {
+                    continue;
+                }
+                display_to_audio[display_idx] = Some(audio_sentences.len());
+                audio_to_display.push(display_idx);
+                audio_sentences.push(sentence.replace(CODE_BLOCK_BACKTICK, "`"));
+            }
             return PageNormalization {
                 audio_sentences,
                 display_to_audio,
@@ -376,6 +450,63 @@ impl TextNormalizer {
         }
     }
 
+    /// Merge runs of up to `group_sentences` display sentences that are each
+    /// voiced as a single clip into one audio unit, as long as the unit stays
+    /// within the chunk limits. Every sentence of a unit maps to it, and the
+    /// unit maps back to its first sentence.
+    fn group_audio_units(&self, plan: PageNormalization) -> PageNormalization {
+        if self.group_sentences <= 1 {
+            return plan;
+        }
+        let PageNormalization {
+            audio_sentences: units,
+            display_to_audio: ungrouped,
+            audio_to_display: unit_sentences,
+        } = plan;
+        let mut chunk_counts = vec![0usize; ungrouped.len()];
+        for display_idx in &unit_sentences {
+            chunk_counts[*display_idx] += 1;
+        }
+        let max_chars = self.config.max_audio_chars_per_chunk.max(40);
+        let max_words = self.config.max_audio_words_per_chunk.max(8);
+
+        let mut audio_sentences: Vec<String> = Vec::with_capacity(units.len());
+        let mut audio_to_display = Vec::with_capacity(units.len());
+        let mut display_to_audio = vec![None; ungrouped.len()];
+        // Sentences in the last unit, or 0 when it is part of a chunked sentence.
+        let mut open_members = 0;
+        for (unit, display_idx) in units.into_iter().zip(unit_sentences) {
+            let single_clip = chunk_counts[display_idx] == 1;
+            let joined = audio_sentences
+                .last()
+                .filter(|_| single_clip && open_members > 0 && open_members < self.group_sentences)
+                .map(|last| format!("{last} {unit}"))
+                .filter(|joined| {
+                    !self.config.chunk_long_sentences
+                        || !exceeds_chunk_limits(joined, max_chars, max_words)
+                });
+            match joined {
+                Some(joined) => {
+                    *audio_sentences.last_mut().expect("joined onto a unit") = joined;
+                    open_members += 1;
+                }
+                None => {
+                    audio_sentences.push(unit);
+                    audio_to_display.push(display_idx);
+                    open_members = usize::from(single_clip);
+                }
+            }
+            if display_to_audio[display_idx].is_none() {
+                display_to_audio[display_idx] = Some(audio_sentences.len() - 1);
+            }
+        }
+        PageNormalization {
+            audio_sentences,
+            display_to_audio,
+            audio_to_display,
+        }
+    }
+
     fn normalize_page_mode(&self, display_sentences: &[String]) -> Vec<String> {
         let joined = display_sentences.join(SENTENCE_MARKER);
         let cleaned = self.clean_text_core(&joined);
@@ -411,19 +542,62 @@ impl TextNormalizer {
             text = RE_INLINE_CODE.replace_all(&text, "$1").to_string();
         }
 
-        if self.config.drop_numeric_bracket_citations {
-            text = RE_NUMERIC_BRACKET_CITE.replace_all(&text, " ").to_string();
-        }
+        // Handled before bracket elision, which would otherwise always drop the marker.
+        let alt_replacement = if self.config.speak_image_alt_text {
+            " Image: $alt. "
+        } else {
+            " "
+        };
+        text = RE_IMAGE_ALT_MARKER
+            .replace_all(&text, alt_replacement)
+            .to_string();
 
-        if self.config.drop_parenthetical_numeric_citations {
-            text = RE_PARENTHETICAL_NUMERIC.replace_all(&text, " ").to_string();
-        }
+        match self.config.citation_mode {
+            CitationMode::Drop => {
+                if self.config.drop_numeric_bracket_citations {
+                    text = RE_NUMERIC_BRACKET_CITE.replace_all(&text, " ").to_string();
+                }
 
-        if self.config.drop_superscript_citations {
-            text = RE_SUPERSCRIPT_CITE.replace_all(&text, " ").to_string();
+                if self.config.drop_parenthetical_numeric_citations {
+                    text = RE_PARENTHETICAL_NUMERIC.replace_all(&text, " ").to_string();
+                }
+
+                if self.config.drop_superscript_citations {
+                    text = RE_SUPERSCRIPT_CITE.replace_all(&text, " ").to_string();
+                }
+            }
+            // Brackets go now so square-bracket elision below leaves the numbers alone.
+            CitationMode::Keep => {
+                text = RE_NUMERIC_BRACKET_CITE
+                    .replace_all(&text, |caps: &regex::Captures| {
+                        format!(" {} ", caps[0].trim_matches(|ch| ch == '[' || ch == ']'))
+                    })
+                    .to_string();
+                text = RE_SUPERSCRIPT_CITE
+                    .replace_all(&text, |caps: &regex::Captures| {
+                        format!(" {}", superscript_digits(&caps[0]))
+                    })
+                    .to_string();
+            }
+            CitationMode::Spoken => {
+                for re in [&RE_NUMERIC_BRACKET_CITE, &RE_PARENTHETICAL_NUMERIC] {
+                    text = re
+                        .replace_all(&text, |caps: &regex::Captures| {
+                            format!(" {} ", spoken_citation(&caps[0]))
+                        })
+                        .to_string();
+                }
+                text = RE_SUPERSCRIPT_CITE
+                    .replace_all(&text, |caps: &regex::Captures| {
+                        format!(" {} ", spoken_citation(&superscript_digits(&caps[0])))
+                    })
+                    .to_string();
+            }
         }
 
-        if self.config.drop_word_suffix_numeric_footnotes {
+        if self.config.citation_mode == CitationMode::Drop
+            && self.config.drop_word_suffix_numeric_footnotes
+        {
             text = RE_WORD_SUFFIX_FOOTNOTE
                 .replace_all(&text, "$prefix")
                 .to_string();
@@ -467,6 +641,11 @@ impl TextNormalizer {
             text = apply_brand_map(&text, &self.config.pronunciation.custom_pronunciations);
         }
 
+        // Runs before the year pass so years read as "nineteen ninety-nine".
+        if self.config.expand_numbers {
+            text = expand_numbers(&text, self.config.pronunciation.year_mode);
+        }
+
         if self.config.pronunciation.year_mode != YearMode::None {
             text = apply_year_pronunciation(&text, &self.config.pronunciation);
         }
@@ -475,6 +654,16 @@ impl TextNormalizer {
             text = apply_acronym_expansion(&text, &self.config.acronyms);
         }
 
+        // Only line breaks inside detected verse are joined; the page markers
+        // in page mode must survive.
+        if self.config.preserve_verse {
+            text = text
+                .split(SENTENCE_MARKER)
+                .map(join_verse_lines)
+                .collect::<Vec<_>>()
+                .join(SENTENCE_MARKER);
+        }
+
         if self.config.collapse_whitespace {
             text = RE_HORIZONTAL_WS.replace_all(&text, " ").to_string();
         }
@@ -483,7 +672,7 @@ impl TextNormalizer {
             text = RE_SPACE_BEFORE_PUNCT.replace_all(&text, "$1").to_string();
         }
 
-        text.trim().to_string()
+        text.trim().replace(CODE_BLOCK_BACKTICK, "`")
     }
 
     fn finalize_sentence(&self, sentence: &str) -> Option<String> {
@@ -599,42 +788,36 @@ impl TextNormalizer {
         *current = segment.to_string();
     }
 
-    fn config_hash(&self) -> String {
-        let serialized = toml::to_string(&self.config).unwrap_or_default();
-        let mut hasher = Sha256::new();
-        hasher.update(serialized.as_bytes());
-        format!("{:x}", hasher.finalize())
-    }
-
     fn normalized_cache_path(
         &self,
         epub_path: &Path,
         page_idx: usize,
-        source_hash: &str,
-        config_hash: &str,
+        display_sentences: &[String],
     ) -> PathBuf {
-        let file_name = format!("p{}-{}-{}.toml", page_idx, source_hash, config_hash);
+        let grouping = if self.group_sentences > 1 {
+            format!("-g{}", self.group_sentences)
+        } else {
+            String::new()
+        };
+        let file_name = format!(
+            "p{}-n{}-{}-{}{}.json",
+            page_idx,
+            display_sentences.len(),
+            hash_sentences(display_sentences),
+            self.config_hash,
+            grouping
+        );
         normalized_dir(epub_path).join(file_name)
     }
 
-    fn normalized_sentence_cache_path(
-        &self,
-        epub_path: &Path,
-        sentence_hash: &str,
-        config_hash: &str,
-    ) -> PathBuf {
-        let file_name = format!("s-{}-{}.toml", sentence_hash, config_hash);
+    fn normalized_sentence_cache_path(&self, epub_path: &Path, sentence_hash: &str) -> PathBuf {
+        let file_name = format!("s-{}-{}.toml", sentence_hash, self.config_hash);
         normalized_dir(epub_path).join(file_name)
     }
 
-    fn normalize_sentence_cached(
-        &self,
-        epub_path: &Path,
-        config_hash: &str,
-        sentence: &str,
-    ) -> Option<String> {
+    fn normalize_sentence_cached(&self, epub_path: &Path, sentence: &str) -> Option<String> {
         let source_hash = hash_sentence(sentence);
-        let cache_path = self.normalized_sentence_cache_path(epub_path, &source_hash, config_hash);
+        let cache_path = self.normalized_sentence_cache_path(epub_path, &source_hash);
 
         if let Ok(contents) = fs::read_to_string(&cache_path) {
             if let Ok(cached) = toml::from_str::<NormalizedSentenceCache>(&contents) {
@@ -671,9 +854,7 @@ impl TextNormalizer {
 
 impl Default for TextNormalizer {
     fn default() -> Self {
-        Self {
-            config: NormalizerConfig::default(),
-        }
+        Self::new(NormalizerConfig::default())
     }
 }
 
@@ -843,6 +1024,309 @@ fn year_to_words(year: usize, cfg: &PronunciationConfig) -> String {
     parts.join(&cfg.number_separator)
 }
 
+const ONES_WORDS: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const TENS_WORDS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const SCALE_WORDS: [&str; 7] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+];
+
+/// Rewrite standalone numbers as English words: prices (`$3.50`), percentages,
+/// ordinals (`21st`), decimals, grouped thousands and, unless `year_mode` is
+/// `None`, four-digit years. Digits glued to letters (`3D`, `mp3`) and dotted
+/// versions (`1.2.3`) are left alone.
+fn expand_numbers(text: &str, year_mode: YearMode) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for caps in RE_NUMBER.captures_iter(text) {
+        let whole = caps.get(0).expect("whole match");
+        let mut before = text[..whole.start()].chars().rev();
+        let mut after = text[whole.end()..].chars();
+        let glued_before = match before.next() {
+            Some('.' | ',') => before.next().is_some_and(|ch| ch.is_ascii_digit()),
+            Some(ch) => ch.is_alphanumeric() || ch == '_',
+            None => false,
+        };
+        let glued_after = match after.next() {
+            Some('.' | ',') => after.next().is_some_and(|ch| ch.is_ascii_digit()),
+            Some(ch) => ch.is_alphanumeric() || ch == '_',
+            None => false,
+        };
+        if glued_before || glued_after {
+            continue;
+        }
+        let Some(spoken) = spoken_number(&caps, year_mode) else {
+            continue;
+        };
+        out.push_str(&text[last..whole.start()]);
+        out.push_str(&spoken);
+        last = whole.end();
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+fn spoken_number(caps: &regex::Captures, year_mode: YearMode) -> Option<String> {
+    let int_digits = caps["int"].replace(',', "");
+    let frac = caps.name("frac").map(|frac| frac.as_str());
+    let suffix = caps.name("suffix").map(|suffix| suffix.as_str());
+    let Ok(value) = int_digits.parse::<u64>() else {
+        // Too long to be a quantity; read it digit by digit.
+        return Some(digit_words(&int_digits));
+    };
+
+    if let Some(symbol) = caps.name("currency") {
+        if suffix.is_some() {
+            return None;
+        }
+        return Some(currency_words(symbol.as_str(), value, frac));
+    }
+    match suffix {
+        Some("%") => {
+            let mut words = cardinal_words(value);
+            if let Some(frac) = frac {
+                words.push_str(" point ");
+                words.push_str(&digit_words(frac));
+            }
+            Some(format!("{words} percent"))
+        }
+        Some(_) if frac.is_some() => None,
+        Some(_) => Some(ordinal_words(value)),
+        None => {
+            if let Some(frac) = frac {
+                return Some(format!(
+                    "{} point {}",
+                    cardinal_words(value),
+                    digit_words(frac)
+                ));
+            }
+            let is_year = year_mode != YearMode::None
+                && int_digits.len() == 4
+                && !caps["int"].contains(',')
+                && (1100..=2099).contains(&value);
+            Some(if is_year {
+                year_words(value)
+            } else {
+                cardinal_words(value)
+            })
+        }
+    }
+}
+
+fn currency_words(symbol: &str, major: u64, frac: Option<&str>) -> String {
+    let (unit, units, sub, subs) = match symbol {
+        "£" => ("pound", "pounds", "penny", "pence"),
+        "€" => ("euro", "euros", "cent", "cents"),
+        _ => ("dollar", "dollars", "cent", "cents"),
+    };
+    // Cents are the first two decimal places; "$3.5" is three dollars fifty.
+    let minor = frac
+        .map(|frac| {
+            let mut cents: String = frac.chars().take(2).collect();
+            while cents.len() < 2 {
+                cents.push('0');
+            }
+            cents.parse::<u64>().unwrap_or(0)
+        })
+        .unwrap_or(0);
+    let major_words = format!(
+        "{} {}",
+        cardinal_words(major),
+        if major == 1 { unit } else { units }
+    );
+    let minor_words = format!(
+        "{} {}",
+        cardinal_words(minor),
+        if minor == 1 { sub } else { subs }
+    );
+    match (major, minor) {
+        (_, 0) => major_words,
+        (0, _) => minor_words,
+        _ => format!("{major_words} and {minor_words}"),
+    }
+}
+
+fn cardinal_words(value: u64) -> String {
+    if value == 0 {
+        return ONES_WORDS[0].to_string();
+    }
+    let mut groups = Vec::new();
+    let mut rest = value;
+    while rest > 0 {
+        groups.push(rest % 1000);
+        rest /= 1000;
+    }
+    let mut parts = Vec::new();
+    for (scale, group) in groups.iter().enumerate().rev() {
+        if *group == 0 {
+            continue;
+        }
+        let mut words = under_thousand_words(*group);
+        if scale > 0 {
+            words.push(' ');
+            words.push_str(SCALE_WORDS[scale]);
+        }
+        parts.push(words);
+    }
+    parts.join(" ")
+}
+
+fn under_thousand_words(value: u64) -> String {
+    let hundreds = value / 100;
+    let rest = value % 100;
+    match (hundreds, rest) {
+        (0, _) => under_hundred_words(rest),
+        (_, 0) => format!("{} hundred", ONES_WORDS[hundreds as usize]),
+        _ => format!(
+            "{} hundred {}",
+            ONES_WORDS[hundreds as usize],
+            under_hundred_words(rest)
+        ),
+    }
+}
+
+fn under_hundred_words(value: u64) -> String {
+    let value = value as usize;
+    if value < 20 {
+        return ONES_WORDS[value].to_string();
+    }
+    match value % 10 {
+        0 => TENS_WORDS[value / 10].to_string(),
+        ones => format!("{}-{}", TENS_WORDS[value / 10], ONES_WORDS[ones]),
+    }
+}
+
+/// Years read in pairs: "nineteen ninety-nine", "nineteen oh five", "two thousand".
+fn year_words(year: u64) -> String {
+    let (high, low) = (year / 100, year % 100);
+    if (2000..2010).contains(&year) {
+        return cardinal_words(year);
+    }
+    match low {
+        0 => format!("{} hundred", under_hundred_words(high)),
+        1..=9 => format!(
+            "{} oh {}",
+            under_hundred_words(high),
+            ONES_WORDS[low as usize]
+        ),
+        _ => format!("{} {}", under_hundred_words(high), under_hundred_words(low)),
+    }
+}
+
+fn ordinal_words(value: u64) -> String {
+    let cardinal = cardinal_words(value);
+    let split = cardinal.rfind([' ', '-']).map_or(0, |idx| idx + 1);
+    let (head, last) = cardinal.split_at(split);
+    let last = match last {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        word if word.ends_with('y') => format!("{}ieth", &word[..word.len() - 1]),
+        word => format!("{word}th"),
+    };
+    format!("{head}{last}")
+}
+
+/// "reference twelve" for `[12]`, "references three and four" for `(3, 4)`.
+fn spoken_citation(citation: &str) -> String {
+    let numbers: Vec<String> = citation
+        .split(|ch: char| !ch.is_ascii_digit())
+        .filter_map(|digits| digits.parse::<u64>().ok())
+        .map(cardinal_words)
+        .collect();
+    match numbers.as_slice() {
+        [] => String::new(),
+        [only] => format!("reference {only}"),
+        [rest @ .., last] => format!("references {} and {last}", rest.join(", ")),
+    }
+}
+
+/// Run the lines of each verse block in `text` together, with a comma where a
+/// line ends without punctuation so the line end is still heard as a pause.
+/// Line breaks outside verse are left alone.
+fn join_verse_lines(text: &str) -> String {
+    if !text.contains('\n') {
+        return text.to_string();
+    }
+    let mut joined = String::with_capacity(text.len());
+    let mut last_end = 0;
+    for block in verse_blocks(text) {
+        joined.push_str(&text[last_end..block.start]);
+        let lines = RE_LINE_BREAK.replace_all(&text[block.clone()], |caps: &regex::Captures| {
+            let last = &caps["last"];
+            if last.chars().all(char::is_alphanumeric) {
+                format!("{last}, ")
+            } else {
+                format!("{last} ")
+            }
+        });
+        joined.push_str(&lines);
+        last_end = block.end;
+    }
+    joined.push_str(&text[last_end..]);
+    joined
+}
+
+fn superscript_digits(superscript: &str) -> String {
+    superscript
+        .chars()
+        .map(|ch| match ch {
+            '⁰' => '0',
+            '¹' => '1',
+            '²' => '2',
+            '³' => '3',
+            '⁴' => '4',
+            '⁵' => '5',
+            '⁶' => '6',
+            '⁷' => '7',
+            '⁸' => '8',
+            '⁹' => '9',
+            other => other,
+        })
+        .collect()
+}
+
+fn digit_words(digits: &str) -> String {
+    digits
+        .chars()
+        .filter_map(|ch| ch.to_digit(10))
+        .map(|digit| ONES_WORDS[digit as usize])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 fn apply_acronym_expansion(text: &str, cfg: &AcronymConfig) -> String {
     let mut out = text.to_string();
 
@@ -1039,6 +1523,66 @@ mod tests {
         assert_eq!(plan.audio_sentences[0], "Mister Hale wrote this.");
     }
 
+    #[test]
+    fn code_block_backticks_survive_inline_code_stripping() {
+        let normalizer = TextNormalizer::default();
+        let code = format!("echo {CODE_BLOCK_BACKTICK}date{CODE_BLOCK_BACKTICK}");
+        let page = vec![format!("Run `ls` then {code}")];
+        let plan = normalizer.plan_page(&page);
+        assert_eq!(plan.audio_sentences.len(), 1);
+        assert!(plan.audio_sentences[0].contains("ls then"));
+        assert!(plan.audio_sentences[0].ends_with("`date`"));
+    }
+
+    #[test]
+    fn grouped_units_keep_every_sentence_mapped() {
+        let mut normalizer = TextNormalizer::default();
+        normalizer.set_sentence_grouping(2);
+        let long = "This sentence keeps going with plenty of words so that it has to be split \
+                    into several chunks for the speech engine, since it runs well past the \
+                    limits set for a single clip of audio.";
+        let page = vec![
+            "Yes.".to_string(),
+            "No.".to_string(),
+            "Maybe.".to_string(),
+            "[12]".to_string(),
+            "Fine.".to_string(),
+            long.to_string(),
+            "Done.".to_string(),
+        ];
+        let plan = normalizer.plan_page(&page);
+        assert_eq!(plan.audio_sentences[0], "Yes. No.");
+        assert_eq!(plan.audio_sentences[1], "Maybe. Fine.");
+        assert_eq!(
+            &plan.display_to_audio[..5],
+            &[Some(0), Some(0), Some(1), None, Some(1)]
+        );
+        assert_eq!(&plan.audio_to_display[..2], &[0, 2]);
+
+        // A chunked sentence stays on its own units and starts a fresh group.
+        let long_units = plan
+            .audio_to_display
+            .iter()
+            .filter(|&&idx| idx == 5)
+            .count();
+        assert!(long_units > 1);
+        assert_eq!(plan.display_to_audio[5], Some(2));
+        assert_eq!(plan.display_to_audio[6], Some(2 + long_units));
+        assert_eq!(
+            plan.audio_sentences.last().map(String::as_str),
+            Some("Done.")
+        );
+        for (audio_idx, display_idx) in plan.audio_to_display.iter().enumerate() {
+            assert!(plan.display_to_audio[*display_idx].is_some_and(|first| first <= audio_idx));
+        }
+
+        let ungrouped = TextNormalizer::default().plan_page(&page);
+        assert_eq!(
+            ungrouped.audio_sentences.len(),
+            plan.audio_sentences.len() + 2
+        );
+    }
+
     #[test]
     fn sentence_mode_cache_reused_across_page_indices() {
         let normalizer = TextNormalizer::default();
@@ -1076,9 +1620,65 @@ mod tests {
             .filter(|name| name.starts_with("s-"))
             .count();
         assert_eq!(second_sentence_files, 3);
-        assert!(
-            !files_after_second.iter().any(|name| name.starts_with("p")),
-            "sentence mode should not create page-level normalization cache files"
+        assert_eq!(
+            files_after_second
+                .iter()
+                .filter(|name| name.starts_with("p"))
+                .count(),
+            2,
+            "each planned page should get one page-level plan file"
+        );
+
+        let _ = fs::remove_dir_all(&cache_root);
+    }
+
+    #[test]
+    fn page_plans_are_reused_until_config_or_sentences_change() {
+        let normalizer = TextNormalizer::default();
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time should be after epoch")
+            .as_nanos();
+        let epub_path = std::env::temp_dir().join(format!("ebup-page-plan-{nonce}.epub"));
+        let cache_root = normalized_dir(&epub_path);
+        let _ = fs::remove_dir_all(&cache_root);
+
+        let page = vec!["Alpha sentence.".to_string(), "Beta sentence.".to_string()];
+        let _ = normalizer.plan_page_cached(&epub_path, 0, &page);
+        let cache_path = normalizer.normalized_cache_path(&epub_path, 0, &page);
+        let marker = PageNormalization {
+            audio_sentences: vec!["from cache".to_string()],
+            display_to_audio: vec![Some(0), None],
+            audio_to_display: vec![0],
+        };
+        fs::write(
+            &cache_path,
+            serde_json::to_string(&marker).expect("serialize"),
+        )
+        .expect("write");
+        assert_eq!(
+            normalizer
+                .plan_page_cached(&epub_path, 0, &page)
+                .audio_sentences,
+            vec!["from cache"]
+        );
+
+        let mut config = NormalizerConfig::default();
+        config.mode = NormalizationMode::Page;
+        let edited = TextNormalizer::new(config);
+        assert_ne!(
+            edited
+                .plan_page_cached(&epub_path, 0, &page)
+                .audio_sentences,
+            vec!["from cache"]
+        );
+        let longer = vec![page[0].clone(), page[1].clone(), "Gamma.".to_string()];
+        assert_eq!(
+            normalizer
+                .plan_page_cached(&epub_path, 0, &longer)
+                .display_to_audio
+                .len(),
+            3
         );
 
         let _ = fs::remove_dir_all(&cache_root);
@@ -1114,6 +1714,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn try_load_rejects_malformed_config() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time should be after epoch")
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("ebup-normalizer-{nonce}.toml"));
+
+        fs::write(&path, "[normalization]\nmode = \"page\"\n").expect("write config");
+        let loaded = TextNormalizer::try_load(&path).expect("valid config");
+        assert_eq!(loaded.config.mode, NormalizationMode::Page);
+
+        fs::write(&path, "[normalization\nmode = ").expect("write config");
+        assert!(TextNormalizer::try_load(&path).is_err());
+        let _ = fs::remove_file(&path);
+        assert!(TextNormalizer::try_load(&path).is_err());
+    }
+
     #[test]
     fn normalizes_unicode_quotes_and_dashes_for_tts() {
         let normalizer = TextNormalizer::default();
@@ -1122,4 +1740,128 @@ mod tests {
         assert_eq!(plan.audio_sentences.len(), 1);
         assert_eq!(plan.audio_sentences[0], "Quote - and 'apostrophe'... done.");
     }
+
+    #[test]
+    fn expands_numbers_into_words_when_enabled() {
+        let expand = |text: &str| expand_numbers(text, YearMode::American);
+        assert_eq!(expand("Chapter 12"), "Chapter twelve");
+        assert_eq!(
+            expand("It cost $3.50."),
+            "It cost three dollars and fifty cents."
+        );
+        assert_eq!(expand("$1 or $0.05"), "one dollar or five cents");
+        assert_eq!(
+            expand("In 1999 and 1905"),
+            "In nineteen ninety-nine and nineteen oh five"
+        );
+        assert_eq!(expand("by 2007"), "by two thousand seven");
+        assert_eq!(
+            expand("the 21st and 112th"),
+            "the twenty-first and one hundred twelfth"
+        );
+        assert_eq!(expand("pi is 3.14"), "pi is three point one four");
+        assert_eq!(
+            expand("1,250 of 45%"),
+            "one thousand two hundred fifty of forty-five percent"
+        );
+        assert_eq!(expand("v1.2.3 in 3D, mp3"), "v1.2.3 in 3D, mp3");
+        assert_eq!(
+            expand_numbers("1999", YearMode::None),
+            "one thousand nine hundred ninety-nine"
+        );
+
+        let mut config = NormalizerConfig::default();
+        let page = vec!["Chapter 12 opens in 1999.".to_string()];
+        assert_eq!(
+            TextNormalizer::new(config.clone())
+                .plan_page(&page)
+                .audio_sentences[0],
+            "Chapter 12 opens in one thousand nine hundred ninety nine."
+        );
+        config.expand_numbers = true;
+        let plan = TextNormalizer::new(config).plan_page(&page);
+        assert_eq!(
+            plan.audio_sentences[0],
+            "Chapter twelve opens in nineteen ninety-nine."
+        );
+        assert_eq!(plan.display_to_audio, vec![Some(0)]);
+    }
+
+    #[test]
+    fn image_alt_markers_are_skipped_unless_spoken() {
+        let mut config = NormalizerConfig::default();
+        let page = vec!["The hall. [Image: A map of the castle] It was cold.".to_string()];
+        let skipped = TextNormalizer::new(config.clone()).plan_page(&page);
+        assert!(
+            skipped
+                .audio_sentences
+                .iter()
+                .all(|sentence| !sentence.contains("map"))
+        );
+
+        config.speak_image_alt_text = true;
+        let spoken = TextNormalizer::new(config).plan_page(&page);
+        assert!(
+            spoken
+                .audio_sentences
+                .iter()
+                .any(|sentence| sentence.contains("Image: A map of the castle."))
+        );
+    }
+
+    #[test]
+    fn citation_mode_keeps_or_speaks_references() {
+        let page = vec!["Water boils[12] at sea level (3, 4) as shown².".to_string()];
+        let audio = |mode: CitationMode| {
+            let config = NormalizerConfig {
+                citation_mode: mode,
+                ..NormalizerConfig::default()
+            };
+            TextNormalizer::new(config).plan_page(&page).audio_sentences[0].clone()
+        };
+
+        assert_eq!(
+            audio(CitationMode::Drop),
+            "Water boils at sea level as shown."
+        );
+        assert_eq!(
+            audio(CitationMode::Keep),
+            "Water boils 12 at sea level (3, 4) as shown 2."
+        );
+        assert_eq!(
+            audio(CitationMode::Spoken),
+            "Water boils reference twelve at sea level references three and four as shown reference two."
+        );
+    }
+    #[test]
+    fn verse_lines_are_read_with_a_pause_at_each_line_end() {
+        let page = vec![
+            "Whose woods these are I think I know\n\
+                         His house is in the village though;\n\
+                         He will not see me stopping here."
+                .to_string(),
+        ];
+        let config = NormalizerConfig {
+            preserve_verse: true,
+            ..NormalizerConfig::default()
+        };
+        let verse = TextNormalizer::new(config);
+        let joined = verse.plan_page(&page);
+        assert_eq!(
+            joined.audio_sentences,
+            vec![
+                "Whose woods these are I think I know, His house is in the village though; He will not see me stopping here."
+            ]
+        );
+
+        // A heading run into a prose line isn't verse.
+        let prose = vec![
+            "Chapter One\nThe morning came slowly over the hills, and nobody in the village was ready for it."
+                .to_string(),
+        ];
+        assert!(!verse.plan_page(&prose).audio_sentences[0].contains("One,"));
+
+        let plain = TextNormalizer::default().plan_page(&page);
+        assert!(plain.audio_sentences[0].contains("know\nHis"));
+    }
 }
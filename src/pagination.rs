@@ -2,9 +2,14 @@
 //!
 //! The strategy here is intentionally simple: we split text into fixed-size
 //! chunks based on a stable character budget so page count remains steady
-//! even when font size changes. The logic is isolated so it can be swapped
-//! for a more sophisticated layout later.
-use crate::text_utils::split_sentences;
+//! even when font size changes. Only a text column too narrow to fit that
+//! budget per line shrinks it, so small windows get more, shorter pages.
+//! Pages always break between sentences, so a
+//! word or sentence is never split across pages. Sentences are rejoined with a
+//! space, except inside verse, where the original line breaks are kept. The
+//! logic is isolated so it can be swapped for a more sophisticated layout later.
+use crate::text_utils::{preserve_verse, split_sentences, verse_blocks};
+use std::ops::Range;
 
 /// Minimum allowed font size (points).
 pub const MIN_FONT_SIZE: u32 = 12;
@@ -14,51 +19,190 @@ pub const MAX_FONT_SIZE: u32 = 36;
 pub const MIN_LINES_PER_PAGE: usize = 8;
 /// Maximum lines per page.
 pub const MAX_LINES_PER_PAGE: usize = 1000;
+/// Approximate characters per rendered line used for the page budget.
+const CHARS_PER_LINE: usize = 80;
+/// Floor for the per-line budget however narrow the text column gets.
+const MIN_CHARS_PER_LINE: usize = 20;
+/// Average glyph advance as a fraction of the font size.
+const AVG_GLYPH_WIDTH_EM: f32 = 0.5;
+/// A trailing page at most this many lines long counts as an orphan.
+const ORPHAN_LINES: usize = 2;
+
+/// Characters per line assumed for a text column `text_width` pixels wide.
+///
+/// Wide columns use the fixed [`CHARS_PER_LINE`] budget; narrower ones scale down.
+pub fn chars_per_line(text_width: f32, font_size: u32) -> usize {
+    let font_size = font_size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE) as f32;
+    let fitting = (text_width.max(0.0) / (font_size * AVG_GLYPH_WIDTH_EM)).floor() as usize;
+    fitting.clamp(MIN_CHARS_PER_LINE, CHARS_PER_LINE)
+}
 
 /// Split the provided text into page-sized chunks.
-pub fn paginate(text: &str, font_size: u32, lines_per_page: usize) -> Vec<String> {
-    let _ = font_size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE); // kept for signature compatibility
+///
+/// With `fold_orphan_pages`, a final page of only a line or two is folded into
+/// the page before it rather than left as an orphan; that page may then run
+/// past the budget by up to [`ORPHAN_LINES`] lines.
+pub fn paginate(
+    text: &str,
+    font_size: u32,
+    lines_per_page: usize,
+    text_width: f32,
+    fold_orphan_pages: bool,
+) -> Vec<String> {
+    let verse = if preserve_verse() {
+        verse_blocks(text)
+    } else {
+        Vec::new()
+    };
+    paginate_with_verse(
+        text,
+        font_size,
+        lines_per_page,
+        text_width,
+        fold_orphan_pages,
+        &verse,
+    )
+}
+
+/// [`paginate`] keeping the line breaks inside the `verse` byte ranges.
+fn paginate_with_verse(
+    text: &str,
+    font_size: u32,
+    lines_per_page: usize,
+    text_width: f32,
+    fold_orphan_pages: bool,
+    verse: &[Range<usize>],
+) -> Vec<String> {
     let lines = lines_per_page.clamp(MIN_LINES_PER_PAGE, MAX_LINES_PER_PAGE);
 
     // Keep a stable page size regardless of font size so page count does not
     // jump when the user tweaks text size. Font size still affects wrapping at
-    // render time, but pagination is based on a fixed character budget.
-    const CHARS_PER_LINE: usize = 80;
-    let chars_per_page = CHARS_PER_LINE.saturating_mul(lines).max(1);
+    // render time, but pagination is based on a fixed character budget unless
+    // the column is too narrow to hold it.
+    let line_chars = chars_per_line(text_width, font_size);
+    let chars_per_page = line_chars.saturating_mul(lines).max(1);
     let sentences = split_sentences(text);
     if sentences.is_empty() {
         return vec![String::new()];
     }
 
     let mut pages = Vec::new();
-    let mut current_sentences: Vec<String> = Vec::new();
+    let mut current = String::new();
     let mut current_len = 0usize;
+    let mut cursor = 0usize;
 
     for sentence in sentences {
         let sentence = sentence.trim();
         if sentence.is_empty() {
             continue;
         }
+        let separator = match text[cursor..].find(sentence) {
+            Some(at) => {
+                let start = cursor + at;
+                let separator = sentence_separator(&text[cursor..start], start, verse);
+                cursor = start + sentence.len();
+                separator
+            }
+            None => " ",
+        };
         let sentence_len = sentence.chars().count();
-        let separator_len = if current_sentences.is_empty() { 0 } else { 1 }; // " "
+        let separator_len = if current.is_empty() {
+            0
+        } else {
+            separator.len()
+        };
         let prospective_len = current_len + separator_len + sentence_len;
 
-        if !current_sentences.is_empty() && prospective_len > chars_per_page {
-            pages.push(current_sentences.join(" "));
-            current_sentences.clear();
+        if !current.is_empty() && prospective_len > chars_per_page {
+            pages.push(std::mem::take(&mut current));
             current_len = 0;
         }
 
-        if !current_sentences.is_empty() {
-            current_len += 1;
+        if !current.is_empty() {
+            current.push_str(separator);
+            current_len += separator.len();
         }
-        current_sentences.push(sentence.to_string());
+        current.push_str(sentence);
         current_len += sentence_len;
     }
 
-    if !current_sentences.is_empty() {
-        pages.push(current_sentences.join(" "));
+    if !current.is_empty() {
+        let tail = current;
+        match pages.last_mut() {
+            Some(previous) if fold_orphan_pages && current_len <= ORPHAN_LINES * line_chars => {
+                previous.push(' ');
+                previous.push_str(&tail);
+            }
+            _ => pages.push(tail),
+        }
+    }
+
+    if pages.is_empty() {
+        vec![String::new()]
+    } else {
+        pages
+    }
+}
+
+/// What goes between two sentences on a page: the line breaks of `gap` when the
+/// sentence starting at `start` is inside verse, a space otherwise.
+fn sentence_separator(gap: &str, start: usize, verse: &[Range<usize>]) -> &'static str {
+    let in_verse = verse.iter().any(|block| block.contains(&start));
+    match gap.matches('\n').count() {
+        0 => " ",
+        _ if !in_verse => " ",
+        1 => "\n",
+        _ => "\n\n",
+    }
+}
+
+/// Paginate like [`paginate`], but start a fresh page at every byte offset in
+/// `breaks` (chapter starts).
+///
+/// Each chapter is paginated on its own, so a chapter never begins mid-page.
+/// Breaks at the edges of `text`, duplicates, and offsets that are not on a
+/// char boundary are ignored, and blank chapters produce no pages.
+pub fn paginate_with_breaks(
+    text: &str,
+    font_size: u32,
+    lines_per_page: usize,
+    text_width: f32,
+    breaks: &[usize],
+    fold_orphan_pages: bool,
+) -> Vec<String> {
+    let mut bounds: Vec<usize> = breaks
+        .iter()
+        .copied()
+        .filter(|offset| *offset > 0 && *offset < text.len() && text.is_char_boundary(*offset))
+        .collect();
+    if bounds.is_empty() {
+        return paginate(
+            text,
+            font_size,
+            lines_per_page,
+            text_width,
+            fold_orphan_pages,
+        );
     }
+    bounds.sort_unstable();
+    bounds.dedup();
+    bounds.insert(0, 0);
+    bounds.push(text.len());
+
+    let pages: Vec<String> = bounds
+        .windows(2)
+        .map(|window| &text[window[0]..window[1]])
+        .filter(|chapter| !chapter.trim().is_empty())
+        .flat_map(|chapter| {
+            paginate(
+                chapter,
+                font_size,
+                lines_per_page,
+                text_width,
+                fold_orphan_pages,
+            )
+        })
+        .collect();
 
     if pages.is_empty() {
         vec![String::new()]
@@ -67,11 +211,46 @@ pub fn paginate(text: &str, font_size: u32, lines_per_page: usize) -> Vec<String
     }
 }
 
+/// Drop blank pages and runs of pages whose text is identical to the page
+/// kept before them.
+///
+/// Comparison ignores case and whitespace so section dividers that differ only
+/// in spacing still collapse. At least one page is always returned.
+pub fn collapse_duplicate_pages(pages: Vec<String>) -> Vec<String> {
+    let mut collapsed: Vec<String> = Vec::with_capacity(pages.len());
+    let mut last_key: Option<String> = None;
+
+    for page in pages {
+        let key = normalized_page_key(&page);
+        if key.is_empty() || last_key.as_deref() == Some(key.as_str()) {
+            continue;
+        }
+        last_key = Some(key);
+        collapsed.push(page);
+    }
+
+    if collapsed.is_empty() {
+        vec![String::new()]
+    } else {
+        collapsed
+    }
+}
+
+fn normalized_page_key(page: &str) -> String {
+    page.split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::text_utils::split_sentences;
 
+    /// Wide enough that the fixed per-line budget applies.
+    const WIDE: f32 = 2000.0;
+
     #[test]
     fn pagination_preserves_sentence_text_across_page_sizes() {
         let sentence = "This sentence is intentionally long so that we can force pagination without splitting sentence content. ";
@@ -89,7 +268,7 @@ mod tests {
             .collect();
 
         for lines in [8usize, 12, 40, 120] {
-            let pages = paginate(&text, 16, lines);
+            let pages = paginate(&text, 16, lines, WIDE, false);
             let rebuilt: Vec<String> = pages
                 .into_iter()
                 .flat_map(|p| split_sentences(&p))
@@ -102,4 +281,120 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn chapter_breaks_start_fresh_pages() {
+        let text = "Chapter One. It was short. Chapter Two. Also short. Chapter Three. The end.";
+        let two = text.find("Chapter Two").unwrap();
+        let three = text.find("Chapter Three").unwrap();
+
+        assert_eq!(paginate(text, 16, 8, WIDE, false).len(), 1);
+        let pages = paginate_with_breaks(text, 16, 8, WIDE, &[three, two, two], false);
+        assert_eq!(
+            pages,
+            vec![
+                "Chapter One. It was short.",
+                "Chapter Two. Also short.",
+                "Chapter Three. The end.",
+            ]
+        );
+    }
+
+    #[test]
+    fn chapter_breaks_at_edges_do_not_add_empty_pages() {
+        let text = "Only chapter. Nothing else.";
+        assert_eq!(
+            paginate_with_breaks(text, 16, 8, WIDE, &[0, text.len(), text.len() + 5], false),
+            paginate(text, 16, 8, WIDE, false)
+        );
+
+        let padded = "Intro.\n\n   \n\nBody text.";
+        let blank = padded.find("   ").unwrap();
+        let body = padded.find("Body").unwrap();
+        let pages = paginate_with_breaks(padded, 16, 8, WIDE, &[blank, body], false);
+        assert_eq!(pages, vec!["Intro.", "Body text."]);
+    }
+
+    #[test]
+    fn short_final_page_is_absorbed_when_breaking_on_sentences() {
+        // Six of these fill an 8-line page almost exactly.
+        let sentence = "Every sentence in this fixture is padded out to a little over one full line of text, give or take a word.";
+        let mut text = [sentence; 6].join(" ");
+        text.push_str(" The end.");
+
+        let plain = paginate(&text, 16, 8, WIDE, false);
+        assert_eq!(plain.len(), 2);
+        assert_eq!(plain[1], "The end.");
+
+        let absorbed = paginate(&text, 16, 8, WIDE, true);
+        assert_eq!(absorbed.len(), 1);
+        assert!(absorbed[0].ends_with("a word. The end."));
+
+        // A longer remainder still gets its own page.
+        text.push_str(&format!(" {sentence} {sentence} {sentence}"));
+        assert_eq!(paginate(&text, 16, 8, WIDE, true).len(), 2);
+    }
+
+    #[test]
+    fn verse_keeps_its_line_breaks() {
+        let text = "He spoke at length. The poem went:\n\n\
+                    Whose woods these are I think I know.\n\
+                    His house is in the village though;\n\
+                    He will not see me stopping here.\n\n\
+                    To watch his woods fill up with snow.\n\
+                    My little horse must think it queer.\n\nThen he stopped.";
+        let pages = paginate_with_verse(text, 16, 40, WIDE, false, &verse_blocks(text));
+        assert_eq!(
+            pages,
+            vec![
+                "He spoke at length. The poem went:\n\n\
+                 Whose woods these are I think I know.\n\
+                 His house is in the village though;\n\
+                 He will not see me stopping here.\n\n\
+                 To watch his woods fill up with snow.\n\
+                 My little horse must think it queer. Then he stopped."
+            ]
+        );
+    }
+
+    #[test]
+    fn narrow_columns_produce_more_pages() {
+        assert_eq!(chars_per_line(WIDE, 16), 80);
+        assert_eq!(chars_per_line(320.0, 16), 40);
+        assert_eq!(chars_per_line(10.0, 16), 20);
+
+        let text = "A short sentence that repeats. ".repeat(200);
+        let wide = paginate(&text, 16, 8, WIDE, false).len();
+        let narrow = paginate(&text, 16, 8, 320.0, false).len();
+        assert!(narrow >= wide * 2 - 1, "wide={wide} narrow={narrow}");
+    }
+
+    const DIVIDER_FIXTURE: &[&str] = &[
+        "Part One. The harbor was quiet.",
+        "* * *",
+        "*  *  *",
+        "   ",
+        "",
+        "The harbor was loud by morning.",
+        "The harbor was loud by morning.",
+        "\n",
+        "Part Two. Nobody slept.",
+    ];
+
+    #[test]
+    fn collapse_duplicate_pages_drops_blank_and_repeated_dividers() {
+        let pages: Vec<String> = DIVIDER_FIXTURE.iter().map(|p| p.to_string()).collect();
+        let collapsed = collapse_duplicate_pages(pages);
+        assert_eq!(collapsed.len(), 4);
+        assert_eq!(collapsed[0], "Part One. The harbor was quiet.");
+        assert_eq!(collapsed[1], "* * *");
+        assert_eq!(collapsed[2], "The harbor was loud by morning.");
+        assert_eq!(collapsed[3], "Part Two. Nobody slept.");
+    }
+
+    #[test]
+    fn collapse_duplicate_pages_keeps_one_page_for_empty_input() {
+        let collapsed = collapse_duplicate_pages(vec![String::new(), " ".to_string()]);
+        assert_eq!(collapsed, vec![String::new()]);
+    }
 }
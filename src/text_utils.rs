@@ -4,10 +4,22 @@ use once_cell::sync::Lazy;
 use serde::Deserialize;
 use std::collections::{BTreeMap, HashSet};
 use std::fs;
+use std::ops::Range;
 use std::path::PathBuf;
 
 const MAX_DISPLAY_SENTENCE_CHARS: usize = 220;
 const MAX_DISPLAY_SENTENCE_WORDS: usize = 36;
+/// Abbreviations whose trailing period never ends a sentence, used unless
+/// `normalization.sentence_abbreviations` overrides them. Multi-period forms
+/// like "e.g." and "U.S." are handled by the initialism check instead.
+const DEFAULT_SENTENCE_ABBREVIATIONS: &[&str] = &[
+    "mr.", "mrs.", "ms.", "dr.", "prof.", "sr.", "jr.", "st.", "mt.", "rev.", "fr.", "gen.",
+    "col.", "capt.", "lt.", "sgt.", "hon.", "gov.", "sen.", "rep.", "vs.", "cf.", "fig.", "vol.",
+    "approx.", "dept.", "mass.",
+];
+/// Longest line, in chars, that still reads as a line of verse rather than a
+/// prose paragraph kept on one line.
+const MAX_VERSE_LINE_CHARS: usize = 72;
 
 /// Very lightweight sentence splitter based on punctuation.
 pub fn split_sentences(text: &str) -> Vec<String> {
@@ -190,27 +202,93 @@ fn period_is_abbreviation(chars: &[char], dot_idx: usize, abbreviations: &HashSe
     false
 }
 
-static ABBREVIATION_TOKENS: Lazy<HashSet<String>> = Lazy::new(load_abbreviation_tokens);
-
-fn load_abbreviation_tokens() -> HashSet<String> {
-    let mut out = HashSet::new();
-    for default in ["mr.", "ms.", "mrs.", "mass.", "st."] {
-        out.insert(default.to_string());
-    }
+static NORMALIZATION: Lazy<NormalizationConfig> = Lazy::new(load_normalization_config);
+static ABBREVIATION_TOKENS: Lazy<HashSet<String>> =
+    Lazy::new(|| abbreviation_tokens(&NORMALIZATION));
 
+fn load_normalization_config() -> NormalizationConfig {
     let path = PathBuf::from("conf/normalizer.toml");
-    let Ok(contents) = fs::read_to_string(&path) else {
-        return out;
+    fs::read_to_string(&path)
+        .ok()
+        .and_then(|contents| toml::from_str::<NormalizerFile>(&contents).ok())
+        .unwrap_or_default()
+        .normalization
+}
+
+/// Whether `normalization.preserve_verse` keeps verse line breaks on the page.
+pub fn preserve_verse() -> bool {
+    NORMALIZATION.preserve_verse
+}
+
+/// Byte ranges of verse in `text`.
+///
+/// A paragraph counts as a stanza when it has at least two lines and every
+/// line is short; prose comes out of conversion with each paragraph on one
+/// line. Stanzas separated only by blank lines merge into one block, so the
+/// breaks between them are kept too.
+pub fn verse_blocks(text: &str) -> Vec<Range<usize>> {
+    let mut blocks: Vec<Range<usize>> = Vec::new();
+    let mut stanza: Option<(Range<usize>, usize, bool)> = None;
+    let mut close = |stanza: Option<(Range<usize>, usize, bool)>| {
+        let Some((range, lines, short)) = stanza else {
+            return;
+        };
+        if lines < 2 || !short {
+            return;
+        }
+        match blocks.last_mut() {
+            Some(last) if text[last.end..range.start].trim().is_empty() => last.end = range.end,
+            _ => blocks.push(range),
+        }
     };
-    if let Ok(file) = toml::from_str::<NormalizerFile>(&contents) {
-        for key in file.normalization.abbreviations.keys() {
-            let normalized = normalize_abbreviation_token(key);
-            if !normalized.is_empty() {
-                out.insert(normalized);
-            }
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let content = line.trim();
+        if content.is_empty() {
+            close(stanza.take());
+            continue;
         }
+        let end = start + line.trim_end().len();
+        let short = content.chars().count() <= MAX_VERSE_LINE_CHARS;
+        stanza = Some(match stanza.take() {
+            Some((range, lines, all_short)) => (range.start..end, lines + 1, all_short && short),
+            None => (
+                start + (line.len() - line.trim_start().len())..end,
+                1,
+                short,
+            ),
+        });
     }
-    out
+    close(stanza);
+    blocks
+}
+
+/// The configured sentence abbreviations (or the English defaults), plus every
+/// key of the TTS expansion map, since an expanded abbreviation is never a
+/// sentence end either.
+fn abbreviation_tokens(config: &NormalizationConfig) -> HashSet<String> {
+    let configured = match &config.sentence_abbreviations {
+        Some(list) => list
+            .iter()
+            .map(|token| normalize_abbreviation_token(token))
+            .collect(),
+        None => DEFAULT_SENTENCE_ABBREVIATIONS
+            .iter()
+            .map(|token| token.to_string())
+            .collect::<Vec<_>>(),
+    };
+    configured
+        .into_iter()
+        .chain(
+            config
+                .abbreviations
+                .keys()
+                .map(|key| normalize_abbreviation_token(key)),
+        )
+        .filter(|token| !token.is_empty())
+        .collect()
 }
 
 fn normalize_abbreviation_token(raw: &str) -> String {
@@ -228,21 +306,59 @@ struct NormalizerFile {
     normalization: NormalizationConfig,
 }
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Deserialize)]
 #[serde(default)]
 struct NormalizationConfig {
     abbreviations: BTreeMap<String, String>,
+    sentence_abbreviations: Option<Vec<String>>,
+    preserve_verse: bool,
+}
+
+impl Default for NormalizationConfig {
+    fn default() -> Self {
+        Self {
+            abbreviations: BTreeMap::new(),
+            sentence_abbreviations: None,
+            preserve_verse: false,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::split_sentences;
+    use super::*;
 
     #[test]
     fn does_not_split_common_abbreviations() {
         let text = "Mr. Smith walked in. Mrs. Jones stayed.";
         let sentences = split_sentences(text);
         assert_eq!(sentences.len(), 2);
+
+        let sentences = split_sentences("Dr. Smith went to Washington.");
+        assert_eq!(sentences, vec!["Dr. Smith went to Washington."]);
+
+        let sentences = split_sentences("Bring a coat, e.g. a parka. Then go.");
+        assert_eq!(sentences.len(), 2);
+    }
+
+    #[test]
+    fn configured_abbreviations_replace_defaults() {
+        let file: NormalizerFile = toml::from_str(
+            "[normalization]\nsentence_abbreviations = [\"Capt\", \"approx.\"]\n\
+             [normalization.abbreviations]\n\"Mr.\" = \"Mister\"\n",
+        )
+        .expect("valid toml");
+        let tokens = abbreviation_tokens(&file.normalization);
+        assert!(tokens.contains("capt."));
+        assert!(tokens.contains("mr."));
+        assert!(!tokens.contains("dr."));
+
+        let text = "Capt. Reyes saw Dr. Wu. They waved.";
+        let sentences = split_sentences_with_abbreviations(text, &tokens);
+        assert_eq!(
+            sentences,
+            vec!["Capt. Reyes saw Dr.", " Wu.", " They waved."]
+        );
     }
 
     #[test]
@@ -271,6 +387,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn verse_blocks_find_stanzas_but_not_prose() {
+        let text = "A prose paragraph that runs on well past the length of any line of verse, as prose does.\n\n\
+                    Whose woods these are I think I know.\n\
+                    His house is in the village though;\n\n\
+                    He will not see me stopping here\n\
+                    To watch his woods fill up with snow.\n\n\
+                    \"Yes,\" she said.\n\nThe end.";
+        let blocks = verse_blocks(text);
+        assert_eq!(blocks.len(), 1);
+        let verse = &text[blocks[0].clone()];
+        assert!(verse.starts_with("Whose woods"));
+        assert!(verse.ends_with("fill up with snow."));
+
+        assert!(verse_blocks("One line only.\n\nAnother single line.").is_empty());
+    }
+
     #[test]
     fn keeps_short_comma_sentence_intact() {
         let text = "Alpha, beta, and gamma are fine.";
@@ -1,12 +1,12 @@
 //! Text-to-speech support using `piper-rs` with caching in `.cache`.
 //! Audio is generated per sentence and stored as WAV for reuse.
 
+use crate::cache::{cached_audio_voices, sentence_cache_path};
 use anyhow::{Context, Result};
 use rodio::buffer::SamplesBuffer;
-use rodio::source::Zero;
 use rodio::{Decoder, OutputStream, Sink, Source};
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
+use std::collections::HashSet;
 use std::env;
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader, BufWriter, Write};
@@ -14,23 +14,59 @@ use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::{
     Arc, Mutex,
-    atomic::{AtomicU64, AtomicUsize, Ordering},
+    atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
     mpsc,
 };
 use std::thread;
 use tracing::{debug, info, warn};
 
+/// Error message of a batch stopped by [`TtsEngine::cancel_preparation`].
+pub const PREPARATION_CANCELLED: &str = "TTS batch preparation cancelled";
+
+/// A Piper voice: an `.onnx` model with its `.onnx.json` config beside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoiceInfo {
+    /// Model file name without extensions, e.g. `en_US-amy-medium`.
+    pub id: String,
+    pub model_path: PathBuf,
+}
+
 #[derive(Clone)]
 pub struct TtsEngine {
     model_path: PathBuf,
+    voice_id: String,
     espeak_root: PathBuf,
     worker_pool: Arc<Mutex<Option<WorkerPoolState>>>,
     prepare_generation: Arc<AtomicU64>,
 }
 
 impl TtsEngine {
-    pub fn new(model_path: PathBuf, espeak_path: PathBuf) -> Result<Self> {
+    /// Create an engine for `voice`, a [`VoiceInfo::id`] found next to
+    /// `model_path`; an empty or unknown voice uses `model_path` itself.
+    pub fn new(model_path: PathBuf, espeak_path: PathBuf, voice: &str) -> Result<Self> {
         let espeak_path = sanitize_espeak_root(espeak_path);
+        let model_path = match model_path
+            .parent()
+            .map(voices_in_dir)
+            .unwrap_or_default()
+            .into_iter()
+            .find(|info| !voice.is_empty() && info.id == voice)
+        {
+            Some(info) => info.model_path,
+            None => {
+                if !voice.is_empty() {
+                    warn!(
+                        voice,
+                        "TTS voice not found next to the model; using the model"
+                    );
+                }
+                model_path
+            }
+        };
+        if !model_path.is_file() {
+            anyhow::bail!("Piper model not found at {}", model_path.display());
+        }
+        let voice_id = voice_id_for_model(&model_path);
         if env::var_os("PIPER_ESPEAKNG_DATA_DIRECTORY").is_none() {
             // Safe because we set a deterministic value early in process startup.
             unsafe {
@@ -39,27 +75,54 @@ impl TtsEngine {
         }
         info!(
             model = %model_path.display(),
+            voice = %voice_id,
             espeak_root = %espeak_path.display(),
             "Initializing TTS engine"
         );
         Ok(Self {
             model_path,
+            voice_id,
             espeak_root: espeak_path,
             worker_pool: Arc::new(Mutex::new(None)),
             prepare_generation: Arc::new(AtomicU64::new(1)),
         })
     }
 
+    pub fn voice_id(&self) -> &str {
+        &self.voice_id
+    }
+
+    /// Voices available to switch to: every Piper model in the model's directory.
+    pub fn list_voices(&self) -> Vec<VoiceInfo> {
+        self.model_path
+            .parent()
+            .map(voices_in_dir)
+            .unwrap_or_default()
+    }
+
+    /// Whether audio for `sentence` is already cached for this voice.
+    pub fn is_cached(&self, sentence: &str) -> bool {
+        self.clip_path(sentence).exists()
+    }
+
+    /// Where the clip for `sentence` is (or will be) cached for this voice.
+    pub fn clip_path(&self, sentence: &str) -> PathBuf {
+        sentence_cache_path(&normalize_sentence(sentence), &self.voice_id)
+    }
+
     pub fn cancel_preparation(&self) {
         self.prepare_generation.fetch_add(1, Ordering::SeqCst);
     }
 
     /// Play a list of audio files sequentially; returns a sink to control playback.
+    ///
+    /// `pauses[i]` is the silence written after `files[i]` and `speeds[i]` the
+    /// speed it plays at.
     pub fn play_files(
         &self,
         files: &[PathBuf],
-        pause_after: std::time::Duration,
-        speed: f32,
+        pauses: &[std::time::Duration],
+        speeds: &[f32],
         volume: f32,
         start_paused: bool,
     ) -> Result<TtsPlayback> {
@@ -77,13 +140,13 @@ impl TtsEngine {
 
         info!(
             count = files.len(),
-            pause_ms = pause_after.as_millis(),
+            longest_pause_ms = pauses.iter().max().map_or(0, |pause| pause.as_millis()),
             volume,
             start_paused,
-            speed,
+            first_speed = speeds.first().copied().unwrap_or(1.0),
             "Starting TTS playback"
         );
-        playback.append_files(files, pause_after, speed)?;
+        playback.append_files(files, pauses, speeds)?;
         if !start_paused {
             playback.play();
         }
@@ -93,11 +156,29 @@ impl TtsEngine {
     /// Prepare a batch of sentences concurrently using a thread pool.
     pub fn prepare_batch(
         &self,
-        cache_root: PathBuf,
         sentences: Vec<String>,
         start_idx: usize,
         threads: usize,
         progress_log_interval: std::time::Duration,
+    ) -> Result<Vec<(PathBuf, std::time::Duration)>> {
+        self.prepare_batch_cancellable(
+            sentences,
+            start_idx,
+            threads,
+            progress_log_interval,
+            &AtomicBool::new(false),
+        )
+    }
+
+    /// [`Self::prepare_batch`] that also stops, with [`PREPARATION_CANCELLED`],
+    /// once `cancelled` is set. Sentences already handed to a worker finish.
+    pub fn prepare_batch_cancellable(
+        &self,
+        sentences: Vec<String>,
+        start_idx: usize,
+        threads: usize,
+        progress_log_interval: std::time::Duration,
+        cancelled: &AtomicBool,
     ) -> Result<Vec<(PathBuf, std::time::Duration)>> {
         let progress_log_interval =
             progress_log_interval.max(std::time::Duration::from_millis(100));
@@ -128,9 +209,11 @@ impl TtsEngine {
         let max_in_flight = threads.max(1);
         let mut next_progress_log = started_at + progress_log_interval;
         loop {
-            if self.prepare_generation.load(Ordering::Acquire) != generation {
+            if self.prepare_generation.load(Ordering::Acquire) != generation
+                || cancelled.load(Ordering::Acquire)
+            {
                 info!("Cancelled TTS batch preparation");
-                return Err(anyhow::anyhow!("TTS batch preparation cancelled"));
+                return Err(anyhow::anyhow!(PREPARATION_CANCELLED));
             }
 
             while pending.len() < max_in_flight {
@@ -138,7 +221,7 @@ impl TtsEngine {
                     break;
                 };
                 let normalized = normalize_sentence(&sentence);
-                let path = cache_path(&cache_root, &self.model_path, &normalized);
+                let path = sentence_cache_path(&normalized, &self.voice_id);
                 if path.exists() {
                     let dur = sentence_duration(&path);
                     collected[offset] = Some((path, dur));
@@ -243,6 +326,53 @@ impl TtsEngine {
         Ok(collected)
     }
 
+    /// Synthesize `sentences` into fresh files under `dir`, bypassing the sentence cache.
+    ///
+    /// Used for throwaway audio such as voice previews; the caller deletes the files.
+    /// On failure none of the batch's files are left behind.
+    pub fn synthesize_uncached(
+        &self,
+        sentences: &[String],
+        dir: &Path,
+        file_prefix: &str,
+        threads: usize,
+    ) -> Result<Vec<PathBuf>> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Creating snippet audio dir {}", dir.display()))?;
+        let pool = self.ensure_worker_pool(threads.max(1))?;
+        let mut jobs = Vec::with_capacity(sentences.len());
+        let mut failure = None;
+        for (idx, sentence) in sentences.iter().enumerate() {
+            let path = dir.join(format!("{file_prefix}-{idx}.wav"));
+            let (result_tx, result_rx) = mpsc::channel();
+            if let Err(err) = pool.dispatch(normalize_sentence(sentence), path.clone(), result_tx) {
+                failure = Some(err);
+                break;
+            }
+            jobs.push((path, result_rx));
+        }
+        // Wait for every dispatched clip, even after a failure, so none is
+        // written after the cleanup below.
+        for (path, result_rx) in &jobs {
+            let result = result_rx.recv().unwrap_or_else(|_| {
+                Err(anyhow::anyhow!(
+                    "TTS worker channel closed before finishing: {}",
+                    path.display()
+                ))
+            });
+            if let Err(err) = result {
+                failure.get_or_insert(err);
+            }
+        }
+        let files: Vec<PathBuf> = jobs.into_iter().map(|(path, _)| path).collect();
+        if let Some(err) = failure {
+            remove_files(&files);
+            return Err(err);
+        }
+        debug!(count = files.len(), dir = %dir.display(), "Synthesized uncached audio");
+        Ok(files)
+    }
+
     fn ensure_worker_pool(&self, threads: usize) -> Result<Arc<WorkerPool>> {
         let mut guard = self.worker_pool.lock().unwrap();
         let rebuild = match guard.as_ref() {
@@ -290,40 +420,26 @@ impl TtsPlayback {
         self.sink.set_volume(volume.max(0.0));
     }
 
+    /// Queue one source per file, returning each clip's duration without the pause.
+    ///
+    /// Clips are decoded up front and `pauses[i]` is written after `files[i]` in
+    /// the same buffer as explicit silence, so the sink never switches formats or
+    /// waits on a decoder between sentences and zero-pause reads stay gapless.
+    /// Files without a matching pause get none; without a matching speed they
+    /// play at 1x.
     pub fn append_files(
         &mut self,
         files: &[PathBuf],
-        pause_after: std::time::Duration,
-        speed: f32,
+        pauses: &[std::time::Duration],
+        speeds: &[f32],
     ) -> Result<Vec<std::time::Duration>> {
-        let speed = if speed <= f32::EPSILON { 1.0 } else { speed };
         let mut appended_durations = Vec::with_capacity(files.len());
-        for file in files {
-            let reader = BufReader::new(File::open(file)?);
-            let source = Decoder::new(reader)?;
-            if (speed - 1.0).abs() <= f32::EPSILON {
-                let dur = source
-                    .total_duration()
-                    .unwrap_or_else(|| sentence_duration(file));
-                appended_durations.push(dur);
-                self.sink.append(source);
-            } else {
-                let channels = source.channels() as u16;
-                let sample_rate = source.sample_rate();
-                let samples: Vec<f32> = source.convert_samples().collect();
-                let stretched = time_stretch(&samples, sample_rate, channels, speed)
-                    .context("Time-stretching audio")?;
-                let dur = std::time::Duration::from_secs_f64(
-                    stretched.len() as f64 / (sample_rate as f64 * channels as f64),
-                );
-                appended_durations.push(dur);
-                let buffer = SamplesBuffer::new(channels, sample_rate, stretched);
-                self.sink.append(buffer);
-            }
-            if pause_after > std::time::Duration::ZERO {
-                let silence = Zero::<f32>::new(1, 48_000).take_duration(pause_after);
-                self.sink.append(silence);
-            }
+        for (idx, file) in files.iter().enumerate() {
+            let speed = speeds.get(idx).copied().unwrap_or(1.0);
+            let clip = SentenceClip::decode(file, speed)?;
+            appended_durations.push(clip.duration());
+            let pause_after = pauses.get(idx).copied().unwrap_or_default();
+            self.sink.append(clip.into_source(pause_after));
         }
         self.sentence_durations
             .extend(appended_durations.iter().copied());
@@ -339,12 +455,177 @@ impl TtsPlayback {
     }
 }
 
-fn cache_path(base: &Path, model_path: &Path, sentence: &str) -> PathBuf {
-    let mut hasher = Sha256::new();
-    hasher.update(model_path.as_os_str().to_string_lossy().as_bytes());
-    hasher.update(sentence.as_bytes());
-    let hash = format!("{:x}", hasher.finalize());
-    base.join(format!("tts-{hash}.wav"))
+/// Decoded, speed-adjusted samples of one sentence's audio.
+struct SentenceClip {
+    channels: u16,
+    sample_rate: u32,
+    samples: Vec<f32>,
+}
+
+impl SentenceClip {
+    fn decode(path: &Path, speed: f32) -> Result<Self> {
+        let speed = if speed <= f32::EPSILON { 1.0 } else { speed };
+        let reader = BufReader::new(
+            File::open(path).with_context(|| format!("Opening {}", path.display()))?,
+        );
+        let source = Decoder::new(reader)?;
+        let channels = source.channels();
+        let sample_rate = source.sample_rate();
+        let samples: Vec<f32> = source.convert_samples().collect();
+        let samples = time_stretch(&samples, sample_rate, channels, speed)
+            .context("Time-stretching audio")?;
+        Ok(Self {
+            channels,
+            sample_rate,
+            samples,
+        })
+    }
+
+    fn duration(&self) -> std::time::Duration {
+        samples_duration(self.samples.len(), self.channels, self.sample_rate)
+    }
+
+    /// The clip followed by `pause_after` of silence in the clip's own format.
+    fn into_source(mut self, pause_after: std::time::Duration) -> SamplesBuffer<f32> {
+        let pause_frames = (pause_after.as_secs_f64() * self.sample_rate as f64).round() as usize;
+        self.samples.resize(
+            self.samples.len() + pause_frames * self.channels as usize,
+            0.0,
+        );
+        SamplesBuffer::new(self.channels, self.sample_rate, self.samples)
+    }
+}
+
+fn samples_duration(samples: usize, channels: u16, sample_rate: u32) -> std::time::Duration {
+    let frames_per_sec = sample_rate as f64 * channels.max(1) as f64;
+    if frames_per_sec <= 0.0 {
+        return std::time::Duration::ZERO;
+    }
+    std::time::Duration::from_secs_f64(samples as f64 / frames_per_sec)
+}
+
+/// Join cached sentence WAVs into one file, with `pause_after` of silence after each.
+///
+/// All inputs must share the first file's format, which holds for audio from one model.
+/// Stops between clips once `cancelled` is set; a cancelled or failed export leaves
+/// neither `output` nor its `.wav.part` file behind.
+pub fn concat_wav_files(
+    files: &[PathBuf],
+    pause_after: std::time::Duration,
+    output: &Path,
+    cancelled: &AtomicBool,
+) -> Result<()> {
+    let first = files.first().context("No audio to export")?;
+    let spec = hound::WavReader::open(first)
+        .with_context(|| format!("Reading {}", first.display()))?
+        .spec();
+
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp_path = output.with_extension("wav.part");
+    let written = write_concatenated_wav(files, spec, pause_after, &temp_path, cancelled)
+        .and_then(|()| fs::rename(&temp_path, output).map_err(Into::into));
+    if written.is_err() {
+        remove_files(std::slice::from_ref(&temp_path));
+    }
+    written
+}
+
+fn write_concatenated_wav(
+    files: &[PathBuf],
+    spec: hound::WavSpec,
+    pause_after: std::time::Duration,
+    temp_path: &Path,
+    cancelled: &AtomicBool,
+) -> Result<()> {
+    let pause_samples = (pause_after.as_secs_f64() * spec.sample_rate as f64).round() as usize
+        * spec.channels as usize;
+    let mut writer = hound::WavWriter::create(temp_path, spec)?;
+    for file in files {
+        if cancelled.load(Ordering::Acquire) {
+            anyhow::bail!("Audio export cancelled");
+        }
+        let mut reader =
+            hound::WavReader::open(file).with_context(|| format!("Reading {}", file.display()))?;
+        if reader.spec() != spec {
+            anyhow::bail!("{} has a different audio format", file.display());
+        }
+        match spec.sample_format {
+            hound::SampleFormat::Int => {
+                for sample in reader.samples::<i32>() {
+                    writer.write_sample(sample?)?;
+                }
+                for _ in 0..pause_samples {
+                    writer.write_sample(0i32)?;
+                }
+            }
+            hound::SampleFormat::Float => {
+                for sample in reader.samples::<f32>() {
+                    writer.write_sample(sample?)?;
+                }
+                for _ in 0..pause_samples {
+                    writer.write_sample(0.0f32)?;
+                }
+            }
+        }
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
+/// Piper models in `dir` that have their JSON config alongside, sorted by id.
+fn voices_in_dir(dir: &Path) -> Vec<VoiceInfo> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut voices: Vec<VoiceInfo> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "onnx"))
+        .filter(|path| path.with_extension("onnx.json").is_file())
+        .map(|model_path| VoiceInfo {
+            id: voice_id_for_model(&model_path),
+            model_path,
+        })
+        .collect();
+    voices.sort_by(|a, b| a.id.cmp(&b.id));
+    voices
+}
+
+fn voice_id_for_model(model_path: &Path) -> String {
+    let name = model_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = name.strip_suffix(".json").unwrap_or(&name);
+    name.strip_suffix(".onnx").unwrap_or(name).to_string()
+}
+
+/// Delete every voice's cached clip for `sentences` except those in `keep`,
+/// returning how many files went.
+pub fn remove_cached_clips(sentences: &[String], keep: &HashSet<PathBuf>) -> usize {
+    let voices = cached_audio_voices();
+    let mut removed = 0;
+    for sentence in sentences {
+        let normalized = normalize_sentence(sentence);
+        for voice in &voices {
+            let path = sentence_cache_path(&normalized, voice);
+            if !keep.contains(&path) && fs::remove_file(path).is_ok() {
+                removed += 1;
+            }
+        }
+    }
+    removed
+}
+
+/// Best-effort removal of temporary audio files.
+pub fn remove_files(files: &[PathBuf]) {
+    for file in files {
+        if let Err(err) = fs::remove_file(file) {
+            debug!(path = %file.display(), "Failed to remove temporary audio: {err}");
+        }
+    }
 }
 
 fn normalize_sentence(sentence: &str) -> String {
@@ -609,3 +890,115 @@ fn read_response(
         Err(anyhow::anyhow!(msg))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn write_tone(path: &Path, sample_rate: u32, frames: usize) {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).expect("create wav");
+        for frame in 0..frames {
+            writer
+                .write_sample(((frame % 100) as i16 - 50) * 200)
+                .expect("write sample");
+        }
+        writer.finalize().expect("finalize wav");
+    }
+
+    #[test]
+    fn queued_audio_is_clips_plus_explicit_pauses() {
+        let dir = env::temp_dir().join(format!("ebup-tts-gapless-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let files = [dir.join("a.wav"), dir.join("b.wav"), dir.join("c.wav")];
+        write_tone(&files[0], 22_050, 11_025);
+        write_tone(&files[1], 22_050, 5_513);
+        write_tone(&files[2], 22_050, 22_050);
+
+        for pause in [Duration::ZERO, Duration::from_millis(250)] {
+            let mut clips_total = Duration::ZERO;
+            let mut queued_total = Duration::ZERO;
+            for file in &files {
+                let clip = SentenceClip::decode(file, 1.0).unwrap();
+                clips_total += clip.duration();
+                queued_total += clip.into_source(pause).total_duration().unwrap();
+            }
+            let expected = clips_total + pause * files.len() as u32;
+            let drift = queued_total.abs_diff(expected);
+            assert!(
+                drift < Duration::from_millis(1),
+                "{queued_total:?} vs {expected:?}"
+            );
+        }
+        assert_eq!(
+            SentenceClip::decode(&files[0], 1.0).unwrap().duration(),
+            Duration::from_millis(500)
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn exported_audio_joins_clips_and_cancel_leaves_no_file() {
+        let dir = env::temp_dir().join(format!("ebup-tts-export-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let files = vec![dir.join("a.wav"), dir.join("b.wav")];
+        write_tone(&files[0], 22_050, 11_025);
+        write_tone(&files[1], 22_050, 5_513);
+        let output = dir.join("exports").join("book.wav");
+        let partial = output.with_extension("wav.part");
+
+        concat_wav_files(
+            &files,
+            Duration::from_millis(100),
+            &output,
+            &AtomicBool::new(false),
+        )
+        .unwrap();
+        let reader = hound::WavReader::open(&output).unwrap();
+        assert_eq!(reader.spec().sample_rate, 22_050);
+        assert_eq!(reader.duration(), 11_025 + 5_513 + 2 * 2_205);
+        drop(reader);
+        assert!(!partial.exists());
+
+        fs::remove_file(&output).unwrap();
+        let err =
+            concat_wav_files(&files, Duration::ZERO, &output, &AtomicBool::new(true)).unwrap_err();
+        assert_eq!(err.to_string(), "Audio export cancelled");
+        assert!(!output.exists());
+        assert!(!partial.exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn lists_voices_that_have_a_config() {
+        let dir = env::temp_dir().join(format!("ebup-tts-voices-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "en_US-ryan-high.onnx",
+            "en_US-ryan-high.onnx.json",
+            "en_GB-alba-medium.onnx",
+            "en_GB-alba-medium.onnx.json",
+            "orphan.onnx",
+            "notes.txt",
+        ] {
+            fs::write(dir.join(name), b"").unwrap();
+        }
+
+        let ids: Vec<String> = voices_in_dir(&dir).into_iter().map(|v| v.id).collect();
+        assert_eq!(ids, vec!["en_GB-alba-medium", "en_US-ryan-high"]);
+        assert_eq!(
+            voice_id_for_model(Path::new("/m/en_US-ryan-high.onnx.json")),
+            "en_US-ryan-high"
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}